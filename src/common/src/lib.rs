@@ -5,7 +5,9 @@
 pub mod bytes;
 pub mod expiry;
 pub mod metrics;
+pub mod monitor;
 pub mod signal;
+pub mod slowlog;
 #[cfg(feature = "boringssl")]
 pub mod ssl;
 pub mod traits;
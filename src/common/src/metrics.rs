@@ -1,4 +1,6 @@
 use metriken::*;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 
 /// Creates a test that verifies that no two metrics have the same name.
 #[macro_export]
@@ -30,3 +32,99 @@ pub static PID: Gauge = Gauge::new();
 pub fn init() {
     PID.set(std::process::id().into());
 }
+
+/// A family of latency histograms with one histogram for each command of a
+/// protocol. Each histogram is registered the first time its command is seen
+/// and is named `<name>/<command>`. The protocol and command are attached as
+/// metadata, so that the Prometheus exposition presents the histograms as a
+/// single family which is labelled by command.
+///
+/// Latencies are recorded through a [`CommandHistogramCache`], which each
+/// thread holds so that the shared registry is only locked the first time the
+/// thread sees a command.
+pub struct CommandHistograms {
+    name: &'static str,
+    description: &'static str,
+    histograms: RwLock<HashMap<&'static str, Arc<DynBoxedMetric<AtomicHistogram>>>>,
+}
+
+impl CommandHistograms {
+    pub fn new(name: &'static str, description: &'static str) -> Self {
+        Self {
+            name,
+            description,
+            histograms: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns an empty cache of the histograms for use by a single thread.
+    pub fn cache(&'static self) -> CommandHistogramCache {
+        CommandHistogramCache {
+            family: self,
+            histograms: HashMap::new(),
+        }
+    }
+
+    // returns the histogram for the command, registering it if this is the
+    // first time the command has been seen by any thread
+    fn histogram(
+        &self,
+        protocol: &'static str,
+        command: &'static str,
+    ) -> Arc<DynBoxedMetric<AtomicHistogram>> {
+        if let Some(histogram) = self.histograms.read().unwrap().get(command) {
+            return histogram.clone();
+        }
+
+        let mut histograms = self.histograms.write().unwrap();
+        histograms
+            .entry(command)
+            .or_insert_with(|| {
+                Arc::new(
+                    MetricBuilder::new(format!("{}/{command}", self.name))
+                        .description(self.description)
+                        .metadata("protocol", protocol)
+                        .metadata("command", command)
+                        .formatter(command_formatter)
+                        .build(AtomicHistogram::new(7, 32)),
+                )
+            })
+            .clone()
+    }
+}
+
+/// A per-thread cache of the histograms in a [`CommandHistograms`] family.
+pub struct CommandHistogramCache {
+    family: &'static CommandHistograms,
+    histograms: HashMap<&'static str, Arc<DynBoxedMetric<AtomicHistogram>>>,
+}
+
+impl CommandHistogramCache {
+    /// Record a latency, in nanoseconds, for a command of the protocol.
+    pub fn increment(&mut self, protocol: &'static str, command: &'static str, value: u64) {
+        let family = self.family;
+        let histogram = self
+            .histograms
+            .entry(command)
+            .or_insert_with(|| family.histogram(protocol, command));
+        let _ = histogram.increment(value);
+    }
+}
+
+// formats a command histogram using the name of the family, so that all the
+// commands share a single Prometheus metric name
+fn command_formatter(metric: &MetricEntry, format: Format) -> String {
+    match format {
+        Format::Prometheus => {
+            let name = metric
+                .name()
+                .rsplit_once('/')
+                .map(|(name, _)| name)
+                .unwrap_or(metric.name());
+            let protocol = metric.metadata().get("protocol").unwrap_or_default();
+            let command = metric.metadata().get("command").unwrap_or_default();
+            format!("{name}{{protocol=\"{protocol}\", command=\"{command}\"}}")
+        }
+        _ => metric.name().to_string(),
+    }
+}
@@ -0,0 +1,318 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! A live tap of the requests handled by the process, in the style of the
+//! Redis `MONITOR` command. Workers publish each request once it is parsed and
+//! any attached observers receive a copy, or a sampled fraction of them.
+//!
+//! Observers must never slow down the data path. Each observer has a bounded
+//! queue and publishing never waits: events which do not fit in the queue, or
+//! which arrive while an observer is being attached or detached, are dropped
+//! and counted instead. When no observers are attached, publishing is a single
+//! atomic load.
+
+use crate::slowlog::truncate;
+use metriken::*;
+use std::fmt::{Display, Formatter};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The number of events which may be queued for each observer.
+const QUEUE_DEPTH: usize = 1024;
+
+#[metric(
+    name = "monitor_event",
+    description = "the number of requests delivered to monitor observers"
+)]
+pub static MONITOR_EVENT: Counter = Counter::new();
+
+#[metric(
+    name = "monitor_drop",
+    description = "the number of requests not delivered to monitor observers which were busy"
+)]
+pub static MONITOR_DROP: Counter = Counter::new();
+
+#[metric(
+    name = "monitor_observer",
+    description = "the number of attached monitor observers"
+)]
+pub static MONITOR_OBSERVER: Gauge = Gauge::new();
+
+/// The request tap for the process.
+pub static MONITOR: Monitor = Monitor::new();
+
+/// A request which was seen by the tap.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MonitorEvent {
+    timestamp: Duration,
+    client: Option<SocketAddr>,
+    args: Vec<Vec<u8>>,
+}
+
+impl MonitorEvent {
+    /// Create an event for a request which was just received. The `args` are
+    /// the arguments following the command and are truncated in the same way
+    /// as for the slow log.
+    pub fn new(command: &str, mut args: Vec<Vec<u8>>, client: Option<SocketAddr>) -> Self {
+        args.insert(0, command.as_bytes().to_vec());
+
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default(),
+            client,
+            args: truncate(args),
+        }
+    }
+
+    /// The time the request was received, since the UNIX epoch.
+    pub fn timestamp(&self) -> Duration {
+        self.timestamp
+    }
+
+    /// The address of the client which sent the request, if known.
+    pub fn client(&self) -> Option<SocketAddr> {
+        self.client
+    }
+
+    /// The command followed by its arguments.
+    pub fn args(&self) -> &[Vec<u8>] {
+        &self.args
+    }
+}
+
+/// Formats the event as Redis does for `MONITOR`, with each argument quoted
+/// and escaped, eg: `1700000000.123456 [127.0.0.1:53422] "get" "key"`
+impl Display for MonitorEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{:06} [",
+            self.timestamp.as_secs(),
+            self.timestamp.subsec_micros()
+        )?;
+
+        match self.client {
+            Some(client) => write!(f, "{client}]")?,
+            None => write!(f, "unknown]")?,
+        }
+
+        for arg in &self.args {
+            f.write_str(" \"")?;
+            for byte in arg {
+                match byte {
+                    b'\\' => f.write_str("\\\\")?,
+                    b'"' => f.write_str("\\\"")?,
+                    b'\n' => f.write_str("\\n")?,
+                    b'\r' => f.write_str("\\r")?,
+                    b'\t' => f.write_str("\\t")?,
+                    0x20..=0x7e => write!(f, "{}", *byte as char)?,
+                    _ => write!(f, "\\x{byte:02x}")?,
+                }
+            }
+            f.write_str("\"")?;
+        }
+
+        Ok(())
+    }
+}
+
+struct Subscriber {
+    id: u64,
+    sample: u64,
+    seen: AtomicU64,
+    dropped: Arc<AtomicU64>,
+    sender: SyncSender<Arc<MonitorEvent>>,
+}
+
+pub struct Monitor {
+    observers: AtomicUsize,
+    next_id: AtomicU64,
+    subscribers: RwLock<Vec<Subscriber>>,
+}
+
+impl Default for Monitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Monitor {
+    /// Create a new tap with no observers.
+    pub const fn new() -> Self {
+        Self {
+            observers: AtomicUsize::new(0),
+            next_id: AtomicU64::new(0),
+            subscribers: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Returns true if there are any observers attached.
+    pub fn is_active(&self) -> bool {
+        self.observers.load(Ordering::Relaxed) > 0
+    }
+
+    /// Attach an observer which receives 1 in every `sample` requests. The
+    /// observer is detached when it is dropped.
+    pub fn subscribe(&'static self, sample: u64) -> Observer {
+        let (sender, receiver) = sync_channel(QUEUE_DEPTH);
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let dropped = Arc::new(AtomicU64::new(0));
+
+        let mut subscribers = self.subscribers.write().unwrap();
+        subscribers.push(Subscriber {
+            id,
+            sample: sample.max(1),
+            seen: AtomicU64::new(0),
+            dropped: dropped.clone(),
+            sender,
+        });
+        self.observers.store(subscribers.len(), Ordering::Relaxed);
+        MONITOR_OBSERVER.increment();
+
+        Observer {
+            id,
+            dropped,
+            monitor: self,
+            receiver,
+        }
+    }
+
+    /// Deliver a request to the attached observers. The event is only built if
+    /// at least one observer will receive it.
+    pub fn publish<F>(&self, event: F)
+    where
+        F: FnOnce() -> MonitorEvent,
+    {
+        if !self.is_active() {
+            return;
+        }
+
+        // an observer is being attached or detached, rather than wait we drop
+        // the request
+        let subscribers = match self.subscribers.try_read() {
+            Ok(subscribers) => subscribers,
+            Err(_) => {
+                MONITOR_DROP.increment();
+                return;
+            }
+        };
+
+        let mut event = Some(event);
+        let mut built: Option<Arc<MonitorEvent>> = None;
+
+        for subscriber in subscribers.iter() {
+            if subscriber.seen.fetch_add(1, Ordering::Relaxed) % subscriber.sample != 0 {
+                continue;
+            }
+
+            let built = built.get_or_insert_with(|| Arc::new((event.take().unwrap())()));
+
+            if subscriber.sender.try_send(built.clone()).is_ok() {
+                MONITOR_EVENT.increment();
+            } else {
+                subscriber.dropped.fetch_add(1, Ordering::Relaxed);
+                MONITOR_DROP.increment();
+            }
+        }
+    }
+
+    fn unsubscribe(&self, id: u64) {
+        let mut subscribers = self.subscribers.write().unwrap();
+        subscribers.retain(|subscriber| subscriber.id != id);
+        self.observers.store(subscribers.len(), Ordering::Relaxed);
+        MONITOR_OBSERVER.decrement();
+    }
+}
+
+/// Receives the requests seen by the tap until it is dropped.
+pub struct Observer {
+    id: u64,
+    dropped: Arc<AtomicU64>,
+    monitor: &'static Monitor,
+    receiver: Receiver<Arc<MonitorEvent>>,
+}
+
+impl Observer {
+    /// Returns the next queued event, if any, without blocking.
+    pub fn try_recv(&self) -> Option<Arc<MonitorEvent>> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// The number of events which were dropped because the queue for this
+    /// observer was full.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for Observer {
+    fn drop(&mut self) {
+        self.monitor.unsubscribe(self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn publish() {
+        let monitor: &'static Monitor = Box::leak(Box::new(Monitor::new()));
+        let get = |key: &[u8]| MonitorEvent::new("get", vec![key.to_vec()], None);
+
+        // nothing is built without an observer
+        monitor.publish(|| panic!("no observers are attached"));
+
+        let all = monitor.subscribe(1);
+        let sampled = monitor.subscribe(2);
+        assert!(monitor.is_active());
+
+        for key in [b"a", b"b", b"c"] {
+            monitor.publish(|| get(key));
+        }
+
+        let keys = |observer: &Observer| {
+            std::iter::from_fn(|| observer.try_recv())
+                .map(|event| event.args()[1].clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            keys(&all),
+            vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()]
+        );
+        assert_eq!(keys(&sampled), vec![b"a".to_vec(), b"c".to_vec()]);
+
+        // a full queue drops events rather than blocking
+        for _ in 0..(QUEUE_DEPTH + 10) {
+            monitor.publish(|| get(b"key"));
+        }
+        assert_eq!(all.dropped(), 10);
+
+        drop(all);
+        drop(sampled);
+        assert!(!monitor.is_active());
+    }
+
+    #[test]
+    fn display() {
+        let event = MonitorEvent {
+            timestamp: Duration::from_micros(1_700_000_000_123_456),
+            client: "127.0.0.1:53422".parse().ok(),
+            args: vec![
+                b"set".to_vec(),
+                b"key".to_vec(),
+                b"a \"b\"\r\n\x01".to_vec(),
+            ],
+        };
+
+        assert_eq!(
+            event.to_string(),
+            "1700000000.123456 [127.0.0.1:53422] \"set\" \"key\" \"a \\\"b\\\"\\r\\n\\x01\""
+        );
+    }
+}
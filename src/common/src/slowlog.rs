@@ -0,0 +1,253 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! A bounded in-memory log of requests which took longer than a threshold to
+//! serve, modelled on the Redis `SLOWLOG`. This allows slow commands to be
+//! found without enabling the command log.
+//!
+//! The log is shared by every thread of the process and is disabled until it
+//! is configured. Checking whether a request is slow is a pair of atomic
+//! loads, so the cost is only paid by the requests which are recorded.
+
+use metriken::*;
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The maximum number of arguments, including the command, which are kept
+/// for each entry.
+const MAX_ARGS: usize = 32;
+
+/// The maximum number of bytes which are kept for each argument.
+const MAX_ARG_LEN: usize = 128;
+
+#[metric(
+    name = "slowlog_record",
+    description = "the number of requests recorded in the slow log"
+)]
+pub static SLOWLOG_RECORD: Counter = Counter::new();
+
+/// The slow log for the process.
+pub static SLOWLOG: SlowLog = SlowLog::new();
+
+/// A request which was recorded in the slow log.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlowLogEntry {
+    id: u64,
+    timestamp: u64,
+    duration: Duration,
+    args: Vec<Vec<u8>>,
+    client: Option<SocketAddr>,
+}
+
+impl SlowLogEntry {
+    /// A unique, increasing identifier for the entry.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// The time the request was recorded, in seconds since the UNIX epoch.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// The time taken to serve the request.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// The command followed by its arguments. Long arguments, and requests
+    /// with many arguments, are truncated with a note of how much was
+    /// omitted.
+    pub fn args(&self) -> &[Vec<u8>] {
+        &self.args
+    }
+
+    /// The address of the client which sent the request, if known.
+    pub fn client(&self) -> Option<SocketAddr> {
+        self.client
+    }
+}
+
+pub struct SlowLog {
+    // in nanoseconds
+    threshold: AtomicU64,
+    max_len: AtomicUsize,
+    next_id: AtomicU64,
+    // the most recent entry is first
+    entries: Mutex<VecDeque<SlowLogEntry>>,
+}
+
+impl Default for SlowLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SlowLog {
+    /// Create a new slow log, which is disabled until it is configured.
+    pub const fn new() -> Self {
+        Self {
+            threshold: AtomicU64::new(u64::MAX),
+            max_len: AtomicUsize::new(0),
+            next_id: AtomicU64::new(0),
+            entries: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Record requests which take at least `threshold`, keeping up to
+    /// `max_len` of the most recent. A `max_len` of zero disables the log.
+    pub fn configure(&self, threshold: Duration, max_len: usize) {
+        self.threshold.store(
+            threshold.as_nanos().min(u64::MAX as u128) as u64,
+            Ordering::Relaxed,
+        );
+        self.max_len.store(max_len, Ordering::Relaxed);
+
+        let mut entries = self.entries.lock().unwrap();
+        entries.truncate(max_len);
+    }
+
+    /// Returns true if a request which took `duration` should be recorded.
+    pub fn is_slow(&self, duration: Duration) -> bool {
+        self.max_len.load(Ordering::Relaxed) > 0
+            && duration.as_nanos() >= self.threshold.load(Ordering::Relaxed) as u128
+    }
+
+    /// Record a request if it took at least the threshold. The `args` are the
+    /// arguments following the command and are only gathered if the request
+    /// is recorded.
+    pub fn record<F>(&self, command: &str, args: F, duration: Duration, client: Option<SocketAddr>)
+    where
+        F: FnOnce() -> Vec<Vec<u8>>,
+    {
+        if !self.is_slow(duration) {
+            return;
+        }
+
+        let mut args = args();
+        args.insert(0, command.as_bytes().to_vec());
+
+        let entry = SlowLogEntry {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|t| t.as_secs())
+                .unwrap_or(0),
+            duration,
+            args: truncate(args),
+            client,
+        };
+
+        SLOWLOG_RECORD.increment();
+
+        let max_len = self.max_len.load(Ordering::Relaxed);
+        let mut entries = self.entries.lock().unwrap();
+        entries.push_front(entry);
+        entries.truncate(max_len);
+    }
+
+    /// Returns up to `count` of the most recent entries, newest first.
+    pub fn get(&self, count: usize) -> Vec<SlowLogEntry> {
+        let entries = self.entries.lock().unwrap();
+        entries.iter().take(count).cloned().collect()
+    }
+
+    /// Returns the number of entries in the log.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Returns true if there are no entries in the log.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all entries from the log.
+    pub fn reset(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+// limits the number and length of the arguments which are kept, following
+// the conventions of the Redis slow log
+pub(crate) fn truncate(mut args: Vec<Vec<u8>>) -> Vec<Vec<u8>> {
+    if args.len() > MAX_ARGS {
+        let omitted = args.len() - MAX_ARGS + 1;
+        args.truncate(MAX_ARGS - 1);
+        args.push(format!("... ({omitted} more arguments)").into_bytes());
+    }
+
+    for arg in args.iter_mut() {
+        if arg.len() > MAX_ARG_LEN {
+            let omitted = arg.len() - MAX_ARG_LEN;
+            arg.truncate(MAX_ARG_LEN);
+            arg.extend_from_slice(format!("... ({omitted} more bytes)").as_bytes());
+        }
+    }
+
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record() {
+        let log = SlowLog::new();
+        log.record(
+            "get",
+            || vec![b"key".to_vec()],
+            Duration::from_secs(1),
+            None,
+        );
+        assert!(log.is_empty());
+
+        log.configure(Duration::from_millis(10), 2);
+        log.record(
+            "get",
+            || vec![b"fast".to_vec()],
+            Duration::from_millis(1),
+            None,
+        );
+        assert!(log.is_empty());
+
+        let client = "127.0.0.1:12321".parse().ok();
+        for key in [b"a", b"b", b"c"] {
+            log.record(
+                "get",
+                || vec![key.to_vec()],
+                Duration::from_millis(10),
+                client,
+            );
+        }
+
+        // only the most recent entries are kept
+        let entries = log.get(10);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id(), 2);
+        assert_eq!(entries[0].args(), &[b"get".to_vec(), b"c".to_vec()]);
+        assert_eq!(entries[0].client(), client);
+        assert_eq!(entries[1].args(), &[b"get".to_vec(), b"b".to_vec()]);
+        assert_eq!(log.get(1).len(), 1);
+
+        log.reset();
+        assert_eq!(log.len(), 0);
+    }
+
+    #[test]
+    fn truncation() {
+        let args = truncate(vec![vec![b'a'; 130]]);
+        assert_eq!(args[0].len(), 128 + b"... (2 more bytes)".len());
+        assert!(args[0].ends_with(b"... (2 more bytes)"));
+
+        let args = truncate((0..40).map(|i| format!("{i}").into_bytes()).collect());
+        assert_eq!(args.len(), 32);
+        assert_eq!(args[30], b"30");
+        assert_eq!(args[31], b"... (9 more arguments)");
+    }
+}
@@ -4,7 +4,7 @@
 
 pub use boring::ssl::*;
 
-use pelikan_net::TlsTcpAcceptor;
+use pelikan_net::{TlsTcpAcceptor, TlsTcpConnector};
 use std::io::{Error as IoError, ErrorKind as IoErrorKind};
 
 pub trait TlsConfig {
@@ -17,11 +17,29 @@ pub trait TlsConfig {
     fn ca_file(&self) -> Option<String>;
 }
 
-/// Create an `TlsTcpAcceptor` from the given `TlsConfig`. Returns an error if
-/// there were any issues during initialization. Otherwise, returns a
+pub use pelikan_net::{ClientVerification, ServerVerification};
+
+pub trait TlsAcceptorConfig: TlsConfig {
+    fn client_verification(&self) -> ClientVerification;
+
+    fn allowed_subjects(&self) -> Vec<String>;
+
+    fn allowed_sans(&self) -> Vec<String>;
+
+    fn crl_file(&self) -> Option<String>;
+}
+
+pub trait TlsConnectorConfig: TlsConfig {
+    fn server_name(&self) -> Option<String>;
+
+    fn verification(&self) -> ServerVerification;
+}
+
+/// Create an `TlsTcpAcceptor` from the given `TlsAcceptorConfig`. Returns an
+/// error if there were any issues during initialization. Otherwise, returns a
 /// `TlsTcpAcceptor` wrapped in an option, where the `None` variant indicates
 /// that TLS should not be used.
-pub fn tls_acceptor(config: &dyn TlsConfig) -> Result<Option<TlsTcpAcceptor>, IoError> {
+pub fn tls_acceptor(config: &dyn TlsAcceptorConfig) -> Result<Option<TlsTcpAcceptor>, IoError> {
     let mut builder = TlsTcpAcceptor::builder();
 
     // we use xor here to check if we have an under-specified tls configuration
@@ -59,5 +77,181 @@ pub fn tls_acceptor(config: &dyn TlsConfig) -> Result<Option<TlsTcpAcceptor>, Io
         builder = builder.certificate_chain_file(f);
     }
 
+    // client certificates can only be verified if we have trusted CAs
+    if config.client_verification() != ClientVerification::None && config.ca_file().is_none() {
+        return Err(IoError::new(
+            IoErrorKind::Other,
+            "client verification requires a ca file",
+        ));
+    }
+
+    // the allow-list and revocation list only apply to verified client
+    // certificates, so without verification they would be silently ignored
+    if config.client_verification() == ClientVerification::None {
+        if !config.allowed_subjects().is_empty() || !config.allowed_sans().is_empty() {
+            return Err(IoError::new(
+                IoErrorKind::Other,
+                "client allow-list requires client verification",
+            ));
+        }
+
+        if config.crl_file().is_some() {
+            return Err(IoError::new(
+                IoErrorKind::Other,
+                "crl file requires client verification",
+            ));
+        }
+    }
+
+    builder = builder
+        .client_verification(config.client_verification())
+        .allowed_subjects(&config.allowed_subjects())
+        .allowed_sans(&config.allowed_sans());
+
+    if let Some(f) = config.crl_file() {
+        builder = builder.crl_file(f);
+    }
+
     Ok(Some(builder.build()?))
 }
+
+/// Create a `TlsTcpConnector` from the given `TlsConnectorConfig`. Returns an
+/// error if there were any issues during initialization. Unlike the acceptor,
+/// the private key and certificate are optional and only needed when the
+/// remote side requires a client certificate.
+pub fn tls_connector(config: &dyn TlsConnectorConfig) -> Result<TlsTcpConnector, IoError> {
+    let mut builder = TlsTcpConnector::builder().verification(config.verification());
+
+    // we use xor here to check if we have an under-specified tls configuration
+    if config.private_key().is_some()
+        ^ (config.certificate_chain().is_some() || config.certificate().is_some())
+    {
+        return Err(IoError::new(
+            IoErrorKind::Other,
+            "incomplete tls configuration",
+        ));
+    }
+
+    if let Some(f) = config.private_key() {
+        builder = builder.private_key_file(f);
+    }
+
+    if let Some(f) = config.ca_file() {
+        builder = builder.ca_file(f);
+    }
+
+    if let Some(f) = config.certificate() {
+        builder = builder.certificate_file(f);
+    }
+
+    if let Some(f) = config.certificate_chain() {
+        builder = builder.certificate_chain_file(f);
+    }
+
+    if let Some(name) = config.server_name() {
+        builder = builder.server_name(name);
+    }
+
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Config {
+        client_verification: ClientVerification,
+        allowed_subjects: Vec<String>,
+        allowed_sans: Vec<String>,
+        crl_file: Option<String>,
+    }
+
+    impl TlsConfig for Config {
+        fn certificate_chain(&self) -> Option<String> {
+            None
+        }
+
+        fn private_key(&self) -> Option<String> {
+            Some("server.key".to_string())
+        }
+
+        fn certificate(&self) -> Option<String> {
+            Some("server.crt".to_string())
+        }
+
+        fn ca_file(&self) -> Option<String> {
+            Some("ca.crt".to_string())
+        }
+    }
+
+    impl TlsAcceptorConfig for Config {
+        fn client_verification(&self) -> ClientVerification {
+            self.client_verification
+        }
+
+        fn allowed_subjects(&self) -> Vec<String> {
+            self.allowed_subjects.clone()
+        }
+
+        fn allowed_sans(&self) -> Vec<String> {
+            self.allowed_sans.clone()
+        }
+
+        fn crl_file(&self) -> Option<String> {
+            self.crl_file.clone()
+        }
+    }
+
+    fn error(config: &Config) -> String {
+        match tls_acceptor(config) {
+            Ok(_) => "ok".to_string(),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn allow_list_requires_verification() {
+        let config = Config {
+            allowed_subjects: vec!["client".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            error(&config),
+            "client allow-list requires client verification"
+        );
+
+        let config = Config {
+            allowed_sans: vec!["client.example.com".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            error(&config),
+            "client allow-list requires client verification"
+        );
+
+        // with verification, the config is accepted and the files are loaded
+        let config = Config {
+            client_verification: ClientVerification::Required,
+            allowed_subjects: vec!["client".to_string()],
+            ..Default::default()
+        };
+        assert!(error(&config).starts_with("failed to load CA file"));
+    }
+
+    #[test]
+    fn crl_requires_verification() {
+        let config = Config {
+            crl_file: Some("ca.crl".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(error(&config), "crl file requires client verification");
+
+        let config = Config {
+            client_verification: ClientVerification::Optional,
+            crl_file: Some("ca.crl".to_string()),
+            ..Default::default()
+        };
+        assert!(error(&config).starts_with("failed to load CA file"));
+    }
+}
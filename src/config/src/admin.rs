@@ -18,6 +18,7 @@ const ADMIN_TW_TICK: usize = 10;
 const ADMIN_TW_CAP: usize = 1000;
 const ADMIN_TW_NTICK: usize = 100;
 const ADMIN_USE_TLS: bool = false;
+const ADMIN_DRAIN_TIMEOUT: usize = 0;
 
 // TODO(bmartin): we will eventually migrate to HTTP by default and make the
 // legacy admin port as optional. At that time, we should consider consolidating
@@ -71,6 +72,10 @@ fn use_tls() -> bool {
     ADMIN_USE_TLS
 }
 
+fn drain_timeout() -> usize {
+    ADMIN_DRAIN_TIMEOUT
+}
+
 // definitions
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Admin {
@@ -96,6 +101,8 @@ pub struct Admin {
     tw_ntick: usize,
     #[serde(default = "use_tls")]
     use_tls: bool,
+    #[serde(default = "drain_timeout")]
+    drain_timeout: usize,
 }
 
 // implementation
@@ -145,6 +152,13 @@ impl Admin {
     pub fn use_tls(&self) -> bool {
         self.use_tls
     }
+
+    /// The time in milliseconds between a shutdown requested through the
+    /// HTTP admin and the shutdown of the process, during which the process
+    /// reports that it is not ready so that clients may be drained.
+    pub fn drain_timeout(&self) -> usize {
+        self.drain_timeout
+    }
 }
 
 // trait implementations
@@ -162,6 +176,7 @@ impl Default for Admin {
             tw_cap: tw_cap(),
             tw_ntick: tw_ntick(),
             use_tls: use_tls(),
+            drain_timeout: drain_timeout(),
         }
     }
 }
@@ -30,6 +30,9 @@ const SAMPLE: usize = 100;
 // single message buffer size in bytes
 const SINGLE_MESSAGE_SIZE: usize = KB;
 
+// the format of each log line
+const FORMAT: KlogFormat = KlogFormat::Text;
+
 ////////////////////////////////////////////////////////////////////////////////
 // helper functions
 ////////////////////////////////////////////////////////////////////////////////
@@ -62,16 +65,34 @@ fn single_message_size() -> usize {
     SINGLE_MESSAGE_SIZE
 }
 
+fn format() -> KlogFormat {
+    FORMAT
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // struct definitions
 ////////////////////////////////////////////////////////////////////////////////
 
+#[derive(Copy, Clone, Serialize, Deserialize, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum KlogFormat {
+    /// The memcache compatible text format, eg:
+    /// `"get key" 4 5`
+    #[default]
+    Text,
+    /// One JSON object per line, with the command, its arguments, the status
+    /// code and the response length as separate fields.
+    Json,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Klog {
     #[serde(default = "backup")]
     backup: Option<String>,
     #[serde(default = "file")]
     file: Option<String>,
+    #[serde(default = "format")]
+    format: KlogFormat,
     #[serde(default = "interval")]
     interval: usize,
     #[serde(default = "max_size")]
@@ -100,6 +121,10 @@ impl Klog {
         }
     }
 
+    pub fn format(&self) -> KlogFormat {
+        self.format
+    }
+
     pub fn interval(&self) -> usize {
         self.interval
     }
@@ -127,6 +152,7 @@ impl Default for Klog {
         Self {
             file: file(),
             backup: backup(),
+            format: format(),
             interval: interval(),
             max_size: max_size(),
             queue_depth: queue_depth(),
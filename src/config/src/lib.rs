@@ -19,6 +19,7 @@ mod rds;
 pub mod seg;
 mod segcache;
 mod server;
+mod slowlog;
 mod sockio;
 mod stats_log;
 mod tcp;
@@ -33,7 +34,7 @@ pub use array::ArrayConfig;
 pub use buf::{Buf, BufConfig};
 pub use dbuf::DbufConfig;
 pub use debug::{Debug, DebugConfig};
-pub use klog::{Klog, KlogConfig};
+pub use klog::{Klog, KlogConfig, KlogFormat};
 pub use momento_proxy::MomentoProxyConfig;
 pub use pingproxy::PingproxyConfig;
 pub use pingserver::PingserverConfig;
@@ -41,10 +42,11 @@ pub use rds::RdsConfig;
 pub use seg::{Seg, SegConfig};
 pub use segcache::SegcacheConfig;
 pub use server::{Server, ServerConfig};
+pub use slowlog::{Slowlog, SlowlogConfig};
 pub use sockio::{Sockio, SockioConfig};
 pub use stats_log::StatsLogConfig;
 pub use tcp::{Tcp, TcpConfig};
 pub use time::{Time, TimeConfig, TimeType};
 #[cfg(feature = "boringssl")]
-pub use tls::{Tls, TlsConfig};
+pub use tls::{ClientVerification, Tls, TlsConfig};
 pub use worker::{Worker, WorkerConfig};
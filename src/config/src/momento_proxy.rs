@@ -1,4 +1,4 @@
-use crate::{Admin, AdminConfig, Debug, DebugConfig, Klog, KlogConfig};
+use crate::{Admin, AdminConfig, Debug, DebugConfig, Klog, KlogConfig, Slowlog, SlowlogConfig};
 use core::num::NonZeroU64;
 use std::net::AddrParseError;
 use std::net::SocketAddr;
@@ -41,6 +41,8 @@ pub struct MomentoProxyConfig {
     debug: Debug,
     #[serde(default)]
     klog: Klog,
+    #[serde(default)]
+    slowlog: Slowlog,
 }
 
 #[derive(Default, Clone, Copy, Serialize, Deserialize, Debug)]
@@ -151,3 +153,9 @@ impl KlogConfig for MomentoProxyConfig {
         &self.klog
     }
 }
+
+impl SlowlogConfig for MomentoProxyConfig {
+    fn slowlog(&self) -> &Slowlog {
+        &self.slowlog
+    }
+}
@@ -5,6 +5,7 @@
 use serde::{Deserialize, Serialize};
 
 use std::net::{AddrParseError, SocketAddr, ToSocketAddrs};
+use std::path::PathBuf;
 
 // constants to define default values
 const LISTEN_ADDRESS: &str = "0.0.0.0:12322";
@@ -40,6 +41,31 @@ fn backend_poolsize() -> usize {
 }
 
 // definitions
+#[derive(Copy, Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerVerification {
+    /// Do not verify the backend certificate.
+    None,
+    /// Verify that the backend certificate is signed by a trusted CA.
+    Peer,
+    /// Verify the certificate chain and that it matches the `server_name`.
+    Full,
+}
+
+impl Default for ServerVerification {
+    fn default() -> Self {
+        Self::Peer
+    }
+}
+
+/// A backend endpoint, either a TCP socket address or the path of a Unix
+/// domain socket.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Endpoint {
+    Tcp(SocketAddr),
+    Unix(PathBuf),
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Listener {
     #[serde(default = "address")]
@@ -71,6 +97,26 @@ pub struct Backend {
     #[serde(default = "backend_poolsize")]
     poolsize: usize,
     endpoints: Vec<String>,
+    #[serde(default)]
+    tls: Option<BackendTls>,
+}
+
+/// TLS settings for connections from the proxy to the backend servers. The
+/// presence of this section enables TLS for all backend endpoints.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct BackendTls {
+    #[serde(default)]
+    ca_file: Option<String>,
+    #[serde(default)]
+    certificate: Option<String>,
+    #[serde(default)]
+    certificate_chain: Option<String>,
+    #[serde(default)]
+    private_key: Option<String>,
+    #[serde(default)]
+    server_name: Option<String>,
+    #[serde(default)]
+    verification: ServerVerification,
 }
 
 // implementation
@@ -129,19 +175,34 @@ impl Backend {
         self.nevent
     }
 
+    /// TLS settings for backend connections, `None` if plaintext TCP should be
+    /// used
+    pub fn tls(&self) -> Option<&BackendTls> {
+        self.tls.as_ref()
+    }
+
     // TODO(bmartin): the handling of ZK service discovery is based on how
     // Aurora serversets work and needs to be factored out into some more
     // general way of handling service discovery. We may want to allow for
     // sending topology information to the admin port so that a sidecar can be
     // used to handle service discovery.
-    pub fn socket_addrs(&self) -> Result<Vec<SocketAddr>, std::io::Error> {
+    /// The backend endpoints to connect to.
+    ///
+    /// Endpoints which are absolute paths are treated as Unix domain sockets,
+    /// all others are resolved as socket addresses.
+    pub fn endpoints(&self) -> Result<Vec<Endpoint>, std::io::Error> {
         if !self.endpoints.is_empty() {
             let mut endpoints = Vec::new();
             for endpoint in &self.endpoints {
+                if endpoint.starts_with('/') {
+                    endpoints.push(Endpoint::Unix(PathBuf::from(endpoint)));
+                    continue;
+                }
+
                 match endpoint.to_socket_addrs() {
                     Ok(mut addrs) => {
                         if let Some(addr) = addrs.next() {
-                            endpoints.push(addr)
+                            endpoints.push(Endpoint::Tcp(addr))
                         } else {
                             return Err(std::io::Error::new(
                                 std::io::ErrorKind::Other,
@@ -164,6 +225,52 @@ impl Backend {
     }
 }
 
+impl BackendTls {
+    /// The server name to send with SNI and to verify the certificate against
+    pub fn server_name(&self) -> Option<String> {
+        self.server_name.clone()
+    }
+
+    /// How the backend certificate should be verified
+    pub fn verification(&self) -> ServerVerification {
+        self.verification
+    }
+}
+
+#[cfg(feature = "boringssl")]
+impl common::ssl::TlsConfig for BackendTls {
+    fn certificate_chain(&self) -> Option<String> {
+        self.certificate_chain.clone()
+    }
+
+    fn private_key(&self) -> Option<String> {
+        self.private_key.clone()
+    }
+
+    fn certificate(&self) -> Option<String> {
+        self.certificate.clone()
+    }
+
+    fn ca_file(&self) -> Option<String> {
+        self.ca_file.clone()
+    }
+}
+
+#[cfg(feature = "boringssl")]
+impl common::ssl::TlsConnectorConfig for BackendTls {
+    fn server_name(&self) -> Option<String> {
+        self.server_name.clone()
+    }
+
+    fn verification(&self) -> common::ssl::ServerVerification {
+        match self.verification {
+            ServerVerification::None => common::ssl::ServerVerification::None,
+            ServerVerification::Peer => common::ssl::ServerVerification::Peer,
+            ServerVerification::Full => common::ssl::ServerVerification::Full,
+        }
+    }
+}
+
 // trait implementations
 impl Default for Listener {
     fn default() -> Self {
@@ -193,6 +300,7 @@ impl Default for Backend {
             threads: backend_threads(),
             endpoints: Vec::new(),
             poolsize: backend_poolsize(),
+            tls: None,
         }
     }
 }
@@ -51,6 +51,8 @@ pub struct RdsConfig {
     tls: Tls,
     #[serde(default)]
     seg: Seg,
+    #[serde(default)]
+    slowlog: Slowlog,
 
     // ccommon
     #[serde(default)]
@@ -143,6 +145,12 @@ impl ServerConfig for RdsConfig {
     }
 }
 
+impl SlowlogConfig for RdsConfig {
+    fn slowlog(&self) -> &Slowlog {
+        &self.slowlog
+    }
+}
+
 impl SockioConfig for RdsConfig {
     fn sockio(&self) -> &Sockio {
         &self.sockio
@@ -191,6 +199,7 @@ impl Default for RdsConfig {
             worker: Default::default(),
             time: Default::default(),
             seg: Default::default(),
+            slowlog: Default::default(),
 
             buf: Default::default(),
             debug: Default::default(),
@@ -10,6 +10,7 @@ const MB: usize = 1024 * 1024;
 
 // defaults for hashtable
 const HASH_POWER: u8 = 16;
+const HASH_POWER_MAX: u8 = 0;
 const OVERFLOW_FACTOR: f64 = 1.0;
 
 // default heap/segment sizing
@@ -24,9 +25,30 @@ const COMPACT_TARGET: usize = 2;
 const MERGE_TARGET: usize = 4;
 const MERGE_MAX: usize = 8;
 
+// large items, zero limits items to a single segment
+const ITEM_MAX_SIZE: usize = 0;
+
+// value compression
+const COMPRESSION: Compression = Compression::None;
+const COMPRESSION_LEVEL: i32 = 3;
+const COMPRESSION_THRESHOLD: usize = 1024;
+
+// write admission
+const ADMISSION: Admission = Admission::None;
+
+// concurrency, a single shard is accessed only from the storage thread
+const SHARDS: usize = 1;
+
 // datapool
 const DATAPOOL_PATH: Option<&str> = None;
 
+// flash tier, disabled by default
+const FLASH_PATH: Option<&str> = None;
+const FLASH_SIZE: usize = 0;
+
+// snapshot, disabled by default
+const SNAPSHOT_PATH: Option<&str> = None;
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 pub enum Eviction {
     None,
@@ -36,6 +58,21 @@ pub enum Eviction {
     Cte,
     Util,
     Merge,
+    Sieve,
+    S3Fifo,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum Compression {
+    None,
+    Lz4,
+    Zstd,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum Admission {
+    None,
+    TinyLfu,
 }
 
 // helper functions for default values
@@ -43,6 +80,10 @@ fn hash_power() -> u8 {
     HASH_POWER
 }
 
+fn hash_power_max() -> u8 {
+    HASH_POWER_MAX
+}
+
 fn overflow_factor() -> f64 {
     OVERFLOW_FACTOR
 }
@@ -71,15 +112,62 @@ fn compact_target() -> usize {
     COMPACT_TARGET
 }
 
+fn item_max_size() -> usize {
+    ITEM_MAX_SIZE
+}
+
+fn compression() -> Compression {
+    COMPRESSION
+}
+
+fn compression_level() -> i32 {
+    COMPRESSION_LEVEL
+}
+
+fn compression_threshold() -> usize {
+    COMPRESSION_THRESHOLD
+}
+
+fn admission() -> Admission {
+    ADMISSION
+}
+
+fn shards() -> usize {
+    SHARDS
+}
+
 fn datapool_path() -> Option<String> {
     DATAPOOL_PATH.map(|v| v.to_string())
 }
 
+fn flash_path() -> Option<String> {
+    FLASH_PATH.map(|v| v.to_string())
+}
+
+fn flash_size() -> usize {
+    FLASH_SIZE
+}
+
+fn snapshot_path() -> Option<String> {
+    SNAPSHOT_PATH.map(|v| v.to_string())
+}
+
 // definitions
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct Namespace {
+    name: String,
+    prefix: String,
+    heap_size: usize,
+    #[serde(default)]
+    port: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Seg {
     #[serde(default = "hash_power")]
     hash_power: u8,
+    #[serde(default = "hash_power_max")]
+    hash_power_max: u8,
     #[serde(default = "overflow_factor")]
     overflow_factor: f64,
     #[serde(default = "heap_size")]
@@ -94,14 +182,35 @@ pub struct Seg {
     merge_max: usize,
     #[serde(default = "compact_target")]
     compact_target: usize,
+    #[serde(default = "item_max_size")]
+    item_max_size: usize,
+    #[serde(default = "compression")]
+    compression: Compression,
+    #[serde(default = "compression_level")]
+    compression_level: i32,
+    #[serde(default = "compression_threshold")]
+    compression_threshold: usize,
+    #[serde(default = "admission")]
+    admission: Admission,
+    #[serde(default = "shards")]
+    shards: usize,
     #[serde(default = "datapool_path")]
     datapool_path: Option<String>,
+    #[serde(default = "flash_path")]
+    flash_path: Option<String>,
+    #[serde(default = "flash_size")]
+    flash_size: usize,
+    #[serde(default = "snapshot_path")]
+    snapshot_path: Option<String>,
+    #[serde(default)]
+    namespace: Vec<Namespace>,
 }
 
 impl Default for Seg {
     fn default() -> Self {
         Self {
             hash_power: hash_power(),
+            hash_power_max: hash_power_max(),
             overflow_factor: overflow_factor(),
             heap_size: heap_size(),
             segment_size: segment_size(),
@@ -109,17 +218,57 @@ impl Default for Seg {
             merge_target: merge_target(),
             merge_max: merge_max(),
             compact_target: compact_target(),
+            item_max_size: item_max_size(),
+            compression: compression(),
+            compression_level: compression_level(),
+            compression_threshold: compression_threshold(),
+            admission: admission(),
+            shards: shards(),
             datapool_path: datapool_path(),
+            flash_path: flash_path(),
+            flash_size: flash_size(),
+            snapshot_path: snapshot_path(),
+            namespace: Vec::new(),
         }
     }
 }
 
 // implementation
+impl Namespace {
+    /// The name used for the metrics of the namespace.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Keys which start with this prefix belong to the namespace.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// The number of bytes of the heap reserved for the namespace.
+    pub fn heap_size(&self) -> usize {
+        self.heap_size
+    }
+
+    /// The port of the listener whose requests belong to the namespace. This
+    /// is not supported by the storage, which only sees keys, so a namespace
+    /// which sets a port is rejected when the storage is created.
+    pub fn port(&self) -> Option<&str> {
+        self.port.as_deref()
+    }
+}
+
 impl Seg {
     pub fn hash_power(&self) -> u8 {
         self.hash_power
     }
 
+    /// The largest hash power the hashtable may grow to. The hashtable does
+    /// not grow if this is not greater than the hash power.
+    pub fn hash_power_max(&self) -> u8 {
+        self.hash_power_max
+    }
+
     pub fn overflow_factor(&self) -> f64 {
         self.overflow_factor
     }
@@ -148,9 +297,67 @@ impl Seg {
         self.compact_target
     }
 
+    /// The largest value which may be stored. Values which exceed the segment
+    /// size are split across multiple segments. Zero limits values to what
+    /// fits within a single segment.
+    pub fn item_max_size(&self) -> usize {
+        self.item_max_size
+    }
+
+    /// The codec used to compress values.
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    /// The compression level, only used by codecs which support levels.
+    pub fn compression_level(&self) -> i32 {
+        self.compression_level
+    }
+
+    /// Values smaller than this many bytes are stored uncompressed.
+    pub fn compression_threshold(&self) -> usize {
+        self.compression_threshold
+    }
+
+    /// The admission policy applied to writes once the cache is full.
+    pub fn admission(&self) -> Admission {
+        self.admission
+    }
+
+    /// The number of independently locked shards. With more than one shard,
+    /// worker threads access the storage directly instead of sending requests
+    /// to a dedicated storage thread.
+    pub fn shards(&self) -> usize {
+        self.shards.max(1)
+    }
+
     pub fn datapool_path(&self) -> Option<PathBuf> {
         self.datapool_path.as_ref().map(|v| Path::new(v).to_owned())
     }
+
+    /// A file, typically on local flash, which holds segments evicted from
+    /// memory. Items read from it are promoted back into memory.
+    pub fn flash_path(&self) -> Option<PathBuf> {
+        self.flash_path.as_ref().map(|v| Path::new(v).to_owned())
+    }
+
+    /// The size of the flash tier in bytes. The flash tier is disabled if this
+    /// is less than the segment size.
+    pub fn flash_size(&self) -> usize {
+        self.flash_size
+    }
+
+    /// A file which holds a portable snapshot of the cache contents. It is
+    /// loaded at startup if it exists, and written on request.
+    pub fn snapshot_path(&self) -> Option<PathBuf> {
+        self.snapshot_path.as_ref().map(|v| Path::new(v).to_owned())
+    }
+
+    /// Namespaces which partition the heap by key prefix. Keys which do not
+    /// match any namespace use the remainder of the heap.
+    pub fn namespaces(&self) -> &[Namespace] {
+        &self.namespace
+    }
 }
 
 // trait definitions
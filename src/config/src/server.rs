@@ -40,6 +40,10 @@ pub struct Server {
     timeout: usize,
     #[serde(default = "nevent")]
     nevent: usize,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    permissions: Option<u32>,
 }
 
 // implementation
@@ -68,6 +72,16 @@ impl Server {
     pub fn nevent(&self) -> usize {
         self.nevent
     }
+
+    /// Path of a Unix domain socket to listen on instead of the host and port
+    pub fn path(&self) -> Option<String> {
+        self.path.clone()
+    }
+
+    /// File permissions for the Unix domain socket, eg: `0o660`
+    pub fn permissions(&self) -> Option<u32> {
+        self.permissions
+    }
 }
 
 // trait implementations
@@ -78,6 +92,8 @@ impl Default for Server {
             port: port(),
             timeout: timeout(),
             nevent: nevent(),
+            path: None,
+            permissions: None,
         }
     }
 }
@@ -0,0 +1,58 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use serde::{Deserialize, Serialize};
+
+use std::time::Duration;
+
+// constants to define default values
+const THRESHOLD: u64 = 10_000;
+const MAX_LEN: usize = 128;
+
+// helper functions
+fn threshold() -> u64 {
+    THRESHOLD
+}
+
+fn max_len() -> usize {
+    MAX_LEN
+}
+
+// struct definitions
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Slowlog {
+    #[serde(default = "threshold")]
+    threshold: u64,
+    #[serde(default = "max_len")]
+    max_len: usize,
+}
+
+// implementation
+impl Slowlog {
+    /// Requests which take at least this long, in microseconds, are recorded
+    /// in the slow log.
+    pub fn threshold(&self) -> Duration {
+        Duration::from_micros(self.threshold)
+    }
+
+    /// The number of entries kept in the slow log. Zero disables it.
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+}
+
+// trait implementations
+impl Default for Slowlog {
+    fn default() -> Self {
+        Self {
+            threshold: threshold(),
+            max_len: max_len(),
+        }
+    }
+}
+
+// trait definitions
+pub trait SlowlogConfig {
+    fn slowlog(&self) -> &Slowlog;
+}
@@ -4,6 +4,23 @@
 
 use serde::{Deserialize, Serialize};
 
+#[derive(Copy, Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientVerification {
+    /// Do not request a client certificate.
+    None,
+    /// Verify the client certificate if one is presented.
+    Optional,
+    /// Require a valid client certificate.
+    Required,
+}
+
+impl Default for ClientVerification {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
 // definitions
 #[derive(Serialize, Deserialize, Debug, Default)]
 pub struct Tls {
@@ -15,6 +32,14 @@ pub struct Tls {
     certificate: Option<String>,
     #[serde(default)]
     ca_file: Option<String>,
+    #[serde(default)]
+    client_verification: ClientVerification,
+    #[serde(default)]
+    allowed_subjects: Vec<String>,
+    #[serde(default)]
+    allowed_sans: Vec<String>,
+    #[serde(default)]
+    crl_file: Option<String>,
 }
 
 // implementation
@@ -36,6 +61,28 @@ impl common::ssl::TlsConfig for Tls {
     }
 }
 
+impl common::ssl::TlsAcceptorConfig for Tls {
+    fn client_verification(&self) -> common::ssl::ClientVerification {
+        match self.client_verification {
+            ClientVerification::None => common::ssl::ClientVerification::None,
+            ClientVerification::Optional => common::ssl::ClientVerification::Optional,
+            ClientVerification::Required => common::ssl::ClientVerification::Required,
+        }
+    }
+
+    fn allowed_subjects(&self) -> Vec<String> {
+        self.allowed_subjects.clone()
+    }
+
+    fn allowed_sans(&self) -> Vec<String> {
+        self.allowed_sans.clone()
+    }
+
+    fn crl_file(&self) -> Option<String> {
+        self.crl_file.clone()
+    }
+}
+
 // trait definitions
 pub trait TlsConfig {
     fn tls(&self) -> &Tls;
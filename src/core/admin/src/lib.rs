@@ -2,24 +2,31 @@
 // Licensed under the Apache License, Version 2.0
 // http://www.apache.org/licenses/LICENSE-2.0
 
+use common::monitor::{Observer, MONITOR};
 use common::signal::Signal;
+use common::slowlog::SLOWLOG;
 use common::ssl::tls_acceptor;
 use config::{AdminConfig, TlsConfig};
 use crossbeam_channel::Receiver;
+use entrystore::EntryStore;
 use logger::*;
+use metriken::histogram::Histogram;
 use metriken::*;
 use pelikan_net::event::{Event, Source};
 use pelikan_net::*;
 use protocol_admin::*;
+use serde::Serialize;
 use session::{Buf, ServerSession, Session};
 use slab::Slab;
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::io::{Error, ErrorKind, Result};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::thread::JoinHandle;
 use std::time::Duration;
-use std::time::UNIX_EPOCH;
+use std::time::Instant;
 use switchboard::{Queues, Waker};
-use tiny_http::{Method, Request, Response};
+use tiny_http::{Header, Method, Request, Response};
 
 #[metric(name = "admin_request_parse")]
 pub static ADMIN_REQUEST_PARSE: Counter = Counter::new();
@@ -120,11 +127,30 @@ pub static ADMIN_SESSION_CLOSE: Counter = Counter::new();
 )]
 pub static ADMIN_SESSION_CURR: Gauge = Gauge::new();
 
+#[metric(
+    name = "admin_snapshot",
+    description = "number of background snapshots which were started"
+)]
+pub static ADMIN_SNAPSHOT: Counter = Counter::new();
+
+#[metric(
+    name = "admin_snapshot_ex",
+    description = "number of background snapshots which failed"
+)]
+pub static ADMIN_SNAPSHOT_EX: Counter = Counter::new();
+
 // consts
 
 const LISTENER_TOKEN: Token = Token(usize::MAX - 1);
 const WAKER_TOKEN: Token = Token(usize::MAX);
 
+// number of keys to fetch from storage for each batch of a metadump
+const METADUMP_BATCH: usize = 1024;
+
+// a session which is watching requests stops taking events once it has this
+// many bytes waiting to be written
+const WATCH_MAX_PENDING: usize = 1024 * 1024;
+
 const KB: u64 = 1024; // one kilobyte in bytes
 const S: u64 = 1_000_000_000; // one second in nanoseconds
 const US: u64 = 1_000; // one microsecond in nanoseconds
@@ -141,21 +167,38 @@ fn map_err(e: std::io::Error) -> Result<()> {
 pub struct Admin {
     /// A backlog of tokens that need to be handled
     backlog: VecDeque<Token>,
+    /// The effective configuration, serialized as JSON
+    config: String,
+    /// The time to wait between a shutdown requested over HTTP and stopping
+    drain_timeout: Duration,
     http_server: Option<tiny_http::Server>,
     /// The actual network listener for the ASCII Admin Endpoint
     listener: pelikan_net::Listener,
     /// The drain handle for the logger
     log_drain: Box<dyn Drain>,
+    /// The scan cursor for each session with a metadump in progress
+    metadumps: HashMap<Token, u64>,
+    /// The observer for each session which is watching requests
+    monitors: HashMap<Token, Observer>,
     /// The maximum number of events to process per call to poll
     nevent: usize,
     /// The actual poll instantance
     poll: Poll,
+    /// Set once the storage is initialized and the listeners are bound
+    ready: Arc<AtomicBool>,
     /// The sessions which have been opened
     sessions: Slab<ServerSession<AdminProtocol, AdminResponse, AdminRequest>>,
+    /// The time at which to stop after a shutdown was requested over HTTP
+    shutdown_at: Option<Instant>,
     /// A queue for receiving signals from the parent thread
     signal_queue_rx: Receiver<Signal>,
     /// A set of queues for sending signals to sibling threads
     signal_queue_tx: Queues<Signal, ()>,
+    /// A background snapshot in progress, which holds the storage handle
+    /// until it completes
+    snapshot: Option<JoinHandle<Snapshot>>,
+    /// A handle to the storage, used to inspect its contents
+    storage: Option<Box<dyn EntryStore + Send>>,
     /// The timeout for each call to poll
     timeout: Duration,
     /// The version of the service
@@ -166,18 +209,25 @@ pub struct Admin {
 
 pub struct AdminBuilder {
     backlog: VecDeque<Token>,
+    config: String,
+    drain_timeout: Duration,
     http_server: Option<tiny_http::Server>,
     listener: pelikan_net::Listener,
     nevent: usize,
     poll: Poll,
+    ready: Arc<AtomicBool>,
     sessions: Slab<ServerSession<AdminProtocol, AdminResponse, AdminRequest>>,
+    storage: Option<Box<dyn EntryStore + Send>>,
     timeout: Duration,
     version: String,
     waker: Arc<Waker>,
 }
 
 impl AdminBuilder {
-    pub fn new<T: AdminConfig + TlsConfig>(config: &T) -> Result<Self> {
+    pub fn new<T: AdminConfig + TlsConfig + Serialize>(config: &T) -> Result<Self> {
+        let effective = serde_json::to_string_pretty(config)
+            .map_err(|e| Error::new(ErrorKind::Other, format!("bad config: {e}")))?;
+
         let tls_config = config.tls();
         let config = config.admin();
 
@@ -202,6 +252,7 @@ impl AdminBuilder {
 
         let nevent = config.nevent();
         let timeout = Duration::from_millis(config.timeout() as u64);
+        let drain_timeout = Duration::from_millis(config.drain_timeout() as u64);
 
         let sessions = Slab::new();
 
@@ -223,11 +274,15 @@ impl AdminBuilder {
 
         Ok(Self {
             backlog,
+            config: effective,
+            drain_timeout,
             http_server,
             listener,
             nevent,
             poll,
+            ready: Arc::new(AtomicBool::new(false)),
             sessions,
+            storage: None,
             timeout,
             version,
             waker,
@@ -238,10 +293,23 @@ impl AdminBuilder {
         self.version = version.to_string();
     }
 
+    /// Provides a handle to the storage which allows the admin to inspect
+    /// it, eg: for `metadump`.
+    pub fn storage(&mut self, storage: Box<dyn EntryStore + Send>) {
+        self.storage = Some(storage);
+    }
+
     pub fn waker(&self) -> Arc<Waker> {
         self.waker.clone()
     }
 
+    /// Returns the flag which should be set once the storage is initialized
+    /// and the listeners are bound. Until then, `GET /ready` reports that the
+    /// process is not ready.
+    pub fn ready(&self) -> Arc<AtomicBool> {
+        self.ready.clone()
+    }
+
     pub fn build(
         self,
         log_drain: Box<dyn Drain>,
@@ -250,14 +318,22 @@ impl AdminBuilder {
     ) -> Admin {
         Admin {
             backlog: self.backlog,
+            config: self.config,
+            drain_timeout: self.drain_timeout,
             http_server: self.http_server,
             listener: self.listener,
             log_drain,
+            metadumps: HashMap::new(),
+            monitors: HashMap::new(),
             nevent: self.nevent,
             poll: self.poll,
+            ready: self.ready,
             sessions: self.sessions,
+            shutdown_at: None,
             signal_queue_rx,
             signal_queue_tx,
+            snapshot: None,
+            storage: self.storage,
             timeout: self.timeout,
             version: self.version,
             waker: self.waker,
@@ -368,19 +444,84 @@ impl Admin {
 
                 // do some request handling
                 match request {
+                    AdminRequest::Bgsave => {
+                        let response = if !self.metadumps.is_empty() {
+                            AdminResponse::error("metadump in progress")
+                        } else if let Some(storage) = self.storage.take() {
+                            ADMIN_SNAPSHOT.increment();
+                            match spawn_snapshot(storage) {
+                                Ok(snapshot) => {
+                                    self.snapshot = Some(snapshot);
+                                    AdminResponse::Ok
+                                }
+                                Err(_) => {
+                                    ADMIN_SNAPSHOT_EX.increment();
+                                    AdminResponse::error("failed to start snapshot")
+                                }
+                            }
+                        } else if self.snapshot.is_some() {
+                            AdminResponse::error("snapshot in progress")
+                        } else {
+                            AdminResponse::error("not supported")
+                        };
+                        session.send(response)?;
+                    }
+                    AdminRequest::Config => {
+                        session.send(AdminResponse::config(&self.config))?;
+                    }
                     AdminRequest::FlushAll => {
                         let _ = self.signal_queue_tx.try_send_all(Signal::FlushAll);
                         session.send(AdminResponse::Ok)?;
                     }
+                    AdminRequest::Metadump if self.snapshot.is_some() => {
+                        session.send(AdminResponse::error("snapshot in progress"))?;
+                    }
+                    AdminRequest::Metadump => {
+                        ADMIN_RESPONSE_COMPOSE.increment();
+                        self.metadumps.remove(&token);
+                        return self.metadump(token);
+                    }
                     AdminRequest::Quit => {
                         return Err(Error::new(ErrorKind::Other, "should hangup"));
                     }
                     AdminRequest::Stats => {
                         session.send(AdminResponse::Stats)?;
                     }
+                    AdminRequest::StatsSettings => {
+                        session.send(AdminResponse::stats_group(settings(&self.config)))?;
+                    }
+                    AdminRequest::StatsItems
+                    | AdminRequest::StatsSegments
+                    | AdminRequest::StatsSizes
+                    | AdminRequest::StatsTtl => {
+                        let response = if let Some(storage) = self.storage.as_mut() {
+                            storage_stats(storage.as_mut(), &request)
+                                .unwrap_or_else(|| AdminResponse::error("not supported"))
+                        } else if self.snapshot.is_some() {
+                            AdminResponse::error("snapshot in progress")
+                        } else {
+                            AdminResponse::error("not supported")
+                        };
+                        session.send(response)?;
+                    }
+                    AdminRequest::Verbosity(verbosity) => {
+                        // levels count up from `error`, with anything beyond
+                        // the most verbose level treated as `trace`. Logging
+                        // cannot be turned off, so zero is also `error`
+                        let level = LevelFilter::iter()
+                            .nth(verbosity.max(1).into())
+                            .unwrap_or(LevelFilter::Trace);
+                        logger::set_log_level(level);
+                        info!("log level set to: {level}");
+                        session.send(AdminResponse::Ok)?;
+                    }
                     AdminRequest::Version => {
                         session.send(AdminResponse::version(self.version.clone()))?;
                     }
+                    AdminRequest::Watch(sample) => {
+                        session.send(AdminResponse::Ok)?;
+                        self.monitors.insert(token, MONITOR.subscribe(sample));
+                    }
                 }
 
                 ADMIN_RESPONSE_COMPOSE.increment();
@@ -420,6 +561,88 @@ impl Admin {
                 ErrorKind::WouldBlock => Ok(()),
                 _ => Err(e),
             },
+        }?;
+
+        // resume a metadump once the previous batch has been written out
+        if session.write_pending() == 0 && self.metadumps.contains_key(&token) {
+            self.metadump(token)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sends batches of key metadata to the session until the scan of the
+    /// storage completes or the session stops accepting writes, in which case
+    /// the metadump resumes when the session becomes writable.
+    fn metadump(&mut self, token: Token) -> Result<()> {
+        let session = self
+            .sessions
+            .get_mut(token.0)
+            .ok_or_else(|| Error::new(ErrorKind::Other, "non-existant session"))?;
+
+        loop {
+            let cursor = self.metadumps.remove(&token).unwrap_or(0);
+
+            // storage which cannot be scanned produces an empty metadump
+            let (cursor, keys) = self
+                .storage
+                .as_mut()
+                .and_then(|storage| storage.scan(cursor, METADUMP_BATCH))
+                .unwrap_or_default();
+
+            let entries = keys.iter().map(|k| (k.key(), k.ttl(), k.size()));
+            session.send(AdminResponse::metadump(entries, cursor == 0))?;
+
+            match session.flush() {
+                Ok(_) => Ok(()),
+                Err(e) => map_err(e),
+            }?;
+
+            if cursor == 0 {
+                break;
+            }
+
+            self.metadumps.insert(token, cursor);
+
+            if session.write_pending() > 0 {
+                break;
+            }
+        }
+
+        let interest = session.interest();
+        session.reregister(self.poll.registry(), token, interest)
+    }
+
+    /// Returns the storage handle to the admin once a background snapshot has
+    /// completed, and reports the outcome.
+    fn finish_snapshot(&mut self) {
+        if !self.snapshot.as_ref().is_some_and(|s| s.is_finished()) {
+            return;
+        }
+
+        let (storage, result) = match self.snapshot.take().map(|s| s.join()) {
+            Some(Ok(snapshot)) => snapshot,
+            _ => {
+                ADMIN_SNAPSHOT_EX.increment();
+                error!("snapshot thread panicked");
+                return;
+            }
+        };
+
+        self.storage = Some(storage);
+
+        match result {
+            Some(Ok(items)) => {
+                info!("saved snapshot with {items} items");
+            }
+            Some(Err(e)) => {
+                ADMIN_SNAPSHOT_EX.increment();
+                error!("failed to save snapshot: {e}");
+            }
+            None => {
+                ADMIN_SNAPSHOT_EX.increment();
+                warn!("snapshots are not supported by the storage or have no path configured");
+            }
         }
     }
 
@@ -431,6 +654,44 @@ impl Admin {
 
             let mut session = self.sessions.remove(token.0);
             let _ = session.flush();
+
+            self.metadumps.remove(&token);
+            self.monitors.remove(&token);
+        }
+    }
+
+    /// Sends the requests seen by the monitor to each session which is
+    /// watching requests.
+    fn watch(&mut self) {
+        let mut closed = Vec::new();
+
+        for (token, observer) in &self.monitors {
+            let session = match self.sessions.get_mut(token.0) {
+                Some(session) => session,
+                None => {
+                    closed.push(*token);
+                    continue;
+                }
+            };
+
+            session.send_monitor(observer, WATCH_MAX_PENDING);
+
+            let result = match session.flush() {
+                Ok(_) => Ok(()),
+                Err(e) => map_err(e),
+            }
+            .and_then(|_| {
+                let interest = session.interest();
+                session.reregister(self.poll.registry(), *token, interest)
+            });
+
+            if result.is_err() {
+                closed.push(*token);
+            }
+        }
+
+        for token in closed {
+            self.close(token);
         }
     }
 
@@ -495,7 +756,7 @@ impl Admin {
     }
 
     /// Handle a HTTP request
-    fn handle_http_request(&self, request: Request) {
+    fn handle_http_request(&mut self, mut request: Request) {
         let url = request.url();
         let parts: Vec<&str> = url.split('?').collect();
         let url = parts[0];
@@ -504,7 +765,18 @@ impl Admin {
             // stats in the Prometheus format
             "/metrics" => match request.method() {
                 Method::Get => {
-                    let _ = request.respond(Response::from_string(prometheus_stats()));
+                    let format = MetricsFormat::from_accept(
+                        request
+                            .headers()
+                            .iter()
+                            .find(|h| h.field.equiv("Accept"))
+                            .map(|h| h.value.as_str()),
+                    );
+                    let content_type =
+                        Header::from_bytes("Content-Type", format.content_type()).unwrap();
+                    let _ = request.respond(
+                        Response::from_string(prometheus_stats(format)).with_header(content_type),
+                    );
                 }
                 _ => {
                     let _ = request.respond(Response::empty(400));
@@ -530,12 +802,145 @@ impl Admin {
                     let _ = request.respond(Response::empty(400));
                 }
             },
+            "/flush_all" => match request.method() {
+                Method::Post => {
+                    let _ = self.signal_queue_tx.try_send_all(Signal::FlushAll);
+                    let _ = request.respond(Response::from_string("OK\n"));
+                }
+                _ => {
+                    let _ = request.respond(Response::empty(400));
+                }
+            },
+            "/version" => match request.method() {
+                Method::Get => {
+                    let _ = request.respond(Response::from_string(format!("{}\n", self.version)));
+                }
+                _ => {
+                    let _ = request.respond(Response::empty(400));
+                }
+            },
+            // the effective config, including defaults for any values which
+            // were not set in the config file
+            "/config" => match request.method() {
+                Method::Get => {
+                    let _ = request.respond(Response::from_string(self.config.clone()));
+                }
+                _ => {
+                    let _ = request.respond(Response::empty(400));
+                }
+            },
+            // the body of a `PUT` is the new level, eg: `debug`
+            "/log_level" => match request.method() {
+                Method::Get => {
+                    let level = max_level().to_string().to_lowercase();
+                    let _ = request.respond(Response::from_string(format!("{level}\n")));
+                }
+                Method::Put => {
+                    let mut body = String::new();
+                    let level = request
+                        .as_reader()
+                        .read_to_string(&mut body)
+                        .ok()
+                        .and_then(|_| body.trim().parse::<LevelFilter>().ok());
+
+                    match level {
+                        Some(level) => {
+                            logger::set_log_level(level);
+                            info!("log level set to: {level}");
+                            let _ = request.respond(Response::from_string("OK\n"));
+                        }
+                        None => {
+                            let _ = request.respond(
+                                Response::from_string("invalid log level\n").with_status_code(400),
+                            );
+                        }
+                    }
+                }
+                _ => {
+                    let _ = request.respond(Response::empty(400));
+                }
+            },
+            // the most recent entries in the slow log, optionally limited with
+            // `?count=N`. A `DELETE` clears the slow log.
+            "/slowlog" => match request.method() {
+                Method::Get => {
+                    let count = parts
+                        .get(1)
+                        .and_then(|query| {
+                            query
+                                .split('&')
+                                .find_map(|param| param.strip_prefix("count="))
+                        })
+                        .and_then(|count| count.parse().ok())
+                        .unwrap_or(usize::MAX);
+                    let content_type =
+                        Header::from_bytes("Content-Type", "application/json").unwrap();
+                    let _ = request.respond(
+                        Response::from_string(json_slowlog(count)).with_header(content_type),
+                    );
+                }
+                Method::Delete => {
+                    SLOWLOG.reset();
+                    let _ = request.respond(Response::from_string("OK\n"));
+                }
+                _ => {
+                    let _ = request.respond(Response::empty(400));
+                }
+            },
+            // the admin thread is able to respond, so the process is alive
+            "/health" => match request.method() {
+                Method::Get => {
+                    let _ = request.respond(Response::from_string("OK\n"));
+                }
+                _ => {
+                    let _ = request.respond(Response::empty(400));
+                }
+            },
+            // ready once the storage is initialized and the listeners are
+            // bound, and until a shutdown is requested
+            "/ready" => match request.method() {
+                Method::Get => {
+                    if self.ready.load(Ordering::Relaxed) && self.shutdown_at.is_none() {
+                        let _ = request.respond(Response::from_string("OK\n"));
+                    } else {
+                        let _ = request
+                            .respond(Response::from_string("NOT READY\n").with_status_code(503));
+                    }
+                }
+                _ => {
+                    let _ = request.respond(Response::empty(400));
+                }
+            },
+            // the process reports that it is not ready for the drain timeout
+            // and then shuts down
+            "/shutdown" => match request.method() {
+                Method::Post => {
+                    if self.shutdown_at.is_none() {
+                        info!("shutdown requested, draining for {:?}", self.drain_timeout);
+                        self.shutdown_at = Some(Instant::now() + self.drain_timeout);
+                    }
+                    let _ = request.respond(Response::from_string("OK\n"));
+                }
+                _ => {
+                    let _ = request.respond(Response::empty(400));
+                }
+            },
             _ => {
                 let _ = request.respond(Response::empty(404));
             }
         }
     }
 
+    /// Broadcast a shutdown to all sibling threads
+    fn shutdown(&mut self) {
+        info!("shutting down");
+        let _ = self.signal_queue_tx.try_send_all(Signal::Shutdown);
+        if self.signal_queue_tx.wake().is_err() {
+            fatal!("error waking threads for shutdown");
+        }
+        let _ = self.log_drain.flush();
+    }
+
     pub fn run(&mut self) {
         info!(
             "running admin on: {}",
@@ -580,10 +985,8 @@ impl Admin {
             }
 
             // handle all http requests if the http server is enabled
-            if let Some(ref server) = self.http_server {
-                while let Ok(Some(request)) = server.try_recv() {
-                    self.handle_http_request(request);
-                }
+            while let Some(Ok(Some(request))) = self.http_server.as_ref().map(|s| s.try_recv()) {
+                self.handle_http_request(request);
             }
 
             // handle all signals
@@ -594,23 +997,150 @@ impl Admin {
                         // if a shutdown is received from any
                         // thread, we will broadcast it to all
                         // sibling threads and stop our event loop
-                        info!("shutting down");
-                        let _ = self.signal_queue_tx.try_send_all(Signal::Shutdown);
-                        if self.signal_queue_tx.wake().is_err() {
-                            fatal!("error waking threads for shutdown");
-                        }
-                        let _ = self.log_drain.flush();
+                        self.shutdown();
                         return;
                     }
                 }
             }
 
+            // stop once a shutdown requested over http has drained
+            if self.shutdown_at.is_some_and(|at| at <= Instant::now()) {
+                self.shutdown();
+                return;
+            }
+
+            self.finish_snapshot();
+
+            self.watch();
+
             // flush pending log entries to log destinations
             let _ = self.log_drain.flush();
         }
     }
 }
 
+/// The storage handle and the outcome of a background snapshot.
+type Snapshot = (Box<dyn EntryStore + Send>, Option<Result<usize>>);
+
+/// Writes a snapshot of the storage from a separate thread, so that the admin
+/// remains responsive while the snapshot is written.
+fn spawn_snapshot(mut storage: Box<dyn EntryStore + Send>) -> Result<JoinHandle<Snapshot>> {
+    std::thread::Builder::new()
+        .name("pelikan_snapshot".to_string())
+        .spawn(move || {
+            let result = storage.save_snapshot();
+            (storage, result)
+        })
+}
+
+/// Flattens the effective config for `stats settings`, with nested values
+/// named by their path, eg: `seg.heap_size`. Unset values are reported as
+/// `none`.
+fn settings(config: &str) -> Vec<(String, String)> {
+    fn flatten(prefix: &str, value: &serde_json::Value, settings: &mut Vec<(String, String)>) {
+        let value = match value {
+            serde_json::Value::Object(map) => {
+                for (key, value) in map {
+                    let name = if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{prefix}.{key}")
+                    };
+                    flatten(&name, value, settings);
+                }
+                return;
+            }
+            serde_json::Value::Array(values) => values
+                .iter()
+                .map(|v| {
+                    v.as_str()
+                        .map(str::to_string)
+                        .unwrap_or_else(|| v.to_string())
+                })
+                .collect::<Vec<_>>()
+                .join(","),
+            serde_json::Value::Null => "none".to_string(),
+            serde_json::Value::String(value) => value.clone(),
+            value => value.to_string(),
+        };
+        settings.push((prefix.to_string(), value));
+    }
+
+    let mut settings = Vec::new();
+    if let Ok(config) = serde_json::from_str(config) {
+        flatten("", &config, &mut settings);
+    }
+    settings
+}
+
+/// Inspects the storage for one of the `stats` subcommands. Returns `None` if
+/// the storage does not support the subcommand.
+///
+/// * `items`: the number of items and the age of the oldest segment, in
+///   seconds, for each TTL bucket, eg: `items:7:number`
+/// * `segments`: the segment size and counts, the number of merges, and the
+///   number of segments in use by age, eg: `age:64` for segments between 33
+///   and 64 seconds old
+/// * `sizes`: the number of items by size, rounded up to a multiple of 32
+///   bytes. This scans every item in the storage.
+/// * `ttl`: the live and dead bytes for each TTL bucket, eg:
+///   `ttl:7:dead_bytes`
+fn storage_stats(storage: &mut dyn EntryStore, request: &AdminRequest) -> Option<AdminResponse> {
+    let mut stats = Vec::new();
+
+    match request {
+        AdminRequest::StatsItems => {
+            for bucket in storage.ttl_stats()? {
+                let id = bucket.bucket();
+                stats.push((format!("items:{id}:number"), bucket.items() as u64));
+                stats.push((format!("items:{id}:age"), bucket.age().as_secs()));
+            }
+        }
+        AdminRequest::StatsSegments => {
+            let segments = storage.segment_stats()?;
+            stats.push(("segment_size".to_string(), segments.segment_size() as u64));
+            stats.push(("segments".to_string(), segments.total() as u64));
+            stats.push(("segments_free".to_string(), segments.free() as u64));
+            stats.push(("merges".to_string(), segments.merges()));
+            for (age, count) in segments.ages() {
+                stats.push((format!("age:{}", age.as_secs()), count as u64));
+            }
+        }
+        AdminRequest::StatsSizes => {
+            let mut sizes: BTreeMap<usize, u64> = BTreeMap::new();
+            let mut cursor = 0;
+            loop {
+                let (next, keys) = storage.scan(cursor, METADUMP_BATCH)?;
+                for key in keys {
+                    *sizes.entry(key.size().div_ceil(32) * 32).or_default() += 1;
+                }
+                if next == 0 {
+                    break;
+                }
+                cursor = next;
+            }
+            stats.extend(
+                sizes
+                    .into_iter()
+                    .map(|(size, count)| (size.to_string(), count)),
+            );
+        }
+        AdminRequest::StatsTtl => {
+            for bucket in storage.ttl_stats()? {
+                let id = bucket.bucket();
+                stats.push((format!("ttl:{id}:ttl"), bucket.ttl().as_secs()));
+                stats.push((format!("ttl:{id}:segments"), bucket.segments() as u64));
+                stats.push((format!("ttl:{id}:live_bytes"), bucket.live_bytes() as u64));
+                stats.push((format!("ttl:{id}:dead_bytes"), bucket.dead_bytes() as u64));
+                stats.push((format!("ttl:{id}:merges"), bucket.merges()));
+            }
+        }
+        _ => return None,
+    }
+
+    Some(AdminResponse::stats_group(stats))
+}
+
 /// A "human-readable" exposition format which outputs one stat per line,
 /// with a LF used as the end of line symbol.
 ///
@@ -648,46 +1178,123 @@ pub fn json_stats() -> String {
     "{".to_string() + &data.join(",") + "}"
 }
 
+/// Returns up to `count` of the most recent slow log entries as a JSON array,
+/// newest first. Arguments which are not valid UTF-8 are lossily converted.
+///
+/// ```text
+/// [{"id": 0,"timestamp": 1700000000,"duration_us": 10500,"args": ["get","key"],"client": "127.0.0.1:53422"}]
+/// ```
+pub fn json_slowlog(count: usize) -> String {
+    let entries: Vec<serde_json::Value> = SLOWLOG
+        .get(count)
+        .iter()
+        .map(|entry| {
+            serde_json::json!({
+                "id": entry.id(),
+                "timestamp": entry.timestamp(),
+                "duration_us": entry.duration().as_micros() as u64,
+                "args": entry
+                    .args()
+                    .iter()
+                    .map(|arg| String::from_utf8_lossy(arg))
+                    .collect::<Vec<_>>(),
+                "client": entry.client().map(|addr| addr.to_string()),
+            })
+        })
+        .collect();
+
+    serde_json::to_string(&entries).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// The text formats which may be negotiated for the `/metrics` endpoint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetricsFormat {
+    /// The Prometheus text exposition format, version 0.0.4
+    Prometheus,
+    /// The OpenMetrics text format, version 1.0.0
+    OpenMetrics,
+}
+
+impl MetricsFormat {
+    /// Selects the format from the value of the `Accept` header. OpenMetrics
+    /// is used whenever the client accepts it, otherwise we fall back to the
+    /// Prometheus text format.
+    pub fn from_accept(accept: Option<&str>) -> Self {
+        let openmetrics = accept.unwrap_or_default().split(',').any(|range| {
+            let mut params = range.split(';').map(str::trim);
+
+            let media = params.next().unwrap_or_default();
+
+            // a quality of zero means the client does not accept the type
+            let rejected = params.any(|param| match param.split_once('=') {
+                Some((key, value)) => {
+                    key.trim().eq_ignore_ascii_case("q") && value.trim().parse() == Ok(0.0)
+                }
+                None => false,
+            });
+
+            media.eq_ignore_ascii_case("application/openmetrics-text") && !rejected
+        });
+
+        if openmetrics {
+            Self::OpenMetrics
+        } else {
+            Self::Prometheus
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Prometheus => "text/plain; version=0.0.4; charset=utf-8",
+            Self::OpenMetrics => "application/openmetrics-text; version=1.0.0; charset=utf-8",
+        }
+    }
+}
+
+/// Histograms are downsampled to this grouping power for export, which gives
+/// two buckets per power of two. This keeps the number of series per
+/// histogram small while still allowing quantiles to be estimated.
+const EXPORT_GROUPING_POWER: u8 = 1;
+
 /// Prometheus / OpenTelemetry compatible stats output. Each stat is
-/// annotated with a type. Percentiles use the label 'percentile' to
-/// indicate which percentile corresponds to the value:
+/// annotated with a type. Histograms are exported as cumulative histograms
+/// with power-of-two bucket boundaries, so they may be aggregated across
+/// hosts. The `_sum` is estimated from the midpoints of the buckets of the
+/// full-resolution histogram:
 ///
 /// ```text
 /// # TYPE get counter
 /// get 0
-/// # TYPE get_cardinality gauge
-/// get_cardinality{percentile="p25"} 0
-/// # TYPE get_cardinality gauge
-/// get_cardinality{percentile="p50"} 0
-/// # TYPE get_cardinality gauge
-/// get_cardinality{percentile="p75"} 0
-/// # TYPE get_cardinality gauge
-/// get_cardinality{percentile="p90"} 0
-/// # TYPE get_cardinality gauge
-/// get_cardinality{percentile="p99"} 0
-/// # TYPE get_cardinality gauge
-/// get_cardinality{percentile="p999"} 0
-/// # TYPE get_cardinality gauge
-/// get_cardinality{percentile="p9999"} 0
+/// # TYPE get_cardinality histogram
+/// get_cardinality_bucket{le="0"} 0
+/// get_cardinality_bucket{le="1"} 0
+/// get_cardinality_bucket{le="2"} 0
+/// get_cardinality_bucket{le="3"} 0
+/// ...
+/// get_cardinality_bucket{le="+Inf"} 0
+/// get_cardinality_sum 0
+/// get_cardinality_count 0
 /// # TYPE get_ex counter
 /// get_ex 0
-/// # TYPE get_key counter
-/// get_key 0
-/// # TYPE get_key_hit counter
-/// get_key_hit 0
-/// # TYPE get_key_miss counter
-/// get_key_miss 0
 /// ```
-pub fn prometheus_stats() -> String {
-    let mut data = Vec::new();
-
-    let snapshots = SNAPSHOTS.read();
-
-    let timestamp = snapshots
-        .timestamp()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_millis();
+///
+/// Metrics which are labelled, such as the per-command latency histograms,
+/// are grouped into a single family with one type annotation.
+///
+/// In the OpenMetrics format, counters are exposed with a `_total` suffix on
+/// the sample and the output is terminated with `# EOF`.
+pub fn prometheus_stats(format: MetricsFormat) -> String {
+    // samples are grouped by metric family, so that metrics which only differ
+    // by their labels share a single type annotation
+    let mut families: BTreeMap<String, (&str, Vec<String>)> = BTreeMap::new();
+
+    let mut push = |family: &str, kind, samples: Vec<String>| {
+        families
+            .entry(family.to_string())
+            .or_insert_with(|| (kind, Vec::new()))
+            .1
+            .extend(samples);
+    };
 
     for metric in &metriken::metrics() {
         let any = match metric.as_any() {
@@ -697,47 +1304,114 @@ pub fn prometheus_stats() -> String {
             }
         };
 
-        let name = metric.name();
+        let formatted = metric.formatted(metriken::Format::Prometheus);
+        let (family, labels) = match formatted.find('{') {
+            Some(idx) => formatted.split_at(idx),
+            None => (formatted.as_str(), ""),
+        };
 
         if let Some(counter) = any.downcast_ref::<Counter>() {
-            if metric.metadata().is_empty() {
-                data.push(format!(
-                    "# TYPE {name}_total counter\n{name}_total {}",
-                    counter.value()
-                ));
-            } else {
-                data.push(format!(
-                    "# TYPE {name} counter\n{} {}",
-                    metric.formatted(metriken::Format::Prometheus),
-                    counter.value()
-                ));
+            let value = counter.value();
+            match format {
+                MetricsFormat::Prometheus if labels.is_empty() => {
+                    let family = format!("{family}_total");
+                    push(&family, "counter", vec![format!("{family} {value}")]);
+                }
+                MetricsFormat::Prometheus => {
+                    push(family, "counter", vec![format!("{formatted} {value}")]);
+                }
+                MetricsFormat::OpenMetrics => {
+                    push(
+                        family,
+                        "counter",
+                        vec![format!("{family}_total{labels} {value}")],
+                    );
+                }
             }
         } else if let Some(gauge) = any.downcast_ref::<Gauge>() {
-            data.push(format!(
-                "# TYPE {name} gauge\n{} {}",
-                metric.formatted(metriken::Format::Prometheus),
-                gauge.value()
-            ));
-        } else if any.downcast_ref::<AtomicHistogram>().is_some()
-            || any.downcast_ref::<RwLockHistogram>().is_some()
-        {
-            for (_label, percentile, value) in snapshots.percentiles(metric.name()) {
-                data.push(format!(
-                    "# TYPE {name} gauge\n{name}{{percentile=\"{:02}\"}} {value} {timestamp}",
-                    percentile,
-                ));
-            }
+            push(
+                family,
+                "gauge",
+                vec![format!("{formatted} {}", gauge.value())],
+            );
+        } else if let Some(histogram) = any.downcast_ref::<AtomicHistogram>() {
+            let snapshot = histogram
+                .load()
+                .unwrap_or_else(|| Histogram::with_config(&histogram.config()));
+            push(
+                family,
+                "histogram",
+                format_histogram(family, labels, &snapshot),
+            );
+        } else if let Some(histogram) = any.downcast_ref::<RwLockHistogram>() {
+            let snapshot = histogram
+                .load()
+                .unwrap_or_else(|| Histogram::with_config(&histogram.config()));
+            push(
+                family,
+                "histogram",
+                format_histogram(family, labels, &snapshot),
+            );
         }
     }
 
-    data.sort();
-    data.dedup();
-    let mut content = data.join("\n");
-    content += "\n";
+    let mut content = String::new();
+    for (family, (kind, samples)) in families {
+        content += &format!("# TYPE {family} {kind}\n");
+        for sample in samples {
+            content += &sample;
+            content += "\n";
+        }
+    }
+    if format == MetricsFormat::OpenMetrics {
+        content += "# EOF\n";
+    }
     let parts: Vec<&str> = content.split('/').collect();
     parts.join("_")
 }
 
+// formats a histogram as cumulative buckets along with the sum and count, the
+// labels are either empty or in the form `{key="value", ...}`
+fn format_histogram(family: &str, labels: &str, histogram: &Histogram) -> Vec<String> {
+    let mut count: u64 = 0;
+    let mut sum: u128 = 0;
+
+    for bucket in histogram {
+        count += bucket.count();
+        let midpoint = bucket.start() as u128 + (bucket.end() - bucket.start()) as u128 / 2;
+        sum += midpoint * bucket.count() as u128;
+    }
+
+    // the bucket boundary is added to any existing labels
+    let prefix = match labels.strip_prefix('{').and_then(|l| l.strip_suffix('}')) {
+        Some(inner) if !inner.is_empty() => format!("{inner}, "),
+        _ => String::new(),
+    };
+
+    let exported = if histogram.config().grouping_power() > EXPORT_GROUPING_POWER {
+        histogram.downsample(EXPORT_GROUPING_POWER).ok()
+    } else {
+        None
+    };
+
+    let mut samples = Vec::new();
+
+    let mut cumulative = 0;
+    for bucket in exported.as_ref().unwrap_or(histogram) {
+        cumulative += bucket.count();
+        samples.push(format!(
+            "{family}_bucket{{{prefix}le=\"{}\"}} {cumulative}",
+            bucket.end()
+        ));
+    }
+
+    samples.push(format!("{family}_bucket{{{prefix}le=\"+Inf\"}} {count}"));
+    samples.push(format!("{family}_sum{labels} {sum}"));
+    samples.push(format!("{family}_count{labels} {count}"));
+
+    samples
+}
+
 // human formatted stats that can be exposed as human stats or converted to json
 fn human_formatted_stats() -> Vec<String> {
     let mut data = Vec::new();
@@ -779,3 +1453,92 @@ fn human_formatted_stats() -> Vec<String> {
 }
 
 common::metrics::test_no_duplicates!();
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metrics_format() {
+        assert_eq!(MetricsFormat::from_accept(None), MetricsFormat::Prometheus);
+        assert_eq!(
+            MetricsFormat::from_accept(Some("text/plain")),
+            MetricsFormat::Prometheus
+        );
+        assert_eq!(
+            MetricsFormat::from_accept(Some("application/openmetrics-text; version=1.0.0")),
+            MetricsFormat::OpenMetrics
+        );
+
+        // the media type is case insensitive and may be any of the ranges
+        assert_eq!(
+            MetricsFormat::from_accept(Some(
+                "text/plain;q=0.5, Application/OpenMetrics-Text;q=0.9"
+            )),
+            MetricsFormat::OpenMetrics
+        );
+
+        // a quality of zero rejects the type
+        assert_eq!(
+            MetricsFormat::from_accept(Some("application/openmetrics-text;q=0, text/plain")),
+            MetricsFormat::Prometheus
+        );
+        assert_eq!(
+            MetricsFormat::from_accept(Some("application/openmetrics-text; Q=0.000")),
+            MetricsFormat::Prometheus
+        );
+        assert_eq!(
+            MetricsFormat::from_accept(Some("application/openmetrics-text; q=0.001")),
+            MetricsFormat::OpenMetrics
+        );
+    }
+
+    #[test]
+    fn histogram() {
+        let mut histogram = Histogram::new(EXPORT_GROUPING_POWER, 4).unwrap();
+        histogram.increment(1).unwrap();
+        histogram.increment(5).unwrap();
+        histogram.increment(5).unwrap();
+
+        // buckets are cumulative, and the sum is estimated from the bucket
+        // midpoints
+        assert_eq!(
+            format_histogram("latency", "", &histogram),
+            vec![
+                "latency_bucket{le=\"0\"} 0",
+                "latency_bucket{le=\"1\"} 1",
+                "latency_bucket{le=\"2\"} 1",
+                "latency_bucket{le=\"3\"} 1",
+                "latency_bucket{le=\"5\"} 3",
+                "latency_bucket{le=\"7\"} 3",
+                "latency_bucket{le=\"11\"} 3",
+                "latency_bucket{le=\"16\"} 3",
+                "latency_bucket{le=\"+Inf\"} 3",
+                "latency_sum 9",
+                "latency_count 3",
+            ]
+        );
+
+        // the bucket boundary is added to existing labels
+        let samples = format_histogram("latency", "{op=\"get\"}", &histogram);
+        assert_eq!(samples[1], "latency_bucket{op=\"get\", le=\"1\"} 1");
+        assert_eq!(samples[9], "latency_sum{op=\"get\"} 9");
+        assert_eq!(samples[10], "latency_count{op=\"get\"} 3");
+
+        // finer histograms are downsampled for export without losing any of
+        // the samples
+        let mut histogram = Histogram::new(4, 10).unwrap();
+        for value in 0..1000 {
+            histogram.increment(value).unwrap();
+        }
+        let samples = format_histogram("latency", "", &histogram);
+        let buckets = Histogram::new(EXPORT_GROUPING_POWER, 10)
+            .unwrap()
+            .into_iter()
+            .count();
+        assert_eq!(samples.len(), buckets + 3);
+        assert_eq!(samples[buckets - 1], "latency_bucket{le=\"1024\"} 1000");
+        assert_eq!(samples[buckets], "latency_bucket{le=\"+Inf\"} 1000");
+        assert_eq!(samples[buckets + 2], "latency_count 1000");
+    }
+}
@@ -51,6 +51,12 @@ pub static BACKEND_EVENT_TOTAL: Counter = Counter::new();
 )]
 pub static BACKEND_EVENT_WRITE: Counter = Counter::new();
 
+#[metric(
+    name = "backend_handshake_ex",
+    description = "the number of backend sessions closed due to a failed handshake"
+)]
+pub static BACKEND_HANDSHAKE_EX: Counter = Counter::new();
+
 pub struct BackendWorkerBuilder<Proto, Request, Response> {
     free_queue: VecDeque<Token>,
     nevent: usize,
@@ -78,18 +84,39 @@ where
         let nevent = config.nevent();
         let timeout = Duration::from_millis(config.timeout() as u64);
 
+        let connector = if let Some(tls_config) = config.tls() {
+            Connector::from(tls_connector(tls_config)?)
+        } else {
+            Connector::from(TcpConnector::new())
+        };
+
         let mut sessions = Slab::new();
         let mut free_queue = VecDeque::new();
 
-        for endpoint in config.socket_addrs()? {
-            let stream = TcpStream::connect(endpoint)?;
+        for endpoint in config.endpoints()? {
+            let stream = match endpoint {
+                Endpoint::Tcp(addr) => connector.connect(addr)?,
+                Endpoint::Unix(path) => {
+                    if config.tls().is_some() {
+                        return Err(Error::new(
+                            ErrorKind::Other,
+                            "tls is not supported for unix domain socket endpoints",
+                        ));
+                    }
+                    Stream::from(UnixStream::connect(path)?)
+                }
+            };
             let mut session = ClientSession::new(Session::from(stream), protocol.clone());
             let s = sessions.vacant_entry();
             let interest = session.interest();
             session
                 .register(poll.registry(), Token(s.key()), interest)
                 .expect("failed to register");
-            free_queue.push_back(Token(s.key()));
+            // sessions which are still handshaking become available once the
+            // handshake completes in the event loop
+            if !session.is_handshaking() {
+                free_queue.push_back(Token(s.key()));
+            }
             s.insert(session);
         }
 
@@ -110,7 +137,7 @@ where
 
     pub fn build(
         self,
-        data_queue: Queues<(Request, Response, Token), (Request, Token)>,
+        data_queue: Queues<(Request, Response, FrontendToken), (Request, FrontendToken)>,
         signal_queue: Queues<(), Signal>,
     ) -> BackendWorker<Proto, Request, Response> {
         BackendWorker {
@@ -130,12 +157,12 @@ where
 }
 
 pub struct BackendWorker<Proto, Request, Response> {
-    backlog: VecDeque<(Request, Token)>,
-    data_queue: Queues<(Request, Response, Token), (Request, Token)>,
+    backlog: VecDeque<(Request, FrontendToken)>,
+    data_queue: Queues<(Request, Response, FrontendToken), (Request, FrontendToken)>,
     free_queue: VecDeque<Token>,
     nevent: usize,
     protocol: Proto,
-    pending: HashMap<Token, Token>,
+    pending: HashMap<Token, FrontendToken>,
     poll: Poll,
     sessions: Slab<ClientSession<Proto, Request, Response>>,
     signal_queue: Queues<(), Signal>,
@@ -195,6 +222,60 @@ where
         }
     }
 
+    /// Drive the handshake for a session which is not yet established. Once
+    /// the handshake completes, the session is made available for requests.
+    fn handshake(&mut self, token: Token) -> Result<()> {
+        let session = self
+            .sessions
+            .get_mut(token.0)
+            .ok_or_else(|| Error::new(ErrorKind::Other, "non-existant session"))?;
+
+        match session.do_handshake() {
+            Ok(()) => {
+                let interest = session.interest();
+                session.reregister(self.poll.registry(), token, interest)?;
+                self.free_queue.push_back(token);
+                Ok(())
+            }
+            Err(e) => {
+                if e.kind() != ErrorKind::WouldBlock {
+                    BACKEND_HANDSHAKE_EX.increment();
+                }
+                map_err(e)
+            }
+        }
+    }
+
+    /// Send a request to the backend on the given session, flushing the
+    /// session if possible.
+    fn send(&mut self, be_token: Token, request: Request, fe_token: FrontendToken) -> Result<()> {
+        let session = &mut self.sessions[be_token.0];
+        session.send(request)?;
+        self.pending.insert(be_token, fe_token);
+
+        if session.write_pending() > 0 {
+            let interest = session.interest();
+            session.reregister(self.poll.registry(), be_token, interest)?;
+        }
+
+        Ok(())
+    }
+
+    /// Send any requests which were queued while there were no available
+    /// backend sessions.
+    fn drain_backlog(&mut self) {
+        while !self.backlog.is_empty() {
+            if let Some(be_token) = self.free_queue.pop_front() {
+                let (request, fe_token) = self.backlog.pop_front().unwrap();
+                if self.send(be_token, request, fe_token).is_err() {
+                    panic!("we don't handle this right now");
+                }
+            } else {
+                return;
+            }
+        }
+    }
+
     /// Run the worker in a loop, handling new events.
     pub fn run(&mut self) {
         // these are buffers which are re-used in each loop iteration to receive
@@ -229,14 +310,11 @@ where
                         self.data_queue.try_recv_all(&mut messages);
                         for (request, fe_token) in messages.drain(..).map(|v| v.into_inner()) {
                             if let Some(be_token) = self.free_queue.pop_front() {
-                                let session = &mut self.sessions[be_token.0];
-                                if session.send(request).is_err() {
+                                if self.send(be_token, request, fe_token).is_err() {
                                     panic!("we don't handle this right now");
-                                } else {
-                                    self.pending.insert(be_token, fe_token);
                                 }
                             } else {
-                                self.backlog.push_back((request, token));
+                                self.backlog.push_back((request, fe_token));
                             }
                         }
 
@@ -262,6 +340,20 @@ where
                             continue;
                         }
 
+                        // complete the handshake before doing any reads or
+                        // writes on the session
+                        if self
+                            .sessions
+                            .get(token.0)
+                            .map(|s| s.is_handshaking())
+                            .unwrap_or(false)
+                        {
+                            if self.handshake(token).is_err() {
+                                self.close(token);
+                            }
+                            continue;
+                        }
+
                         if event.is_writable() {
                             BACKEND_EVENT_WRITE.increment();
 
@@ -283,6 +375,9 @@ where
                 }
             }
 
+            // sessions may have become available during this iteration
+            self.drain_backlog();
+
             // wakes the storage thread if necessary
             let _ = self.data_queue.wake();
         }
@@ -319,7 +414,10 @@ where
     pub fn build(
         mut self,
         mut data_queues: Vec<
-            Queues<(BackendRequest, BackendResponse, Token), (BackendRequest, Token)>,
+            Queues<
+                (BackendRequest, BackendResponse, FrontendToken),
+                (BackendRequest, FrontendToken),
+            >,
         >,
         mut signal_queues: Vec<Queues<(), Signal>>,
     ) -> Vec<BackendWorker<BackendProto, BackendRequest, BackendResponse>> {
@@ -4,7 +4,8 @@
 
 use super::map_result;
 use crate::*;
-use protocol_common::Protocol;
+use common::metrics::{CommandHistogramCache, CommandHistograms};
+use protocol_common::{Command, Protocol};
 
 #[metric(
     name = "frontend_event_depth",
@@ -48,6 +49,15 @@ pub static FRONTEND_EVENT_TOTAL: Counter = Counter::new();
 )]
 pub static FRONTEND_EVENT_WRITE: Counter = Counter::new();
 
+/// Per-command latency, from receiving a request until the response is
+/// written back to the session.
+pub static FRONTEND_COMMAND_LATENCY: Lazy<CommandHistograms> = Lazy::new(|| {
+    CommandHistograms::new(
+        "frontend_command_latency",
+        "distribution of the time from receiving a request until the response is written, in nanoseconds",
+    )
+});
+
 pub struct FrontendWorkerBuilder<
     FrontendProto,
     FrontendRequest,
@@ -104,7 +114,10 @@ impl<FrontendProto, FrontendRequest, FrontendResponse, BackendRequest, BackendRe
 
     pub fn build(
         self,
-        data_queue: Queues<(BackendRequest, Token), (BackendRequest, BackendResponse, Token)>,
+        data_queue: Queues<
+            (BackendRequest, FrontendToken),
+            (BackendRequest, BackendResponse, FrontendToken),
+        >,
         session_queue: Queues<Session, Session>,
         signal_queue: Queues<(), Signal>,
     ) -> FrontendWorker<
@@ -116,6 +129,7 @@ impl<FrontendProto, FrontendRequest, FrontendResponse, BackendRequest, BackendRe
     > {
         FrontendWorker {
             data_queue,
+            latency: FRONTEND_COMMAND_LATENCY.cache(),
             nevent: self.nevent,
             protocol: self.protocol,
             poll: self.poll,
@@ -135,7 +149,9 @@ pub struct FrontendWorker<
     BackendRequest,
     BackendResponse,
 > {
-    data_queue: Queues<(BackendRequest, Token), (BackendRequest, BackendResponse, Token)>,
+    data_queue:
+        Queues<(BackendRequest, FrontendToken), (BackendRequest, BackendResponse, FrontendToken)>,
+    latency: CommandHistogramCache,
     nevent: usize,
     protocol: FrontendProto,
     poll: Poll,
@@ -159,7 +175,7 @@ where
     FrontendResponse: Compose,
     FrontendResponse: From<BackendResponse>,
     BackendRequest: From<FrontendRequest>,
-    BackendRequest: Compose,
+    BackendRequest: Compose + Command,
     BackendResponse: Compose,
 {
     /// Return the `Session` to the `Listener` to handle flush/close
@@ -183,10 +199,17 @@ where
         map_result(session.fill())?;
 
         // process up to one request
+        let received = Instant::now();
         match session.receive() {
             Ok(request) => self
                 .data_queue
-                .try_send_to(0, (BackendRequest::from(request), token))
+                .try_send_to(
+                    0,
+                    (
+                        BackendRequest::from(request),
+                        FrontendToken { token, received },
+                    ),
+                )
                 .map_err(|_| Error::new(ErrorKind::Other, "data queue is full")),
             Err(e) => map_err(e),
         }
@@ -255,7 +278,7 @@ where
 
                         // handle all pending messages on the data queue
                         self.data_queue.try_recv_all(&mut messages);
-                        for (_request, response, token) in
+                        for (request, response, FrontendToken { token, received }) in
                             messages.drain(..).map(|v| v.into_inner())
                         {
                             if let Some(session) = self.sessions.get_mut(token.0) {
@@ -263,7 +286,17 @@ where
                                     let _ = session.send(FrontendResponse::from(response));
                                     self.close(token);
                                     continue;
-                                } else if session.send(FrontendResponse::from(response)).is_err() {
+                                }
+
+                                let result = session.send(FrontendResponse::from(response));
+                                let latency = Instant::now() - received;
+                                self.latency.increment(
+                                    BackendRequest::PROTOCOL,
+                                    request.command(),
+                                    latency.as_nanos(),
+                                );
+
+                                if result.is_err() {
                                     self.close(token);
                                     continue;
                                 } else if session.write_pending() > 0 {
@@ -385,7 +418,10 @@ where
     pub fn build(
         mut self,
         mut data_queues: Vec<
-            Queues<(BackendRequest, Token), (BackendRequest, BackendResponse, Token)>,
+            Queues<
+                (BackendRequest, FrontendToken),
+                (BackendRequest, BackendResponse, FrontendToken),
+            >,
         >,
         mut session_queues: Vec<Queues<Session, Session>>,
         mut signal_queues: Vec<Queues<(), Signal>>,
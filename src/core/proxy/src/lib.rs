@@ -14,7 +14,7 @@ extern crate metriken;
 use admin::AdminBuilder;
 use clocksource::precise::Instant;
 use common::signal::Signal;
-use common::ssl::tls_acceptor;
+use common::ssl::{tls_acceptor, tls_connector};
 use config::proxy::*;
 use config::*;
 use core::marker::PhantomData;
@@ -25,7 +25,7 @@ use logger::Drain;
 use metriken::*;
 use pelikan_net::event::{Event, Source};
 use pelikan_net::*;
-use protocol_common::{Compose, Execute, Parse};
+use protocol_common::{Command, Compose, Execute, Parse};
 use session::{Buf, ServerSession, Session};
 use slab::Slab;
 use std::io::{Error, ErrorKind, Result};
@@ -68,6 +68,15 @@ pub static PERCENTILES: &[(&str, f64)] = &[
     ("p9999", 99.99),
 ];
 
+/// Identifies the frontend session a request was received on, along with the
+/// time the request was received. This is passed through the backend so that
+/// the frontend can record the latency once it writes the response.
+#[derive(Clone, Copy)]
+pub struct FrontendToken {
+    token: Token,
+    received: Instant,
+}
+
 fn map_err(e: std::io::Error) -> Result<()> {
     match e.kind() {
         ErrorKind::WouldBlock => Ok(()),
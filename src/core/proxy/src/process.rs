@@ -7,6 +7,8 @@ use config::proxy::BackendConfig;
 use config::proxy::FrontendConfig;
 use config::proxy::ListenerConfig;
 use protocol_common::Protocol;
+use serde::Serialize;
+use std::sync::atomic::Ordering;
 use std::thread::JoinHandle;
 
 pub struct ProcessBuilder<
@@ -48,14 +50,16 @@ impl<
     >
 where
     BackendProto: 'static + Protocol<BackendRequest, BackendResponse> + Clone + Send,
-    BackendRequest: 'static + Send + Compose + From<FrontendRequest> + Compose,
+    BackendRequest: 'static + Send + Compose + From<FrontendRequest> + Command,
     BackendResponse: 'static + Compose + Send,
     FrontendProto: 'static + Protocol<FrontendRequest, FrontendResponse> + Clone + Send,
     FrontendRequest: 'static + Send,
     FrontendResponse: 'static + Compose + Send,
     FrontendResponse: From<BackendResponse> + Compose,
 {
-    pub fn new<T: AdminConfig + FrontendConfig + BackendConfig + TlsConfig + ListenerConfig>(
+    pub fn new<
+        T: AdminConfig + FrontendConfig + BackendConfig + TlsConfig + ListenerConfig + Serialize,
+    >(
         config: &T,
         log_drain: Box<dyn Drain>,
         backend_protocol: BackendProto,
@@ -85,6 +89,8 @@ where
         thread_wakers.extend_from_slice(&self.backend.wakers());
         thread_wakers.extend_from_slice(&self.frontend.wakers());
 
+        let ready = self.admin.ready();
+
         // channel for the parent `Process` to send `Signal`s to the admin thread
         let (signal_tx, signal_rx) = bounded(QUEUE_CAPACITY);
 
@@ -155,6 +161,9 @@ where
             })
             .collect();
 
+        // the listener was bound before the threads were spawned
+        ready.store(true, Ordering::Relaxed);
+
         Process {
             admin,
             backend,
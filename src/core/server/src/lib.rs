@@ -79,7 +79,7 @@
 //! specifies a single worker thread, the thread also owns the underlying cache
 //! datastructure. However, if the configuration specifies multiple worker
 //! threads a fully parsed request is handed over to the `storage` thread for
-//! execution.
+//! execution, unless the storage is shared.
 //!
 //! ### Storage
 //! An optional thread which is used only if there is more than one worker
@@ -87,11 +87,18 @@
 //! execute requests. The storage thread will receive requests from a worker
 //! over a queue, execute the request, and returns the result back to the worker
 //! thread.
+//!
+//! ### Shared Storage
+//! If the storage can be safely accessed from multiple threads, such as a
+//! segcache configured with more than one shard, the storage thread is omitted.
+//! Each worker holds a handle to the shared storage and executes requests
+//! directly, using the same event loop as the single worker thread model.
 
 #[macro_use]
 extern crate logger;
 
 use admin::AdminBuilder;
+use clocksource::precise::Instant;
 use common::signal::Signal;
 use common::ssl::tls_acceptor;
 use config::*;
@@ -103,7 +110,7 @@ use logger::{Drain, Klog};
 use metriken::*;
 use pelikan_net::event::{Event, Source};
 use pelikan_net::*;
-use protocol_common::{Compose, Execute};
+use protocol_common::{Command, Compose, Execute};
 use session::{Buf, ServerSession, Session};
 use slab::Slab;
 use std::io::{Error, ErrorKind, Result};
@@ -126,6 +133,10 @@ const QUEUE_RETRIES: usize = 3;
 
 const QUEUE_CAPACITY: usize = 64 * 1024;
 
+// a session which is monitoring requests stops taking events once it has this
+// many bytes waiting to be written
+const MONITOR_MAX_PENDING: usize = 1024 * 1024;
+
 // determines the max number of calls to accept when the listener is ready
 const ACCEPT_BATCH: usize = 8;
 
@@ -81,17 +81,34 @@ impl ListenerBuilder {
         let tls_config = config.tls();
         let config = config.server();
 
-        let addr = config.socket_addr().map_err(|e| {
-            error!("{}", e);
-            std::io::Error::new(std::io::ErrorKind::Other, "Bad listen address")
-        })?;
+        let mut listener = if let Some(path) = config.path() {
+            if tls_acceptor(tls_config)?.is_some() {
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    "tls is not supported for unix domain socket listeners",
+                ));
+            }
+
+            let unix_listener = UnixListener::bind(path)?;
 
-        let tcp_listener = TcpListener::bind(addr)?;
+            if let Some(mode) = config.permissions() {
+                unix_listener.set_permissions(mode)?;
+            }
 
-        let mut listener = if let Some(tls_acceptor) = tls_acceptor(tls_config)? {
-            pelikan_net::Listener::from((tcp_listener, tls_acceptor))
+            pelikan_net::Listener::from(unix_listener)
         } else {
-            pelikan_net::Listener::from(tcp_listener)
+            let addr = config.socket_addr().map_err(|e| {
+                error!("{}", e);
+                std::io::Error::new(std::io::ErrorKind::Other, "Bad listen address")
+            })?;
+
+            let tcp_listener = TcpListener::bind(addr)?;
+
+            if let Some(tls_acceptor) = tls_acceptor(tls_config)? {
+                pelikan_net::Listener::from((tcp_listener, tls_acceptor))
+            } else {
+                pelikan_net::Listener::from(tcp_listener)
+            }
         };
 
         let poll = Poll::new()?;
@@ -284,9 +301,10 @@ impl Listener {
         info!(
             "running server on: {}",
             self.listener
-                .local_addr()
-                .map(|v| format!("{v}"))
-                .unwrap_or_else(|_| "unknown address".to_string())
+                .local_path()
+                .map(|v| v.display().to_string())
+                .or_else(|| self.listener.local_addr().ok().map(|v| format!("{v}")))
+                .unwrap_or_else(|| "unknown address".to_string())
         );
 
         let mut events = Events::with_capacity(self.nevent);
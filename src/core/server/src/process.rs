@@ -5,8 +5,10 @@
 use crate::*;
 use libc::c_int;
 use protocol_common::Protocol;
+use serde::Serialize;
 use signal_hook::consts::signal::*;
 use signal_hook::iterator::Signals;
+use std::sync::atomic::Ordering;
 use std::thread::JoinHandle;
 
 pub struct ProcessBuilder<Parser, Request, Response, Storage> {
@@ -19,17 +21,21 @@ pub struct ProcessBuilder<Parser, Request, Response, Storage> {
 impl<P, Request, Response, Storage> ProcessBuilder<P, Request, Response, Storage>
 where
     P: 'static + Protocol<Request, Response> + Clone + Send,
-    Request: 'static + Klog + Klog<Response = Response> + Send,
+    Request: 'static + Klog + Klog<Response = Response> + Command + Send,
     Response: 'static + Compose + Send,
     Storage: 'static + Execute<Request, Response> + EntryStore + Send,
 {
-    pub fn new<T: AdminConfig + ServerConfig + TlsConfig + WorkerConfig>(
+    pub fn new<T: AdminConfig + ServerConfig + TlsConfig + WorkerConfig + Serialize>(
         config: &T,
         log_drain: Box<dyn Drain>,
         protocol: P,
         storage: Storage,
     ) -> Result<Self> {
-        let admin = AdminBuilder::new(config)?;
+        let mut admin = AdminBuilder::new(config)?;
+        if let Some(storage) = storage.admin_handle() {
+            admin.storage(Box::new(storage));
+        }
+
         let listener = ListenerBuilder::new(config)?;
         let workers = WorkersBuilder::new(config, protocol, storage)?;
 
@@ -50,6 +56,8 @@ where
         let mut thread_wakers = vec![self.listener.waker()];
         thread_wakers.extend_from_slice(&self.workers.wakers());
 
+        let ready = self.admin.ready();
+
         // channel for the parent `Process` to send `Signal`s to the admin thread
         let (signal_tx, signal_rx) = bounded(QUEUE_CAPACITY);
 
@@ -85,6 +93,11 @@ where
             .unwrap();
 
         let workers = workers.spawn();
+
+        // the storage was initialized and the listener was bound before the
+        // threads were spawned
+        ready.store(true, Ordering::Relaxed);
+
         let cloned_signal_tx = signal_tx.clone();
 
         // NOTE: Signal handler join handle is not taken ownership of by [Process] as it's
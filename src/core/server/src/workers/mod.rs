@@ -3,7 +3,12 @@
 // http://www.apache.org/licenses/LICENSE-2.0
 
 use crate::*;
-use protocol_common::Protocol;
+use common::metrics::{CommandHistogramCache, CommandHistograms};
+use common::monitor::{MonitorEvent, Observer, MONITOR};
+use common::slowlog::SLOWLOG;
+use protocol_common::{Command, Protocol};
+use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::thread::JoinHandle;
 
 mod multi;
@@ -56,6 +61,80 @@ pub static WORKER_EVENT_TOTAL: Counter = Counter::new();
 )]
 pub static WORKER_EVENT_WRITE: Counter = Counter::new();
 
+/// Per-command service time, from parsing a request until its response is
+/// composed into the session buffer.
+pub static WORKER_COMMAND_LATENCY: Lazy<CommandHistograms> = Lazy::new(|| {
+    CommandHistograms::new(
+        "worker_command_latency",
+        "distribution of the time from parsing a request until the response is composed, in nanoseconds",
+    )
+});
+
+/// Per-command time spent executing requests against the storage.
+pub static STORAGE_COMMAND_LATENCY: Lazy<CommandHistograms> = Lazy::new(|| {
+    CommandHistograms::new(
+        "storage_command_latency",
+        "distribution of the time to execute a request against the storage, in nanoseconds",
+    )
+});
+
+/// Identifies the session a request was received on, along with the time the
+/// request was parsed. This is passed through the storage thread so that the
+/// worker can record the service time once it composes the response.
+#[derive(Clone, Copy)]
+pub struct WorkerToken {
+    token: Token,
+    parsed: Instant,
+}
+
+// records the time elapsed since `start` for the command of the request
+fn record_latency<Request: Command>(
+    histograms: &mut CommandHistogramCache,
+    request: &Request,
+    start: Instant,
+) {
+    let latency = Instant::now() - start;
+    histograms.increment(Request::PROTOCOL, request.command(), latency.as_nanos());
+}
+
+// records the request in the slow log if the time elapsed since it was parsed
+// exceeds the threshold. The client address is only looked up for requests
+// which are recorded.
+fn record_slowlog<Request: Command>(
+    request: &Request,
+    parsed: Instant,
+    client: impl FnOnce() -> Option<SocketAddr>,
+) {
+    let duration = Duration::from_nanos((Instant::now() - parsed).as_nanos());
+    if SLOWLOG.is_slow(duration) {
+        SLOWLOG.record(request.command(), || request.args(), duration, client());
+    }
+}
+
+// publishes a request to any sessions which are monitoring requests. The
+// request is only described if there is an observer to receive it.
+fn publish<Request: Command>(request: &Request, client: impl FnOnce() -> Option<SocketAddr>) {
+    MONITOR.publish(|| MonitorEvent::new(request.command(), request.args(), client()));
+}
+
+// sends the queued events to a session which is monitoring requests and
+// attempts to flush them
+fn drain_monitor<Proto, Request, Response>(
+    session: &mut ServerSession<Proto, Response, Request>,
+    observer: &Observer,
+) -> Result<()>
+where
+    Proto: Protocol<Request, Response>,
+    Response: Compose,
+{
+    session.send_monitor(observer, MONITOR_MAX_PENDING);
+
+    match session.flush() {
+        Ok(_) => Ok(()),
+        Err(e) => map_err(e),
+    }
+}
+
 fn map_result(result: Result<usize>) -> Result<()> {
     match result {
         Ok(0) => Err(Error::new(ErrorKind::Other, "client hangup")),
@@ -73,14 +152,17 @@ pub enum Workers<Parser, Request, Response, Storage> {
     },
     Multi {
         workers: Vec<MultiWorker<Parser, Request, Response>>,
-        storage: StorageWorker<Request, Response, Storage, Token>,
+        storage: StorageWorker<Request, Response, Storage, WorkerToken>,
+    },
+    Shared {
+        workers: Vec<SingleWorker<Parser, Request, Response, Storage>>,
     },
 }
 
 impl<Proto, Request, Response, Storage> Workers<Proto, Request, Response, Storage>
 where
     Proto: 'static + Protocol<Request, Response> + Clone + Send,
-    Request: 'static + Klog + Klog<Response = Response> + Send,
+    Request: 'static + Klog + Klog<Response = Response> + Command + Send,
     Response: 'static + Compose + Send,
     Storage: 'static + EntryStore + Execute<Request, Response> + Send,
 {
@@ -112,6 +194,16 @@ where
 
                 join_handles
             }
+            Self::Shared { mut workers } => workers
+                .drain(..)
+                .enumerate()
+                .map(|(id, mut worker)| {
+                    std::thread::Builder::new()
+                        .name(format!("{THREAD_PREFIX}_work_{id}"))
+                        .spawn(move || worker.run())
+                        .unwrap()
+                })
+                .collect(),
         }
     }
 }
@@ -124,6 +216,9 @@ pub enum WorkersBuilder<Proto, Request, Response, Storage> {
         workers: Vec<MultiWorkerBuilder<Proto, Request, Response>>,
         storage: StorageWorkerBuilder<Request, Response, Storage>,
     },
+    Shared {
+        workers: Vec<SingleWorkerBuilder<Proto, Request, Response, Storage>>,
+    },
 }
 
 impl<Proto, Request, Response, Storage> WorkersBuilder<Proto, Request, Response, Storage>
@@ -135,7 +230,18 @@ where
     pub fn new<T: WorkerConfig>(config: &T, protocol: Proto, storage: Storage) -> Result<Self> {
         let threads = config.worker().threads();
 
-        if threads > 1 {
+        if threads > 1 && storage.share().is_some() {
+            // each worker executes requests directly against its own handle
+            // to the shared storage
+            let mut workers = vec![];
+            for _ in 1..threads {
+                let storage = storage.share().unwrap();
+                workers.push(SingleWorkerBuilder::new(config, protocol.clone(), storage)?);
+            }
+            workers.push(SingleWorkerBuilder::new(config, protocol, storage)?);
+
+            Ok(Self::Shared { workers })
+        } else if threads > 1 {
             let mut workers = vec![];
             for _ in 0..threads {
                 workers.push(MultiWorkerBuilder::new(config, protocol.clone())?)
@@ -161,6 +267,7 @@ where
                 workers,
                 storage: _,
             } => workers.iter().map(|w| w.waker()).collect(),
+            Self::Shared { workers } => workers.iter().map(|w| w.waker()).collect(),
         }
     }
 
@@ -176,6 +283,7 @@ where
                 }
                 wakers
             }
+            Self::Shared { workers } => workers.iter().map(|w| w.waker()).collect(),
         }
     }
 
@@ -221,6 +329,12 @@ where
             Self::Single { worker } => Workers::Single {
                 worker: worker.build(session_queues.remove(0), signal_queues.remove(0)),
             },
+            Self::Shared { mut workers } => Workers::Shared {
+                workers: workers
+                    .drain(..)
+                    .map(|worker| worker.build(session_queues.remove(0), signal_queues.remove(0)))
+                    .collect(),
+            },
         }
     }
 }
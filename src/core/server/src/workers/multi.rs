@@ -42,12 +42,13 @@ impl<Proto, Request, Response> MultiWorkerBuilder<Proto, Request, Response> {
 
     pub fn build(
         self,
-        data_queue: Queues<(Request, Token), (Request, Response, Token)>,
+        data_queue: Queues<(Request, WorkerToken), (Request, Response, WorkerToken)>,
         session_queue: Queues<Session, Session>,
         signal_queue: Queues<(), Signal>,
     ) -> MultiWorker<Proto, Request, Response> {
         MultiWorker {
             data_queue,
+            monitors: HashMap::new(),
             nevent: self.nevent,
             protocol: self.protocol,
             poll: self.poll,
@@ -56,12 +57,14 @@ impl<Proto, Request, Response> MultiWorkerBuilder<Proto, Request, Response> {
             signal_queue,
             timeout: self.timeout,
             waker: self.waker,
+            worker_latency: WORKER_COMMAND_LATENCY.cache(),
         }
     }
 }
 
 pub struct MultiWorker<Proto, Request, Response> {
-    data_queue: Queues<(Request, Token), (Request, Response, Token)>,
+    data_queue: Queues<(Request, WorkerToken), (Request, Response, WorkerToken)>,
+    monitors: HashMap<Token, Observer>,
     nevent: usize,
     protocol: Proto,
     poll: Poll,
@@ -70,16 +73,19 @@ pub struct MultiWorker<Proto, Request, Response> {
     signal_queue: Queues<(), Signal>,
     timeout: Duration,
     waker: Arc<Waker>,
+    worker_latency: CommandHistogramCache,
 }
 
 impl<Proto, Request, Response> MultiWorker<Proto, Request, Response>
 where
     Proto: Protocol<Request, Response> + Clone,
-    Request: Klog + Klog<Response = Response>,
+    Request: Klog + Klog<Response = Response> + Command,
     Response: Compose,
 {
     /// Return the `Session` to the `Listener` to handle flush/close
     fn close(&mut self, token: Token) {
+        self.monitors.remove(&token);
+
         if self.sessions.contains(token.0) {
             let mut session = self.sessions.remove(token.0).into_inner();
             let _ = session.deregister(self.poll.registry());
@@ -99,11 +105,14 @@ where
         map_result(session.fill())?;
 
         // process up to one request
+        let parsed = Instant::now();
         match session.receive() {
-            Ok(request) => self
-                .data_queue
-                .try_send_to(0, (request, token))
-                .map_err(|_| Error::new(ErrorKind::Other, "data queue is full")),
+            Ok(request) => {
+                publish(&request, || session.peer_addr().ok());
+                self.data_queue
+                    .try_send_to(0, (request, WorkerToken { token, parsed }))
+                    .map_err(|_| Error::new(ErrorKind::Other, "data queue is full"))
+            }
             Err(e) => map_err(e),
         }
     }
@@ -121,6 +130,40 @@ where
         }
     }
 
+    /// Send the requests seen by the monitor to each session which is
+    /// monitoring requests. This happens once per iteration of the event loop,
+    /// so an idle worker delivers events after at most the poll timeout.
+    fn monitor(&mut self) {
+        let mut closed = Vec::new();
+
+        for (token, observer) in &self.monitors {
+            let session = match self.sessions.get_mut(token.0) {
+                Some(session) => session,
+                None => {
+                    closed.push(*token);
+                    continue;
+                }
+            };
+
+            let result = drain_monitor(session, observer).and_then(|_| {
+                if session.write_pending() > 0 {
+                    let interest = session.interest();
+                    session.reregister(self.poll.registry(), *token, interest)
+                } else {
+                    Ok(())
+                }
+            });
+
+            if result.is_err() {
+                closed.push(*token);
+            }
+        }
+
+        for token in closed {
+            self.close(token);
+        }
+    }
+
     /// Run the worker in a loop, handling new events.
     pub fn run(&mut self) {
         // these are buffers which are re-used in each loop iteration to receive
@@ -171,7 +214,8 @@ where
 
                         // handle all pending messages on the data queue
                         self.data_queue.try_recv_all(&mut messages);
-                        for (request, response, token) in messages.drain(..).map(|v| v.into_inner())
+                        for (request, response, WorkerToken { token, parsed }) in
+                            messages.drain(..).map(|v| v.into_inner())
                         {
                             request.klog(&response);
                             if let Some(session) = self.sessions.get_mut(token.0) {
@@ -179,7 +223,16 @@ where
                                     let _ = session.send(response);
                                     self.close(token);
                                     continue;
-                                } else if session.send(response).is_err() {
+                                }
+
+                                let result = session.send(response);
+                                record_latency(&mut self.worker_latency, &request, parsed);
+                                record_slowlog(&request, parsed, || session.peer_addr().ok());
+                                if request.is_monitor() {
+                                    self.monitors.insert(token, MONITOR.subscribe(1));
+                                }
+
+                                if result.is_err() {
                                     self.close(token);
                                     continue;
                                 } else if session.write_pending() > 0 {
@@ -256,6 +309,8 @@ where
                 }
             }
 
+            self.monitor();
+
             // wakes the storage thread if necessary
             let _ = self.data_queue.wake();
         }
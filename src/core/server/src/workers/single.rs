@@ -51,6 +51,7 @@ impl<Proto, Request, Response, Storage> SingleWorkerBuilder<Proto, Request, Resp
         signal_queue: Queues<(), Signal>,
     ) -> SingleWorker<Proto, Request, Response, Storage> {
         SingleWorker {
+            monitors: HashMap::new(),
             nevent: self.nevent,
             protocol: self.protocol,
             pending: self.pending,
@@ -59,13 +60,16 @@ impl<Proto, Request, Response, Storage> SingleWorkerBuilder<Proto, Request, Resp
             sessions: self.sessions,
             signal_queue,
             storage: self.storage,
+            storage_latency: STORAGE_COMMAND_LATENCY.cache(),
             timeout: self.timeout,
             waker: self.waker,
+            worker_latency: WORKER_COMMAND_LATENCY.cache(),
         }
     }
 }
 
 pub struct SingleWorker<Proto, Request, Response, Storage> {
+    monitors: HashMap<Token, Observer>,
     nevent: usize,
     protocol: Proto,
     pending: VecDeque<Token>,
@@ -74,19 +78,23 @@ pub struct SingleWorker<Proto, Request, Response, Storage> {
     sessions: Slab<ServerSession<Proto, Response, Request>>,
     signal_queue: Queues<(), Signal>,
     storage: Storage,
+    storage_latency: CommandHistogramCache,
     timeout: Duration,
     waker: Arc<Waker>,
+    worker_latency: CommandHistogramCache,
 }
 
 impl<Proto, Request, Response, Storage> SingleWorker<Proto, Request, Response, Storage>
 where
     Proto: Protocol<Request, Response> + Clone,
-    Request: Klog + Klog<Response = Response>,
+    Request: Klog + Klog<Response = Response> + Command,
     Response: Compose,
     Storage: EntryStore + Execute<Request, Response>,
 {
     /// Return the `Session` to the `Listener` to handle flush/close
     fn close(&mut self, token: Token) {
+        self.monitors.remove(&token);
+
         if self.sessions.contains(token.0) {
             let mut session = self.sessions.remove(token.0).into_inner();
             let _ = self.poll.registry().deregister(&mut session);
@@ -106,16 +114,26 @@ where
         map_result(session.fill())?;
 
         // process up to one pending request
+        let parsed = Instant::now();
         match session.receive() {
             Ok(request) => {
+                publish(&request, || session.peer_addr().ok());
+                let start = Instant::now();
                 let response = self.storage.execute(&request);
+                record_latency(&mut self.storage_latency, &request, start);
                 PROCESS_REQ.increment();
                 if response.should_hangup() {
                     let _ = session.send(response);
                     return Err(Error::new(ErrorKind::Other, "should hangup"));
                 }
                 request.klog(&response);
-                match session.send(response) {
+                let result = session.send(response);
+                record_latency(&mut self.worker_latency, &request, parsed);
+                record_slowlog(&request, parsed, || session.peer_addr().ok());
+                if request.is_monitor() {
+                    self.monitors.insert(token, MONITOR.subscribe(1));
+                }
+                match result {
                     Ok(_) => {
                         // attempt to flush immediately if there's now data in
                         // the write buffer
@@ -178,6 +196,40 @@ where
         }
     }
 
+    /// Send the requests seen by the monitor to each session which is
+    /// monitoring requests. This happens once per iteration of the event loop,
+    /// so an idle worker delivers events after at most the poll timeout.
+    fn monitor(&mut self) {
+        let mut closed = Vec::new();
+
+        for (token, observer) in &self.monitors {
+            let session = match self.sessions.get_mut(token.0) {
+                Some(session) => session,
+                None => {
+                    closed.push(*token);
+                    continue;
+                }
+            };
+
+            let result = drain_monitor(session, observer).and_then(|_| {
+                if session.write_pending() > 0 {
+                    let interest = session.interest();
+                    self.poll.registry().reregister(session, *token, interest)
+                } else {
+                    Ok(())
+                }
+            });
+
+            if result.is_err() {
+                closed.push(*token);
+            }
+        }
+
+        for token in closed {
+            self.close(token);
+        }
+    }
+
     /// Run the worker in a loop, handling new events.
     pub fn run(&mut self) {
         let mut events = Events::with_capacity(self.nevent);
@@ -282,6 +334,8 @@ where
                     }
                 }
             }
+
+            self.monitor();
         }
     }
 }
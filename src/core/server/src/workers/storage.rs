@@ -2,7 +2,7 @@
 // Licensed under the Apache License, Version 2.0
 // http://www.apache.org/licenses/LICENSE-2.0
 
-use crate::*;
+use super::*;
 
 #[metric(
     name = "storage_event_loop",
@@ -54,7 +54,7 @@ impl<Request, Response, Storage> StorageWorkerBuilder<Request, Response, Storage
         self.waker.clone()
     }
 
-    pub fn build(
+    pub fn build<Token>(
         self,
         data_queue: Queues<(Request, Response, Token), (Request, Token)>,
         signal_queue: Queues<(), Signal>,
@@ -65,6 +65,7 @@ impl<Request, Response, Storage> StorageWorkerBuilder<Request, Response, Storage
             poll: self.poll,
             signal_queue,
             storage: self.storage,
+            storage_latency: STORAGE_COMMAND_LATENCY.cache(),
             timeout: self.timeout,
             waker: self.waker,
             _request: PhantomData,
@@ -79,6 +80,7 @@ pub struct StorageWorker<Request, Response, Storage, Token> {
     poll: Poll,
     signal_queue: Queues<(), Signal>,
     storage: Storage,
+    storage_latency: CommandHistogramCache,
     timeout: Duration,
     #[allow(dead_code)]
     waker: Arc<Waker>,
@@ -89,7 +91,7 @@ pub struct StorageWorker<Request, Response, Storage, Token> {
 impl<Request, Response, Storage, Token> StorageWorker<Request, Response, Storage, Token>
 where
     Storage: Execute<Request, Response> + EntryStore,
-    Request: Klog + Klog<Response = Response>,
+    Request: Klog + Klog<Response = Response> + Command,
     Response: Compose,
 {
     /// Run the `StorageWorker` in a loop, handling new session events.
@@ -120,7 +122,9 @@ where
                     let sender = message.sender();
                     let (request, token) = message.into_inner();
                     trace!("handling request from worker: {}", sender);
+                    let start = Instant::now();
                     let response = self.storage.execute(&request);
+                    record_latency(&mut self.storage_latency, &request, start);
                     PROCESS_REQ.increment();
                     let mut message = (request, response, token);
                     for retry in 0..QUEUE_RETRIES {
@@ -13,6 +13,8 @@ mod segcache;
 pub use self::noop::*;
 pub use self::segcache::*;
 
+pub use ::segcache::{KeyInfo, SegmentStats, TtlStats};
+
 /// A trait defining the basic requirements of a type which may be used for
 /// storage.
 pub trait EntryStore {
@@ -24,4 +26,56 @@ pub trait EntryStore {
 
     /// Remove all existing values from the entry store.
     fn clear(&mut self);
+
+    /// Returns another handle to the same storage which may be used from a
+    /// different thread, allowing each worker to execute requests directly.
+    /// Storage types which require exclusive access return `None`, which is
+    /// the default.
+    fn share(&self) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
+    /// Returns another handle to the same storage for use by the admin thread,
+    /// which inspects the storage while workers are executing requests. This
+    /// defaults to the handle returned by `share`.
+    fn admin_handle(&self) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        self.share()
+    }
+
+    /// Returns metadata for up to `count` keys starting from the cursor, along
+    /// with the cursor from which to resume. A cursor of zero starts a new
+    /// scan and is returned once the scan is complete. Storage types which
+    /// cannot enumerate their keys return `None`, which is the default.
+    fn scan(&mut self, cursor: u64, count: usize) -> Option<(u64, Vec<KeyInfo>)> {
+        let _ = (cursor, count);
+        None
+    }
+
+    /// Returns a summary of the segments which hold the storage contents.
+    /// Storage types which are not segment-structured return `None`, which is
+    /// the default.
+    fn segment_stats(&mut self) -> Option<SegmentStats> {
+        None
+    }
+
+    /// Returns the occupancy of each TTL bucket which holds items. Storage
+    /// types which do not group items by TTL return `None`, which is the
+    /// default.
+    fn ttl_stats(&mut self) -> Option<Vec<TtlStats>> {
+        None
+    }
+
+    /// Writes a snapshot of the storage contents to the configured location,
+    /// returning the number of entries which were written. Storage types which
+    /// do not support snapshots, or which have no snapshot location
+    /// configured, return `None`, which is the default.
+    fn save_snapshot(&mut self) -> Option<std::io::Result<usize>> {
+        None
+    }
 }
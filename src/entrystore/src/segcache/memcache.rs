@@ -42,25 +42,17 @@ impl Storage for Seg {
     fn get(&mut self, get: &Get) -> Response {
         let mut values = Vec::with_capacity(get.keys().len());
         for key in get.keys().iter() {
-            if let Some(item) = self.data.get(key) {
+            let value = self.data.get(key, |item| {
                 let o = item.optional().unwrap_or(&[0, 0, 0, 0]);
                 let flags = u32::from_be_bytes([o[0], o[1], o[2], o[3]]);
                 match item.value() {
-                    segcache::Value::Bytes(b) => {
-                        values.push(Value::new(item.key(), flags, None, b));
-                    }
+                    segcache::Value::Bytes(b) => Value::new(item.key(), flags, None, b),
                     segcache::Value::U64(v) => {
-                        values.push(Value::new(
-                            item.key(),
-                            flags,
-                            None,
-                            format!("{v}").as_bytes(),
-                        ));
+                        Value::new(item.key(), flags, None, format!("{v}").as_bytes())
                     }
                 }
-            } else {
-                values.push(Value::none(key));
-            }
+            });
+            values.push(value.unwrap_or_else(|| Value::none(key)));
         }
         Values::new(values.into_boxed_slice()).into()
     }
@@ -68,25 +60,18 @@ impl Storage for Seg {
     fn gets(&mut self, get: &Get) -> Response {
         let mut values = Vec::with_capacity(get.keys().len());
         for key in get.keys().iter() {
-            if let Some(item) = self.data.get(key) {
+            let value = self.data.get(key, |item| {
                 let o = item.optional().unwrap_or(&[0, 0, 0, 0]);
                 let flags = u32::from_be_bytes([o[0], o[1], o[2], o[3]]);
+                let cas = Some(item.cas().into());
                 match item.value() {
-                    segcache::Value::Bytes(b) => {
-                        values.push(Value::new(item.key(), flags, Some(item.cas().into()), b));
-                    }
+                    segcache::Value::Bytes(b) => Value::new(item.key(), flags, cas, b),
                     segcache::Value::U64(v) => {
-                        values.push(Value::new(
-                            item.key(),
-                            flags,
-                            Some(item.cas().into()),
-                            format!("{v}").as_bytes(),
-                        ));
+                        Value::new(item.key(), flags, cas, format!("{v}").as_bytes())
                     }
                 }
-            } else {
-                values.push(Value::none(key));
-            }
+            });
+            values.push(value.unwrap_or_else(|| Value::none(key)));
         }
         Values::new(values.into_boxed_slice()).into()
     }
@@ -108,7 +93,7 @@ impl Storage for Seg {
                         Some(&set.flags().to_be_bytes()),
                         Duration::from_secs(ttl as u64),
                     )
-                    .is_ok()
+                    .stored()
                 {
                     Response::stored(set.noreply())
                 } else {
@@ -122,7 +107,7 @@ impl Storage for Seg {
                     Some(&set.flags().to_be_bytes()),
                     Duration::from_secs(ttl as u64),
                 )
-                .is_ok()
+                .stored()
             {
                 Response::stored(set.noreply())
             } else {
@@ -136,7 +121,7 @@ impl Storage for Seg {
                 Some(&set.flags().to_be_bytes()),
                 Duration::from_secs(ttl as u64),
             )
-            .is_ok()
+            .stored()
         {
             Response::stored(set.noreply())
         } else {
@@ -145,7 +130,11 @@ impl Storage for Seg {
     }
 
     fn add(&mut self, add: &Add) -> Response {
-        if self.data.get_no_freq_incr(add.key()).is_some() {
+        // hold the lock so the key cannot change between the check and the
+        // write
+        let mut shard = self.data.lock(add.key());
+
+        if shard.get_no_freq_incr(add.key()).is_some() {
             return Response::not_stored(add.noreply());
         }
 
@@ -153,47 +142,44 @@ impl Storage for Seg {
 
         if ttl < 0 {
             // immediate expire maps to a delete
-            self.data.delete(add.key());
+            shard.delete(add.key());
             Response::stored(add.noreply())
         } else if let Ok(s) = std::str::from_utf8(add.value()) {
             if let Ok(v) = s.parse::<u64>() {
-                if self
-                    .data
+                if shard
                     .insert(
                         add.key(),
                         v,
                         Some(&add.flags().to_be_bytes()),
                         Duration::from_secs(ttl as u64),
                     )
-                    .is_ok()
+                    .stored()
                 {
                     Response::stored(add.noreply())
                 } else {
                     Response::server_error("")
                 }
-            } else if self
-                .data
+            } else if shard
                 .insert(
                     add.key(),
                     add.value(),
                     Some(&add.flags().to_be_bytes()),
                     Duration::from_secs(ttl as u64),
                 )
-                .is_ok()
+                .stored()
             {
                 Response::stored(add.noreply())
             } else {
                 Response::server_error("")
             }
-        } else if self
-            .data
+        } else if shard
             .insert(
                 add.key(),
                 add.value(),
                 Some(&add.flags().to_be_bytes()),
                 Duration::from_secs(ttl as u64),
             )
-            .is_ok()
+            .stored()
         {
             Response::stored(add.noreply())
         } else {
@@ -202,7 +188,11 @@ impl Storage for Seg {
     }
 
     fn replace(&mut self, replace: &Replace) -> Response {
-        if self.data.get_no_freq_incr(replace.key()).is_none() {
+        // hold the lock so the key cannot change between the check and the
+        // write
+        let mut shard = self.data.lock(replace.key());
+
+        if shard.get_no_freq_incr(replace.key()).is_none() {
             return Response::not_stored(replace.noreply());
         }
 
@@ -210,47 +200,44 @@ impl Storage for Seg {
 
         if ttl < 0 {
             // immediate expire maps to a delete
-            self.data.delete(replace.key());
+            shard.delete(replace.key());
             Response::stored(replace.noreply())
         } else if let Ok(s) = std::str::from_utf8(replace.value()) {
             if let Ok(v) = s.parse::<u64>() {
-                if self
-                    .data
+                if shard
                     .insert(
                         replace.key(),
                         v,
                         Some(&replace.flags().to_be_bytes()),
                         Duration::from_secs(ttl as u64),
                     )
-                    .is_ok()
+                    .stored()
                 {
                     Response::stored(replace.noreply())
                 } else {
                     Response::server_error("")
                 }
-            } else if self
-                .data
+            } else if shard
                 .insert(
                     replace.key(),
                     replace.value(),
                     Some(&replace.flags().to_be_bytes()),
                     Duration::from_secs(ttl as u64),
                 )
-                .is_ok()
+                .stored()
             {
                 Response::stored(replace.noreply())
             } else {
                 Response::server_error("")
             }
-        } else if self
-            .data
+        } else if shard
             .insert(
                 replace.key(),
                 replace.value(),
                 Some(&replace.flags().to_be_bytes()),
                 Duration::from_secs(ttl as u64),
             )
-            .is_ok()
+            .stored()
         {
             Response::stored(replace.noreply())
         } else {
@@ -318,43 +305,47 @@ impl Storage for Seg {
             Duration::from_secs(ttl as u64)
         };
 
+        // hold the lock so that the item cannot be replaced before it is
+        // deleted
+        let mut shard = self.data.lock(cas.key());
+
         let response = if let Ok(s) = std::str::from_utf8(cas.value()) {
             if let Ok(v) = s.parse::<u64>() {
-                match self.data.cas(
+                match shard.cas(
                     cas.key(),
                     v,
                     Some(&cas.flags().to_be_bytes()),
                     ttl,
                     cas.cas() as u32,
                 ) {
-                    Ok(_) => Response::stored(cas.noreply()),
+                    Ok(_) | Err(SegcacheError::Rejected) => Response::stored(cas.noreply()),
                     Err(SegcacheError::NotFound) => Response::not_found(cas.noreply()),
                     Err(SegcacheError::Exists) => Response::exists(cas.noreply()),
                     Err(_) => Response::error(),
                 }
             } else {
-                match self.data.cas(
+                match shard.cas(
                     cas.key(),
                     cas.value(),
                     Some(&cas.flags().to_be_bytes()),
                     ttl,
                     cas.cas() as u32,
                 ) {
-                    Ok(_) => Response::stored(cas.noreply()),
+                    Ok(_) | Err(SegcacheError::Rejected) => Response::stored(cas.noreply()),
                     Err(SegcacheError::NotFound) => Response::not_found(cas.noreply()),
                     Err(SegcacheError::Exists) => Response::exists(cas.noreply()),
                     Err(_) => Response::error(),
                 }
             }
         } else {
-            match self.data.cas(
+            match shard.cas(
                 cas.key(),
                 cas.value(),
                 Some(&cas.flags().to_be_bytes()),
                 ttl,
                 cas.cas() as u32,
             ) {
-                Ok(_) => Response::stored(cas.noreply()),
+                Ok(_) | Err(SegcacheError::Rejected) => Response::stored(cas.noreply()),
                 Err(SegcacheError::NotFound) => Response::not_found(cas.noreply()),
                 Err(SegcacheError::Exists) => Response::exists(cas.noreply()),
                 Err(_) => Response::error(),
@@ -365,7 +356,7 @@ impl Storage for Seg {
         // item.
         if delete_after {
             if let Response::Stored(_) = response {
-                self.data.delete(cas.key());
+                shard.delete(cas.key());
             }
         }
 
@@ -7,19 +7,29 @@
 //! See: [`::segcache`] crate for more details behind the underlying storage
 //! design.
 
-use crate::EntryStore;
+use crate::{EntryStore, KeyInfo, SegmentStats, TtlStats};
 
-use config::seg::Eviction;
+use config::seg::{Admission, Compression, Eviction};
 use config::SegConfig;
 use segcache::{Policy, SegcacheError};
 
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Error, ErrorKind};
+use std::path::PathBuf;
+
 mod memcache;
 mod resp;
 
 /// A wrapper around [`seg::Seg`] which implements `EntryStore` and storage
 /// protocol traits.
+///
+/// When configured with more than one shard, or with namespaces, the storage
+/// may be shared between worker threads. Commands which read and then write a
+/// key, such as `add` or `append`, hold the lock on the shard for the key until
+/// they complete, so they are atomic with respect to other workers.
 pub struct Seg {
-    data: segcache::Segcache,
+    data: segcache::ConcurrentSegcache,
+    snapshot_path: Option<PathBuf>,
 }
 
 impl Seg {
@@ -41,19 +51,82 @@ impl Seg {
                 merge: config.merge_target(),
                 compact: config.compact_target(),
             },
+            Eviction::Sieve => Policy::Sieve {
+                max: config.merge_max(),
+            },
+            Eviction::S3Fifo => Policy::S3Fifo {
+                max: config.merge_max(),
+            },
+        };
+
+        let compression = match config.compression() {
+            Compression::None => segcache::Compression::None,
+            Compression::Lz4 => segcache::Compression::Lz4,
+            Compression::Zstd => segcache::Compression::Zstd {
+                level: config.compression_level(),
+            },
+        };
+
+        let admission = match config.admission() {
+            Admission::None => segcache::Admission::None,
+            Admission::TinyLfu => segcache::Admission::TinyLfu,
         };
 
         // build the datastructure from the config
-        let data = segcache::Segcache::builder()
+        let mut builder = segcache::Segcache::builder()
             .hash_power(config.hash_power())
+            .hash_power_max(config.hash_power_max())
             .overflow_factor(config.overflow_factor())
             .heap_size(config.heap_size())
             .segment_size(config.segment_size())
             .eviction(eviction)
+            .large_item_max(config.item_max_size())
+            .compression(compression)
+            .compression_threshold(config.compression_threshold())
+            .admission(admission)
             .datapool_path(config.datapool_path())
-            .build()?;
+            .flash_path(config.flash_path())
+            .flash_size(config.flash_size())
+            .shards(config.shards());
+
+        for namespace in config.namespaces() {
+            // requests reach the storage without the listener they arrived on,
+            // so a namespace can only be selected by key prefix
+            if let Some(port) = namespace.port() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "namespace {}: selecting a namespace by listener port ({port}) is not supported",
+                        namespace.name()
+                    ),
+                ));
+            }
+            builder = builder.namespace(
+                namespace.name(),
+                namespace.prefix().as_bytes(),
+                namespace.heap_size(),
+            );
+        }
+
+        let data = builder.build_concurrent()?;
+
+        // restore the contents from the snapshot if there is one
+        let snapshot_path = config.snapshot_path();
+        if let Some(path) = snapshot_path.as_ref().filter(|path| path.exists()) {
+            File::open(path)
+                .and_then(|file| data.load_snapshot(BufReader::new(file)))
+                .map_err(|e| {
+                    Error::new(
+                        e.kind(),
+                        format!("failed to load snapshot {}: {e}", path.display()),
+                    )
+                })?;
+        }
 
-        Ok(Self { data })
+        Ok(Self {
+            data,
+            snapshot_path,
+        })
     }
 }
 
@@ -65,4 +138,72 @@ impl EntryStore for Seg {
     fn clear(&mut self) {
         self.data.clear();
     }
+
+    fn share(&self) -> Option<Self> {
+        if self.data.shards() > 1 {
+            Some(Self {
+                data: self.data.clone(),
+                snapshot_path: self.snapshot_path.clone(),
+            })
+        } else {
+            None
+        }
+    }
+
+    // each shard is locked for every access, so even a single shard may be
+    // inspected from the admin thread
+    fn admin_handle(&self) -> Option<Self> {
+        Some(Self {
+            data: self.data.clone(),
+            snapshot_path: self.snapshot_path.clone(),
+        })
+    }
+
+    fn scan(&mut self, cursor: u64, count: usize) -> Option<(u64, Vec<KeyInfo>)> {
+        Some(self.data.scan(cursor, count))
+    }
+
+    fn segment_stats(&mut self) -> Option<SegmentStats> {
+        Some(self.data.segment_stats())
+    }
+
+    fn ttl_stats(&mut self) -> Option<Vec<TtlStats>> {
+        Some(self.data.ttl_stats())
+    }
+
+    // the snapshot is written to a temporary file which replaces the previous
+    // snapshot once it is complete, so a failed save leaves it intact
+    fn save_snapshot(&mut self) -> Option<std::io::Result<usize>> {
+        let path = self.snapshot_path.as_ref()?;
+
+        let mut tmp = path.clone().into_os_string();
+        tmp.push(".tmp");
+
+        let result = File::create(&tmp).and_then(|file| {
+            let mut writer = BufWriter::new(file);
+            let saved = self.data.save_snapshot(&mut writer)?;
+            writer.into_inner()?.sync_all()?;
+            std::fs::rename(&tmp, path)?;
+            Ok(saved)
+        });
+
+        if result.is_err() {
+            let _ = std::fs::remove_file(&tmp);
+        }
+
+        Some(result)
+    }
+}
+
+/// Interprets the result of a write for the client. Writes which are rejected
+/// by the admission policy are reported as stored, as the item could equally
+/// have been evicted immediately after it was written.
+trait WriteOutcome {
+    fn stored(&self) -> bool;
+}
+
+impl WriteOutcome for Result<(), SegcacheError> {
+    fn stored(&self) -> bool {
+        matches!(self, Ok(()) | Err(SegcacheError::Rejected))
+    }
 }
@@ -9,29 +9,155 @@ use super::*;
 
 use protocol_common::*;
 use protocol_resp::*;
+use segcache::Segcache;
 
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Redis limits string values to 512MB. Larger values are further limited by
+/// the segment size.
+const MAX_STRING_LEN: u64 = 512 * 1024 * 1024;
 
 impl Execute<Request, Response> for Seg {
     fn execute(&mut self, request: &Request) -> Response {
         match request {
             Request::Get(get) => self.get(get),
             Request::Set(set) => self.set(set),
+            Request::Exists(exists) => self.exists(exists),
+            Request::Expire(expire) => Storage::expire(self, expire),
+            Request::ExpireAt(expire_at) => self.expire_at(expire_at),
+            Request::ExpireMilliseconds(expire) => self.expire_milliseconds(expire),
+            Request::KeyType(key_type) => self.key_type(key_type),
+            Request::Persist(persist) => self.persist(persist),
+            // the worker attaches the session to the monitor once it has
+            // sent the response
+            Request::Monitor(_) => Response::simple_string("OK"),
+            Request::Scan(scan) => Storage::scan(self, scan),
+            Request::SlowLog(slowlog) => slowlog.respond(&common::slowlog::SLOWLOG),
+            Request::TimeToLive(ttl) => self.time_to_live(ttl),
+            Request::TimeToLiveMilliseconds(ttl) => self.time_to_live_milliseconds(ttl),
+            Request::Append(append) => self.append(append),
+            Request::Decr(decr) => self.decr(decr),
+            Request::GetDel(get_del) => self.get_del(get_del),
+            Request::GetRange(get_range) => self.get_range(get_range),
+            Request::Incr(incr) => self.incr(incr),
+            Request::IncrBy(incr_by) => self.incr_by(incr_by),
+            Request::MultiGet(multi_get) => self.multi_get(multi_get),
+            Request::MultiSet(multi_set) => self.multi_set(multi_set),
+            Request::SetNx(set_nx) => self.set_nx(set_nx),
+            Request::SetRange(set_range) => self.set_range(set_range),
+            Request::StringLength(strlen) => self.string_length(strlen),
             _ => Response::error("not supported"),
         }
     }
 }
 
+/// Returns a copy of the value stored at the key, formatting numeric values as
+/// strings.
+fn value(shard: &mut Segcache, key: &[u8]) -> Option<Vec<u8>> {
+    shard.get(key).map(|item| match item.value() {
+        segcache::Value::Bytes(b) => b.to_vec(),
+        segcache::Value::U64(v) => format!("{v}").into_bytes(),
+    })
+}
+
+/// Returns the remaining TTL for the key, so that read-modify-write commands
+/// can preserve it when the item is re-inserted. Keys without an expiry, and
+/// missing keys, return a zero TTL.
+fn remaining_ttl(shard: &mut Segcache, key: &[u8]) -> Duration {
+    match shard.ttl(key) {
+        // avoid rounding down to zero, which would remove the expiry
+        Ok(Some(ttl)) => ttl.max(Duration::from_secs(1)),
+        _ => Duration::ZERO,
+    }
+}
+
+impl Seg {
+    /// Returns the length of the value stored at the key as a string, or zero
+    /// if the key is missing.
+    fn length(&mut self, key: &[u8]) -> usize {
+        self.data
+            .get(key, |item| match item.value() {
+                segcache::Value::Bytes(b) => b.len(),
+                segcache::Value::U64(v) => format!("{v}").len(),
+            })
+            .unwrap_or(0)
+    }
+
+    /// Adds a signed delta to the integer stored at the key, treating a missing
+    /// key as zero. Values stored in numeric form are updated in place, others
+    /// are parsed and re-inserted with their TTL preserved. The shard is locked
+    /// throughout, so concurrent updates from other workers are not lost.
+    fn increment(&mut self, key: &[u8], delta: i64) -> Response {
+        let mut shard = self.data.lock(key);
+
+        let (current, numeric) = match shard.get(key) {
+            Some(item) => match item.value() {
+                segcache::Value::U64(v) => (i64::try_from(v).ok(), true),
+                segcache::Value::Bytes(b) => (parse_integer(b), false),
+            },
+            None => (Some(0), false),
+        };
+
+        let current = match current {
+            Some(current) => current,
+            None => return Response::error("ERR value is not an integer or out of range"),
+        };
+
+        let value = match current.checked_add(delta) {
+            Some(value) => value,
+            None => return Response::error("ERR increment or decrement would overflow"),
+        };
+
+        if numeric && value >= 0 {
+            let result = if delta >= 0 {
+                shard.wrapping_add(key, delta as u64).map(|_| ())
+            } else {
+                shard.saturating_sub(key, delta.unsigned_abs()).map(|_| ())
+            };
+
+            if result.is_ok() {
+                return Response::integer(value);
+            }
+        }
+
+        let ttl = remaining_ttl(&mut shard, key);
+        let result = if value >= 0 {
+            shard.insert(key, value as u64, None, ttl)
+        } else {
+            shard.insert(key, value.to_string().as_bytes(), None, ttl)
+        };
+
+        if result.stored() {
+            Response::integer(value)
+        } else {
+            Response::error("not stored")
+        }
+    }
+
+    /// Changes the TTL of an existing item, following the `EXPIRE` family
+    /// semantics: a non-positive TTL deletes the item and the response is 1 if
+    /// the key existed and 0 otherwise.
+    fn update_ttl(&mut self, key: &[u8], seconds: i64) -> Response {
+        if seconds <= 0 {
+            return Response::integer(self.data.delete(key) as i64);
+        }
+
+        match self.data.set_ttl(key, Duration::from_secs(seconds as u64)) {
+            Ok(()) => Response::integer(1),
+            Err(segcache::SegcacheError::NotFound) => Response::integer(0),
+            Err(_) => Response::error("not stored"),
+        }
+    }
+}
+
 impl Storage for Seg {
     fn get(&mut self, get: &Get) -> Response {
-        if let Some(item) = self.data.get(get.key()) {
-            match item.value() {
+        self.data
+            .get(get.key(), |item| match item.value() {
                 segcache::Value::Bytes(b) => Response::bulk_string(b),
                 segcache::Value::U64(v) => Response::bulk_string(format!("{v}").as_bytes()),
-            }
-        } else {
-            Response::null()
-        }
+            })
+            .unwrap_or_else(Response::null)
     }
 
     fn set(&mut self, set: &Set) -> Response {
@@ -43,11 +169,279 @@ impl Storage for Seg {
         if self
             .data
             .insert(set.key(), set.value(), None, Duration::from_secs(ttl))
-            .is_ok()
+            .stored()
         {
             Response::simple_string("OK")
         } else {
             Response::error("not stored")
         }
     }
+
+    fn exists(&mut self, exists: &Exists) -> Response {
+        let count = exists
+            .keys()
+            .iter()
+            .filter(|key| self.data.get_no_freq_incr(key, |_| ()).is_some())
+            .count();
+
+        Response::integer(count as i64)
+    }
+
+    fn expire(&mut self, expire: &Expire) -> Response {
+        self.update_ttl(expire.key(), expire.seconds())
+    }
+
+    fn expire_at(&mut self, expire_at: &ExpireAt) -> Response {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+
+        self.update_ttl(expire_at.key(), expire_at.timestamp().saturating_sub(now))
+    }
+
+    fn expire_milliseconds(&mut self, expire: &ExpireMilliseconds) -> Response {
+        // segcache tracks expiry with second granularity, so round up to avoid
+        // expiring the item early
+        let milliseconds = expire.milliseconds();
+        let seconds = if milliseconds > 0 {
+            milliseconds.saturating_add(999) / 1000
+        } else {
+            milliseconds
+        };
+
+        self.update_ttl(expire.key(), seconds)
+    }
+
+    fn key_type(&mut self, key_type: &KeyType) -> Response {
+        if self.data.get_no_freq_incr(key_type.key(), |_| ()).is_some() {
+            Response::simple_string("string")
+        } else {
+            Response::simple_string("none")
+        }
+    }
+
+    fn persist(&mut self, persist: &Persist) -> Response {
+        let mut shard = self.data.lock(persist.key());
+
+        match shard.ttl(persist.key()) {
+            Ok(Some(_)) => match shard.set_ttl(persist.key(), Duration::ZERO) {
+                Ok(()) => Response::integer(1),
+                Err(segcache::SegcacheError::NotFound) => Response::integer(0),
+                Err(_) => Response::error("not stored"),
+            },
+            _ => Response::integer(0),
+        }
+    }
+
+    fn scan(&mut self, scan: &Scan) -> Response {
+        let (cursor, keys) = self.data.scan(scan.cursor(), scan.count());
+
+        // like redis, the pattern is applied after the keys are examined, so a
+        // page may be empty even though the iteration is not complete
+        let keys = keys
+            .iter()
+            .filter(|info| scan.matches(info.key()))
+            .map(|info| Response::bulk_string(info.key()))
+            .collect();
+
+        Response::array(vec![
+            Response::bulk_string(cursor.to_string().as_bytes()),
+            Response::array(keys),
+        ])
+    }
+
+    fn time_to_live(&mut self, ttl: &TimeToLive) -> Response {
+        match self.data.ttl(ttl.key()) {
+            Ok(Some(ttl)) => Response::integer(ttl.as_secs() as i64),
+            Ok(None) => Response::integer(-1),
+            Err(_) => Response::integer(-2),
+        }
+    }
+
+    fn time_to_live_milliseconds(&mut self, ttl: &TimeToLiveMilliseconds) -> Response {
+        match self.data.ttl(ttl.key()) {
+            Ok(Some(ttl)) => Response::integer(ttl.as_millis() as i64),
+            Ok(None) => Response::integer(-1),
+            Err(_) => Response::integer(-2),
+        }
+    }
+
+    fn append(&mut self, append: &Append) -> Response {
+        let mut shard = self.data.lock(append.key());
+
+        let ttl = remaining_ttl(&mut shard, append.key());
+        let mut value = value(&mut shard, append.key()).unwrap_or_default();
+        value.extend_from_slice(append.value());
+
+        if shard
+            .insert(append.key(), value.as_slice(), None, ttl)
+            .stored()
+        {
+            Response::integer(value.len() as i64)
+        } else {
+            Response::error("not stored")
+        }
+    }
+
+    fn decr(&mut self, decr: &Decr) -> Response {
+        self.increment(decr.key(), -1)
+    }
+
+    fn get_del(&mut self, get_del: &GetDel) -> Response {
+        let mut shard = self.data.lock(get_del.key());
+
+        if let Some(value) = value(&mut shard, get_del.key()) {
+            shard.delete(get_del.key());
+            Response::bulk_string(&value)
+        } else {
+            Response::null()
+        }
+    }
+
+    fn get_range(&mut self, get_range: &GetRange) -> Response {
+        let value =
+            value(&mut self.data.lock(get_range.key()), get_range.key()).unwrap_or_default();
+        let len = value.len() as i64;
+
+        let mut start = get_range.start();
+        let mut end = get_range.end();
+
+        if start < 0 {
+            start += len;
+        }
+        if end < 0 {
+            end += len;
+        }
+
+        let start = start.max(0);
+        let end = end.max(0).min(len - 1);
+
+        if len == 0 || start > end {
+            Response::bulk_string(b"")
+        } else {
+            Response::bulk_string(&value[start as usize..=end as usize])
+        }
+    }
+
+    fn incr(&mut self, incr: &Incr) -> Response {
+        self.increment(incr.key(), 1)
+    }
+
+    fn incr_by(&mut self, incr_by: &IncrBy) -> Response {
+        self.increment(incr_by.key(), incr_by.increment())
+    }
+
+    fn multi_get(&mut self, multi_get: &MultiGet) -> Response {
+        let values = multi_get
+            .keys()
+            .iter()
+            .map(|key| match value(&mut self.data.lock(key), key) {
+                Some(value) => Response::bulk_string(&value),
+                None => Response::null(),
+            })
+            .collect();
+
+        Response::array(values)
+    }
+
+    fn multi_set(&mut self, multi_set: &MultiSet) -> Response {
+        // reject values which can never be stored before writing any keys, so
+        // that an oversized value does not leave the earlier keys written.
+        // Other failures, such as running out of segments, may still leave a
+        // partial write as there is no rollback.
+        let max = self.data.max_item_size();
+        if multi_set.data().iter().any(|(_, value)| value.len() > max) {
+            return Response::error("not stored");
+        }
+
+        // lock every shard which is written, so that the keys are updated
+        // together with respect to other workers
+        let mut shards = self
+            .data
+            .lock_many(multi_set.data().iter().map(|(key, _)| &**key));
+
+        for (key, value) in multi_set.data() {
+            if !shards
+                .shard(key)
+                .insert(key, &**value, None, Duration::ZERO)
+                .stored()
+            {
+                return Response::error("not stored");
+            }
+        }
+
+        Response::simple_string("OK")
+    }
+
+    fn set_nx(&mut self, set_nx: &SetNx) -> Response {
+        let mut shard = self.data.lock(set_nx.key());
+
+        if shard.get_no_freq_incr(set_nx.key()).is_some() {
+            return Response::integer(0);
+        }
+
+        if shard
+            .insert(set_nx.key(), set_nx.value(), None, Duration::ZERO)
+            .stored()
+        {
+            Response::integer(1)
+        } else {
+            Response::error("not stored")
+        }
+    }
+
+    fn set_range(&mut self, set_range: &SetRange) -> Response {
+        // an empty write does not create the key
+        if set_range.value().is_empty() {
+            return Response::integer(self.length(set_range.key()) as i64);
+        }
+
+        // check the length before growing the value, so that a large offset
+        // cannot allocate more than the cache could ever store
+        let max = MAX_STRING_LEN.min(self.data.max_item_size() as u64);
+        if set_range
+            .offset()
+            .saturating_add(set_range.value().len() as u64)
+            > max
+        {
+            return Response::error("ERR string exceeds maximum allowed size (proto-max-bulk-len)");
+        }
+
+        let mut shard = self.data.lock(set_range.key());
+        let mut value = value(&mut shard, set_range.key()).unwrap_or_default();
+
+        let start = set_range.offset() as usize;
+        let end = start + set_range.value().len();
+
+        if value.len() < end {
+            value.resize(end, 0);
+        }
+        value[start..end].copy_from_slice(set_range.value());
+
+        let ttl = remaining_ttl(&mut shard, set_range.key());
+
+        if shard
+            .insert(set_range.key(), value.as_slice(), None, ttl)
+            .stored()
+        {
+            Response::integer(value.len() as i64)
+        } else {
+            Response::error("not stored")
+        }
+    }
+
+    fn string_length(&mut self, strlen: &StringLength) -> Response {
+        Response::integer(self.length(strlen.key()) as i64)
+    }
+}
+
+/// Parses a stored value as a signed integer, matching the strictness of
+/// Redis which rejects leading `+` signs and surrounding whitespace.
+fn parse_integer(value: &[u8]) -> Option<i64> {
+    if value.first() == Some(&b'+') {
+        return None;
+    }
+
+    std::str::from_utf8(value).ok()?.parse().ok()
 }
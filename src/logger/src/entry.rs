@@ -0,0 +1,144 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! Parsing of command log lines in either the text or the json klog format, as
+//! written by [`klog_record`](crate::klog_record). This is used by tools which
+//! read the command log back, such as replay and simulation, and is enabled by
+//! the `parse` feature.
+
+use chrono::DateTime;
+use serde::Deserialize;
+
+/// The klog status codes which are used for requests that read a value.
+const MISS: u32 = 0;
+const HIT: u32 = 4;
+
+/// A single command from the command log.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KlogEntry {
+    // milliseconds since the UNIX epoch
+    time: i64,
+    command: String,
+    args: Vec<String>,
+    status: u32,
+}
+
+#[derive(Deserialize)]
+struct JsonEntry {
+    time: String,
+    command: String,
+    args: Vec<String>,
+    status: u32,
+}
+
+impl KlogEntry {
+    /// Parse a line in either klog format, eg:
+    /// `2024-01-01T00:00:00.000+00:00 "get key" 4 5` or
+    /// `{"time":"2024-01-01T00:00:00.000+00:00","command":"get",...}`
+    ///
+    /// Returns `None` if the line is not a command log entry.
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim_end();
+
+        if line.starts_with('{') {
+            let entry: JsonEntry = serde_json::from_str(line).ok()?;
+
+            return Some(Self {
+                time: parse_time(&entry.time)?,
+                command: entry.command,
+                args: entry.args,
+                status: entry.status,
+            });
+        }
+
+        let (time, rest) = line.split_once(' ')?;
+        let rest = rest.strip_prefix('"')?;
+        let (request, result) = rest.rsplit_once('"')?;
+        // the response length is not kept
+        let (status, _len) = result.trim().split_once(' ')?;
+
+        let mut words = request.split(' ');
+        let command = words.next().filter(|c| !c.is_empty())?.to_string();
+
+        Some(Self {
+            time: parse_time(time)?,
+            command,
+            args: words.map(String::from).collect(),
+            status: status.parse().ok()?,
+        })
+    }
+
+    /// The time the command was logged, in milliseconds since the UNIX epoch.
+    pub fn time(&self) -> i64 {
+        self.time
+    }
+
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    /// The arguments following the command, with values replaced by their
+    /// length as they are in the command log.
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
+
+    /// The key of the request, which is the first argument for every command
+    /// which has one.
+    pub fn key(&self) -> Option<&str> {
+        self.args.first().map(|k| k.as_str())
+    }
+
+    /// Whether the request read a value, and if so whether it was a hit when
+    /// the command was logged.
+    pub fn hit(&self) -> Option<bool> {
+        match self.status {
+            HIT => Some(true),
+            MISS => Some(false),
+            _ => None,
+        }
+    }
+}
+
+fn parse_time(time: &str) -> Option<i64> {
+    DateTime::parse_from_rfc3339(time)
+        .ok()
+        .map(|t| t.timestamp_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text() {
+        let entry = KlogEntry::parse("2024-01-01T00:00:01.250+00:00 \"set key 0 3600 5\" 5 8\n")
+            .expect("failed to parse");
+
+        assert_eq!(entry.time(), 1_704_067_201_250);
+        assert_eq!(entry.command(), "set");
+        assert_eq!(entry.args(), &["key", "0", "3600", "5"]);
+        assert_eq!(entry.hit(), None);
+
+        let entry = KlogEntry::parse("2024-01-01T00:00:01.250+00:00 \"get key\" 4 5").unwrap();
+        assert_eq!(entry.key(), Some("key"));
+        assert_eq!(entry.hit(), Some(true));
+
+        assert!(KlogEntry::parse("ping 6").is_none());
+        assert!(KlogEntry::parse("").is_none());
+    }
+
+    #[test]
+    fn json() {
+        let entry = KlogEntry::parse(
+            r#"{"time":"2024-01-01T00:00:01.250+00:00","command":"hget","args":["a key","field"],"status":0,"len":0}"#,
+        )
+        .expect("failed to parse");
+
+        assert_eq!(entry.time(), 1_704_067_201_250);
+        assert_eq!(entry.command(), "hget");
+        assert_eq!(entry.args(), &["a key", "field"]);
+        assert_eq!(entry.hit(), Some(false));
+    }
+}
@@ -36,13 +36,25 @@
 
 pub use ringlog::*;
 
-use config::{DebugConfig, KlogConfig};
+#[cfg(feature = "parse")]
+mod entry;
+
+#[cfg(feature = "parse")]
+pub use entry::KlogEntry;
+
+use clocksource::datetime::DateTime;
+use config::{DebugConfig, KlogConfig, KlogFormat};
+use std::fmt::{Display, Formatter, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 ////////////////////////////////////////////////////////////////////////////////
 // TODO(bmartin): everything below is Pelikan specific, and should be factored
 // out into a helper when we move this crate into rustcommon
 ////////////////////////////////////////////////////////////////////////////////
 
+/// Writes a message to the klog. Commands should be logged with
+/// [`klog_record`] instead, as the json format relies on the message being a
+/// klog record to complete the object.
 #[macro_export]
 macro_rules! klog {
     ($($arg:tt)*) => (
@@ -58,6 +70,87 @@ pub trait Klog {
     fn klog(&self, response: &Self::Response);
 }
 
+// set when the klog is configured to write json lines
+static KLOG_JSON: AtomicBool = AtomicBool::new(false);
+
+/// Log a single command to the klog in the configured format. The `args` are
+/// the arguments following the command, eg: the key, flags, ttl and value
+/// length for a memcache `set`, and `status` is one of the memcache compatible
+/// response codes.
+pub fn klog_record(command: &str, args: &[&dyn Display], status: u32, response_len: usize) {
+    klog!(
+        "{}",
+        KlogRecord {
+            command,
+            args,
+            status,
+            response_len,
+        }
+    );
+}
+
+/// A command log entry, which is only rendered if the klog sampling keeps it.
+struct KlogRecord<'a> {
+    command: &'a str,
+    args: &'a [&'a dyn Display],
+    status: u32,
+    response_len: usize,
+}
+
+impl Display for KlogRecord<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !KLOG_JSON.load(Ordering::Relaxed) {
+            write!(f, "\"{}", self.command)?;
+            for arg in self.args {
+                write!(f, " {arg}")?;
+            }
+            return write!(f, "\" {} {}", self.status, self.response_len);
+        }
+
+        // the opening brace and the timestamp are written by `klog_json_format`
+        f.write_str("\"command\":")?;
+        write_json_string(f, self.command)?;
+        f.write_str(",\"args\":[")?;
+        for (i, arg) in self.args.iter().enumerate() {
+            if i > 0 {
+                f.write_char(',')?;
+            }
+            write_json_string(f, &arg.to_string())?;
+        }
+        write!(
+            f,
+            "],\"status\":{},\"len\":{}}}",
+            self.status, self.response_len
+        )
+    }
+}
+
+fn write_json_string(f: &mut Formatter<'_>, s: &str) -> std::fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+/// Formats a klog record as a json object on a single line, eg:
+/// `{"time":"...","command":"get","args":["key"],"status":4,"len":5}`
+pub fn klog_json_format(
+    w: &mut dyn std::io::Write,
+    now: DateTime,
+    record: &Record,
+) -> Result<(), std::io::Error> {
+    writeln!(w, "{{\"time\":\"{}\",{}", now, record.args())
+}
+
 pub fn configure_logging<T: DebugConfig + KlogConfig>(config: &T) -> Box<dyn Drain> {
     let debug_config = config.debug();
 
@@ -85,9 +178,14 @@ pub fn configure_logging<T: DebugConfig + KlogConfig>(config: &T) -> Box<dyn Dra
         let output = Box::new(
             File::new(&file, &backup, klog_config.max_size()).expect("failed to open klog file"),
         );
+        let format = match klog_config.format() {
+            KlogFormat::Text => klog_format,
+            KlogFormat::Json => klog_json_format,
+        };
+        KLOG_JSON.store(klog_config.format() == KlogFormat::Json, Ordering::Relaxed);
         SamplingLogBuilder::new()
             .output(output)
-            .format(klog_format)
+            .format(format)
             .sample(klog_config.sample())
             .log_queue_depth(klog_config.queue_depth())
             .single_message_size(klog_config.single_message_size())
@@ -97,10 +195,48 @@ pub fn configure_logging<T: DebugConfig + KlogConfig>(config: &T) -> Box<dyn Dra
         NopLogBuilder::new().build()
     };
 
-    MultiLogBuilder::new()
-        .level_filter(debug_config.log_level().to_level_filter())
+    // the logger accepts every level so that the level may be changed at
+    // runtime with `set_log_level()`, which filters before the logger
+    let drain = MultiLogBuilder::new()
+        .level_filter(LevelFilter::Trace)
         .default(debug_log)
         .add_target("klog", klog)
         .build()
-        .start()
+        .start();
+
+    set_log_level(debug_config.log_level().to_level_filter());
+
+    drain
+}
+
+/// Change the level of messages which are logged. Klog messages are only
+/// filtered if the level is set to `off`.
+pub fn set_log_level(level: LevelFilter) {
+    set_max_level(level);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record() {
+        let record = KlogRecord {
+            command: "set",
+            args: &[&"a \"key\"", &0, &3600, &5],
+            status: 5,
+            response_len: 8,
+        };
+
+        assert_eq!(record.to_string(), "\"set a \"key\" 0 3600 5\" 5 8");
+
+        KLOG_JSON.store(true, Ordering::Relaxed);
+        let json = record.to_string();
+        KLOG_JSON.store(false, Ordering::Relaxed);
+
+        assert_eq!(
+            json,
+            r#""command":"set","args":["a \"key\"","0","3600","5"],"status":5,"len":8}"#
+        );
+    }
 }
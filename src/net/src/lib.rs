@@ -6,6 +6,7 @@ mod connector;
 mod listener;
 mod stream;
 mod tcp;
+mod uds;
 
 #[cfg(any(feature = "boringssl", feature = "openssl"))]
 mod tls_tcp;
@@ -14,6 +15,7 @@ pub use connector::*;
 pub use listener::*;
 pub use stream::*;
 pub use tcp::*;
+pub use uds::*;
 
 #[cfg(any(feature = "boringssl", feature = "openssl"))]
 pub use tls_tcp::*;
@@ -10,6 +10,7 @@ pub struct Listener {
 
 enum ListenerType {
     Plain(TcpListener),
+    Unix(UnixListener),
     #[cfg(any(feature = "boringssl", feature = "openssl"))]
     Tls((TcpListener, TlsTcpAcceptor)),
 }
@@ -22,6 +23,14 @@ impl From<TcpListener> for Listener {
     }
 }
 
+impl From<UnixListener> for Listener {
+    fn from(other: UnixListener) -> Self {
+        Self {
+            inner: ListenerType::Unix(other),
+        }
+    }
+}
+
 #[cfg(any(feature = "boringssl", feature = "openssl"))]
 impl From<(TcpListener, TlsTcpAcceptor)> for Listener {
     fn from(other: (TcpListener, TlsTcpAcceptor)) -> Self {
@@ -59,6 +68,10 @@ impl Listener {
                 let (stream, _addr) = listener.accept()?;
                 Ok(Stream::from(stream))
             }
+            ListenerType::Unix(listener) => {
+                let stream = listener.accept()?;
+                Ok(Stream::from(stream))
+            }
             #[cfg(any(feature = "boringssl", feature = "openssl"))]
             ListenerType::Tls((listener, acceptor)) => {
                 let (stream, _addr) = listener.accept()?;
@@ -68,15 +81,31 @@ impl Listener {
         }
     }
 
+    /// Returns the local socket address. Unix domain socket listeners do not
+    /// have a socket address, use `local_path()` instead.
     pub fn local_addr(&self) -> Result<SocketAddr> {
         match &self.inner {
             ListenerType::Plain(listener) => listener.local_addr(),
+            ListenerType::Unix(_) => Err(Error::new(
+                ErrorKind::Unsupported,
+                "unix listener has no socket address",
+            )),
             #[cfg(any(feature = "boringssl", feature = "openssl"))]
             ListenerType::Tls((listener, _acceptor)) => listener.local_addr(),
         }
     }
 }
 
+impl Listener {
+    /// Returns the path of the socket file for Unix domain socket listeners.
+    pub fn local_path(&self) -> Option<&std::path::Path> {
+        match &self.inner {
+            ListenerType::Unix(listener) => Some(listener.path()),
+            _ => None,
+        }
+    }
+}
+
 impl event::Source for Listener {
     fn register(
         &mut self,
@@ -86,6 +115,7 @@ impl event::Source for Listener {
     ) -> Result<()> {
         match &mut self.inner {
             ListenerType::Plain(listener) => listener.register(registry, token, interests),
+            ListenerType::Unix(listener) => listener.register(registry, token, interests),
             #[cfg(any(feature = "boringssl", feature = "openssl"))]
             ListenerType::Tls((listener, _acceptor)) => {
                 listener.register(registry, token, interests)
@@ -101,6 +131,7 @@ impl event::Source for Listener {
     ) -> Result<()> {
         match &mut self.inner {
             ListenerType::Plain(listener) => listener.reregister(registry, token, interests),
+            ListenerType::Unix(listener) => listener.reregister(registry, token, interests),
             #[cfg(any(feature = "boringssl", feature = "openssl"))]
             ListenerType::Tls((listener, _acceptor)) => {
                 listener.reregister(registry, token, interests)
@@ -111,6 +142,7 @@ impl event::Source for Listener {
     fn deregister(&mut self, registry: &mio::Registry) -> Result<()> {
         match &mut self.inner {
             ListenerType::Plain(listener) => listener.deregister(registry),
+            ListenerType::Unix(listener) => listener.deregister(registry),
             #[cfg(any(feature = "boringssl", feature = "openssl"))]
             ListenerType::Tls((listener, _acceptor)) => listener.deregister(registry),
         }
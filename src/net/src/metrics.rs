@@ -33,6 +33,42 @@ pub static TCP_RECV_BYTE: Counter = Counter::new();
 )]
 pub static TCP_SEND_BYTE: Counter = Counter::new();
 
+#[metric(
+    name = "unix_accept",
+    description = "number of Unix domain socket streams passively opened with accept"
+)]
+pub static UNIX_ACCEPT: Counter = Counter::new();
+
+#[metric(
+    name = "unix_connect",
+    description = "number of Unix domain socket streams actively opened with connect"
+)]
+pub static UNIX_CONNECT: Counter = Counter::new();
+
+#[metric(
+    name = "unix_close",
+    description = "number of Unix domain socket streams closed"
+)]
+pub static UNIX_CLOSE: Counter = Counter::new();
+
+#[metric(
+    name = "unix_conn_curr",
+    description = "current number of open Unix domain socket streams"
+)]
+pub static UNIX_CONN_CURR: Gauge = Gauge::new();
+
+#[metric(
+    name = "unix_recv_byte",
+    description = "number of bytes received on Unix domain socket streams"
+)]
+pub static UNIX_RECV_BYTE: Counter = Counter::new();
+
+#[metric(
+    name = "unix_send_byte",
+    description = "number of bytes sent on Unix domain socket streams"
+)]
+pub static UNIX_SEND_BYTE: Counter = Counter::new();
+
 #[metric(name = "stream_accept", description = "number of calls to accept")]
 pub static STREAM_ACCEPT: Counter = Counter::new();
 
@@ -57,6 +93,36 @@ pub static STREAM_HANDSHAKE: Counter = Counter::new();
 )]
 pub static STREAM_HANDSHAKE_EX: Counter = Counter::new();
 
+#[metric(
+    name = "stream_handshake_ex_no_cert",
+    description = "number of handshakes which failed because the peer did not present a required certificate"
+)]
+pub static STREAM_HANDSHAKE_EX_NO_CERT: Counter = Counter::new();
+
+#[metric(
+    name = "stream_handshake_ex_not_allowed",
+    description = "number of handshakes which failed because the peer certificate was not in the allow-list"
+)]
+pub static STREAM_HANDSHAKE_EX_NOT_ALLOWED: Counter = Counter::new();
+
+#[metric(
+    name = "stream_handshake_ex_revoked",
+    description = "number of handshakes which failed because the peer certificate was revoked"
+)]
+pub static STREAM_HANDSHAKE_EX_REVOKED: Counter = Counter::new();
+
+#[metric(
+    name = "stream_handshake_ex_verify",
+    description = "number of handshakes which failed because the peer certificate could not be verified"
+)]
+pub static STREAM_HANDSHAKE_EX_VERIFY: Counter = Counter::new();
+
+#[metric(
+    name = "stream_handshake_ex_other",
+    description = "number of handshakes which failed for reasons unrelated to the peer certificate"
+)]
+pub static STREAM_HANDSHAKE_EX_OTHER: Counter = Counter::new();
+
 #[metric(
     name = "stream_shutdown",
     description = "number of streams gracefully shutdown"
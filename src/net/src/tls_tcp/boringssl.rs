@@ -6,8 +6,10 @@ pub use boring::ssl::ShutdownResult;
 
 use std::os::unix::prelude::AsRawFd;
 
-use boring::ssl::{ErrorCode, Ssl, SslFiletype, SslMethod, SslStream};
-use boring::x509::X509;
+use boring::nid::Nid;
+use boring::ssl::{ErrorCode, Ssl, SslFiletype, SslMethod, SslRef, SslStream, SslVerifyMode};
+use boring::x509::store::X509StoreBuilderRef;
+use boring::x509::{X509Ref, X509};
 use foreign_types_shared_03::{ForeignType, ForeignTypeRef};
 
 use crate::*;
@@ -23,6 +25,7 @@ enum TlsState {
 pub struct TlsTcpStream {
     inner: SslStream<TcpStream>,
     state: TlsState,
+    verification: ClientVerification,
 }
 
 impl AsRawFd for TlsTcpStream {
@@ -36,6 +39,10 @@ impl TlsTcpStream {
         self.inner.get_mut().set_nodelay(nodelay)
     }
 
+    pub fn peer_addr(&self) -> Result<SocketAddr> {
+        self.inner.get_ref().peer_addr()
+    }
+
     pub fn is_handshaking(&self) -> bool {
         self.state == TlsState::Handshaking
     }
@@ -69,6 +76,8 @@ impl TlsTcpStream {
                             STREAM_HANDSHAKE_EX.increment();
                         }
 
+                        handshake_failure(self.inner.ssl(), self.verification).record();
+
                         Err(Error::new(ErrorKind::Other, "handshake failed"))
                     }
                 }
@@ -147,11 +156,137 @@ impl event::Source for TlsTcpStream {
     }
 }
 
+/// Determine why a handshake failed using the verification result for the
+/// session.
+fn handshake_failure(ssl: &SslRef, verification: ClientVerification) -> HandshakeFailure {
+    let verify_result = unsafe { boring_sys::SSL_get_verify_result(ssl.as_ptr()) } as i64;
+    HandshakeFailure::new(
+        verification,
+        ssl.peer_certificate().is_some(),
+        verify_result,
+    )
+}
+
+/// Returns the subject common names and the subject alternative names of a
+/// certificate.
+fn certificate_names(cert: &X509Ref) -> (Vec<String>, Vec<String>) {
+    let common_names = cert
+        .subject_name()
+        .entries_by_nid(Nid::COMMONNAME)
+        .filter_map(|entry| entry.data().as_utf8().ok().map(|v| v.to_string()))
+        .collect();
+
+    let mut sans = Vec::new();
+    if let Some(names) = cert.subject_alt_names() {
+        for name in &names {
+            if let Some(v) = name.dnsname() {
+                sans.push(v.to_string());
+            } else if let Some(v) = name.uri() {
+                sans.push(v.to_string());
+            } else if let Some(v) = name.email() {
+                sans.push(v.to_string());
+            } else if let Some(v) = name.ipaddress() {
+                match v.len() {
+                    4 => {
+                        let octets: [u8; 4] = v.try_into().unwrap();
+                        sans.push(std::net::IpAddr::from(octets).to_string());
+                    }
+                    16 => {
+                        let octets: [u8; 16] = v.try_into().unwrap();
+                        sans.push(std::net::IpAddr::from(octets).to_string());
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    (common_names, sans)
+}
+
+/// Load all the PEM-formatted CRLs in a file into the certificate store and
+/// enable revocation checking for the full certificate chain.
+fn load_crl_file(store: &mut X509StoreBuilderRef, file: &std::path::Path) -> Result<()> {
+    let pem = std::fs::read(file).map_err(|e| {
+        Error::new(
+            ErrorKind::Other,
+            format!("failed to load CRL file: {}\n{}", file.display(), e),
+        )
+    })?;
+
+    let mut loaded = 0;
+
+    unsafe {
+        let bio = boring_sys::BIO_new_mem_buf(pem.as_ptr() as *const _, pem.len() as _);
+        if bio.is_null() {
+            return Err(Error::new(ErrorKind::Other, "failed to allocate BIO"));
+        }
+
+        loop {
+            let crl = boring_sys::PEM_read_bio_X509_CRL(
+                bio,
+                std::ptr::null_mut(),
+                None,
+                std::ptr::null_mut(),
+            );
+            if crl.is_null() {
+                // only running out of PEM blocks is a clean end of the file,
+                // anything else is a truncated or corrupt CRL which must not
+                // be skipped
+                let err = boring_sys::ERR_peek_last_error();
+                boring_sys::ERR_clear_error();
+
+                if boring_sys::ERR_GET_LIB(err) == boring_sys::ERR_LIB_PEM.0 as _
+                    && boring_sys::ERR_GET_REASON(err) == boring_sys::PEM_R_NO_START_LINE
+                {
+                    break;
+                }
+
+                boring_sys::BIO_free(bio);
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    format!("bad CRL in CRL file: {}", file.display()),
+                ));
+            }
+
+            let ret = boring_sys::X509_STORE_add_crl(store.as_ptr(), crl);
+            boring_sys::X509_CRL_free(crl);
+
+            if ret != 1 {
+                boring_sys::BIO_free(bio);
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    format!("bad CRL in CRL file: {}", file.display()),
+                ));
+            }
+
+            loaded += 1;
+        }
+
+        boring_sys::BIO_free(bio);
+
+        boring_sys::X509_STORE_set_flags(
+            store.as_ptr(),
+            (boring_sys::X509_V_FLAG_CRL_CHECK | boring_sys::X509_V_FLAG_CRL_CHECK_ALL) as _,
+        );
+    }
+
+    if loaded == 0 {
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!("no CRLs found in CRL file: {}", file.display()),
+        ));
+    }
+
+    Ok(())
+}
+
 /// Provides a wrapped acceptor for server-side TLS. This returns our wrapped
 /// `TlsStream` type so that clients can store negotiated and handshaking
 /// streams in a structure with a uniform type.
 pub struct TlsTcpAcceptor {
     inner: boring::ssl::SslContext,
+    verification: ClientVerification,
 }
 
 impl TlsTcpAcceptor {
@@ -170,6 +305,51 @@ impl TlsTcpAcceptor {
             })?;
         }
 
+        // request and verify client certificates, if enabled
+        let mode = match builder.client_verification {
+            ClientVerification::None => SslVerifyMode::NONE,
+            ClientVerification::Optional => SslVerifyMode::PEER,
+            ClientVerification::Required => {
+                SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT
+            }
+        };
+
+        if builder.allow_list.is_empty() {
+            acceptor.set_verify(mode);
+        } else {
+            let allow_list = builder.allow_list.clone();
+            acceptor.set_verify_callback(mode, move |preverified, ctx| {
+                // only the leaf certificate is checked against the allow-list
+                if !preverified || ctx.error_depth() != 0 {
+                    return preverified;
+                }
+
+                let allowed = ctx
+                    .current_cert()
+                    .map(|cert| {
+                        let (common_names, sans) = certificate_names(cert);
+                        allow_list.allows(&common_names, &sans)
+                    })
+                    .unwrap_or(false);
+
+                if !allowed {
+                    unsafe {
+                        boring_sys::X509_STORE_CTX_set_error(
+                            ctx.as_ptr(),
+                            boring_sys::X509_V_ERR_APPLICATION_VERIFICATION as _,
+                        );
+                    }
+                }
+
+                allowed
+            });
+        }
+
+        // load the certificate revocation lists, if provided
+        if let Some(f) = builder.crl_file {
+            load_crl_file(acceptor.cert_store_mut(), &f)?;
+        }
+
         // load the private key from file
         if let Some(f) = builder.private_key_file {
             acceptor
@@ -273,7 +453,10 @@ impl TlsTcpAcceptor {
 
         let inner = acceptor.build().into_context();
 
-        Ok(TlsTcpAcceptor { inner })
+        Ok(TlsTcpAcceptor {
+            inner,
+            verification: builder.client_verification,
+        })
     }
 
     pub fn accept(&self, stream: TcpStream) -> Result<TlsTcpStream> {
@@ -287,6 +470,7 @@ impl TlsTcpAcceptor {
             Ok(TlsTcpStream {
                 inner: stream,
                 state: TlsState::Negotiated,
+                verification: self.verification,
             })
         } else {
             let code = unsafe {
@@ -296,8 +480,12 @@ impl TlsTcpAcceptor {
                 ErrorCode::WANT_READ | ErrorCode::WANT_WRITE => Ok(TlsTcpStream {
                     inner: stream,
                     state: TlsState::Handshaking,
+                    verification: self.verification,
                 }),
-                _ => Err(Error::new(ErrorKind::Other, "handshake failed")),
+                _ => {
+                    handshake_failure(stream.ssl(), self.verification).record();
+                    Err(Error::new(ErrorKind::Other, "handshake failed"))
+                }
             }
         }
     }
@@ -309,13 +497,33 @@ impl TlsTcpAcceptor {
 #[allow(dead_code)]
 pub struct TlsTcpConnector {
     inner: boring::ssl::SslContext,
+    server_name: Option<String>,
+    verification: ServerVerification,
 }
 
 impl TlsTcpConnector {
     pub fn build(builder: TlsTcpConnectorBuilder) -> Result<TlsTcpConnector> {
-        let mut connector =
-            ::boring::ssl::SslAcceptor::mozilla_intermediate_v5(SslMethod::tls_server())
-                .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+        let mut connector = boring::ssl::SslConnector::builder(SslMethod::tls_client())
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+
+        // configure verification of the server certificate
+        match builder.verification {
+            ServerVerification::None => {
+                connector.set_verify(SslVerifyMode::NONE);
+            }
+            ServerVerification::Peer => {
+                connector.set_verify(SslVerifyMode::PEER);
+            }
+            ServerVerification::Full => {
+                if builder.server_name.is_none() {
+                    return Err(Error::new(
+                        ErrorKind::Other,
+                        "full server verification requires a server name",
+                    ));
+                }
+                connector.set_verify(SslVerifyMode::PEER);
+            }
+        }
 
         // load the CA file, if provided
         if let Some(f) = builder.ca_file {
@@ -324,8 +532,20 @@ impl TlsTcpConnector {
             })?;
         }
 
+        // a client certificate is optional, but if one part of the key pair is
+        // provided then the other must be as well
+        let has_certificate =
+            builder.certificate_chain_file.is_some() || builder.certificate_file.is_some();
+
         // load the private key from file
         if let Some(f) = builder.private_key_file {
+            if !has_certificate {
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    "no certificate file or certificate chain file provided",
+                ));
+            }
+
             connector
                 .set_private_key_file(f, SslFiletype::PEM)
                 .map_err(|e| {
@@ -334,7 +554,7 @@ impl TlsTcpConnector {
                         format!("failed to load private key file: {e}"),
                     )
                 })?;
-        } else {
+        } else if has_certificate {
             return Err(Error::new(ErrorKind::Other, "no private key file provided"));
         }
 
@@ -400,16 +620,18 @@ impl TlsTcpConnector {
                     })?;
             }
             (None, None) => {
-                return Err(Error::new(
-                    ErrorKind::Other,
-                    "no certificate file or certificate chain file provided",
-                ));
+                // no client certificate, the connection will only be
+                // authenticated on the server side
             }
         }
 
         let inner = connector.build().into_context();
 
-        Ok(TlsTcpConnector { inner })
+        Ok(TlsTcpConnector {
+            inner,
+            server_name: builder.server_name,
+            verification: builder.verification,
+        })
     }
 
     pub fn connect<A: ToSocketAddrs>(&self, addr: A) -> Result<TlsTcpStream> {
@@ -422,7 +644,17 @@ impl TlsTcpConnector {
             }
         }
 
-        let ssl = Ssl::new(&self.inner)?;
+        let mut ssl = Ssl::new(&self.inner)?;
+
+        // send the server name using SNI and, if requested, check that it
+        // matches the certificate presented by the server
+        if let Some(name) = &self.server_name {
+            ssl.set_hostname(name)?;
+
+            if self.verification == ServerVerification::Full {
+                ssl.param_mut().set_host(name)?;
+            }
+        }
 
         let stream = unsafe { SslStream::from_raw_parts(ssl.into_ptr(), s?) };
 
@@ -432,6 +664,7 @@ impl TlsTcpConnector {
             Ok(TlsTcpStream {
                 inner: stream,
                 state: TlsState::Negotiated,
+                verification: ClientVerification::None,
             })
         } else {
             let code = unsafe {
@@ -441,6 +674,7 @@ impl TlsTcpConnector {
                 ErrorCode::WANT_READ | ErrorCode::WANT_WRITE => Ok(TlsTcpStream {
                     inner: stream,
                     state: TlsState::Handshaking,
+                    verification: ClientVerification::None,
                 }),
                 _ => Err(Error::new(ErrorKind::Other, "handshake failed")),
             }
@@ -29,6 +29,114 @@ impl Default for Implementation {
     }
 }
 
+/// Determines how a `TlsTcpConnector` verifies the certificate presented by
+/// the server.
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub enum ServerVerification {
+    /// Do not verify the server certificate. This should only be used for
+    /// testing.
+    None,
+    /// Verify that the server certificate chains up to a trusted root.
+    #[default]
+    Peer,
+    /// In addition to `Peer`, verify that the server certificate matches the
+    /// configured server name.
+    Full,
+}
+
+/// Determines whether a `TlsTcpAcceptor` requests and verifies a certificate
+/// from the client.
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
+pub enum ClientVerification {
+    /// Do not request a client certificate.
+    #[default]
+    None,
+    /// Request a client certificate and verify it if one is presented, but
+    /// allow clients which do not present a certificate.
+    Optional,
+    /// Require a valid client certificate (mTLS).
+    Required,
+}
+
+/// The reasons a TLS/SSL handshake may fail. These are used to provide a
+/// breakdown of handshake failures in the metrics.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub(crate) enum HandshakeFailure {
+    /// The peer did not present a certificate, but one was required.
+    NoCertificate,
+    /// The peer certificate was valid, but was not in the allow-list.
+    NotAllowed,
+    /// The peer certificate has been revoked.
+    Revoked,
+    /// The peer certificate failed verification for another reason.
+    Verification,
+    /// The handshake failed for a reason unrelated to the peer certificate.
+    Other,
+}
+
+impl HandshakeFailure {
+    // the X509_V_* values are the same for OpenSSL and BoringSSL
+    const X509_V_OK: i64 = 0;
+    const X509_V_ERR_CERT_REVOKED: i64 = 23;
+    const X509_V_ERR_APPLICATION_VERIFICATION: i64 = 50;
+
+    /// Determine the failure reason from the presence of a peer certificate
+    /// and the verification result of the session.
+    pub(crate) fn new(
+        verification: ClientVerification,
+        has_peer_certificate: bool,
+        verify_result: i64,
+    ) -> Self {
+        match verify_result {
+            Self::X509_V_OK => {
+                if verification == ClientVerification::Required && !has_peer_certificate {
+                    Self::NoCertificate
+                } else {
+                    Self::Other
+                }
+            }
+            Self::X509_V_ERR_CERT_REVOKED => Self::Revoked,
+            Self::X509_V_ERR_APPLICATION_VERIFICATION => Self::NotAllowed,
+            _ => Self::Verification,
+        }
+    }
+
+    /// Increment the metric for this failure reason.
+    pub(crate) fn record(self) {
+        metric! {
+            match self {
+                Self::NoCertificate => STREAM_HANDSHAKE_EX_NO_CERT.increment(),
+                Self::NotAllowed => STREAM_HANDSHAKE_EX_NOT_ALLOWED.increment(),
+                Self::Revoked => STREAM_HANDSHAKE_EX_REVOKED.increment(),
+                Self::Verification => STREAM_HANDSHAKE_EX_VERIFY.increment(),
+                Self::Other => STREAM_HANDSHAKE_EX_OTHER.increment(),
+            };
+        }
+    }
+}
+
+/// An allow-list for client certificates. A certificate is allowed if its
+/// subject common name is in the list of subjects or if any of its subject
+/// alternative names are in the list of SANs. An empty allow-list allows any
+/// certificate which passes verification.
+#[derive(Clone, Default)]
+pub(crate) struct ClientAllowList {
+    subjects: Vec<String>,
+    sans: Vec<String>,
+}
+
+impl ClientAllowList {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.subjects.is_empty() && self.sans.is_empty()
+    }
+
+    pub(crate) fn allows(&self, common_names: &[String], sans: &[String]) -> bool {
+        self.is_empty()
+            || common_names.iter().any(|cn| self.subjects.contains(cn))
+            || sans.iter().any(|san| self.sans.contains(san))
+    }
+}
+
 #[derive(PartialEq, Debug, Copy, Clone)]
 pub enum ShutdownResult {
     Sent,
@@ -89,6 +197,15 @@ impl TlsTcpStream {
         }
     }
 
+    pub fn peer_addr(&self) -> Result<SocketAddr> {
+        match &self.inner {
+            #[cfg(feature = "boringssl")]
+            TlsTcpStreamImpl::Boringssl(s) => s.peer_addr(),
+            #[cfg(feature = "openssl")]
+            TlsTcpStreamImpl::Openssl(s) => s.peer_addr(),
+        }
+    }
+
     pub fn is_handshaking(&self) -> bool {
         match &self.inner {
             #[cfg(feature = "boringssl")]
@@ -235,6 +352,9 @@ pub struct TlsTcpAcceptorBuilder {
     certificate_file: Option<PathBuf>,
     certificate_chain_file: Option<PathBuf>,
     private_key_file: Option<PathBuf>,
+    client_verification: ClientVerification,
+    allow_list: ClientAllowList,
+    crl_file: Option<PathBuf>,
 }
 
 impl TlsTcpAcceptorBuilder {
@@ -299,6 +419,38 @@ impl TlsTcpAcceptorBuilder {
         self.private_key_file = Some(file.as_ref().to_path_buf());
         self
     }
+
+    /// Selects whether client certificates are requested and verified.
+    /// Defaults to `ClientVerification::None`. Client certificates are verified
+    /// against the CAs loaded with `ca_file`.
+    pub fn client_verification(mut self, verification: ClientVerification) -> Self {
+        self.client_verification = verification;
+        self
+    }
+
+    /// Restrict client certificates to those with a subject common name in the
+    /// provided list.
+    pub fn allowed_subjects<S: AsRef<str>>(mut self, subjects: &[S]) -> Self {
+        self.allow_list.subjects = subjects.iter().map(|s| s.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Restrict client certificates to those with a subject alternative name
+    /// in the provided list. DNS names, URIs, email addresses, and IP addresses
+    /// are compared using their string representation.
+    pub fn allowed_sans<S: AsRef<str>>(mut self, sans: &[S]) -> Self {
+        self.allow_list.sans = sans.iter().map(|s| s.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Load certificate revocation lists from a file.
+    ///
+    /// The file should contain a sequence of PEM-formatted CRLs. When provided,
+    /// revocation is checked for every certificate in the client chain.
+    pub fn crl_file<P: AsRef<Path>>(mut self, file: P) -> Self {
+        self.crl_file = Some(file.as_ref().to_path_buf());
+        self
+    }
 }
 
 pub struct TlsTcpConnector {
@@ -338,6 +490,8 @@ pub struct TlsTcpConnectorBuilder {
     certificate_file: Option<PathBuf>,
     certificate_chain_file: Option<PathBuf>,
     private_key_file: Option<PathBuf>,
+    server_name: Option<String>,
+    verification: ServerVerification,
 }
 
 impl TlsTcpConnectorBuilder {
@@ -398,8 +552,25 @@ impl TlsTcpConnectorBuilder {
     }
 
     /// Loads the private key from a PEM-formatted file.
+    ///
+    /// The private key and a certificate are only required when the server
+    /// requests a client certificate (mTLS).
     pub fn private_key_file<P: AsRef<Path>>(mut self, file: P) -> Self {
         self.private_key_file = Some(file.as_ref().to_path_buf());
         self
     }
+
+    /// Sets the server name which is sent using SNI and, with
+    /// `ServerVerification::Full`, checked against the server certificate.
+    pub fn server_name<S: AsRef<str>>(mut self, name: S) -> Self {
+        self.server_name = Some(name.as_ref().to_string());
+        self
+    }
+
+    /// Selects how the server certificate is verified. Defaults to
+    /// `ServerVerification::Peer`.
+    pub fn verification(mut self, verification: ServerVerification) -> Self {
+        self.verification = verification;
+        self
+    }
 }
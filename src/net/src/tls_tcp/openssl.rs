@@ -6,8 +6,11 @@ pub use ::openssl::ssl::ShutdownResult;
 
 use std::os::unix::prelude::AsRawFd;
 
-use ::openssl::ssl::{ErrorCode, Ssl, SslFiletype, SslMethod, SslStream};
-use ::openssl::x509::X509;
+use ::openssl::nid::Nid;
+use ::openssl::ssl::{ErrorCode, Ssl, SslFiletype, SslMethod, SslRef, SslStream, SslVerifyMode};
+use ::openssl::x509::store::{X509Lookup, X509StoreBuilderRef};
+use ::openssl::x509::verify::X509VerifyFlags;
+use ::openssl::x509::{X509Ref, X509};
 use foreign_types_shared_01::ForeignTypeRef;
 
 use crate::*;
@@ -23,6 +26,7 @@ enum TlsState {
 pub struct TlsTcpStream {
     inner: SslStream<TcpStream>,
     state: TlsState,
+    verification: ClientVerification,
 }
 
 impl AsRawFd for TlsTcpStream {
@@ -36,6 +40,10 @@ impl TlsTcpStream {
         self.inner.get_mut().set_nodelay(nodelay)
     }
 
+    pub fn peer_addr(&self) -> Result<SocketAddr> {
+        self.inner.get_ref().peer_addr()
+    }
+
     pub fn is_handshaking(&self) -> bool {
         self.state == TlsState::Handshaking
     }
@@ -69,6 +77,8 @@ impl TlsTcpStream {
                             STREAM_HANDSHAKE_EX.increment();
                         }
 
+                        handshake_failure(self.inner.ssl(), self.verification).record();
+
                         Err(Error::new(ErrorKind::Other, "handshake failed"))
                     }
                 }
@@ -147,11 +157,85 @@ impl event::Source for TlsTcpStream {
     }
 }
 
+/// Determine why a handshake failed using the verification result for the
+/// session.
+fn handshake_failure(ssl: &SslRef, verification: ClientVerification) -> HandshakeFailure {
+    let verify_result = unsafe { openssl_sys::SSL_get_verify_result(ssl.as_ptr()) } as i64;
+    HandshakeFailure::new(
+        verification,
+        ssl.peer_certificate().is_some(),
+        verify_result,
+    )
+}
+
+/// Returns the subject common names and the subject alternative names of a
+/// certificate.
+fn certificate_names(cert: &X509Ref) -> (Vec<String>, Vec<String>) {
+    let common_names = cert
+        .subject_name()
+        .entries_by_nid(Nid::COMMONNAME)
+        .filter_map(|entry| entry.data().as_utf8().ok().map(|v| v.to_string()))
+        .collect();
+
+    let mut sans = Vec::new();
+    if let Some(names) = cert.subject_alt_names() {
+        for name in &names {
+            if let Some(v) = name.dnsname() {
+                sans.push(v.to_string());
+            } else if let Some(v) = name.uri() {
+                sans.push(v.to_string());
+            } else if let Some(v) = name.email() {
+                sans.push(v.to_string());
+            } else if let Some(v) = name.ipaddress() {
+                match v.len() {
+                    4 => {
+                        let octets: [u8; 4] = v.try_into().unwrap();
+                        sans.push(std::net::IpAddr::from(octets).to_string());
+                    }
+                    16 => {
+                        let octets: [u8; 16] = v.try_into().unwrap();
+                        sans.push(std::net::IpAddr::from(octets).to_string());
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    (common_names, sans)
+}
+
+/// Load all the PEM-formatted CRLs in a file into the certificate store and
+/// enable revocation checking for the full certificate chain.
+fn load_crl_file(store: &mut X509StoreBuilderRef, file: &std::path::Path) -> Result<()> {
+    let loaded = store
+        .add_lookup(X509Lookup::file())
+        .and_then(|lookup| lookup.load_crl_file(file, SslFiletype::PEM))
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed to load CRL file: {}\n{}", file.display(), e),
+            )
+        })?;
+
+    if loaded == 0 {
+        return Err(Error::new(
+            ErrorKind::Other,
+            format!("no CRLs found in CRL file: {}", file.display()),
+        ));
+    }
+
+    store
+        .set_flags(X509VerifyFlags::CRL_CHECK | X509VerifyFlags::CRL_CHECK_ALL)
+        .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))
+}
+
 /// Provides a wrapped acceptor for server-side TLS. This returns our wrapped
 /// `TlsStream` type so that clients can store negotiated and handshaking
 /// streams in a structure with a uniform type.
 pub struct TlsTcpAcceptor {
     inner: ::openssl::ssl::SslContext,
+    verification: ClientVerification,
 }
 
 impl TlsTcpAcceptor {
@@ -170,6 +254,51 @@ impl TlsTcpAcceptor {
             })?;
         }
 
+        // request and verify client certificates, if enabled
+        let mode = match builder.client_verification {
+            ClientVerification::None => SslVerifyMode::NONE,
+            ClientVerification::Optional => SslVerifyMode::PEER,
+            ClientVerification::Required => {
+                SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT
+            }
+        };
+
+        if builder.allow_list.is_empty() {
+            acceptor.set_verify(mode);
+        } else {
+            let allow_list = builder.allow_list.clone();
+            acceptor.set_verify_callback(mode, move |preverified, ctx| {
+                // only the leaf certificate is checked against the allow-list
+                if !preverified || ctx.error_depth() != 0 {
+                    return preverified;
+                }
+
+                let allowed = ctx
+                    .current_cert()
+                    .map(|cert| {
+                        let (common_names, sans) = certificate_names(cert);
+                        allow_list.allows(&common_names, &sans)
+                    })
+                    .unwrap_or(false);
+
+                if !allowed {
+                    unsafe {
+                        openssl_sys::X509_STORE_CTX_set_error(
+                            ctx.as_ptr(),
+                            openssl_sys::X509_V_ERR_APPLICATION_VERIFICATION as _,
+                        );
+                    }
+                }
+
+                allowed
+            });
+        }
+
+        // load the certificate revocation lists, if provided
+        if let Some(f) = builder.crl_file {
+            load_crl_file(acceptor.cert_store_mut(), &f)?;
+        }
+
         // load the private key from file
         if let Some(f) = builder.private_key_file {
             acceptor
@@ -273,7 +402,10 @@ impl TlsTcpAcceptor {
 
         let inner = acceptor.build().into_context();
 
-        Ok(TlsTcpAcceptor { inner })
+        Ok(TlsTcpAcceptor {
+            inner,
+            verification: builder.client_verification,
+        })
     }
 
     pub fn accept(&self, stream: TcpStream) -> Result<TlsTcpStream> {
@@ -287,6 +419,7 @@ impl TlsTcpAcceptor {
             Ok(TlsTcpStream {
                 inner: stream,
                 state: TlsState::Negotiated,
+                verification: self.verification,
             })
         } else {
             let code = unsafe {
@@ -296,8 +429,12 @@ impl TlsTcpAcceptor {
                 ErrorCode::WANT_READ | ErrorCode::WANT_WRITE => Ok(TlsTcpStream {
                     inner: stream,
                     state: TlsState::Handshaking,
+                    verification: self.verification,
                 }),
-                _ => Err(Error::new(ErrorKind::Other, "handshake failed")),
+                _ => {
+                    handshake_failure(stream.ssl(), self.verification).record();
+                    Err(Error::new(ErrorKind::Other, "handshake failed"))
+                }
             }
         }
     }
@@ -309,6 +446,8 @@ impl TlsTcpAcceptor {
 #[allow(dead_code)]
 pub struct TlsTcpConnector {
     inner: ::openssl::ssl::SslContext,
+    server_name: Option<String>,
+    verification: ServerVerification,
 }
 
 impl TlsTcpConnector {
@@ -316,6 +455,25 @@ impl TlsTcpConnector {
         let mut connector = ::openssl::ssl::SslConnector::builder(SslMethod::tls_client())
             .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
 
+        // configure verification of the server certificate
+        match builder.verification {
+            ServerVerification::None => {
+                connector.set_verify(SslVerifyMode::NONE);
+            }
+            ServerVerification::Peer => {
+                connector.set_verify(SslVerifyMode::PEER);
+            }
+            ServerVerification::Full => {
+                if builder.server_name.is_none() {
+                    return Err(Error::new(
+                        ErrorKind::Other,
+                        "full server verification requires a server name",
+                    ));
+                }
+                connector.set_verify(SslVerifyMode::PEER);
+            }
+        }
+
         // load the CA file, if provided
         if let Some(f) = builder.ca_file {
             connector.set_ca_file(f).map_err(|e| {
@@ -323,8 +481,20 @@ impl TlsTcpConnector {
             })?;
         }
 
+        // a client certificate is optional, but if one part of the key pair is
+        // provided then the other must be as well
+        let has_certificate =
+            builder.certificate_chain_file.is_some() || builder.certificate_file.is_some();
+
         // load the private key from file
         if let Some(f) = builder.private_key_file {
+            if !has_certificate {
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    "no certificate file or certificate chain file provided",
+                ));
+            }
+
             connector
                 .set_private_key_file(f, SslFiletype::PEM)
                 .map_err(|e| {
@@ -333,7 +503,7 @@ impl TlsTcpConnector {
                         format!("failed to load private key file: {e}"),
                     )
                 })?;
-        } else {
+        } else if has_certificate {
             return Err(Error::new(ErrorKind::Other, "no private key file provided"));
         }
 
@@ -399,16 +569,18 @@ impl TlsTcpConnector {
                     })?;
             }
             (None, None) => {
-                return Err(Error::new(
-                    ErrorKind::Other,
-                    "no certificate file or certificate chain file provided",
-                ));
+                // no client certificate, the connection will only be
+                // authenticated on the server side
             }
         }
 
         let inner = connector.build().into_context();
 
-        Ok(TlsTcpConnector { inner })
+        Ok(TlsTcpConnector {
+            inner,
+            server_name: builder.server_name,
+            verification: builder.verification,
+        })
     }
 
     pub fn connect<A: ToSocketAddrs>(&self, addr: A) -> Result<TlsTcpStream> {
@@ -421,7 +593,17 @@ impl TlsTcpConnector {
             }
         }
 
-        let ssl = Ssl::new(&self.inner)?;
+        let mut ssl = Ssl::new(&self.inner)?;
+
+        // send the server name using SNI and, if requested, check that it
+        // matches the certificate presented by the server
+        if let Some(name) = &self.server_name {
+            ssl.set_hostname(name)?;
+
+            if self.verification == ServerVerification::Full {
+                ssl.param_mut().set_host(name)?;
+            }
+        }
 
         let stream = SslStream::new(ssl, s?)?;
 
@@ -431,6 +613,7 @@ impl TlsTcpConnector {
             Ok(TlsTcpStream {
                 inner: stream,
                 state: TlsState::Negotiated,
+                verification: ClientVerification::None,
             })
         } else {
             let code = unsafe {
@@ -440,6 +623,7 @@ impl TlsTcpConnector {
                 ErrorCode::WANT_READ | ErrorCode::WANT_WRITE => Ok(TlsTcpStream {
                     inner: stream,
                     state: TlsState::Handshaking,
+                    verification: ClientVerification::None,
                 }),
                 _ => Err(Error::new(ErrorKind::Other, "handshake failed")),
             }
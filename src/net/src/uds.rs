@@ -0,0 +1,293 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use crate::*;
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::os::unix::prelude::AsRawFd;
+use std::path::{Path, PathBuf};
+
+pub struct UnixStream {
+    inner: mio::net::UnixStream,
+}
+
+impl UnixStream {
+    pub fn connect<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let inner = mio::net::UnixStream::connect(path)?;
+
+        metric! {
+            UNIX_CONN_CURR.increment();
+            UNIX_CONNECT.increment();
+        }
+
+        Ok(Self { inner })
+    }
+
+    pub fn shutdown(&self, how: Shutdown) -> Result<()> {
+        self.inner.shutdown(how)
+    }
+}
+
+impl AsRawFd for UnixStream {
+    fn as_raw_fd(&self) -> i32 {
+        self.inner.as_raw_fd()
+    }
+}
+
+impl Drop for UnixStream {
+    fn drop(&mut self) {
+        metric! {
+            UNIX_CONN_CURR.decrement();
+            UNIX_CLOSE.increment();
+        }
+    }
+}
+
+impl Debug for UnixStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "{:?}", self.inner)
+    }
+}
+
+impl Read for UnixStream {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        match self.inner.read(buf) {
+            Ok(amt) => {
+                metric! {
+                    UNIX_RECV_BYTE.add(amt as _);
+                }
+
+                Ok(amt)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl Write for UnixStream {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        match self.inner.write(buf) {
+            Ok(amt) => {
+                metric! {
+                    UNIX_SEND_BYTE.add(amt as _);
+                }
+
+                Ok(amt)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl event::Source for UnixStream {
+    fn register(
+        &mut self,
+        registry: &mio::Registry,
+        token: mio::Token,
+        interest: mio::Interest,
+    ) -> Result<()> {
+        self.inner.register(registry, token, interest)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &mio::Registry,
+        token: mio::Token,
+        interest: mio::Interest,
+    ) -> Result<()> {
+        self.inner.reregister(registry, token, interest)
+    }
+
+    fn deregister(&mut self, registry: &mio::Registry) -> Result<()> {
+        self.inner.deregister(registry)
+    }
+}
+
+/// A listener for Unix domain stream sockets. The socket file is removed when
+/// the listener is dropped.
+pub struct UnixListener {
+    inner: mio::net::UnixListener,
+    path: PathBuf,
+}
+
+impl UnixListener {
+    /// Bind a new listener to the provided path.
+    ///
+    /// If a socket file already exists at the path and no process is accepting
+    /// connections on it, it is considered stale and removed. An error is
+    /// returned if the socket is in use or if the path exists but is not a
+    /// socket.
+    pub fn bind<P: AsRef<Path>>(path: P) -> Result<UnixListener> {
+        let path = path.as_ref();
+
+        if let Ok(metadata) = std::fs::symlink_metadata(path) {
+            if !metadata.file_type().is_socket() {
+                return Err(Error::new(
+                    ErrorKind::AlreadyExists,
+                    format!("path exists and is not a socket: {}", path.display()),
+                ));
+            }
+
+            match std::os::unix::net::UnixStream::connect(path) {
+                Ok(_) => {
+                    return Err(Error::new(
+                        ErrorKind::AddrInUse,
+                        format!("socket is in use: {}", path.display()),
+                    ));
+                }
+                Err(_) => {
+                    std::fs::remove_file(path)?;
+                }
+            }
+        }
+
+        let inner = mio::net::UnixListener::bind(path)?;
+
+        Ok(Self {
+            inner,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Set the file permissions of the socket file, for example `0o660`.
+    pub fn set_permissions(&self, mode: u32) -> Result<()> {
+        std::fs::set_permissions(&self.path, std::fs::Permissions::from_mode(mode))
+    }
+
+    #[allow(clippy::let_and_return)]
+    pub fn accept(&self) -> Result<UnixStream> {
+        let result = self
+            .inner
+            .accept()
+            .map(|(stream, _addr)| UnixStream { inner: stream });
+
+        metric! {
+            if result.is_ok() {
+                UNIX_ACCEPT.increment();
+                UNIX_CONN_CURR.increment();
+            }
+        }
+
+        result
+    }
+
+    /// Returns the path of the socket file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for UnixListener {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+impl event::Source for UnixListener {
+    fn register(
+        &mut self,
+        registry: &mio::Registry,
+        token: mio::Token,
+        interests: mio::Interest,
+    ) -> Result<()> {
+        self.inner.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &mio::Registry,
+        token: mio::Token,
+        interests: mio::Interest,
+    ) -> Result<()> {
+        self.inner.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &mio::Registry) -> Result<()> {
+        self.inner.deregister(registry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn socket_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("pelikan-net-{}-{}.sock", name, std::process::id()))
+    }
+
+    #[test]
+    fn stale_socket_is_removed() {
+        let path = socket_path("stale");
+
+        // leave a socket file behind without a listener
+        let listener = std::os::unix::net::UnixListener::bind(&path).expect("failed to bind");
+        drop(listener);
+        assert!(path.exists());
+
+        let listener = UnixListener::bind(&path).expect("failed to bind over stale socket");
+        drop(listener);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn socket_in_use() {
+        let path = socket_path("in-use");
+
+        let _listener = UnixListener::bind(&path).expect("failed to bind");
+        assert!(UnixListener::bind(&path).is_err());
+    }
+
+    #[test]
+    fn ping_pong() {
+        let path = socket_path("ping-pong");
+
+        let listener = Listener::from(UnixListener::bind(&path).expect("failed to bind"));
+
+        let mut client_stream =
+            Stream::from(UnixStream::connect(&path).expect("failed to connect"));
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        let mut server_stream = listener.accept().expect("failed to accept");
+
+        client_stream
+            .write_all(b"PING\r\n")
+            .expect("failed to write");
+        client_stream.flush().expect("failed to flush");
+
+        std::thread::sleep(std::time::Duration::from_millis(100));
+
+        let mut buf = [0; 4096];
+
+        match server_stream.read(&mut buf) {
+            Ok(6) => {
+                assert_eq!(&buf[0..6], b"PING\r\n");
+                server_stream
+                    .write_all(b"PONG\r\n")
+                    .expect("failed to write");
+            }
+            Ok(n) => {
+                panic!("read: {n} bytes but expected 6");
+            }
+            Err(e) => {
+                panic!("error reading: {e}");
+            }
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(100));
+
+        match client_stream.read(&mut buf) {
+            Ok(6) => {
+                assert_eq!(&buf[0..6], b"PONG\r\n");
+            }
+            Ok(n) => {
+                panic!("read: {n} bytes but expected 6");
+            }
+            Err(e) => {
+                panic!("error reading: {e}");
+            }
+        }
+    }
+}
@@ -11,16 +11,29 @@ use crate::*;
 use common::bytes::SliceExtension;
 use metriken::*;
 
+use std::fmt::Display;
 use std::io::{Error, ErrorKind};
+use std::time::Duration;
 
 // TODO(bmartin): see TODO for protocol::data::Request, this is cleaner here
 // since the variants are simple, but better to take the same approach in both
 // modules.
 #[derive(PartialEq, Eq, Debug)]
 pub enum AdminRequest {
+    Bgsave,
+    Config,
     FlushAll,
+    Metadump,
     Stats,
+    StatsItems,
+    StatsSegments,
+    StatsSettings,
+    StatsSizes,
+    StatsTtl,
+    Verbosity(u8),
     Version,
+    /// Stream 1 in every N requests seen by the process to the session.
+    Watch(u64),
     Quit,
 }
 
@@ -45,24 +58,53 @@ impl Protocol<AdminRequest, AdminResponse> for AdminProtocol {
             let mut single_byte_windows = trimmed_buffer.windows(1);
             if let Some(command_verb_end) = single_byte_windows.position(|w| w == b" ") {
                 let command_verb = &trimmed_buffer[0..command_verb_end];
-                // TODO(bmartin): 'stats slab' will go here eventually which will
-                // remove the need for ignoring this lint.
-                #[allow(clippy::match_single_binding)]
-                match command_verb {
-                    _ => Err(Error::from(ErrorKind::InvalidInput)),
-                }
+                let mut args = trimmed_buffer[command_verb_end..]
+                    .split(|b| *b == b' ')
+                    .filter(|arg| !arg.is_empty());
+
+                let request = match (command_verb, args.next(), args.next()) {
+                    (b"stats", Some(b"items"), None) => AdminRequest::StatsItems,
+                    (b"stats", Some(b"segments"), None) => AdminRequest::StatsSegments,
+                    (b"stats", Some(b"settings"), None) => AdminRequest::StatsSettings,
+                    (b"stats", Some(b"sizes"), None) => AdminRequest::StatsSizes,
+                    (b"stats", Some(b"ttl"), None) => AdminRequest::StatsTtl,
+                    (b"verbosity", Some(level), None) => std::str::from_utf8(level)
+                        .ok()
+                        .and_then(|level| level.parse().ok())
+                        .map(AdminRequest::Verbosity)
+                        .ok_or_else(|| Error::from(ErrorKind::InvalidInput))?,
+                    (b"watch", Some(sample), None) => std::str::from_utf8(sample)
+                        .ok()
+                        .and_then(|sample| sample.parse().ok())
+                        .filter(|sample| *sample > 0)
+                        .map(AdminRequest::Watch)
+                        .ok_or_else(|| Error::from(ErrorKind::InvalidInput))?,
+                    _ => return Err(Error::from(ErrorKind::InvalidInput)),
+                };
+
+                Ok(ParseOk::new(request, command_end + CRLF.len()))
             } else {
                 match &trimmed_buffer[0..] {
+                    b"bgsave" => Ok(ParseOk::new(AdminRequest::Bgsave, command_end + CRLF.len())),
+                    b"config" => Ok(ParseOk::new(AdminRequest::Config, command_end + CRLF.len())),
                     b"flush_all" => Ok(ParseOk::new(
                         AdminRequest::FlushAll,
                         command_end + CRLF.len(),
                     )),
+                    b"metadump" => Ok(ParseOk::new(
+                        AdminRequest::Metadump,
+                        command_end + CRLF.len(),
+                    )),
                     b"stats" => Ok(ParseOk::new(AdminRequest::Stats, command_end + CRLF.len())),
                     b"quit" => Ok(ParseOk::new(AdminRequest::Quit, command_end + CRLF.len())),
                     b"version" => Ok(ParseOk::new(
                         AdminRequest::Version,
                         command_end + CRLF.len(),
                     )),
+                    b"watch" => Ok(ParseOk::new(
+                        AdminRequest::Watch(1),
+                        command_end + CRLF.len(),
+                    )),
                     _ => Err(Error::from(ErrorKind::InvalidInput)),
                 }
             }
@@ -76,10 +118,29 @@ impl Protocol<AdminRequest, AdminResponse> for AdminProtocol {
         request: &admin::AdminRequest,
         buffer: &mut dyn protocol_common::BufMut,
     ) -> std::result::Result<usize, std::io::Error> {
+        let verbosity;
+        let watch;
         let cmd = match request {
+            AdminRequest::Bgsave => "bgsave\r\n",
+            AdminRequest::Config => "config\r\n",
             AdminRequest::FlushAll => "flush_all\r\n",
+            AdminRequest::Metadump => "metadump\r\n",
             AdminRequest::Stats => "stats\r\n",
+            AdminRequest::StatsItems => "stats items\r\n",
+            AdminRequest::StatsSegments => "stats segments\r\n",
+            AdminRequest::StatsSettings => "stats settings\r\n",
+            AdminRequest::StatsSizes => "stats sizes\r\n",
+            AdminRequest::StatsTtl => "stats ttl\r\n",
+            AdminRequest::Verbosity(level) => {
+                verbosity = format!("verbosity {level}\r\n");
+                &verbosity
+            }
             AdminRequest::Version => "version\r\n",
+            AdminRequest::Watch(1) => "watch\r\n",
+            AdminRequest::Watch(sample) => {
+                watch = format!("watch {sample}\r\n");
+                &watch
+            }
             AdminRequest::Quit => "quit\r\n",
         };
 
@@ -90,10 +151,59 @@ impl Protocol<AdminRequest, AdminResponse> for AdminProtocol {
 
     fn parse_response(
         &self,
-        _: &admin::AdminRequest,
-        _: &[u8],
+        request: &admin::AdminRequest,
+        buffer: &[u8],
     ) -> std::result::Result<protocol_common::ParseOk<admin::AdminResponse>, std::io::Error> {
-        todo!("this is not implemented yet")
+        // the server closes the connection without responding
+        if *request == AdminRequest::Quit {
+            return Ok(ParseOk::new(AdminResponse::Hangup, 0));
+        }
+
+        let line_end = buffer
+            .windows(CRLF.len())
+            .position(|w| w == CRLF.as_bytes())
+            .ok_or_else(|| Error::from(ErrorKind::WouldBlock))?;
+        let line = &buffer[0..line_end];
+        let consumed = line_end + CRLF.len();
+
+        if let Some(message) = line.strip_prefix(b"SERVER_ERROR ") {
+            let message = String::from_utf8_lossy(message);
+            return Ok(ParseOk::new(AdminResponse::error(&message), consumed));
+        }
+
+        match request {
+            AdminRequest::Version => {
+                let version = line
+                    .strip_prefix(b"VERSION ")
+                    .ok_or_else(|| Error::from(ErrorKind::InvalidData))?;
+                let version = String::from_utf8_lossy(version).into_owned();
+                Ok(ParseOk::new(AdminResponse::version(version), consumed))
+            }
+            AdminRequest::Bgsave
+            | AdminRequest::FlushAll
+            | AdminRequest::Verbosity(_)
+            | AdminRequest::Watch(_) => {
+                if line == b"OK" {
+                    Ok(ParseOk::new(AdminResponse::Ok, consumed))
+                } else {
+                    Err(Error::from(ErrorKind::InvalidData))
+                }
+            }
+            // the remaining responses are a series of lines terminated with
+            // `END`, which are kept as they were sent
+            _ => {
+                let consumed = end_of_lines(buffer)?;
+                let data = buffer[0..consumed].to_vec();
+                let response = match request {
+                    AdminRequest::Config => {
+                        AdminResponse::Config(String::from_utf8_lossy(&data).into_owned())
+                    }
+                    AdminRequest::Metadump => AdminResponse::Metadump(Metadump { data }),
+                    _ => AdminResponse::StatsGroup(StatsGroup { data }),
+                };
+                Ok(ParseOk::new(response, consumed))
+            }
+        }
     }
 
     fn compose_response(
@@ -104,6 +214,33 @@ impl Protocol<AdminRequest, AdminResponse> for AdminProtocol {
     ) -> std::result::Result<usize, std::io::Error> {
         Ok(response.compose(buffer))
     }
+
+    fn compose_monitor(
+        &self,
+        event: &common::monitor::MonitorEvent,
+        buffer: &mut dyn protocol_common::BufMut,
+    ) -> usize {
+        let line = format!("{event}\r\n");
+        buffer.put_slice(line.as_bytes());
+        line.len()
+    }
+}
+
+/// Returns the length of a response which consists of lines terminated by an
+/// `END` line, including the `END` line.
+fn end_of_lines(buffer: &[u8]) -> std::result::Result<usize, Error> {
+    let mut start = 0;
+    while let Some(line_end) = buffer[start..]
+        .windows(CRLF.len())
+        .position(|w| w == CRLF.as_bytes())
+    {
+        let line = &buffer[start..(start + line_end)];
+        start += line_end + CRLF.len();
+        if line == b"END" {
+            return Ok(start);
+        }
+    }
+    Err(Error::from(ErrorKind::WouldBlock))
 }
 
 pub struct Version {
@@ -120,18 +257,119 @@ impl Compose for Version {
     }
 }
 
+/// A batch of key metadata, one key per line. Keys are percent-encoded so
+/// that each line may be split on whitespace, and a TTL of -1 indicates that
+/// the key does not expire. The final batch is terminated with `END`.
+pub struct Metadump {
+    data: Vec<u8>,
+}
+
+impl Metadump {
+    fn new<'a>(
+        entries: impl IntoIterator<Item = (&'a [u8], Option<Duration>, usize)>,
+        end: bool,
+    ) -> Self {
+        let mut data = Vec::new();
+
+        for (key, ttl, size) in entries {
+            data.extend_from_slice(b"key=");
+            for byte in key {
+                if byte.is_ascii_alphanumeric() || b"-_.~:".contains(byte) {
+                    data.push(*byte);
+                } else {
+                    data.extend_from_slice(format!("%{byte:02X}").as_bytes());
+                }
+            }
+
+            let ttl = ttl.map(|ttl| ttl.as_secs() as i64).unwrap_or(-1);
+            data.extend_from_slice(format!(" ttl={ttl} size={size}\r\n").as_bytes());
+        }
+
+        if end {
+            data.extend_from_slice(b"END\r\n");
+        }
+
+        Self { data }
+    }
+}
+
+impl Compose for Metadump {
+    fn compose(&self, buf: &mut dyn BufMut) -> usize {
+        buf.put_slice(&self.data);
+        self.data.len()
+    }
+}
+
+/// A set of named values, one `STAT name value` line each, as returned by the
+/// `stats` subcommands. Terminated with `END`.
+pub struct StatsGroup {
+    data: Vec<u8>,
+}
+
+impl StatsGroup {
+    fn new<K: Display, V: Display>(entries: impl IntoIterator<Item = (K, V)>) -> Self {
+        let mut data = Vec::new();
+
+        for (name, value) in entries {
+            data.extend_from_slice(format!("STAT {name} {value}\r\n").as_bytes());
+        }
+
+        data.extend_from_slice(b"END\r\n");
+
+        Self { data }
+    }
+}
+
+impl Compose for StatsGroup {
+    fn compose(&self, buf: &mut dyn BufMut) -> usize {
+        buf.put_slice(&self.data);
+        self.data.len()
+    }
+}
+
 pub enum AdminResponse {
+    Config(String),
+    Error(String),
     Hangup,
+    Metadump(Metadump),
     Ok,
     Stats,
+    StatsGroup(StatsGroup),
     Version(Version),
 }
 
 impl AdminResponse {
+    /// The effective configuration, which is sent line by line and
+    /// terminated with `END`.
+    pub fn config(config: &str) -> Self {
+        let mut data = String::new();
+        for line in config.lines() {
+            data.push_str(line);
+            data.push_str("\r\n");
+        }
+        data.push_str("END\r\n");
+
+        Self::Config(data)
+    }
+
+    /// An error which prevented the request from being handled.
+    pub fn error(message: &str) -> Self {
+        Self::Error(message.to_string())
+    }
+
     pub fn hangup() -> Self {
         Self::Hangup
     }
 
+    /// A batch of metadata for keys as `(key, ttl, size)`. The last batch
+    /// of a metadump should set `end`.
+    pub fn metadump<'a>(
+        entries: impl IntoIterator<Item = (&'a [u8], Option<Duration>, usize)>,
+        end: bool,
+    ) -> Self {
+        Self::Metadump(Metadump::new(entries, end))
+    }
+
     pub fn ok() -> Self {
         Self::Ok
     }
@@ -140,6 +378,11 @@ impl AdminResponse {
         Self::Stats
     }
 
+    /// A set of named values for one of the `stats` subcommands.
+    pub fn stats_group<K: Display, V: Display>(entries: impl IntoIterator<Item = (K, V)>) -> Self {
+        Self::StatsGroup(StatsGroup::new(entries))
+    }
+
     pub fn version(version: String) -> Self {
         Self::Version(Version { version })
     }
@@ -148,7 +391,18 @@ impl AdminResponse {
 impl Compose for AdminResponse {
     fn compose(&self, buf: &mut dyn BufMut) -> usize {
         match self {
+            Self::Config(config) => {
+                buf.put_slice(config.as_bytes());
+                config.len()
+            }
+            Self::Error(message) => {
+                buf.put_slice(b"SERVER_ERROR ");
+                buf.put_slice(message.as_bytes());
+                buf.put_slice(b"\r\n");
+                15 + message.len()
+            }
             Self::Hangup => 0,
+            Self::Metadump(m) => m.compose(buf),
             Self::Ok => {
                 buf.put_slice(b"OK\r\n");
                 4
@@ -158,6 +412,7 @@ impl Compose for AdminResponse {
                 buf.put_slice(message.as_bytes());
                 message.len()
             }
+            Self::StatsGroup(s) => s.compose(buf),
             Self::Version(v) => v.compose(buf),
         }
     }
@@ -211,6 +466,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_bgsave() {
+        let protocol = AdminProtocol::default();
+
+        let parsed = protocol.parse_request(b"bgsave\r\n");
+        assert!(parsed.is_ok());
+        assert_eq!(parsed.unwrap().into_inner(), AdminRequest::Bgsave);
+    }
+
     #[test]
     fn parse_flush_all() {
         let protocol = AdminProtocol::default();
@@ -220,6 +484,36 @@ mod tests {
         assert_eq!(parsed.unwrap().into_inner(), AdminRequest::FlushAll);
     }
 
+    #[test]
+    fn parse_metadump() {
+        let protocol = AdminProtocol::default();
+
+        let parsed = protocol.parse_request(b"metadump\r\n");
+        assert!(parsed.is_ok());
+        assert_eq!(parsed.unwrap().into_inner(), AdminRequest::Metadump);
+    }
+
+    #[test]
+    fn compose_metadump() {
+        let mut buf = Vec::new();
+
+        let entries: Vec<(&[u8], Option<Duration>, usize)> = vec![
+            (b"user:1", Some(Duration::from_secs(30)), 64),
+            (b"a key\n", None, 72),
+        ];
+        let len = AdminResponse::metadump(entries, true).compose(&mut buf);
+
+        assert_eq!(len, buf.len());
+        assert_eq!(
+            buf,
+            b"key=user:1 ttl=30 size=64\r\nkey=a%20key%0A ttl=-1 size=72\r\nEND\r\n"
+        );
+
+        buf.clear();
+        AdminResponse::metadump(vec![], false).compose(&mut buf);
+        assert!(buf.is_empty());
+    }
+
     #[test]
     fn parse_quit() {
         let protocol = AdminProtocol::default();
@@ -238,6 +532,130 @@ mod tests {
         assert_eq!(parsed.unwrap().into_inner(), AdminRequest::Stats);
     }
 
+    #[test]
+    fn parse_stats_subcommands() {
+        let protocol = AdminProtocol::default();
+
+        let requests: Vec<(&[u8], AdminRequest)> = vec![
+            (b"stats items\r\n", AdminRequest::StatsItems),
+            (b"stats segments\r\n", AdminRequest::StatsSegments),
+            (b"stats settings\r\n", AdminRequest::StatsSettings),
+            (b"stats  sizes \r\n", AdminRequest::StatsSizes),
+            (b"stats ttl\r\n", AdminRequest::StatsTtl),
+        ];
+        for (buffer, request) in requests {
+            let parsed = protocol.parse_request(buffer);
+            assert!(parsed.is_ok());
+            assert_eq!(parsed.unwrap().into_inner(), request);
+        }
+
+        let buffers: Vec<&[u8]> = vec![b"stats slabs\r\n", b"stats items extra\r\n"];
+        for buffer in buffers {
+            let parsed = protocol.parse_request(buffer);
+            assert_eq!(parsed.unwrap_err().kind(), ErrorKind::InvalidInput);
+        }
+    }
+
+    #[test]
+    fn parse_watch() {
+        let protocol = AdminProtocol::default();
+
+        let requests: Vec<(&[u8], AdminRequest)> = vec![
+            (b"watch\r\n", AdminRequest::Watch(1)),
+            (b"watch 100\r\n", AdminRequest::Watch(100)),
+        ];
+        for (buffer, request) in requests {
+            let parsed = protocol.parse_request(buffer);
+            assert!(parsed.is_ok());
+            assert_eq!(parsed.unwrap().into_inner(), request);
+        }
+
+        let buffers: Vec<&[u8]> = vec![b"watch 0\r\n", b"watch x\r\n", b"watch 1 2\r\n"];
+        for buffer in buffers {
+            let parsed = protocol.parse_request(buffer);
+            assert_eq!(parsed.unwrap_err().kind(), ErrorKind::InvalidInput);
+        }
+    }
+
+    #[test]
+    fn parse_verbosity() {
+        let protocol = AdminProtocol::default();
+
+        let parsed = protocol.parse_request(b"verbosity 4\r\n");
+        assert!(parsed.is_ok());
+        assert_eq!(parsed.unwrap().into_inner(), AdminRequest::Verbosity(4));
+
+        let buffers: Vec<&[u8]> = vec![b"verbosity\r\n", b"verbosity x\r\n", b"verbosity 1 2\r\n"];
+        for buffer in buffers {
+            let parsed = protocol.parse_request(buffer);
+            assert_eq!(parsed.unwrap_err().kind(), ErrorKind::InvalidInput);
+        }
+    }
+
+    #[test]
+    fn parse_config() {
+        let protocol = AdminProtocol::default();
+
+        let parsed = protocol.parse_request(b"config\r\n");
+        assert!(parsed.is_ok());
+        assert_eq!(parsed.unwrap().into_inner(), AdminRequest::Config);
+    }
+
+    #[test]
+    fn compose_stats_group() {
+        let mut buf = Vec::new();
+
+        let entries = vec![("segments", 64), ("segments_free", 60)];
+        let len = AdminResponse::stats_group(entries).compose(&mut buf);
+
+        assert_eq!(len, buf.len());
+        assert_eq!(buf, b"STAT segments 64\r\nSTAT segments_free 60\r\nEND\r\n");
+
+        buf.clear();
+        let len = AdminResponse::config("{\n  \"a\": 1\n}").compose(&mut buf);
+        assert_eq!(len, buf.len());
+        assert_eq!(buf, b"{\r\n  \"a\": 1\r\n}\r\nEND\r\n");
+    }
+
+    #[test]
+    fn parse_responses() {
+        let protocol = AdminProtocol::default();
+
+        let responses: Vec<(AdminRequest, &[u8])> = vec![
+            (AdminRequest::FlushAll, b"OK\r\n"),
+            (AdminRequest::Version, b"VERSION 0.3.2\r\n"),
+            (
+                AdminRequest::Verbosity(4),
+                b"SERVER_ERROR unknown level\r\n",
+            ),
+            (
+                AdminRequest::StatsSegments,
+                b"STAT segments 64\r\nSTAT segments_free 60\r\nEND\r\n",
+            ),
+            (AdminRequest::Config, b"{\r\n  \"a\": 1\r\n}\r\nEND\r\n"),
+            (AdminRequest::Metadump, b"key=a ttl=-1 size=72\r\nEND\r\n"),
+        ];
+        for (request, buffer) in responses {
+            let parsed = protocol.parse_response(&request, buffer).unwrap();
+            assert_eq!(parsed.consumed(), buffer.len());
+
+            // the response composes back to what was sent
+            let mut composed = Vec::new();
+            parsed.into_inner().compose(&mut composed);
+            assert_eq!(composed, buffer);
+        }
+
+        let incomplete: Vec<(AdminRequest, &[u8])> = vec![
+            (AdminRequest::Version, b"VERSION 0.3.2"),
+            (AdminRequest::Stats, b"STAT pid 1\r\nSTAT uptime 2\r\n"),
+            (AdminRequest::Metadump, b"key=a ttl=-1 size=72\r\nEN"),
+        ];
+        for (request, buffer) in incomplete {
+            let parsed = protocol.parse_response(&request, buffer);
+            assert!(matches!(parsed, Err(e) if e.kind() == ErrorKind::WouldBlock));
+        }
+    }
+
     #[test]
     fn parse_version() {
         let protocol = AdminProtocol::default();
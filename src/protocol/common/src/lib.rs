@@ -7,6 +7,7 @@
 //! protocol implementations.
 
 pub use bytes::BufMut;
+use common::monitor::MonitorEvent;
 
 pub const CRLF: &str = "\r\n";
 
@@ -21,6 +22,30 @@ pub trait Compose {
     }
 }
 
+/// Identifies the command of a request, so that metrics can be recorded for
+/// each command of a protocol.
+pub trait Command {
+    /// The name of the protocol, eg: `memcache`
+    const PROTOCOL: &'static str;
+
+    /// The name of the command, eg: `get`
+    fn command(&self) -> &'static str;
+
+    /// The arguments which follow the command, eg: the keys for `get`. This
+    /// is used to describe a request after it has been handled, for example
+    /// in the slow log, and is empty for protocols which do not provide it.
+    fn args(&self) -> Vec<Vec<u8>> {
+        Vec::new()
+    }
+
+    /// Returns true if the session which sent the request should receive
+    /// every request seen by the process from now on, eg: the RESP `MONITOR`
+    /// command.
+    fn is_monitor(&self) -> bool {
+        false
+    }
+}
+
 pub trait Execute<Request, Response: Compose> {
     fn execute(&mut self, request: &Request) -> Response;
 }
@@ -68,4 +93,11 @@ pub trait Protocol<Request, Response> {
         response: &Response,
         buffer: &mut dyn BufMut,
     ) -> Result<usize, std::io::Error>;
+
+    /// Composes a request seen by the process for a session which is
+    /// monitoring requests, eg: following the RESP `MONITOR` command.
+    /// Protocols which cannot monitor requests compose nothing.
+    fn compose_monitor(&self, _event: &MonitorEvent, _buffer: &mut dyn BufMut) -> usize {
+        0
+    }
 }
@@ -5,9 +5,9 @@
 use std::fmt;
 use std::mem::MaybeUninit;
 
-use crate::{response::status_line, Error, ParseResult};
+use crate::{Error, ParseResult};
 use httparse::{Header, ParserConfig, Status};
-use logger::{error, klog};
+use logger::klog_record;
 use protocol_common::{Parse, ParseOk};
 
 #[derive(Clone)]
@@ -149,20 +149,14 @@ impl logger::Klog for Request {
     fn klog(&self, response: &Self::Response) {
         use bstr::BStr;
 
-        let status = response.status();
-        let line = status_line(status).unwrap_or("");
+        let status = response.status() as u32;
+        let len = response.body_len();
 
         match self.data() {
-            RequestData::Get(key) => klog!("GET '{}' => {} {}", BStr::new(key), status, line),
-            RequestData::Delete(key) => klog!("DELETE '{}' => {} {}", BStr::new(key), status, line),
+            RequestData::Get(key) => klog_record("GET", &[&BStr::new(key)], status, len),
+            RequestData::Delete(key) => klog_record("DELETE", &[&BStr::new(key)], status, len),
             RequestData::Put(key, val) => {
-                klog!(
-                    "PUT '{}' {} => {} {}",
-                    BStr::new(key),
-                    val.len(),
-                    status,
-                    line
-                )
+                klog_record("PUT", &[&BStr::new(key), &val.len()], status, len)
             }
         };
     }
@@ -20,6 +20,11 @@ impl Response {
     pub fn status(&self) -> u16 {
         self.builder.status
     }
+
+    /// The length of the body, which is zero for a response without one.
+    pub fn body_len(&self) -> usize {
+        self.body.as_ref().map(|body| body.len()).unwrap_or(0)
+    }
 }
 
 pub struct ResponseBuilder {
@@ -2,9 +2,6 @@
 // Licensed under the Apache License, Version 2.0
 // http://www.apache.org/licenses/LICENSE-2.0
 
-#[macro_use]
-extern crate logger;
-
 pub mod binary;
 pub mod text;
 
@@ -25,7 +22,7 @@ pub use storage::*;
 pub use protocol_common::{Compose, Parse, ParseOk, Protocol};
 
 pub use common::expiry::TimeType;
-use logger::Klog;
+use logger::{klog_record, Klog};
 
 const CRLF: &[u8] = b"\r\n";
 
@@ -46,14 +46,16 @@ impl Klog for Add {
                 return;
             }
         };
-        klog!(
-            "\"add {} {} {} {}\" {} {}",
-            string_key(self.key()),
-            self.flags(),
-            self.ttl.get().unwrap_or(0),
-            self.value().len(),
-            code,
-            len
+        klog_record(
+            "add",
+            &[
+                &string_key(self.key()),
+                &self.flags(),
+                &self.ttl.get().unwrap_or(0),
+                &self.value().len(),
+            ],
+            code as u32,
+            len,
         );
     }
 }
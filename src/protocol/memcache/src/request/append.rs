@@ -46,14 +46,16 @@ impl Klog for Append {
                 return;
             }
         };
-        klog!(
-            "\"append {} {} {} {}\" {} {}",
-            string_key(self.key()),
-            self.flags(),
-            self.ttl.get().unwrap_or(0),
-            self.value().len(),
-            code,
-            len
+        klog_record(
+            "append",
+            &[
+                &string_key(self.key()),
+                &self.flags(),
+                &self.ttl.get().unwrap_or(0),
+                &self.value().len(),
+            ],
+            code as u32,
+            len,
         );
     }
 }
@@ -52,15 +52,17 @@ impl Klog for Cas {
                 return;
             }
         };
-        klog!(
-            "\"cas {} {} {} {} {}\" {} {}",
-            string_key(self.key()),
-            self.flags(),
-            self.ttl.get().unwrap_or(0),
-            self.value().len(),
-            self.cas(),
-            code,
-            len
+        klog_record(
+            "cas",
+            &[
+                &string_key(self.key()),
+                &self.flags(),
+                &self.ttl.get().unwrap_or(0),
+                &self.value().len(),
+                &self.cas(),
+            ],
+            code as u32,
+            len,
         );
     }
 }
@@ -36,6 +36,6 @@ impl Klog for Decr {
                 return;
             }
         };
-        klog!("\"decr {}\" {} {}", string_key(self.key()), code, len);
+        klog_record("decr", &[&string_key(self.key())], code as u32, len);
     }
 }
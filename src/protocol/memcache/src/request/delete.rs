@@ -32,6 +32,6 @@ impl Klog for Delete {
                 return;
             }
         };
-        klog!("\"delete {}\" {} {}", string_key(self.key()), code, len);
+        klog_record("delete", &[&string_key(self.key())], code as u32, len);
     }
 }
@@ -30,20 +30,12 @@ impl Klog for Get {
             let verb = if self.cas { "gets" } else { "get" };
 
             for value in res.values() {
-                if value.len().is_none() {
-                    klog!(
-                        "\"{verb} {}\" {} 0",
-                        String::from_utf8_lossy(value.key()),
-                        MISS
-                    );
-                } else {
-                    klog!(
-                        "\"{verb} {}\" {} {}",
-                        String::from_utf8_lossy(value.key()),
-                        HIT,
-                        value.len().unwrap(),
-                    );
-                }
+                let (code, len) = match value.len() {
+                    Some(len) => (HIT, len),
+                    None => (MISS, 0),
+                };
+
+                klog_record(verb, &[&string_key(value.key())], code as u32, len);
             }
         }
     }
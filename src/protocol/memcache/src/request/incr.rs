@@ -36,6 +36,6 @@ impl Klog for Incr {
                 return;
             }
         };
-        klog!("\"incr {}\" {} {}", string_key(self.key()), code, len);
+        klog_record("incr", &[&string_key(self.key())], code as u32, len);
     }
 }
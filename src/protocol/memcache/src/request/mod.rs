@@ -79,6 +79,16 @@ impl Request {
         })
     }
 
+    pub fn append(key: Box<[u8]>, value: Box<[u8]>, flags: u32, ttl: Ttl, noreply: bool) -> Self {
+        Self::Append(Append {
+            key,
+            value,
+            flags,
+            ttl,
+            noreply,
+        })
+    }
+
     pub fn cas(
         key: Box<[u8]>,
         value: Box<[u8]>,
@@ -139,6 +149,16 @@ impl Request {
         })
     }
 
+    pub fn prepend(key: Box<[u8]>, value: Box<[u8]>, flags: u32, ttl: Ttl, noreply: bool) -> Self {
+        Self::Prepend(Prepend {
+            key,
+            value,
+            flags,
+            ttl,
+            noreply,
+        })
+    }
+
     pub fn replace(key: Box<[u8]>, value: Box<[u8]>, flags: u32, ttl: Ttl, noreply: bool) -> Self {
         Self::Replace(Replace {
             key,
@@ -159,29 +179,88 @@ impl Request {
             opaque: None,
         })
     }
+
+    /// Returns true if the server will not send a response to the request.
+    pub fn noreply(&self) -> bool {
+        match self {
+            Self::Add(r) => r.noreply(),
+            Self::Append(r) => r.noreply(),
+            Self::Cas(r) => r.noreply(),
+            Self::Decr(r) => r.noreply(),
+            Self::Delete(r) => r.noreply(),
+            Self::FlushAll(r) => r.noreply(),
+            Self::Get(_) => false,
+            Self::Incr(r) => r.noreply(),
+            Self::Prepend(r) => r.noreply(),
+            // the server closes the connection instead of responding
+            Self::Quit(_) => true,
+            Self::Replace(r) => r.noreply(),
+            Self::Set(r) => r.noreply(),
+        }
+    }
 }
 
 impl Display for Request {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        f.write_str(protocol_common::Command::command(self))
+    }
+}
+
+impl protocol_common::Command for Request {
+    const PROTOCOL: &'static str = "memcache";
+
+    fn command(&self) -> &'static str {
         match self {
-            Request::Add(_) => write!(f, "add"),
-            Request::Append(_) => write!(f, "append"),
-            Request::Cas(_) => write!(f, "cas"),
-            Request::Decr(_) => write!(f, "decr"),
-            Request::Delete(_) => write!(f, "delete"),
-            Request::FlushAll(_) => write!(f, "flush_all"),
-            Request::Incr(_) => write!(f, "incr"),
+            Request::Add(_) => "add",
+            Request::Append(_) => "append",
+            Request::Cas(_) => "cas",
+            Request::Decr(_) => "decr",
+            Request::Delete(_) => "delete",
+            Request::FlushAll(_) => "flush_all",
+            Request::Incr(_) => "incr",
             Request::Get(r) => {
                 if r.cas {
-                    write!(f, "gets")
+                    "gets"
                 } else {
-                    write!(f, "get")
+                    "get"
                 }
             }
-            Request::Prepend(_) => write!(f, "prepend"),
-            Request::Quit(_) => write!(f, "quit"),
-            Request::Replace(_) => write!(f, "replace"),
-            Request::Set(_) => write!(f, "set"),
+            Request::Prepend(_) => "prepend",
+            Request::Quit(_) => "quit",
+            Request::Replace(_) => "replace",
+            Request::Set(_) => "set",
+        }
+    }
+
+    fn args(&self) -> Vec<Vec<u8>> {
+        // follows the order of the arguments in the text protocol, with the
+        // value of storage commands last
+        fn storage(key: &[u8], flags: u32, ttl: Ttl, value: &[u8]) -> Vec<Vec<u8>> {
+            vec![
+                key.to_vec(),
+                flags.to_string().into_bytes(),
+                ttl.get().unwrap_or(0).to_string().into_bytes(),
+                value.to_vec(),
+            ]
+        }
+
+        match self {
+            Request::Add(r) => storage(r.key(), r.flags(), r.ttl(), r.value()),
+            Request::Append(r) => storage(r.key(), r.flags(), r.ttl(), r.value()),
+            Request::Cas(r) => {
+                let mut args = storage(r.key(), r.flags(), r.ttl(), r.value());
+                args.insert(3, r.cas().to_string().into_bytes());
+                args
+            }
+            Request::Decr(r) => vec![r.key().to_vec(), r.value().to_string().into_bytes()],
+            Request::Delete(r) => vec![r.key().to_vec()],
+            Request::FlushAll(r) => vec![r.delay().to_string().into_bytes()],
+            Request::Incr(r) => vec![r.key().to_vec(), r.value().to_string().into_bytes()],
+            Request::Get(r) => r.keys().iter().map(|key| key.to_vec()).collect(),
+            Request::Prepend(r) => storage(r.key(), r.flags(), r.ttl(), r.value()),
+            Request::Quit(_) => Vec::new(),
+            Request::Replace(r) => storage(r.key(), r.flags(), r.ttl(), r.value()),
+            Request::Set(r) => storage(r.key(), r.flags(), r.ttl(), r.value()),
         }
     }
 }
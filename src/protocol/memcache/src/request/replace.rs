@@ -46,14 +46,16 @@ impl Klog for Replace {
                 return;
             }
         };
-        klog!(
-            "\"replace {} {} {} {}\" {} {}",
-            string_key(self.key()),
-            self.flags(),
-            self.ttl.get().unwrap_or(0),
-            self.value().len(),
-            code,
-            len
+        klog_record(
+            "replace",
+            &[
+                &string_key(self.key()),
+                &self.flags(),
+                &self.ttl.get().unwrap_or(0),
+                &self.value().len(),
+            ],
+            code as u32,
+            len,
         );
     }
 }
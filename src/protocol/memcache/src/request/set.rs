@@ -47,14 +47,16 @@ impl Klog for Set {
                 return;
             }
         };
-        klog!(
-            "\"set {} {} {} {}\" {} {}",
-            string_key(self.key()),
-            self.flags(),
-            self.ttl.get().unwrap_or(0),
-            self.value().len(),
-            code,
-            len
+        klog_record(
+            "set",
+            &[
+                &string_key(self.key()),
+                &self.flags(),
+                &self.ttl.get().unwrap_or(0),
+                &self.value().len(),
+            ],
+            code as u32,
+            len,
         );
     }
 }
@@ -163,4 +163,24 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn args() {
+        let protocol = TextProtocol::new();
+        let (_, request) = protocol
+            ._parse_request(b"cas key 7 60 5 42\r\nvalue\r\n")
+            .unwrap();
+
+        assert_eq!(protocol_common::Command::command(&request), "cas");
+        assert_eq!(
+            protocol_common::Command::args(&request),
+            vec![
+                b"key".to_vec(),
+                b"7".to_vec(),
+                b"60".to_vec(),
+                b"42".to_vec(),
+                b"value".to_vec()
+            ]
+        );
+    }
 }
@@ -9,9 +9,6 @@
 // TODO(bmartin): this crate should probably be split into one crate per
 // protocol to help separate the metrics namespaces.
 
-#[macro_use]
-extern crate logger;
-
 use crate::wire::parse::parse_keyword;
 use crate::wire::parse::parse_ping;
 use crate::wire::Keyword;
@@ -38,25 +35,27 @@ impl Protocol<Request, Response> for PingProtocol {
 
     fn compose_request(
         &self,
-        _: &Request,
-        _: &mut dyn protocol_common::BufMut,
+        request: &Request,
+        buffer: &mut dyn protocol_common::BufMut,
     ) -> std::result::Result<usize, std::io::Error> {
-        todo!()
+        Ok(request.compose(buffer))
     }
+
     fn parse_response(
         &self,
         _: &Request,
-        _: &[u8],
+        buffer: &[u8],
     ) -> std::result::Result<protocol_common::ParseOk<Response>, std::io::Error> {
-        todo!()
+        ResponseParser::new().parse(buffer)
     }
+
     fn compose_response(
         &self,
         _: &Request,
-        _: &Response,
-        _: &mut dyn protocol_common::BufMut,
+        response: &Response,
+        buffer: &mut dyn protocol_common::BufMut,
     ) -> std::result::Result<usize, std::io::Error> {
-        todo!()
+        Ok(response.compose(buffer))
     }
 }
 
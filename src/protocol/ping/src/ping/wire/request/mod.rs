@@ -13,7 +13,7 @@ mod test;
 
 use crate::Response;
 pub use keyword::Keyword;
-use logger::Klog;
+use logger::{klog_record, Klog};
 
 #[derive(Debug)]
 /// A collection of all possible `Ping` request types.
@@ -21,12 +21,23 @@ pub enum Request {
     Ping,
 }
 
+impl protocol_common::Command for Request {
+    const PROTOCOL: &'static str = "ping";
+
+    fn command(&self) -> &'static str {
+        match self {
+            Request::Ping => "ping",
+        }
+    }
+}
+
 impl Klog for Request {
     type Response = Response;
 
     fn klog(&self, _response: &Self::Response) {
         match self {
-            Request::Ping => klog!("ping {}", 6),
+            // there is no status for a ping, only the length of `PONG\r\n`
+            Request::Ping => klog_record("ping", &[], 0, 6),
         }
     }
 }
@@ -2,9 +2,6 @@
 // Licensed under the Apache License, Version 2.0
 // http://www.apache.org/licenses/LICENSE-2.0
 
-#[macro_use]
-extern crate logger;
-
 mod message;
 mod request;
 mod response;
@@ -62,6 +59,15 @@ impl protocol_common::Protocol<Request, Response> for Protocol {
     ) -> std::result::Result<usize, std::io::Error> {
         Ok(response.compose(buffer))
     }
+
+    fn compose_monitor(
+        &self,
+        event: &common::monitor::MonitorEvent,
+        buffer: &mut dyn protocol_common::BufMut,
+    ) -> usize {
+        // the event is escaped, so it is safe to send as a simple string
+        Response::simple_string(event).compose(buffer)
+    }
 }
 
 common::metrics::test_no_duplicates!();
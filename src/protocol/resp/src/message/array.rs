@@ -35,8 +35,6 @@ impl Compose for Array {
             for value in values {
                 len += value.compose(session);
             }
-            session.put_slice(b"\r\n");
-            len += 2;
         } else {
             // A null array is serialized as `*-1\r\n`.
             session.put_slice(b"*-1\r\n");
@@ -137,6 +135,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn compose() {
+        // an array ends with its last element, so pipelined responses such
+        // as this HGETALL reply are read back to back
+        let response = Message::array(vec![
+            Message::bulk_string(b"field"),
+            Message::bulk_string(b"value"),
+        ]);
+        let mut buf = Vec::new();
+        let len = response.compose(&mut buf);
+        assert_eq!(buf, b"*2\r\n$5\r\nfield\r\n$5\r\nvalue\r\n");
+        assert_eq!(len, buf.len());
+        assert_eq!(message(&buf), Ok((&b""[..], response)));
+
+        let mut buf = Vec::new();
+        assert_eq!(Message::Array(Array::null()).compose(&mut buf), 5);
+        assert_eq!(buf, b"*-1\r\n");
+    }
+
     #[test]
     fn iter() {
         let message = Array::null();
@@ -9,6 +9,12 @@ pub struct Error {
     pub(crate) inner: String,
 }
 
+impl AsRef<str> for Error {
+    fn as_ref(&self) -> &str {
+        &self.inner
+    }
+}
+
 impl Compose for Error {
     fn compose(&self, buf: &mut dyn BufMut) -> usize {
         buf.put_slice(b"-");
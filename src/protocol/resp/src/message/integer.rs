@@ -28,10 +28,19 @@ impl Compose for Integer {
 }
 
 pub fn parse(input: &[u8]) -> IResult<&[u8], Integer> {
-    let (input, string) = digit1(input)?;
+    let (input, negative) = match input.first() {
+        Some(b'-') => (&input[1..], true),
+        _ => (input, false),
+    };
+    let (input, digits) = digit1(input)?;
     let (input, _) = crlf(input)?;
 
-    let string = unsafe { std::str::from_utf8_unchecked(string).to_owned() };
+    let digits = unsafe { std::str::from_utf8_unchecked(digits) };
+    let string = if negative {
+        format!("-{digits}")
+    } else {
+        digits.to_owned()
+    };
     let value = string
         .parse::<i64>()
         .map_err(|_| Err::Failure(nom::error::Error::new(input, nom::error::ErrorKind::Tag)))?;
@@ -50,5 +59,12 @@ mod tests {
             message(b":1000\r\n"),
             Ok((&b""[..], Message::integer(1000),))
         );
+
+        assert_eq!(message(b":-2\r\n"), Ok((&b""[..], Message::integer(-2),)));
+
+        assert_eq!(
+            message(b":-9223372036854775808\r\n"),
+            Ok((&b""[..], Message::integer(i64::MIN),))
+        );
     }
 }
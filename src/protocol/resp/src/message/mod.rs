@@ -50,6 +50,12 @@ impl Message {
     pub fn bulk_string(value: &[u8]) -> Self {
         Self::BulkString(BulkString::new(value))
     }
+
+    pub fn array(values: Vec<Message>) -> Self {
+        Self::Array(Array {
+            inner: Some(values),
+        })
+    }
 }
 
 impl Compose for Message {
@@ -0,0 +1,121 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use super::*;
+use std::io::{Error, ErrorKind};
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Append {
+    key: Arc<[u8]>,
+    value: Arc<[u8]>,
+}
+
+impl TryFrom<Message> for Append {
+    type Error = Error;
+
+    fn try_from(other: Message) -> Result<Self, Error> {
+        let array = match other {
+            Message::Array(array) => array,
+            _ => return Err(Error::new(ErrorKind::Other, "malformed command")),
+        };
+
+        let mut array = array
+            .inner
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+        if array.len() != 3 {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let _command = take_bulk_string(&mut array)?;
+        let key = take_bulk_string(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        if key.is_empty() {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let value = take_bulk_string(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        Ok(Self { key, value })
+    }
+}
+
+impl Append {
+    pub fn new(key: &[u8], value: &[u8]) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+}
+
+impl From<&Append> for Message {
+    fn from(value: &Append) -> Self {
+        Message::Array(Array {
+            inner: Some(vec![
+                Message::bulk_string(b"APPEND"),
+                Message::BulkString(BulkString::from(value.key.clone())),
+                Message::BulkString(BulkString::from(value.value.clone())),
+            ]),
+        })
+    }
+}
+
+impl Compose for Append {
+    fn compose(&self, buf: &mut dyn BufMut) -> usize {
+        Message::from(self).compose(buf)
+    }
+}
+
+impl Klog for Append {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = match response {
+            Message::Integer(_) => (ResponseCode::Stored, 0),
+            _ => (ResponseCode::NotStored, 0),
+        };
+
+        klog_record(
+            "append",
+            &[&string_key(self.key()), &self.value().len()],
+            code as u32,
+            len,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser() {
+        let parser = RequestParser::new();
+        assert_eq!(
+            parser.parse(b"append 0 1\r\n").unwrap().into_inner(),
+            Request::Append(Append::new(b"0", b"1"))
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"*3\r\n$6\r\nappend\r\n$1\r\n0\r\n$1\r\n1\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::Append(Append::new(b"0", b"1"))
+        );
+
+        assert!(parser.parse(b"append 0\r\n").is_err());
+    }
+}
@@ -129,6 +129,27 @@ impl Compose for BtreeAdd {
     }
 }
 
+impl Klog for BtreeAdd {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = write_status(response);
+
+        for (field, value) in self.inner_key_value_pairs.iter() {
+            klog_record(
+                "badd",
+                &[
+                    &string_key(self.outer_key()),
+                    &string_key(field),
+                    &value.len(),
+                ],
+                code as u32,
+                len,
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
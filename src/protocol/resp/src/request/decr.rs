@@ -0,0 +1,105 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use super::*;
+use std::io::{Error, ErrorKind};
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Decr {
+    key: Arc<[u8]>,
+}
+
+impl TryFrom<Message> for Decr {
+    type Error = Error;
+
+    fn try_from(other: Message) -> Result<Self, Error> {
+        let array = match other {
+            Message::Array(array) => array,
+            _ => return Err(Error::new(ErrorKind::Other, "malformed command")),
+        };
+
+        let mut array = array
+            .inner
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+        if array.len() != 2 {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let _command = take_bulk_string(&mut array)?;
+        let key = take_bulk_string(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        if key.is_empty() {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        Ok(Self { key })
+    }
+}
+
+impl Decr {
+    pub fn new(key: &[u8]) -> Self {
+        Self { key: key.into() }
+    }
+
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+}
+
+impl From<&Decr> for Message {
+    fn from(value: &Decr) -> Self {
+        Message::Array(Array {
+            inner: Some(vec![
+                Message::bulk_string(b"DECR"),
+                Message::BulkString(BulkString::from(value.key.clone())),
+            ]),
+        })
+    }
+}
+
+impl Compose for Decr {
+    fn compose(&self, buf: &mut dyn BufMut) -> usize {
+        Message::from(self).compose(buf)
+    }
+}
+
+impl Klog for Decr {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = match response {
+            Message::Integer(_) => (ResponseCode::Stored, 0),
+            _ => (ResponseCode::NotStored, 0),
+        };
+
+        klog_record("decr", &[&string_key(self.key())], code as u32, len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser() {
+        let parser = RequestParser::new();
+        assert_eq!(
+            parser.parse(b"decr 0\r\n").unwrap().into_inner(),
+            Request::Decr(Decr::new(b"0"))
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"*2\r\n$4\r\ndecr\r\n$1\r\n0\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::Decr(Decr::new(b"0"))
+        );
+
+        assert!(parser.parse(b"decr\r\n").is_err());
+        assert!(parser.parse(b"decr 0 1\r\n").is_err());
+    }
+}
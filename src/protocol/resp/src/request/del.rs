@@ -74,6 +74,19 @@ impl Compose for Del {
     }
 }
 
+impl Klog for Del {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        klog_list(
+            "del",
+            None,
+            self.keys(),
+            update_status(response, ResponseCode::Deleted),
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
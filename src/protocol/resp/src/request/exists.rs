@@ -0,0 +1,115 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use std::io::Error;
+use std::sync::Arc;
+
+use super::*;
+
+#[metric(name = "exists")]
+pub static EXISTS: Counter = Counter::new();
+
+#[metric(name = "exists_ex")]
+pub static EXISTS_EX: Counter = Counter::new();
+
+#[metric(name = "exists_hit")]
+pub static EXISTS_HIT: Counter = Counter::new();
+
+#[metric(name = "exists_miss")]
+pub static EXISTS_MISS: Counter = Counter::new();
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Exists {
+    keys: Vec<Arc<[u8]>>,
+}
+
+impl TryFrom<Message> for Exists {
+    type Error = Error;
+
+    fn try_from(value: Message) -> Result<Self, Self::Error> {
+        let array = match value {
+            Message::Array(array) => array,
+            _ => return Err(Error::new(ErrorKind::Other, "malformed command")),
+        };
+
+        let mut array = array
+            .inner
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+        if array.len() < 2 {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let _command = take_bulk_string(&mut array)?;
+
+        let mut keys = Vec::with_capacity(array.len());
+        while !array.is_empty() {
+            keys.push(
+                take_bulk_string(&mut array)?
+                    .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?,
+            );
+        }
+
+        Ok(Self { keys })
+    }
+}
+
+impl Exists {
+    pub fn new(keys: &[&[u8]]) -> Self {
+        Self {
+            keys: keys.iter().copied().map(From::from).collect(),
+        }
+    }
+
+    pub fn keys(&self) -> &[Arc<[u8]>] {
+        &self.keys
+    }
+}
+
+impl From<&Exists> for Message {
+    fn from(value: &Exists) -> Self {
+        let mut vals = Vec::with_capacity(value.keys().len() + 1);
+        vals.push(Message::bulk_string(b"EXISTS"));
+        vals.extend(value.keys().iter().map(|v| Message::bulk_string(v)));
+
+        Message::Array(Array { inner: Some(vals) })
+    }
+}
+
+impl Compose for Exists {
+    fn compose(&self, dst: &mut dyn BufMut) -> usize {
+        Message::from(self).compose(dst)
+    }
+}
+
+impl Klog for Exists {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        klog_list("exists", None, self.keys(), read_status(response));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser() {
+        let parser = RequestParser::new();
+        assert_eq!(
+            parser.parse(b"EXISTS k1 k2 k1\r\n").unwrap().into_inner(),
+            Request::Exists(Exists::new(&[b"k1", b"k2", b"k1"]))
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"*2\r\n$6\r\nEXISTS\r\n$2\r\nk1\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::Exists(Exists::new(&[b"k1"]))
+        );
+
+        assert!(parser.parse(b"EXISTS\r\n").is_err());
+    }
+}
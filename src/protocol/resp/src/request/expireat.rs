@@ -0,0 +1,136 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use super::*;
+use std::io::{Error, ErrorKind};
+use std::sync::Arc;
+
+#[metric(name = "expireat")]
+pub static EXPIREAT: Counter = Counter::new();
+
+#[metric(name = "expireat_ex")]
+pub static EXPIREAT_EX: Counter = Counter::new();
+
+#[metric(name = "expireat_hit")]
+pub static EXPIREAT_HIT: Counter = Counter::new();
+
+#[metric(name = "expireat_miss")]
+pub static EXPIREAT_MISS: Counter = Counter::new();
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ExpireAt {
+    key: Arc<[u8]>,
+    timestamp: i64,
+}
+
+impl TryFrom<Message> for ExpireAt {
+    type Error = Error;
+
+    fn try_from(other: Message) -> Result<Self, Error> {
+        let array = match other {
+            Message::Array(array) => array,
+            _ => return Err(Error::new(ErrorKind::Other, "malformed command")),
+        };
+
+        let mut array = array
+            .inner
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+        if array.len() != 3 {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let _command = take_bulk_string(&mut array)?;
+        let key = take_bulk_string(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        if key.is_empty() {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let timestamp = take_bulk_string_as_i64(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        Ok(Self { key, timestamp })
+    }
+}
+
+impl ExpireAt {
+    pub fn new(key: &[u8], timestamp: i64) -> Self {
+        Self {
+            key: key.into(),
+            timestamp,
+        }
+    }
+
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// The unix time, in seconds, at which the key expires. Times in the
+    /// past expire the key immediately.
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+}
+
+impl From<&ExpireAt> for Message {
+    fn from(value: &ExpireAt) -> Self {
+        Message::Array(Array {
+            inner: Some(vec![
+                Message::bulk_string(b"EXPIREAT"),
+                Message::BulkString(BulkString::from(value.key.clone())),
+                Message::bulk_string(value.timestamp.to_string().as_bytes()),
+            ]),
+        })
+    }
+}
+
+impl Compose for ExpireAt {
+    fn compose(&self, buf: &mut dyn BufMut) -> usize {
+        Message::from(self).compose(buf)
+    }
+}
+
+impl Klog for ExpireAt {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = update_status(response, ResponseCode::Stored);
+
+        klog_record(
+            "expireat",
+            &[&string_key(self.key()), &self.timestamp()],
+            code as u32,
+            len,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser() {
+        let parser = RequestParser::new();
+        assert_eq!(
+            parser
+                .parse(b"expireat 0 1700000000\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::ExpireAt(ExpireAt::new(b"0", 1700000000))
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"*3\r\n$8\r\nexpireat\r\n$1\r\n0\r\n$1\r\n0\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::ExpireAt(ExpireAt::new(b"0", 0))
+        );
+
+        assert!(parser.parse(b"expireat 0\r\n").is_err());
+        assert!(parser.parse(b"expireat 0 soon\r\n").is_err());
+    }
+}
@@ -3,7 +3,6 @@
 // http://www.apache.org/licenses/LICENSE-2.0
 
 use super::*;
-use logger::klog;
 use std::io::{Error, ErrorKind};
 use std::sync::Arc;
 
@@ -88,7 +87,7 @@ impl Klog for Get {
             _ => (ResponseCode::Miss, 0),
         };
 
-        klog!("\"get {}\" {} {}", string_key(self.key()), code as u32, len);
+        klog_record("get", &[&string_key(self.key())], code as u32, len);
     }
 }
 #[cfg(test)]
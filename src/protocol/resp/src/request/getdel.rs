@@ -0,0 +1,118 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use super::*;
+use std::io::{Error, ErrorKind};
+use std::sync::Arc;
+
+#[metric(name = "getdel")]
+pub static GETDEL: Counter = Counter::new();
+
+#[metric(name = "getdel_ex")]
+pub static GETDEL_EX: Counter = Counter::new();
+
+#[metric(name = "getdel_hit")]
+pub static GETDEL_HIT: Counter = Counter::new();
+
+#[metric(name = "getdel_miss")]
+pub static GETDEL_MISS: Counter = Counter::new();
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct GetDel {
+    key: Arc<[u8]>,
+}
+
+impl TryFrom<Message> for GetDel {
+    type Error = Error;
+
+    fn try_from(other: Message) -> Result<Self, Error> {
+        let array = match other {
+            Message::Array(array) => array,
+            _ => return Err(Error::new(ErrorKind::Other, "malformed command")),
+        };
+
+        let mut array = array
+            .inner
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+        if array.len() != 2 {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let _command = take_bulk_string(&mut array)?;
+        let key = take_bulk_string(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        if key.is_empty() {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        Ok(Self { key })
+    }
+}
+
+impl GetDel {
+    pub fn new(key: &[u8]) -> Self {
+        Self { key: key.into() }
+    }
+
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+}
+
+impl From<&GetDel> for Message {
+    fn from(value: &GetDel) -> Self {
+        Message::Array(Array {
+            inner: Some(vec![
+                Message::bulk_string(b"GETDEL"),
+                Message::BulkString(BulkString::from(value.key.clone())),
+            ]),
+        })
+    }
+}
+
+impl Compose for GetDel {
+    fn compose(&self, buf: &mut dyn BufMut) -> usize {
+        Message::from(self).compose(buf)
+    }
+}
+
+impl Klog for GetDel {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = match response {
+            Message::BulkString(_) if *response == Response::null() => (ResponseCode::Miss, 0),
+            Message::BulkString(s) => (ResponseCode::Hit, s.len()),
+            _ => (ResponseCode::Miss, 0),
+        };
+
+        klog_record("getdel", &[&string_key(self.key())], code as u32, len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser() {
+        let parser = RequestParser::new();
+        assert_eq!(
+            parser.parse(b"getdel 0\r\n").unwrap().into_inner(),
+            Request::GetDel(GetDel::new(b"0"))
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"*2\r\n$6\r\ngetdel\r\n$1\r\n0\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::GetDel(GetDel::new(b"0"))
+        );
+
+        assert!(parser.parse(b"getdel\r\n").is_err());
+        assert!(parser.parse(b"getdel 0 1\r\n").is_err());
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use super::*;
+use std::io::{Error, ErrorKind};
+use std::sync::Arc;
+
+#[metric(name = "getrange")]
+pub static GETRANGE: Counter = Counter::new();
+
+#[metric(name = "getrange_ex")]
+pub static GETRANGE_EX: Counter = Counter::new();
+
+#[metric(name = "getrange_hit")]
+pub static GETRANGE_HIT: Counter = Counter::new();
+
+#[metric(name = "getrange_miss")]
+pub static GETRANGE_MISS: Counter = Counter::new();
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct GetRange {
+    key: Arc<[u8]>,
+    start: i64,
+    end: i64,
+}
+
+impl TryFrom<Message> for GetRange {
+    type Error = Error;
+
+    fn try_from(other: Message) -> Result<Self, Error> {
+        let array = match other {
+            Message::Array(array) => array,
+            _ => return Err(Error::new(ErrorKind::Other, "malformed command")),
+        };
+
+        let mut array = array
+            .inner
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+        if array.len() != 4 {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let _command = take_bulk_string(&mut array)?;
+        let key = take_bulk_string(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        if key.is_empty() {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let start = take_bulk_string_as_i64(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+        let end = take_bulk_string_as_i64(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        Ok(Self { key, start, end })
+    }
+}
+
+impl GetRange {
+    pub fn new(key: &[u8], start: i64, end: i64) -> Self {
+        Self {
+            key: key.into(),
+            start,
+            end,
+        }
+    }
+
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// The inclusive start offset. Negative offsets count back from the
+    /// end of the value.
+    pub fn start(&self) -> i64 {
+        self.start
+    }
+
+    /// The inclusive end offset. Negative offsets count back from the end
+    /// of the value.
+    pub fn end(&self) -> i64 {
+        self.end
+    }
+}
+
+impl From<&GetRange> for Message {
+    fn from(value: &GetRange) -> Self {
+        Message::Array(Array {
+            inner: Some(vec![
+                Message::bulk_string(b"GETRANGE"),
+                Message::BulkString(BulkString::from(value.key.clone())),
+                Message::bulk_string(value.start.to_string().as_bytes()),
+                Message::bulk_string(value.end.to_string().as_bytes()),
+            ]),
+        })
+    }
+}
+
+impl Compose for GetRange {
+    fn compose(&self, buf: &mut dyn BufMut) -> usize {
+        Message::from(self).compose(buf)
+    }
+}
+
+impl Klog for GetRange {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = match response {
+            Message::BulkString(s) if s.len() > 0 => (ResponseCode::Hit, s.len()),
+            _ => (ResponseCode::Miss, 0),
+        };
+
+        klog_record(
+            "getrange",
+            &[&string_key(self.key()), &self.start(), &self.end()],
+            code as u32,
+            len,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser() {
+        let parser = RequestParser::new();
+        assert_eq!(
+            parser.parse(b"getrange 0 0 -1\r\n").unwrap().into_inner(),
+            Request::GetRange(GetRange::new(b"0", 0, -1))
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"*4\r\n$8\r\ngetrange\r\n$1\r\n0\r\n$1\r\n0\r\n$2\r\n-1\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::GetRange(GetRange::new(b"0", 0, -1))
+        );
+
+        assert!(parser.parse(b"getrange 0 0\r\n").is_err());
+        assert!(parser.parse(b"getrange 0 a b\r\n").is_err());
+    }
+}
@@ -101,6 +101,19 @@ impl Compose for HashDelete {
     }
 }
 
+impl Klog for HashDelete {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        klog_list(
+            "hdel",
+            Some(self.key()),
+            self.fields(),
+            update_status(response, ResponseCode::Deleted),
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,6 +97,21 @@ impl Compose for HashExists {
     }
 }
 
+impl Klog for HashExists {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = read_status(response);
+
+        klog_record(
+            "hexists",
+            &[&string_key(self.key()), &string_key(self.field())],
+            code as u32,
+            len,
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
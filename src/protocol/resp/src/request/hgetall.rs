@@ -82,6 +82,16 @@ impl Compose for HashGetAll {
     }
 }
 
+impl Klog for HashGetAll {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = read_status(response);
+
+        klog_record("hgetall", &[&string_key(self.key())], code as u32, len);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,6 +90,25 @@ impl Compose for HashIncrBy {
     }
 }
 
+impl Klog for HashIncrBy {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = write_status(response);
+
+        klog_record(
+            "hincrby",
+            &[
+                &string_key(self.key()),
+                &string_key(self.field()),
+                &self.increment(),
+            ],
+            code as u32,
+            len,
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,6 +82,16 @@ impl Compose for HashKeys {
     }
 }
 
+impl Klog for HashKeys {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = read_status(response);
+
+        klog_record("hkeys", &[&string_key(self.key())], code as u32, len);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -111,6 +111,33 @@ impl Compose for HashMultiGet {
     }
 }
 
+impl Klog for HashMultiGet {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let values = match response {
+            Message::Array(Array {
+                inner: Some(values),
+            }) => values,
+            _ => return,
+        };
+
+        for (field, value) in self.fields().iter().zip(values.iter()) {
+            let (code, len) = match value {
+                Message::BulkString(BulkString { inner: Some(v) }) => (ResponseCode::Hit, v.len()),
+                _ => (ResponseCode::Miss, 0),
+            };
+
+            klog_record(
+                "hmget",
+                &[&string_key(self.key()), &string_key(field)],
+                code as u32,
+                len,
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
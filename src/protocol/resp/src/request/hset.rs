@@ -128,6 +128,23 @@ impl Compose for HashSet {
     }
 }
 
+impl Klog for HashSet {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = write_status(response);
+
+        for (field, value) in self.data().iter() {
+            klog_record(
+                "hset",
+                &[&string_key(self.key()), &string_key(field), &value.len()],
+                code as u32,
+                len,
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -81,6 +81,16 @@ impl Compose for HashValues {
     }
 }
 
+impl Klog for HashValues {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = read_status(response);
+
+        klog_record("hvals", &[&string_key(self.key())], code as u32, len);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,105 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use super::*;
+use std::io::{Error, ErrorKind};
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Incr {
+    key: Arc<[u8]>,
+}
+
+impl TryFrom<Message> for Incr {
+    type Error = Error;
+
+    fn try_from(other: Message) -> Result<Self, Error> {
+        let array = match other {
+            Message::Array(array) => array,
+            _ => return Err(Error::new(ErrorKind::Other, "malformed command")),
+        };
+
+        let mut array = array
+            .inner
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+        if array.len() != 2 {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let _command = take_bulk_string(&mut array)?;
+        let key = take_bulk_string(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        if key.is_empty() {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        Ok(Self { key })
+    }
+}
+
+impl Incr {
+    pub fn new(key: &[u8]) -> Self {
+        Self { key: key.into() }
+    }
+
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+}
+
+impl From<&Incr> for Message {
+    fn from(value: &Incr) -> Self {
+        Message::Array(Array {
+            inner: Some(vec![
+                Message::bulk_string(b"INCR"),
+                Message::BulkString(BulkString::from(value.key.clone())),
+            ]),
+        })
+    }
+}
+
+impl Compose for Incr {
+    fn compose(&self, buf: &mut dyn BufMut) -> usize {
+        Message::from(self).compose(buf)
+    }
+}
+
+impl Klog for Incr {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = match response {
+            Message::Integer(_) => (ResponseCode::Stored, 0),
+            _ => (ResponseCode::NotStored, 0),
+        };
+
+        klog_record("incr", &[&string_key(self.key())], code as u32, len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser() {
+        let parser = RequestParser::new();
+        assert_eq!(
+            parser.parse(b"incr 0\r\n").unwrap().into_inner(),
+            Request::Incr(Incr::new(b"0"))
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"*2\r\n$4\r\nincr\r\n$1\r\n0\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::Incr(Incr::new(b"0"))
+        );
+
+        assert!(parser.parse(b"incr\r\n").is_err());
+        assert!(parser.parse(b"incr 0 1\r\n").is_err());
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use super::*;
+use std::io::{Error, ErrorKind};
+use std::sync::Arc;
+
+#[metric(name = "incrby")]
+pub static INCRBY: Counter = Counter::new();
+
+#[metric(name = "incrby_ex")]
+pub static INCRBY_EX: Counter = Counter::new();
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct IncrBy {
+    key: Arc<[u8]>,
+    increment: i64,
+}
+
+impl TryFrom<Message> for IncrBy {
+    type Error = Error;
+
+    fn try_from(other: Message) -> Result<Self, Error> {
+        let array = match other {
+            Message::Array(array) => array,
+            _ => return Err(Error::new(ErrorKind::Other, "malformed command")),
+        };
+
+        let mut array = array
+            .inner
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+        if array.len() != 3 {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let _command = take_bulk_string(&mut array)?;
+        let key = take_bulk_string(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        if key.is_empty() {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let increment = take_bulk_string_as_i64(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        Ok(Self { key, increment })
+    }
+}
+
+impl IncrBy {
+    pub fn new(key: &[u8], increment: i64) -> Self {
+        Self {
+            key: key.into(),
+            increment,
+        }
+    }
+
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    pub fn increment(&self) -> i64 {
+        self.increment
+    }
+}
+
+impl From<&IncrBy> for Message {
+    fn from(value: &IncrBy) -> Self {
+        Message::Array(Array {
+            inner: Some(vec![
+                Message::bulk_string(b"INCRBY"),
+                Message::BulkString(BulkString::from(value.key.clone())),
+                Message::bulk_string(value.increment.to_string().as_bytes()),
+            ]),
+        })
+    }
+}
+
+impl Compose for IncrBy {
+    fn compose(&self, buf: &mut dyn BufMut) -> usize {
+        Message::from(self).compose(buf)
+    }
+}
+
+impl Klog for IncrBy {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = match response {
+            Message::Integer(_) => (ResponseCode::Stored, 0),
+            _ => (ResponseCode::NotStored, 0),
+        };
+
+        klog_record(
+            "incrby",
+            &[&string_key(self.key()), &self.increment()],
+            code as u32,
+            len,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser() {
+        let parser = RequestParser::new();
+        assert_eq!(
+            parser.parse(b"incrby 0 5\r\n").unwrap().into_inner(),
+            Request::IncrBy(IncrBy::new(b"0", 5))
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"*3\r\n$6\r\nincrby\r\n$1\r\n0\r\n$1\r\n5\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::IncrBy(IncrBy::new(b"0", 5))
+        );
+
+        assert_eq!(
+            parser.parse(b"incrby 0 -5\r\n").unwrap().into_inner(),
+            Request::IncrBy(IncrBy::new(b"0", -5))
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"*3\r\n$6\r\nincrby\r\n$1\r\n0\r\n$2\r\n-5\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::IncrBy(IncrBy::new(b"0", -5))
+        );
+
+        assert!(parser.parse(b"incrby 0\r\n").is_err());
+        assert!(parser.parse(b"incrby 0 one\r\n").is_err());
+    }
+}
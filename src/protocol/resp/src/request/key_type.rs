@@ -0,0 +1,116 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use super::*;
+use std::io::{Error, ErrorKind};
+use std::sync::Arc;
+
+#[metric(name = "type")]
+pub static TYPE: Counter = Counter::new();
+
+#[metric(name = "type_ex")]
+pub static TYPE_EX: Counter = Counter::new();
+
+#[metric(name = "type_hit")]
+pub static TYPE_HIT: Counter = Counter::new();
+
+#[metric(name = "type_miss")]
+pub static TYPE_MISS: Counter = Counter::new();
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct KeyType {
+    key: Arc<[u8]>,
+}
+
+impl TryFrom<Message> for KeyType {
+    type Error = Error;
+
+    fn try_from(other: Message) -> Result<Self, Error> {
+        let array = match other {
+            Message::Array(array) => array,
+            _ => return Err(Error::new(ErrorKind::Other, "malformed command")),
+        };
+
+        let mut array = array
+            .inner
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+        if array.len() != 2 {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let _command = take_bulk_string(&mut array)?;
+        let key = take_bulk_string(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        if key.is_empty() {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        Ok(Self { key })
+    }
+}
+
+impl KeyType {
+    pub fn new(key: &[u8]) -> Self {
+        Self { key: key.into() }
+    }
+
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+}
+
+impl From<&KeyType> for Message {
+    fn from(value: &KeyType) -> Self {
+        Message::Array(Array {
+            inner: Some(vec![
+                Message::bulk_string(b"TYPE"),
+                Message::BulkString(BulkString::from(value.key.clone())),
+            ]),
+        })
+    }
+}
+
+impl Compose for KeyType {
+    fn compose(&self, buf: &mut dyn BufMut) -> usize {
+        Message::from(self).compose(buf)
+    }
+}
+
+impl Klog for KeyType {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = match response {
+            Message::SimpleString(s) if s.as_ref() != "none" => (ResponseCode::Hit, s.len()),
+            _ => (ResponseCode::Miss, 0),
+        };
+
+        klog_record("type", &[&string_key(self.key())], code as u32, len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser() {
+        let parser = RequestParser::new();
+        assert_eq!(
+            parser.parse(b"type 0\r\n").unwrap().into_inner(),
+            Request::KeyType(KeyType::new(b"0"))
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"*2\r\n$4\r\ntype\r\n$1\r\n0\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::KeyType(KeyType::new(b"0"))
+        );
+
+        assert!(parser.parse(b"type 0 1\r\n").is_err());
+    }
+}
@@ -81,6 +81,21 @@ impl Compose for ListIndex {
     }
 }
 
+impl Klog for ListIndex {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = read_status(response);
+
+        klog_record(
+            "lindex",
+            &[&string_key(self.key()), &self.index()],
+            code as u32,
+            len,
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,6 +64,16 @@ impl Compose for ListLen {
     }
 }
 
+impl Klog for ListLen {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = read_status(response);
+
+        klog_record("llen", &[&string_key(self.key())], code as u32, len);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -77,6 +77,21 @@ impl Compose for ListPop {
     }
 }
 
+impl Klog for ListPop {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = read_status(response);
+
+        match self.count() {
+            Some(count) => {
+                klog_record("lpop", &[&string_key(self.key()), &count], code as u32, len)
+            }
+            None => klog_record("lpop", &[&string_key(self.key())], code as u32, len),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
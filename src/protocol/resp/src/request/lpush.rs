@@ -87,6 +87,21 @@ impl Compose for ListPush {
     }
 }
 
+impl Klog for ListPush {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = write_status(response);
+
+        klog_record(
+            "lpush",
+            &[&string_key(self.key()), &self.elements().len()],
+            code as u32,
+            len,
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -84,6 +84,21 @@ impl Compose for ListRange {
     }
 }
 
+impl Klog for ListRange {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = read_status(response);
+
+        klog_record(
+            "lrange",
+            &[&string_key(self.key()), &self.start(), &self.stop()],
+            code as u32,
+            len,
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -84,6 +84,21 @@ impl Compose for ListTrim {
     }
 }
 
+impl Klog for ListTrim {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = write_status(response);
+
+        klog_record(
+            "ltrim",
+            &[&string_key(self.key()), &self.start(), &self.stop()],
+            code as u32,
+            len,
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
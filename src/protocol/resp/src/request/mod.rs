@@ -5,7 +5,7 @@
 use crate::message::*;
 use crate::*;
 use core::f64;
-use logger::Klog;
+use logger::{klog_record, Klog};
 use protocol_common::BufMut;
 use protocol_common::Parse;
 use protocol_common::ParseOk;
@@ -14,9 +14,16 @@ use std::fmt::{Display, Formatter};
 use std::io::{Error, ErrorKind};
 use std::sync::Arc;
 
+mod append;
 mod badd;
+mod decr;
 mod del;
+mod exists;
+mod expire;
+mod expireat;
 mod get;
+mod getdel;
+mod getrange;
 mod hdel;
 mod hexists;
 mod hget;
@@ -27,22 +34,37 @@ mod hlen;
 mod hmget;
 mod hset;
 mod hvals;
+mod incr;
+mod incrby;
+mod key_type;
 mod lindex;
 mod llen;
 mod lpop;
 mod lpush;
 mod lrange;
 mod ltrim;
+mod mget;
+mod monitor;
+mod mset;
+mod persist;
+mod pexpire;
+mod pttl;
 mod rpop;
 mod rpush;
 mod sadd;
+mod scan;
 mod sdiff;
 mod set;
+mod setnx;
+mod setrange;
 mod sinter;
 mod sismember;
+mod slowlog;
 mod smembers;
 mod srem;
+mod strlen;
 mod sunion;
+mod ttl;
 mod zadd;
 mod zcard;
 mod zcount;
@@ -65,12 +87,20 @@ pub use self::rpush::*;
 pub use self::sdiff::*;
 pub use self::sinter::*;
 pub use self::sismember::*;
+pub use self::slowlog::*;
 pub use self::smembers::*;
 pub use self::srem::*;
 pub use self::sunion::*;
+pub use append::*;
 pub use badd::*;
+pub use decr::*;
 pub use del::*;
+pub use exists::*;
+pub use expire::*;
+pub use expireat::*;
 pub use get::*;
+pub use getdel::*;
+pub use getrange::*;
 pub use hdel::*;
 pub use hexists::*;
 pub use hget::*;
@@ -81,8 +111,22 @@ pub use hlen::*;
 pub use hmget::*;
 pub use hset::*;
 pub use hvals::*;
+pub use incr::*;
+pub use incrby::*;
+pub use key_type::*;
+pub use mget::*;
+pub use monitor::*;
+pub use mset::*;
+pub use persist::*;
+pub use pexpire::*;
+pub use pttl::*;
 pub use sadd::*;
+pub use scan::*;
 pub use set::*;
+pub use setnx::*;
+pub use setrange::*;
+pub use strlen::*;
+pub use ttl::*;
 pub use zadd::*;
 pub use zcard::*;
 pub use zcount::*;
@@ -99,6 +143,7 @@ pub use zunionstore::*;
 /// matches Memcache protocol response codes for compatibility with existing tools
 /// [crate::memcache::MISS]
 #[allow(dead_code)]
+#[derive(Copy, Clone)]
 enum ResponseCode {
     Miss = 0,
     Hit = 4,
@@ -109,6 +154,62 @@ enum ResponseCode {
     NotStored = 9,
 }
 
+/// The klog response code and length for a request which reads a value. The
+/// length is that of a bulk string, or the number of elements in an array. A
+/// null or empty response, a count of zero, or an error is a miss.
+fn read_status(response: &Response) -> (ResponseCode, usize) {
+    match response {
+        Message::BulkString(BulkString { inner: Some(value) }) => (ResponseCode::Hit, value.len()),
+        Message::Array(Array {
+            inner: Some(values),
+        }) if !values.is_empty() => (ResponseCode::Hit, values.len()),
+        Message::Integer(count) if count.value() > 0 => (ResponseCode::Hit, 0),
+        Message::SimpleString(s) => (ResponseCode::Hit, s.len()),
+        _ => (ResponseCode::Miss, 0),
+    }
+}
+
+/// The klog response code and length for a request which stores or modifies
+/// a value. Any response other than an error means the value was stored.
+fn write_status(response: &Response) -> (ResponseCode, usize) {
+    match response {
+        Message::Error(_) => (ResponseCode::NotStored, 0),
+        Message::BulkString(BulkString { inner: Some(value) }) => {
+            (ResponseCode::Stored, value.len())
+        }
+        Message::SimpleString(s) => (ResponseCode::Stored, s.len()),
+        _ => (ResponseCode::Stored, 0),
+    }
+}
+
+/// The klog response code for a request which modifies an existing key, such
+/// as `expire`, or removes keys, fields or members. A count of zero means that
+/// nothing was found.
+fn update_status(response: &Response, found: ResponseCode) -> (ResponseCode, usize) {
+    match response {
+        Message::Integer(count) if count.value() > 0 => (found, 0),
+        _ => (ResponseCode::NotFound, 0),
+    }
+}
+
+/// Log a request whose arguments are a list of keys, fields or members, which
+/// may follow a key.
+fn klog_list(
+    command: &str,
+    key: Option<&[u8]>,
+    items: &[Arc<[u8]>],
+    (code, len): (ResponseCode, usize),
+) {
+    let items: Vec<Cow<'_, str>> = key
+        .into_iter()
+        .chain(items.iter().map(|item| &**item))
+        .map(string_key)
+        .collect();
+    let args: Vec<&dyn Display> = items.iter().map(|item| item as &dyn Display).collect();
+
+    klog_record(command, &args, code as u32, len);
+}
+
 pub type FieldValuePair = (Arc<[u8]>, Arc<[u8]>);
 pub type ScoreMemberPair = (f64, Arc<[u8]>);
 
@@ -189,9 +290,17 @@ macro_rules! decl_request {
 
 decl_request! {
     pub enum Request {
+        Append(Append) => "append",
         BtreeAdd(BtreeAdd) => "badd",
+        Decr(Decr) => "decr",
         Del(Del) => "del",
+        Exists(Exists) => "exists",
+        Expire(Expire) => "expire",
+        ExpireAt(ExpireAt) => "expireat",
+        ExpireMilliseconds(ExpireMilliseconds) => "pexpire",
         Get(Get) => "get",
+        GetDel(GetDel) => "getdel",
+        GetRange(GetRange) => "getrange",
         HashDelete(HashDelete) => "hdel",
         HashExists(HashExists) => "hexists",
         HashGet(HashGet) => "hget",
@@ -202,6 +311,9 @@ decl_request! {
         HashSet(HashSet) => "hset",
         HashValues(HashValues) => "hvals",
         HashIncrBy(HashIncrBy) => "hincrby",
+        Incr(Incr) => "incr",
+        IncrBy(IncrBy) => "incrby",
+        KeyType(KeyType) => "type",
         ListIndex(ListIndex) => "lindex",
         ListLen(ListLen) => "llen",
         ListPop(ListPop) => "lpop",
@@ -210,6 +322,11 @@ decl_request! {
         ListPush(ListPush) => "lpush",
         ListPushBack(ListPushBack) => "rpush",
         ListTrim(ListTrim) => "ltrim",
+        Monitor(Monitor) => "monitor",
+        MultiGet(MultiGet) => "mget",
+        MultiSet(MultiSet) => "mset",
+        Persist(Persist) => "persist",
+        Scan(Scan) => "scan",
         Set(Set) => "set",
         SetAdd(SetAdd) => "sadd",
         SetRem(SetRem) => "srem",
@@ -218,6 +335,8 @@ decl_request! {
         SetIntersect(SetIntersect) => "sinter",
         SetMembers(SetMembers) => "smembers",
         SetIsMember(SetIsMember) => "sismember",
+        SetNx(SetNx) => "setnx",
+        SetRange(SetRange) => "setrange",
         SortedSetCardinality(SortedSetCardinality) => "zcard",
         SortedSetIncrement(SortedSetIncrement) => "zincrby",
         SortedSetScore(SortedSetScore) => "zscore",
@@ -229,6 +348,10 @@ decl_request! {
         SortedSetReverseRank(SortedSetReverseRank) => "zrevrank",
         SortedSetCount(SortedSetCount) => "zcount",
         SortedSetUnionStore(SortedSetUnionStore) => "zunionstore",
+        SlowLog(SlowLog) => "slowlog",
+        StringLength(StringLength) => "strlen",
+        TimeToLive(TimeToLive) => "ttl",
+        TimeToLiveMilliseconds(TimeToLiveMilliseconds) => "pttl",
     }
 }
 
@@ -241,27 +364,151 @@ impl RequestParser {
     }
 }
 
+impl protocol_common::Command for Request {
+    const PROTOCOL: &'static str = "resp";
+
+    fn command(&self) -> &'static str {
+        Request::command(self)
+    }
+
+    fn is_monitor(&self) -> bool {
+        matches!(self, Request::Monitor(_))
+    }
+
+    fn args(&self) -> Vec<Vec<u8>> {
+        // every request is composed as an array of bulk strings, so the
+        // arguments are recovered by re-parsing the composed request
+        let mut buf = Vec::new();
+        self.compose(&mut buf);
+
+        let array = match MessageParser::default().parse(&buf).map(|m| m.into_inner()) {
+            Ok(Message::Array(Array { inner: Some(array) })) => array,
+            _ => return Vec::new(),
+        };
+
+        array
+            .into_iter()
+            .skip(1)
+            .filter_map(|arg| match arg {
+                Message::BulkString(BulkString { inner: Some(arg) }) => Some(arg.to_vec()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
 impl Klog for Request {
     type Response = Response;
 
     fn klog(&self, response: &Self::Response) {
         match self {
+            Request::Append(r) => r.klog(response),
+            Request::BtreeAdd(r) => r.klog(response),
+            Request::Decr(r) => r.klog(response),
+            Request::Del(r) => r.klog(response),
+            Request::Exists(r) => r.klog(response),
+            Request::Expire(r) => r.klog(response),
+            Request::ExpireAt(r) => r.klog(response),
+            Request::ExpireMilliseconds(r) => r.klog(response),
             Request::Get(r) => r.klog(response),
+            Request::GetDel(r) => r.klog(response),
+            Request::GetRange(r) => r.klog(response),
+            Request::HashDelete(r) => r.klog(response),
+            Request::HashExists(r) => r.klog(response),
+            Request::HashGet(r) => r.klog(response),
+            Request::HashGetAll(r) => r.klog(response),
+            Request::HashKeys(r) => r.klog(response),
+            Request::HashLength(r) => r.klog(response),
+            Request::HashMultiGet(r) => r.klog(response),
+            Request::HashSet(r) => r.klog(response),
+            Request::HashValues(r) => r.klog(response),
+            Request::HashIncrBy(r) => r.klog(response),
+            Request::Incr(r) => r.klog(response),
+            Request::IncrBy(r) => r.klog(response),
+            Request::KeyType(r) => r.klog(response),
+            Request::ListIndex(r) => r.klog(response),
+            Request::ListLen(r) => r.klog(response),
+            Request::ListPop(r) => r.klog(response),
+            Request::ListPopBack(r) => r.klog(response),
+            Request::ListRange(r) => r.klog(response),
+            Request::ListPush(r) => r.klog(response),
+            Request::ListPushBack(r) => r.klog(response),
+            Request::ListTrim(r) => r.klog(response),
+            Request::MultiGet(r) => r.klog(response),
+            Request::MultiSet(r) => r.klog(response),
+            Request::Persist(r) => r.klog(response),
+            Request::Scan(r) => r.klog(response),
             Request::Set(r) => r.klog(response),
-            _ => (),
+            Request::SetAdd(r) => r.klog(response),
+            Request::SetRem(r) => r.klog(response),
+            Request::SetDiff(r) => r.klog(response),
+            Request::SetUnion(r) => r.klog(response),
+            Request::SetIntersect(r) => r.klog(response),
+            Request::SetMembers(r) => r.klog(response),
+            Request::SetIsMember(r) => r.klog(response),
+            Request::SetNx(r) => r.klog(response),
+            Request::SetRange(r) => r.klog(response),
+            Request::SortedSetCardinality(r) => r.klog(response),
+            Request::SortedSetIncrement(r) => r.klog(response),
+            Request::SortedSetScore(r) => r.klog(response),
+            Request::SortedSetMultiScore(r) => r.klog(response),
+            Request::SortedSetRemove(r) => r.klog(response),
+            Request::SortedSetRank(r) => r.klog(response),
+            Request::SortedSetRange(r) => r.klog(response),
+            Request::SortedSetAdd(r) => r.klog(response),
+            Request::SortedSetReverseRank(r) => r.klog(response),
+            Request::SortedSetCount(r) => r.klog(response),
+            Request::SortedSetUnionStore(r) => r.klog(response),
+            Request::StringLength(r) => r.klog(response),
+            Request::TimeToLive(r) => r.klog(response),
+            Request::TimeToLiveMilliseconds(r) => r.klog(response),
+            // administrative commands are not part of the command log
+            Request::Monitor(_) | Request::SlowLog(_) => (),
         }
     }
 }
 
 impl Request {
+    pub fn append(key: &[u8], value: &[u8]) -> Self {
+        Self::Append(Append::new(key, value))
+    }
+
+    pub fn decr(key: &[u8]) -> Self {
+        Self::Decr(Decr::new(key))
+    }
+
     pub fn del(keys: &[&[u8]]) -> Self {
         Self::Del(Del::new(keys))
     }
 
+    pub fn exists(keys: &[&[u8]]) -> Self {
+        Self::Exists(Exists::new(keys))
+    }
+
+    pub fn expire(key: &[u8], seconds: i64) -> Self {
+        Self::Expire(Expire::new(key, seconds))
+    }
+
+    pub fn expire_at(key: &[u8], timestamp: i64) -> Self {
+        Self::ExpireAt(ExpireAt::new(key, timestamp))
+    }
+
+    pub fn expire_milliseconds(key: &[u8], milliseconds: i64) -> Self {
+        Self::ExpireMilliseconds(ExpireMilliseconds::new(key, milliseconds))
+    }
+
     pub fn get(key: &[u8]) -> Self {
         Self::Get(Get::new(key))
     }
 
+    pub fn get_del(key: &[u8]) -> Self {
+        Self::GetDel(GetDel::new(key))
+    }
+
+    pub fn get_range(key: &[u8], start: i64, end: i64) -> Self {
+        Self::GetRange(GetRange::new(key, start, end))
+    }
+
     pub fn hash_delete(key: &[u8], fields: &[&[u8]]) -> Self {
         Self::HashDelete(HashDelete::new(key, fields))
     }
@@ -302,6 +549,34 @@ impl Request {
         Self::HashIncrBy(HashIncrBy::new(key, field, increment))
     }
 
+    pub fn incr(key: &[u8]) -> Self {
+        Self::Incr(Incr::new(key))
+    }
+
+    pub fn incr_by(key: &[u8], increment: i64) -> Self {
+        Self::IncrBy(IncrBy::new(key, increment))
+    }
+
+    pub fn key_type(key: &[u8]) -> Self {
+        Self::KeyType(KeyType::new(key))
+    }
+
+    pub fn multi_get(keys: &[&[u8]]) -> Self {
+        Self::MultiGet(MultiGet::new(keys))
+    }
+
+    pub fn multi_set(data: &[(&[u8], &[u8])]) -> Self {
+        Self::MultiSet(MultiSet::new(data))
+    }
+
+    pub fn persist(key: &[u8]) -> Self {
+        Self::Persist(Persist::new(key))
+    }
+
+    pub fn scan(cursor: u64, pattern: Option<&[u8]>, count: usize) -> Self {
+        Self::Scan(Scan::new(cursor, pattern, count))
+    }
+
     pub fn set(
         key: &[u8],
         value: &[u8],
@@ -311,6 +586,26 @@ impl Request {
     ) -> Self {
         Self::Set(Set::new(key, value, expire_time, mode, get_old))
     }
+
+    pub fn set_nx(key: &[u8], value: &[u8]) -> Self {
+        Self::SetNx(SetNx::new(key, value))
+    }
+
+    pub fn set_range(key: &[u8], offset: u64, value: &[u8]) -> Self {
+        Self::SetRange(SetRange::new(key, offset, value))
+    }
+
+    pub fn string_length(key: &[u8]) -> Self {
+        Self::StringLength(StringLength::new(key))
+    }
+
+    pub fn time_to_live(key: &[u8]) -> Self {
+        Self::TimeToLive(TimeToLive::new(key))
+    }
+
+    pub fn time_to_live_milliseconds(key: &[u8]) -> Self {
+        Self::TimeToLiveMilliseconds(TimeToLiveMilliseconds::new(key))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -367,6 +662,7 @@ fn parse_sorted_set_score(score: &[u8]) -> Result<f64, std::io::Error> {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::RequestParser;
     use protocol_common::Parse;
 
@@ -375,4 +671,31 @@ mod tests {
         let parser = RequestParser::new();
         assert!(parser.parse(b"GET test\n").is_err());
     }
+
+    #[test]
+    fn klog_status() {
+        let status = |(code, len): (ResponseCode, usize)| (code as u32, len);
+
+        assert_eq!(status(read_status(&Message::bulk_string(b"abc"))), (4, 3));
+        assert_eq!(status(read_status(&Message::null())), (0, 0));
+        assert_eq!(status(read_status(&Message::integer(0))), (0, 0));
+        assert_eq!(
+            status(read_status(&Message::Array(Array {
+                inner: Some(vec![Message::bulk_string(b"a")])
+            }))),
+            (4, 1)
+        );
+
+        assert_eq!(status(write_status(&Message::simple_string("OK"))), (5, 2));
+        assert_eq!(status(write_status(&Message::error("WRONGTYPE"))), (9, 0));
+
+        let deleted = |count| {
+            status(update_status(
+                &Message::integer(count),
+                ResponseCode::Deleted,
+            ))
+        };
+        assert_eq!(deleted(2), (7, 0));
+        assert_eq!(deleted(0), (8, 0));
+    }
 }
@@ -0,0 +1,90 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use super::*;
+use std::io::{Error, ErrorKind};
+
+#[metric(name = "monitor")]
+pub static MONITOR: Counter = Counter::new();
+
+/// Represents the `MONITOR` command, after which the session receives every
+/// request seen by the process.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Monitor {}
+
+impl TryFrom<Message> for Monitor {
+    type Error = Error;
+
+    fn try_from(other: Message) -> Result<Self, Error> {
+        let array = match other {
+            Message::Array(array) => array,
+            _ => return Err(Error::new(ErrorKind::Other, "malformed command")),
+        };
+
+        let array = array
+            .inner
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+        if array.len() != 1 {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        Ok(Self {})
+    }
+}
+
+impl Monitor {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl From<&Monitor> for Message {
+    fn from(_: &Monitor) -> Self {
+        Message::Array(Array {
+            inner: Some(vec![Message::bulk_string(b"MONITOR")]),
+        })
+    }
+}
+
+impl Compose for Monitor {
+    fn compose(&self, buf: &mut dyn BufMut) -> usize {
+        Message::from(self).compose(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser() {
+        let parser = RequestParser::new();
+        assert_eq!(
+            parser.parse(b"monitor\r\n").unwrap().into_inner(),
+            Request::Monitor(Monitor::new())
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"*1\r\n$7\r\nMONITOR\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::Monitor(Monitor::new())
+        );
+
+        assert!(parser.parse(b"monitor all\r\n").is_err());
+    }
+
+    #[test]
+    fn compose_monitor() {
+        use common::monitor::MonitorEvent;
+
+        let event = MonitorEvent::new("get", vec![b"key".to_vec()], None);
+        let mut buf = Vec::new();
+        let len = ProtocolTrait::compose_monitor(&Protocol::default(), &event, &mut buf);
+        assert_eq!(len, buf.len());
+        assert!(buf.starts_with(b"+"));
+        assert!(buf.ends_with(b" [unknown] \"get\" \"key\"\r\n"));
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use super::*;
+use std::io::{Error, ErrorKind};
+
+#[metric(name = "mset")]
+pub static MSET: Counter = Counter::new();
+
+#[metric(name = "mset_ex")]
+pub static MSET_EX: Counter = Counter::new();
+
+#[metric(name = "mset_key")]
+pub static MSET_KEY: Counter = Counter::new();
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct MultiSet {
+    data: Box<[FieldValuePair]>,
+}
+
+impl TryFrom<Message> for MultiSet {
+    type Error = Error;
+
+    fn try_from(other: Message) -> Result<Self, Error> {
+        let array = match other {
+            Message::Array(array) => array,
+            _ => return Err(Error::new(ErrorKind::Other, "malformed command")),
+        };
+
+        let mut array = array
+            .inner
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        // the command followed by one or more key-value pairs
+        if array.len() < 3 || array.len() % 2 == 0 {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let _command = take_bulk_string(&mut array)?;
+
+        let mut data = Vec::with_capacity(array.len() / 2);
+        while let Some(key) = take_bulk_string(&mut array)? {
+            if key.is_empty() {
+                return Err(Error::new(ErrorKind::Other, "malformed command"));
+            }
+
+            let value = take_bulk_string(&mut array)?
+                .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+            data.push((key, value));
+        }
+
+        Ok(Self {
+            data: data.into_boxed_slice(),
+        })
+    }
+}
+
+impl MultiSet {
+    pub fn new(data: &[(&[u8], &[u8])]) -> Self {
+        let data: Vec<FieldValuePair> = data
+            .iter()
+            .map(|(k, v)| ((*k).into(), (*v).into()))
+            .collect();
+
+        Self { data: data.into() }
+    }
+
+    /// The key-value pairs to store.
+    pub fn data(&self) -> &[FieldValuePair] {
+        &self.data
+    }
+}
+
+impl From<&MultiSet> for Message {
+    fn from(value: &MultiSet) -> Self {
+        let mut data = Vec::with_capacity(value.data.len() * 2 + 1);
+        data.push(Message::bulk_string(b"MSET"));
+
+        for (key, value) in value.data.iter() {
+            data.push(Message::BulkString(BulkString::from(key.clone())));
+            data.push(Message::BulkString(BulkString::from(value.clone())));
+        }
+
+        Message::Array(Array { inner: Some(data) })
+    }
+}
+
+impl Compose for MultiSet {
+    fn compose(&self, buf: &mut dyn BufMut) -> usize {
+        Message::from(self).compose(buf)
+    }
+}
+
+impl Klog for MultiSet {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let code = match response {
+            Message::SimpleString(_) => ResponseCode::Stored,
+            _ => ResponseCode::NotStored,
+        } as u32;
+
+        for (key, value) in self.data.iter() {
+            klog_record("mset", &[&string_key(key), &value.len()], code, 0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser() {
+        let parser = RequestParser::new();
+        assert_eq!(
+            parser.parse(b"mset a 1 b 2\r\n").unwrap().into_inner(),
+            Request::MultiSet(MultiSet::new(&[(b"a", b"1"), (b"b", b"2")]))
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"*3\r\n$4\r\nmset\r\n$1\r\na\r\n$1\r\n1\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::MultiSet(MultiSet::new(&[(b"a", b"1")]))
+        );
+
+        assert!(parser.parse(b"mset a\r\n").is_err());
+        assert!(parser.parse(b"mset a 1 b\r\n").is_err());
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use super::*;
+use std::io::{Error, ErrorKind};
+use std::sync::Arc;
+
+#[metric(name = "persist")]
+pub static PERSIST: Counter = Counter::new();
+
+#[metric(name = "persist_ex")]
+pub static PERSIST_EX: Counter = Counter::new();
+
+#[metric(name = "persist_hit")]
+pub static PERSIST_HIT: Counter = Counter::new();
+
+#[metric(name = "persist_miss")]
+pub static PERSIST_MISS: Counter = Counter::new();
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Persist {
+    key: Arc<[u8]>,
+}
+
+impl TryFrom<Message> for Persist {
+    type Error = Error;
+
+    fn try_from(other: Message) -> Result<Self, Error> {
+        let array = match other {
+            Message::Array(array) => array,
+            _ => return Err(Error::new(ErrorKind::Other, "malformed command")),
+        };
+
+        let mut array = array
+            .inner
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+        if array.len() != 2 {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let _command = take_bulk_string(&mut array)?;
+        let key = take_bulk_string(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        if key.is_empty() {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        Ok(Self { key })
+    }
+}
+
+impl Persist {
+    pub fn new(key: &[u8]) -> Self {
+        Self { key: key.into() }
+    }
+
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+}
+
+impl From<&Persist> for Message {
+    fn from(value: &Persist) -> Self {
+        Message::Array(Array {
+            inner: Some(vec![
+                Message::bulk_string(b"PERSIST"),
+                Message::BulkString(BulkString::from(value.key.clone())),
+            ]),
+        })
+    }
+}
+
+impl Compose for Persist {
+    fn compose(&self, buf: &mut dyn BufMut) -> usize {
+        Message::from(self).compose(buf)
+    }
+}
+
+impl Klog for Persist {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = update_status(response, ResponseCode::Stored);
+
+        klog_record("persist", &[&string_key(self.key())], code as u32, len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser() {
+        let parser = RequestParser::new();
+        assert_eq!(
+            parser.parse(b"persist 0\r\n").unwrap().into_inner(),
+            Request::Persist(Persist::new(b"0"))
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"*2\r\n$7\r\npersist\r\n$1\r\n0\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::Persist(Persist::new(b"0"))
+        );
+
+        assert!(parser.parse(b"persist 0 1\r\n").is_err());
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use super::*;
+use std::io::{Error, ErrorKind};
+use std::sync::Arc;
+
+#[metric(name = "pexpire")]
+pub static PEXPIRE: Counter = Counter::new();
+
+#[metric(name = "pexpire_ex")]
+pub static PEXPIRE_EX: Counter = Counter::new();
+
+#[metric(name = "pexpire_hit")]
+pub static PEXPIRE_HIT: Counter = Counter::new();
+
+#[metric(name = "pexpire_miss")]
+pub static PEXPIRE_MISS: Counter = Counter::new();
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ExpireMilliseconds {
+    key: Arc<[u8]>,
+    milliseconds: i64,
+}
+
+impl TryFrom<Message> for ExpireMilliseconds {
+    type Error = Error;
+
+    fn try_from(other: Message) -> Result<Self, Error> {
+        let array = match other {
+            Message::Array(array) => array,
+            _ => return Err(Error::new(ErrorKind::Other, "malformed command")),
+        };
+
+        let mut array = array
+            .inner
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+        if array.len() != 3 {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let _command = take_bulk_string(&mut array)?;
+        let key = take_bulk_string(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        if key.is_empty() {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let milliseconds = take_bulk_string_as_i64(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        Ok(Self { key, milliseconds })
+    }
+}
+
+impl ExpireMilliseconds {
+    pub fn new(key: &[u8], milliseconds: i64) -> Self {
+        Self {
+            key: key.into(),
+            milliseconds,
+        }
+    }
+
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// The number of milliseconds until the key expires. Non-positive values
+    /// expire the key immediately.
+    pub fn milliseconds(&self) -> i64 {
+        self.milliseconds
+    }
+}
+
+impl From<&ExpireMilliseconds> for Message {
+    fn from(value: &ExpireMilliseconds) -> Self {
+        Message::Array(Array {
+            inner: Some(vec![
+                Message::bulk_string(b"PEXPIRE"),
+                Message::BulkString(BulkString::from(value.key.clone())),
+                Message::bulk_string(value.milliseconds.to_string().as_bytes()),
+            ]),
+        })
+    }
+}
+
+impl Compose for ExpireMilliseconds {
+    fn compose(&self, buf: &mut dyn BufMut) -> usize {
+        Message::from(self).compose(buf)
+    }
+}
+
+impl Klog for ExpireMilliseconds {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = update_status(response, ResponseCode::Stored);
+
+        klog_record(
+            "pexpire",
+            &[&string_key(self.key()), &self.milliseconds()],
+            code as u32,
+            len,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser() {
+        let parser = RequestParser::new();
+        assert_eq!(
+            parser.parse(b"pexpire 0 1500\r\n").unwrap().into_inner(),
+            Request::ExpireMilliseconds(ExpireMilliseconds::new(b"0", 1500))
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"*3\r\n$7\r\npexpire\r\n$1\r\n0\r\n$1\r\n0\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::ExpireMilliseconds(ExpireMilliseconds::new(b"0", 0))
+        );
+
+        assert!(parser.parse(b"pexpire 0\r\n").is_err());
+        assert!(parser.parse(b"pexpire 0 soon\r\n").is_err());
+    }
+}
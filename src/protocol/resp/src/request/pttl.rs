@@ -0,0 +1,117 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use super::*;
+use std::io::{Error, ErrorKind};
+use std::sync::Arc;
+
+#[metric(name = "pttl")]
+pub static PTTL: Counter = Counter::new();
+
+#[metric(name = "pttl_ex")]
+pub static PTTL_EX: Counter = Counter::new();
+
+#[metric(name = "pttl_hit")]
+pub static PTTL_HIT: Counter = Counter::new();
+
+#[metric(name = "pttl_miss")]
+pub static PTTL_MISS: Counter = Counter::new();
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct TimeToLiveMilliseconds {
+    key: Arc<[u8]>,
+}
+
+impl TryFrom<Message> for TimeToLiveMilliseconds {
+    type Error = Error;
+
+    fn try_from(other: Message) -> Result<Self, Error> {
+        let array = match other {
+            Message::Array(array) => array,
+            _ => return Err(Error::new(ErrorKind::Other, "malformed command")),
+        };
+
+        let mut array = array
+            .inner
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+        if array.len() != 2 {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let _command = take_bulk_string(&mut array)?;
+        let key = take_bulk_string(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        if key.is_empty() {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        Ok(Self { key })
+    }
+}
+
+impl TimeToLiveMilliseconds {
+    pub fn new(key: &[u8]) -> Self {
+        Self { key: key.into() }
+    }
+
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+}
+
+impl From<&TimeToLiveMilliseconds> for Message {
+    fn from(value: &TimeToLiveMilliseconds) -> Self {
+        Message::Array(Array {
+            inner: Some(vec![
+                Message::bulk_string(b"PTTL"),
+                Message::BulkString(BulkString::from(value.key.clone())),
+            ]),
+        })
+    }
+}
+
+impl Compose for TimeToLiveMilliseconds {
+    fn compose(&self, buf: &mut dyn BufMut) -> usize {
+        Message::from(self).compose(buf)
+    }
+}
+
+impl Klog for TimeToLiveMilliseconds {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        // a ttl of -2 means that the key does not exist
+        let (code, len) = match response {
+            Message::Integer(ttl) if ttl.value() != -2 => (ResponseCode::Hit, 0),
+            _ => (ResponseCode::Miss, 0),
+        };
+
+        klog_record("pttl", &[&string_key(self.key())], code as u32, len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser() {
+        let parser = RequestParser::new();
+        assert_eq!(
+            parser.parse(b"pttl 0\r\n").unwrap().into_inner(),
+            Request::TimeToLiveMilliseconds(TimeToLiveMilliseconds::new(b"0"))
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"*2\r\n$4\r\npttl\r\n$1\r\n0\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::TimeToLiveMilliseconds(TimeToLiveMilliseconds::new(b"0"))
+        );
+
+        assert!(parser.parse(b"pttl 0 1\r\n").is_err());
+    }
+}
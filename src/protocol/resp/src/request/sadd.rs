@@ -89,6 +89,19 @@ impl Compose for SetAdd {
     }
 }
 
+impl Klog for SetAdd {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        klog_list(
+            "sadd",
+            Some(self.key()),
+            self.members(),
+            write_status(response),
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,277 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use super::*;
+use std::io::{Error, ErrorKind};
+use std::sync::Arc;
+
+#[metric(name = "scan")]
+pub static SCAN: Counter = Counter::new();
+
+#[metric(name = "scan_ex")]
+pub static SCAN_EX: Counter = Counter::new();
+
+/// The number of keys examined per call when `COUNT` is not provided.
+const DEFAULT_COUNT: usize = 10;
+
+/// Represents the arguments to the `SCAN` command:
+/// `SCAN cursor [MATCH pattern] [COUNT count]`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Scan {
+    cursor: u64,
+    pattern: Option<Arc<[u8]>>,
+    count: usize,
+}
+
+impl TryFrom<Message> for Scan {
+    type Error = Error;
+
+    fn try_from(other: Message) -> Result<Self, Error> {
+        let array = match other {
+            Message::Array(array) => array,
+            _ => return Err(Error::new(ErrorKind::Other, "malformed command")),
+        };
+
+        let mut array = array
+            .inner
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        let _command = take_bulk_string(&mut array)?;
+        let cursor = take_bulk_string_as_u64(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        let mut pattern = None;
+        let mut count = DEFAULT_COUNT;
+
+        while let Some(arg) = take_bulk_string(&mut array)? {
+            if arg.eq_ignore_ascii_case(b"MATCH") {
+                pattern = Some(
+                    take_bulk_string(&mut array)?
+                        .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?,
+                );
+            } else if arg.eq_ignore_ascii_case(b"COUNT") {
+                count = take_bulk_string_as_u64(&mut array)?
+                    .filter(|count| *count > 0)
+                    .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?
+                    as usize;
+            } else {
+                return Err(Error::new(ErrorKind::Other, "malformed command"));
+            }
+        }
+
+        Ok(Self {
+            cursor,
+            pattern,
+            count,
+        })
+    }
+}
+
+impl Scan {
+    pub fn new(cursor: u64, pattern: Option<&[u8]>, count: usize) -> Self {
+        Self {
+            cursor,
+            pattern: pattern.map(|p| p.into()),
+            count,
+        }
+    }
+
+    /// The opaque cursor returned by the previous call, or zero to start a
+    /// new iteration.
+    pub fn cursor(&self) -> u64 {
+        self.cursor
+    }
+
+    pub fn pattern(&self) -> Option<&[u8]> {
+        self.pattern.as_deref()
+    }
+
+    /// A hint for how many keys to examine. Keys are filtered by the pattern
+    /// after they are examined, so fewer keys may be returned.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns true if the key matches the glob-style pattern, or if there is
+    /// no pattern. Supports `*`, `?`, `[...]` character classes with ranges
+    /// and `^` negation, and `\` escapes.
+    pub fn matches(&self, key: &[u8]) -> bool {
+        match &self.pattern {
+            Some(pattern) => glob_match(pattern, key),
+            None => true,
+        }
+    }
+}
+
+fn glob_match(pattern: &[u8], key: &[u8]) -> bool {
+    let (mut p, mut k) = (0, 0);
+    // position to resume from after the most recent `*`
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while k < key.len() {
+        let step = if p < pattern.len() {
+            match pattern[p] {
+                b'*' => {
+                    backtrack = Some((p, k));
+                    p += 1;
+                    continue;
+                }
+                b'?' => Some(p + 1),
+                b'[' => match_class(&pattern[p..], key[k]).map(|len| p + len),
+                b'\\' if p + 1 < pattern.len() => (pattern[p + 1] == key[k]).then_some(p + 2),
+                c => (c == key[k]).then_some(p + 1),
+            }
+        } else {
+            None
+        };
+
+        match (step, backtrack) {
+            (Some(next), _) => {
+                p = next;
+                k += 1;
+            }
+            (None, Some((star, start))) => {
+                // let the `*` consume one more byte and retry
+                p = star + 1;
+                k = start + 1;
+                backtrack = Some((star, start + 1));
+            }
+            (None, None) => return false,
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == b'*')
+}
+
+/// Matches a single byte against the character class at the start of the
+/// pattern, returning the length of the class if it matches.
+fn match_class(pattern: &[u8], byte: u8) -> Option<usize> {
+    let mut i = 1;
+    let negate = pattern.get(i) == Some(&b'^');
+    if negate {
+        i += 1;
+    }
+
+    let mut matched = false;
+    while i < pattern.len() && pattern[i] != b']' {
+        if pattern[i] == b'\\' && i + 1 < pattern.len() {
+            i += 1;
+            matched |= pattern[i] == byte;
+        } else if i + 2 < pattern.len() && pattern[i + 1] == b'-' && pattern[i + 2] != b']' {
+            let (lo, hi) = if pattern[i] <= pattern[i + 2] {
+                (pattern[i], pattern[i + 2])
+            } else {
+                (pattern[i + 2], pattern[i])
+            };
+            matched |= (lo..=hi).contains(&byte);
+            i += 2;
+        } else {
+            matched |= pattern[i] == byte;
+        }
+        i += 1;
+    }
+
+    // an unterminated class runs to the end of the pattern
+    let len = (i + 1).min(pattern.len());
+    (matched != negate).then_some(len)
+}
+
+impl From<&Scan> for Message {
+    fn from(value: &Scan) -> Self {
+        let mut inner = vec![
+            Message::bulk_string(b"SCAN"),
+            Message::bulk_string(value.cursor.to_string().as_bytes()),
+        ];
+
+        if let Some(pattern) = &value.pattern {
+            inner.push(Message::bulk_string(b"MATCH"));
+            inner.push(Message::BulkString(BulkString::from(pattern.clone())));
+        }
+
+        inner.push(Message::bulk_string(b"COUNT"));
+        inner.push(Message::bulk_string(value.count.to_string().as_bytes()));
+
+        Message::Array(Array { inner: Some(inner) })
+    }
+}
+
+impl Compose for Scan {
+    fn compose(&self, buf: &mut dyn BufMut) -> usize {
+        Message::from(self).compose(buf)
+    }
+}
+
+impl Klog for Scan {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = read_status(response);
+        let pattern = string_key(self.pattern().unwrap_or(b"*"));
+
+        klog_record(
+            "scan",
+            &[&self.cursor(), &pattern, &self.count()],
+            code as u32,
+            len,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser() {
+        let parser = RequestParser::new();
+        assert_eq!(
+            parser.parse(b"scan 0\r\n").unwrap().into_inner(),
+            Request::Scan(Scan::new(0, None, 10))
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"scan 42 match user:* count 100\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::Scan(Scan::new(42, Some(b"user:*"), 100))
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"*4\r\n$4\r\nSCAN\r\n$1\r\n7\r\n$5\r\nCOUNT\r\n$1\r\n5\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::Scan(Scan::new(7, None, 5))
+        );
+
+        assert!(parser.parse(b"scan\r\n").is_err());
+        assert!(parser.parse(b"scan -1\r\n").is_err());
+        assert!(parser.parse(b"scan 0 count 0\r\n").is_err());
+        assert!(parser.parse(b"scan 0 match\r\n").is_err());
+        assert!(parser.parse(b"scan 0 type string\r\n").is_err());
+    }
+
+    #[test]
+    fn glob() {
+        assert!(glob_match(b"*", b""));
+        assert!(glob_match(b"*", b"anything"));
+        assert!(glob_match(b"user:*", b"user:1"));
+        assert!(!glob_match(b"user:*", b"session:1"));
+        assert!(glob_match(b"*:1", b"user:1"));
+        assert!(glob_match(b"h?llo", b"hello"));
+        assert!(!glob_match(b"h?llo", b"hllo"));
+        assert!(glob_match(b"h*llo", b"heeeello"));
+        assert!(glob_match(b"h[ae]llo", b"hallo"));
+        assert!(!glob_match(b"h[ae]llo", b"hillo"));
+        assert!(glob_match(b"h[^e]llo", b"hallo"));
+        assert!(!glob_match(b"h[^e]llo", b"hello"));
+        assert!(glob_match(b"h[a-c]llo", b"hbllo"));
+        assert!(!glob_match(b"h[a-c]llo", b"hdllo"));
+        assert!(glob_match(b"h\\*llo", b"h*llo"));
+        assert!(!glob_match(b"h\\*llo", b"hello"));
+        assert!(glob_match(b"a*b*c", b"axxbyyc"));
+        assert!(!glob_match(b"a*b*c", b"axxbyy"));
+    }
+}
@@ -74,6 +74,14 @@ impl Compose for SetDiff {
     }
 }
 
+impl Klog for SetDiff {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        klog_list("sdiff", None, self.keys(), read_status(response));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,7 +3,6 @@
 // http://www.apache.org/licenses/LICENSE-2.0
 
 use super::*;
-use logger::klog;
 use std::fmt::{Display, Formatter};
 use std::io::{Error, ErrorKind};
 use std::sync::Arc;
@@ -260,14 +259,16 @@ impl Klog for Set {
             _ => (ResponseCode::NotStored, 0),
         };
 
-        klog!(
-            "\"set {} {} {} {}\" {} {}",
-            string_key(self.key()),
-            FLAG,
-            self.expire_time().unwrap_or_default(),
-            self.value().len(),
+        klog_record(
+            "set",
+            &[
+                &string_key(self.key()),
+                &FLAG,
+                &self.expire_time().unwrap_or_default(),
+                &self.value().len(),
+            ],
             code as u32,
-            len
+            len,
         );
     }
 }
@@ -0,0 +1,133 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use super::*;
+use std::io::{Error, ErrorKind};
+use std::sync::Arc;
+
+#[metric(name = "setnx")]
+pub static SETNX: Counter = Counter::new();
+
+#[metric(name = "setnx_ex")]
+pub static SETNX_EX: Counter = Counter::new();
+
+#[metric(name = "setnx_stored")]
+pub static SETNX_STORED: Counter = Counter::new();
+
+#[metric(name = "setnx_not_stored")]
+pub static SETNX_NOT_STORED: Counter = Counter::new();
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct SetNx {
+    key: Arc<[u8]>,
+    value: Arc<[u8]>,
+}
+
+impl TryFrom<Message> for SetNx {
+    type Error = Error;
+
+    fn try_from(other: Message) -> Result<Self, Error> {
+        let array = match other {
+            Message::Array(array) => array,
+            _ => return Err(Error::new(ErrorKind::Other, "malformed command")),
+        };
+
+        let mut array = array
+            .inner
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+        if array.len() != 3 {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let _command = take_bulk_string(&mut array)?;
+        let key = take_bulk_string(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        if key.is_empty() {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let value = take_bulk_string(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        Ok(Self { key, value })
+    }
+}
+
+impl SetNx {
+    pub fn new(key: &[u8], value: &[u8]) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+}
+
+impl From<&SetNx> for Message {
+    fn from(value: &SetNx) -> Self {
+        Message::Array(Array {
+            inner: Some(vec![
+                Message::bulk_string(b"SETNX"),
+                Message::BulkString(BulkString::from(value.key.clone())),
+                Message::BulkString(BulkString::from(value.value.clone())),
+            ]),
+        })
+    }
+}
+
+impl Compose for SetNx {
+    fn compose(&self, buf: &mut dyn BufMut) -> usize {
+        Message::from(self).compose(buf)
+    }
+}
+
+impl Klog for SetNx {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let code = match response {
+            Message::Integer(i) if i.value() == 1 => ResponseCode::Stored,
+            _ => ResponseCode::NotStored,
+        };
+
+        klog_record(
+            "setnx",
+            &[&string_key(self.key()), &self.value().len()],
+            code as u32,
+            0,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser() {
+        let parser = RequestParser::new();
+        assert_eq!(
+            parser.parse(b"setnx 0 1\r\n").unwrap().into_inner(),
+            Request::SetNx(SetNx::new(b"0", b"1"))
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"*3\r\n$5\r\nsetnx\r\n$1\r\n0\r\n$1\r\n1\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::SetNx(SetNx::new(b"0", b"1"))
+        );
+
+        assert!(parser.parse(b"setnx 0\r\n").is_err());
+    }
+}
@@ -0,0 +1,137 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use super::*;
+use std::io::{Error, ErrorKind};
+use std::sync::Arc;
+
+#[metric(name = "setrange")]
+pub static SETRANGE: Counter = Counter::new();
+
+#[metric(name = "setrange_ex")]
+pub static SETRANGE_EX: Counter = Counter::new();
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct SetRange {
+    key: Arc<[u8]>,
+    offset: u64,
+    value: Arc<[u8]>,
+}
+
+impl TryFrom<Message> for SetRange {
+    type Error = Error;
+
+    fn try_from(other: Message) -> Result<Self, Error> {
+        let array = match other {
+            Message::Array(array) => array,
+            _ => return Err(Error::new(ErrorKind::Other, "malformed command")),
+        };
+
+        let mut array = array
+            .inner
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+        if array.len() != 4 {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let _command = take_bulk_string(&mut array)?;
+        let key = take_bulk_string(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        if key.is_empty() {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let offset = take_bulk_string_as_u64(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+        let value = take_bulk_string(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        Ok(Self { key, offset, value })
+    }
+}
+
+impl SetRange {
+    pub fn new(key: &[u8], offset: u64, value: &[u8]) -> Self {
+        Self {
+            key: key.into(),
+            offset,
+            value: value.into(),
+        }
+    }
+
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+}
+
+impl From<&SetRange> for Message {
+    fn from(value: &SetRange) -> Self {
+        Message::Array(Array {
+            inner: Some(vec![
+                Message::bulk_string(b"SETRANGE"),
+                Message::BulkString(BulkString::from(value.key.clone())),
+                Message::bulk_string(value.offset.to_string().as_bytes()),
+                Message::BulkString(BulkString::from(value.value.clone())),
+            ]),
+        })
+    }
+}
+
+impl Compose for SetRange {
+    fn compose(&self, buf: &mut dyn BufMut) -> usize {
+        Message::from(self).compose(buf)
+    }
+}
+
+impl Klog for SetRange {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = match response {
+            Message::Integer(_) => (ResponseCode::Stored, 0),
+            _ => (ResponseCode::NotStored, 0),
+        };
+
+        klog_record(
+            "setrange",
+            &[&string_key(self.key()), &self.offset(), &self.value().len()],
+            code as u32,
+            len,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser() {
+        let parser = RequestParser::new();
+        assert_eq!(
+            parser.parse(b"setrange 0 5 abc\r\n").unwrap().into_inner(),
+            Request::SetRange(SetRange::new(b"0", 5, b"abc"))
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"*4\r\n$8\r\nsetrange\r\n$1\r\n0\r\n$1\r\n5\r\n$3\r\nabc\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::SetRange(SetRange::new(b"0", 5, b"abc"))
+        );
+
+        assert!(parser.parse(b"setrange 0 5\r\n").is_err());
+        assert!(parser.parse(b"setrange 0 -1 abc\r\n").is_err());
+    }
+}
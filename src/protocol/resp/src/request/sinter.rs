@@ -74,6 +74,14 @@ impl Compose for SetIntersect {
     }
 }
 
+impl Klog for SetIntersect {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        klog_list("sinter", None, self.keys(), read_status(response));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -83,6 +83,21 @@ impl Compose for SetIsMember {
     }
 }
 
+impl Klog for SetIsMember {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = read_status(response);
+
+        klog_record(
+            "sismember",
+            &[&string_key(self.key()), &string_key(self.field())],
+            code as u32,
+            len,
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,226 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use super::*;
+use common::slowlog::SlowLogEntry;
+use std::io::{Error, ErrorKind};
+
+#[metric(name = "slowlog")]
+pub static SLOWLOG: Counter = Counter::new();
+
+#[metric(name = "slowlog_ex")]
+pub static SLOWLOG_EX: Counter = Counter::new();
+
+/// The number of entries returned by `SLOWLOG GET` when no count is provided.
+const DEFAULT_COUNT: usize = 10;
+
+/// Represents the `SLOWLOG` command and its subcommands:
+/// `SLOWLOG GET [count]`, `SLOWLOG LEN`, and `SLOWLOG RESET`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SlowLog {
+    /// Return up to this many of the most recent entries.
+    Get(usize),
+    Len,
+    Reset,
+}
+
+impl TryFrom<Message> for SlowLog {
+    type Error = Error;
+
+    fn try_from(other: Message) -> Result<Self, Error> {
+        let array = match other {
+            Message::Array(array) => array,
+            _ => return Err(Error::new(ErrorKind::Other, "malformed command")),
+        };
+
+        let mut array = array
+            .inner
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        let _command = take_bulk_string(&mut array)?;
+        let subcommand = take_bulk_string(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        let request = if subcommand.eq_ignore_ascii_case(b"GET") {
+            // as with redis, a negative count returns every entry
+            let count = match take_bulk_string_as_i64(&mut array)? {
+                Some(count) if count < 0 => usize::MAX,
+                Some(count) => count as usize,
+                None => DEFAULT_COUNT,
+            };
+            SlowLog::Get(count)
+        } else if subcommand.eq_ignore_ascii_case(b"LEN") {
+            SlowLog::Len
+        } else if subcommand.eq_ignore_ascii_case(b"RESET") {
+            SlowLog::Reset
+        } else {
+            return Err(Error::new(ErrorKind::Other, "unknown subcommand"));
+        };
+
+        if !array.is_empty() {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        Ok(request)
+    }
+}
+
+impl SlowLog {
+    /// Builds the response to this request from the contents of the slow log.
+    /// Entries are formatted as they are by redis: the id, the unix timestamp,
+    /// the duration in microseconds, the command and its arguments, the client
+    /// address, and the client name, which is always empty.
+    pub fn respond(&self, log: &common::slowlog::SlowLog) -> Response {
+        match self {
+            Self::Get(count) => Response::array(log.get(*count).iter().map(entry).collect()),
+            Self::Len => Response::integer(log.len() as i64),
+            Self::Reset => {
+                log.reset();
+                Response::simple_string("OK")
+            }
+        }
+    }
+}
+
+fn entry(entry: &SlowLogEntry) -> Message {
+    let client = entry
+        .client()
+        .map(|addr| addr.to_string())
+        .unwrap_or_default();
+
+    Message::array(vec![
+        Message::integer(entry.id() as i64),
+        Message::integer(entry.timestamp() as i64),
+        Message::integer(entry.duration().as_micros() as i64),
+        Message::array(
+            entry
+                .args()
+                .iter()
+                .map(|arg| Message::bulk_string(arg))
+                .collect(),
+        ),
+        Message::bulk_string(client.as_bytes()),
+        Message::bulk_string(b""),
+    ])
+}
+
+impl From<&SlowLog> for Message {
+    fn from(value: &SlowLog) -> Self {
+        let mut inner = vec![Message::bulk_string(b"SLOWLOG")];
+
+        match value {
+            SlowLog::Get(count) => {
+                inner.push(Message::bulk_string(b"GET"));
+                if *count == usize::MAX {
+                    inner.push(Message::bulk_string(b"-1"));
+                } else {
+                    inner.push(Message::bulk_string(count.to_string().as_bytes()));
+                }
+            }
+            SlowLog::Len => inner.push(Message::bulk_string(b"LEN")),
+            SlowLog::Reset => inner.push(Message::bulk_string(b"RESET")),
+        }
+
+        Message::Array(Array { inner: Some(inner) })
+    }
+}
+
+impl Compose for SlowLog {
+    fn compose(&self, buf: &mut dyn BufMut) -> usize {
+        Message::from(self).compose(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser() {
+        let parser = RequestParser::new();
+        assert_eq!(
+            parser.parse(b"slowlog get\r\n").unwrap().into_inner(),
+            Request::SlowLog(SlowLog::Get(10))
+        );
+
+        assert_eq!(
+            parser.parse(b"SLOWLOG GET 3\r\n").unwrap().into_inner(),
+            Request::SlowLog(SlowLog::Get(3))
+        );
+
+        assert_eq!(
+            parser.parse(b"slowlog get -1\r\n").unwrap().into_inner(),
+            Request::SlowLog(SlowLog::Get(usize::MAX))
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"*2\r\n$7\r\nSLOWLOG\r\n$3\r\nLEN\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::SlowLog(SlowLog::Len)
+        );
+
+        assert_eq!(
+            parser.parse(b"slowlog reset\r\n").unwrap().into_inner(),
+            Request::SlowLog(SlowLog::Reset)
+        );
+
+        assert!(parser.parse(b"slowlog\r\n").is_err());
+        assert!(parser.parse(b"slowlog help\r\n").is_err());
+        assert!(parser.parse(b"slowlog get x\r\n").is_err());
+        assert!(parser.parse(b"slowlog len 1\r\n").is_err());
+    }
+
+    #[test]
+    fn respond() {
+        let log = common::slowlog::SlowLog::new();
+        log.configure(std::time::Duration::ZERO, 8);
+        log.record(
+            "get",
+            || vec![b"key".to_vec()],
+            std::time::Duration::from_micros(1500),
+            "127.0.0.1:12321".parse().ok(),
+        );
+
+        assert_eq!(SlowLog::Len.respond(&log), Response::integer(1));
+
+        let response = SlowLog::Get(10).respond(&log);
+        let entries = match response {
+            Message::Array(Array {
+                inner: Some(entries),
+            }) => entries,
+            _ => panic!("expected an array"),
+        };
+        assert_eq!(entries.len(), 1);
+        let fields = match &entries[0] {
+            Message::Array(Array {
+                inner: Some(fields),
+            }) => fields,
+            _ => panic!("expected an array"),
+        };
+        assert_eq!(fields[0], Message::integer(0));
+        assert_eq!(fields[2], Message::integer(1500));
+        assert_eq!(
+            fields[3],
+            Message::array(vec![
+                Message::bulk_string(b"get"),
+                Message::bulk_string(b"key")
+            ])
+        );
+        assert_eq!(fields[4], Message::bulk_string(b"127.0.0.1:12321"));
+
+        assert_eq!(SlowLog::Reset.respond(&log), Response::simple_string("OK"));
+        assert_eq!(SlowLog::Len.respond(&log), Response::integer(0));
+    }
+
+    #[test]
+    fn args() {
+        let request = Request::SlowLog(SlowLog::Get(usize::MAX));
+        assert_eq!(
+            protocol_common::Command::args(&request),
+            vec![b"GET".to_vec(), b"-1".to_vec()]
+        );
+    }
+}
@@ -66,6 +66,16 @@ impl Compose for SetMembers {
     }
 }
 
+impl Klog for SetMembers {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = read_status(response);
+
+        klog_record("smembers", &[&string_key(self.key())], code as u32, len);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,6 +88,19 @@ impl Compose for SetRem {
     }
 }
 
+impl Klog for SetRem {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        klog_list(
+            "srem",
+            Some(self.key()),
+            self.members(),
+            update_status(response, ResponseCode::Deleted),
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
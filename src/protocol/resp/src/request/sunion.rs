@@ -74,6 +74,14 @@ impl Compose for SetUnion {
     }
 }
 
+impl Klog for SetUnion {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        klog_list("sunion", None, self.keys(), read_status(response));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,117 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use super::*;
+use std::io::{Error, ErrorKind};
+use std::sync::Arc;
+
+#[metric(name = "ttl")]
+pub static TTL: Counter = Counter::new();
+
+#[metric(name = "ttl_ex")]
+pub static TTL_EX: Counter = Counter::new();
+
+#[metric(name = "ttl_hit")]
+pub static TTL_HIT: Counter = Counter::new();
+
+#[metric(name = "ttl_miss")]
+pub static TTL_MISS: Counter = Counter::new();
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct TimeToLive {
+    key: Arc<[u8]>,
+}
+
+impl TryFrom<Message> for TimeToLive {
+    type Error = Error;
+
+    fn try_from(other: Message) -> Result<Self, Error> {
+        let array = match other {
+            Message::Array(array) => array,
+            _ => return Err(Error::new(ErrorKind::Other, "malformed command")),
+        };
+
+        let mut array = array
+            .inner
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+        if array.len() != 2 {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        let _command = take_bulk_string(&mut array)?;
+        let key = take_bulk_string(&mut array)?
+            .ok_or_else(|| Error::new(ErrorKind::Other, "malformed command"))?;
+
+        if key.is_empty() {
+            return Err(Error::new(ErrorKind::Other, "malformed command"));
+        }
+
+        Ok(Self { key })
+    }
+}
+
+impl TimeToLive {
+    pub fn new(key: &[u8]) -> Self {
+        Self { key: key.into() }
+    }
+
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+}
+
+impl From<&TimeToLive> for Message {
+    fn from(value: &TimeToLive) -> Self {
+        Message::Array(Array {
+            inner: Some(vec![
+                Message::bulk_string(b"TTL"),
+                Message::BulkString(BulkString::from(value.key.clone())),
+            ]),
+        })
+    }
+}
+
+impl Compose for TimeToLive {
+    fn compose(&self, buf: &mut dyn BufMut) -> usize {
+        Message::from(self).compose(buf)
+    }
+}
+
+impl Klog for TimeToLive {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        // a ttl of -2 means that the key does not exist
+        let (code, len) = match response {
+            Message::Integer(ttl) if ttl.value() != -2 => (ResponseCode::Hit, 0),
+            _ => (ResponseCode::Miss, 0),
+        };
+
+        klog_record("ttl", &[&string_key(self.key())], code as u32, len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser() {
+        let parser = RequestParser::new();
+        assert_eq!(
+            parser.parse(b"ttl 0\r\n").unwrap().into_inner(),
+            Request::TimeToLive(TimeToLive::new(b"0"))
+        );
+
+        assert_eq!(
+            parser
+                .parse(b"*2\r\n$3\r\nttl\r\n$1\r\n0\r\n")
+                .unwrap()
+                .into_inner(),
+            Request::TimeToLive(TimeToLive::new(b"0"))
+        );
+
+        assert!(parser.parse(b"ttl 0 1\r\n").is_err());
+    }
+}
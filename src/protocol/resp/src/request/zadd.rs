@@ -180,6 +180,21 @@ impl Compose for SortedSetAdd {
     }
 }
 
+impl Klog for SortedSetAdd {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = write_status(response);
+
+        klog_record(
+            "zadd",
+            &[&string_key(self.key()), &self.members().len()],
+            code as u32,
+            len,
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
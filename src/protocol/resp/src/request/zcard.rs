@@ -77,6 +77,16 @@ impl Compose for SortedSetCardinality {
     }
 }
 
+impl Klog for SortedSetCardinality {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = read_status(response);
+
+        klog_record("zcard", &[&string_key(self.key())], code as u32, len);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
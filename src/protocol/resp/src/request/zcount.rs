@@ -166,6 +166,25 @@ fn parse_score_boundary_as_float(value: &[u8]) -> Result<(f64, bool), Error> {
     }
 }
 
+impl Klog for SortedSetCount {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = read_status(response);
+
+        klog_record(
+            "zcount",
+            &[
+                &string_key(self.key()),
+                &self.min_score(),
+                &self.max_score(),
+            ],
+            code as u32,
+            len,
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,6 +99,25 @@ impl Compose for SortedSetIncrement {
     }
 }
 
+impl Klog for SortedSetIncrement {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = write_status(response);
+
+        klog_record(
+            "zincrby",
+            &[
+                &string_key(self.key()),
+                &self.increment(),
+                &string_key(self.member()),
+            ],
+            code as u32,
+            len,
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,6 +104,33 @@ impl Compose for SortedSetMultiScore {
     }
 }
 
+impl Klog for SortedSetMultiScore {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let scores = match response {
+            Message::Array(Array {
+                inner: Some(scores),
+            }) => scores,
+            _ => return,
+        };
+
+        for (member, score) in self.members().iter().zip(scores.iter()) {
+            let (code, len) = match score {
+                Message::BulkString(BulkString { inner: Some(s) }) => (ResponseCode::Hit, s.len()),
+                _ => (ResponseCode::Miss, 0),
+            };
+
+            klog_record(
+                "zmscore",
+                &[&string_key(self.key()), &string_key(member)],
+                code as u32,
+                len,
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
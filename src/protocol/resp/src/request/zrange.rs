@@ -294,6 +294,25 @@ impl Compose for SortedSetRange {
     }
 }
 
+impl Klog for SortedSetRange {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = read_status(response);
+
+        klog_record(
+            "zrange",
+            &[
+                &string_key(self.key()),
+                &string_key(self.start()),
+                &string_key(self.stop()),
+            ],
+            code as u32,
+            len,
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -105,6 +105,25 @@ impl Compose for SortedSetRank {
     }
 }
 
+impl Klog for SortedSetRank {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        // a rank of zero is still a hit
+        let (code, len) = match response {
+            Message::Integer(_) => (ResponseCode::Hit, 0),
+            _ => read_status(response),
+        };
+
+        klog_record(
+            "zrank",
+            &[&string_key(self.key()), &string_key(self.member())],
+            code as u32,
+            len,
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
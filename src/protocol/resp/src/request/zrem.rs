@@ -98,6 +98,19 @@ impl Compose for SortedSetRemove {
     }
 }
 
+impl Klog for SortedSetRemove {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        klog_list(
+            "zrem",
+            Some(self.key()),
+            self.members(),
+            update_status(response, ResponseCode::Deleted),
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
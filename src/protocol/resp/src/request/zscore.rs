@@ -89,6 +89,21 @@ impl Compose for SortedSetScore {
     }
 }
 
+impl Klog for SortedSetScore {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        let (code, len) = read_status(response);
+
+        klog_record(
+            "zscore",
+            &[&string_key(self.key()), &string_key(self.member())],
+            code as u32,
+            len,
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -269,6 +269,19 @@ impl Compose for SortedSetUnionStore {
     }
 }
 
+impl Klog for SortedSetUnionStore {
+    type Response = Response;
+
+    fn klog(&self, response: &Self::Response) {
+        klog_list(
+            "zunionstore",
+            Some(self.destination_key()),
+            self.source_keys(),
+            write_status(response),
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
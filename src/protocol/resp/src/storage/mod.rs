@@ -7,4 +7,24 @@ use crate::*;
 pub trait Storage {
     fn get(&mut self, request: &Get) -> Response;
     fn set(&mut self, request: &Set) -> Response;
+    fn exists(&mut self, request: &Exists) -> Response;
+    fn expire(&mut self, request: &Expire) -> Response;
+    fn expire_at(&mut self, request: &ExpireAt) -> Response;
+    fn expire_milliseconds(&mut self, request: &ExpireMilliseconds) -> Response;
+    fn key_type(&mut self, request: &KeyType) -> Response;
+    fn persist(&mut self, request: &Persist) -> Response;
+    fn scan(&mut self, request: &Scan) -> Response;
+    fn time_to_live(&mut self, request: &TimeToLive) -> Response;
+    fn time_to_live_milliseconds(&mut self, request: &TimeToLiveMilliseconds) -> Response;
+    fn append(&mut self, request: &Append) -> Response;
+    fn decr(&mut self, request: &Decr) -> Response;
+    fn get_del(&mut self, request: &GetDel) -> Response;
+    fn get_range(&mut self, request: &GetRange) -> Response;
+    fn incr(&mut self, request: &Incr) -> Response;
+    fn incr_by(&mut self, request: &IncrBy) -> Response;
+    fn multi_get(&mut self, request: &MultiGet) -> Response;
+    fn multi_set(&mut self, request: &MultiSet) -> Response;
+    fn set_nx(&mut self, request: &SetNx) -> Response;
+    fn set_range(&mut self, request: &SetRange) -> Response;
+    fn string_length(&mut self, request: &StringLength) -> Response;
 }
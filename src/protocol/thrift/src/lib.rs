@@ -119,6 +119,15 @@ impl Message {
     }
 }
 
+// messages are opaque, so we do not distinguish between commands
+impl protocol_common::Command for Message {
+    const PROTOCOL: &'static str = "thrift";
+
+    fn command(&self) -> &'static str {
+        "message"
+    }
+}
+
 impl Compose for Message {
     fn compose(&self, session: &mut dyn BufMut) -> usize {
         MESSAGES_COMPOSED.increment();
@@ -4,13 +4,16 @@
 
 use crate::protocol::*;
 use crate::*;
+use common::slowlog::SLOWLOG;
 use pelikan_net::TCP_SEND_BYTE;
 use protocol_memcache::Protocol;
+use protocol_resp::Command;
 use session::Buf;
 use std::collections::BTreeMap;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::mpsc;
 
 pub(crate) async fn handle_memcache_client(
@@ -307,6 +310,7 @@ pub(crate) async fn handle_resp_client(
         let consumed = request.consumed();
         let request = request.into_inner();
         let command = request.command();
+        let start = Instant::now();
 
         let mut response_buf = Vec::<u8>::new();
 
@@ -315,6 +319,18 @@ pub(crate) async fn handle_resp_client(
                 resp::Request::Del(r) => {
                     resp::del(&mut client, &cache_name, &mut response_buf, r).await?
                 }
+                resp::Request::Exists(r) => {
+                    resp::exists(&mut client, &cache_name, &mut response_buf, r).await?
+                }
+                resp::Request::Expire(r) => {
+                    resp::expire(&mut client, &cache_name, &mut response_buf, r).await?
+                }
+                resp::Request::ExpireAt(r) => {
+                    resp::expireat(&mut client, &cache_name, &mut response_buf, r).await?
+                }
+                resp::Request::ExpireMilliseconds(r) => {
+                    resp::pexpire(&mut client, &cache_name, &mut response_buf, r).await?
+                }
                 resp::Request::Get(r) => {
                     resp::get(&mut client, &cache_name, &mut response_buf, r.key()).await?
                 }
@@ -348,6 +364,9 @@ pub(crate) async fn handle_resp_client(
                 resp::Request::HashValues(r) => {
                     resp::hvals(&mut client, &cache_name, &mut response_buf, r).await?
                 }
+                resp::Request::KeyType(r) => {
+                    resp::key_type(&mut client, &cache_name, &mut response_buf, r).await?
+                }
                 resp::Request::ListIndex(r) => {
                     resp::lindex(&mut client, &cache_name, &mut response_buf, r).await?
                 }
@@ -393,6 +412,13 @@ pub(crate) async fn handle_resp_client(
                 resp::Request::SetIsMember(r) => {
                     resp::sismember(&mut client, &cache_name, &mut response_buf, r).await?
                 }
+                resp::Request::SlowLog(r) => resp::slowlog(&mut response_buf, r).await?,
+                resp::Request::TimeToLive(r) => {
+                    resp::ttl(&mut client, &cache_name, &mut response_buf, r).await?
+                }
+                resp::Request::TimeToLiveMilliseconds(r) => {
+                    resp::pttl(&mut client, &cache_name, &mut response_buf, r).await?
+                }
                 _ => return Err(ProxyError::UnsupportedCommand(request.command())),
             }
 
@@ -400,6 +426,12 @@ pub(crate) async fn handle_resp_client(
         }
         .await;
 
+        let duration = start.elapsed();
+        if SLOWLOG.is_slow(duration) {
+            let client = socket.peer_addr().ok();
+            SLOWLOG.record(command, || request.args(), duration, client);
+        }
+
         let fatal = match result {
             Ok(()) => false,
             Err(e) => {
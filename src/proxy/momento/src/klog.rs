@@ -2,7 +2,7 @@
 // Licensed under the Apache License, Version 2.0
 // http://www.apache.org/licenses/LICENSE-2.0
 
-use core::fmt::Display;
+use logger::klog_record;
 
 #[allow(dead_code)]
 /// A collection of klog status codes taken from:
@@ -20,40 +20,32 @@ pub enum Status {
     Timeout = 12, // NOTE: this is not a standard klog code
 }
 
-pub(crate) fn klog_1(
-    command: &dyn Display,
-    key: &dyn AsRef<[u8]>,
-    status: Status,
-    response_len: usize,
-) {
-    klog!(
-        "\"{} {}\" {} {}",
+pub(crate) fn klog_1(command: &str, key: &dyn AsRef<[u8]>, status: Status, response_len: usize) {
+    klog_record(
         command,
-        EscapedStr::new(key),
-        status as u8,
-        response_len
+        &[&EscapedStr::new(key)],
+        status as u32,
+        response_len,
     );
 }
 
 pub(crate) fn klog_2(
-    command: &dyn Display,
+    command: &str,
     key: &dyn AsRef<[u8]>,
     field: &dyn AsRef<[u8]>,
     status: Status,
     response_len: usize,
 ) {
-    klog!(
-        "\"{} {} {}\" {} {}",
+    klog_record(
         command,
-        EscapedStr::new(key),
-        EscapedStr::new(field),
-        status as u8,
-        response_len
+        &[&EscapedStr::new(key), &EscapedStr::new(field)],
+        status as u32,
+        response_len,
     );
 }
 
 pub(crate) fn klog_7(
-    command: &dyn Display,
+    command: &str,
     key: &dyn AsRef<[u8]>,
     field: &dyn AsRef<[u8]>,
     ttl: i32,
@@ -61,15 +53,16 @@ pub(crate) fn klog_7(
     status: Status,
     response_len: usize,
 ) {
-    klog!(
-        "\"{} {} {} {} {}\" {} {}",
+    klog_record(
         command,
-        EscapedStr::new(key),
-        EscapedStr::new(field),
-        ttl,
-        value_len,
-        status as u8,
-        response_len
+        &[
+            &EscapedStr::new(key),
+            &EscapedStr::new(field),
+            &ttl,
+            &value_len,
+        ],
+        status as u32,
+        response_len,
     );
 }
 
@@ -81,14 +74,11 @@ pub fn klog_set(
     status: Status,
     response_len: usize,
 ) {
-    klog!(
-        "\"set {} {} {} {}\" {} {}",
-        EscapedStr::new(key),
-        flags,
-        ttl,
-        value_len,
-        status as u8,
-        response_len
+    klog_record(
+        "set",
+        &[&EscapedStr::new(key), &flags, &ttl, &value_len],
+        status as u32,
+        response_len,
     );
 }
 
@@ -5,7 +5,7 @@
 #[macro_use]
 extern crate logger;
 
-use ::config::{AdminConfig, MomentoProxyConfig, TimeType};
+use ::config::{AdminConfig, MomentoProxyConfig, SlowlogConfig, TimeType};
 use backtrace::Backtrace;
 use clap::{Arg, Command};
 use core::num::NonZeroUsize;
@@ -153,6 +153,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // initialize metrics
     common::metrics::init();
 
+    // initialize the slow log
+    common::slowlog::SLOWLOG.configure(config.slowlog().threshold(), config.slowlog().max_len());
+
     // output stats descriptions and exit if the `stats` option was provided
     if matches.get_flag("stats") {
         println!("{:<31} {:<15} DESCRIPTION", "NAME", "TYPE");
@@ -0,0 +1,57 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use crate::klog::{klog_1, Status};
+use crate::*;
+use protocol_resp::{Exists, EXISTS, EXISTS_EX, EXISTS_HIT, EXISTS_MISS};
+
+use super::update_method_metrics;
+
+pub async fn exists(
+    client: &mut CacheClient,
+    cache_name: &str,
+    response_buf: &mut Vec<u8>,
+    req: &Exists,
+) -> ProxyResult {
+    update_method_metrics(&EXISTS, &EXISTS_EX, async move {
+        let mut count = 0;
+
+        // keys are checked one at a time so that repeated keys are counted
+        // once per occurrence, matching redis
+        for key in req.keys() {
+            let key: &[u8] = key;
+
+            let response = match timeout(
+                Duration::from_millis(200),
+                client.key_exists(cache_name, key),
+            )
+            .await
+            {
+                Ok(Ok(r)) => r,
+                Ok(Err(e)) => {
+                    klog_1(&"exists", &key, Status::ServerError, 0);
+                    return Err(ProxyError::from(e));
+                }
+                Err(e) => {
+                    klog_1(&"exists", &key, Status::Timeout, 0);
+                    return Err(ProxyError::from(e));
+                }
+            };
+
+            if response.exists {
+                EXISTS_HIT.increment();
+                count += 1;
+                klog_1(&"exists", &key, Status::Hit, 0);
+            } else {
+                EXISTS_MISS.increment();
+                klog_1(&"exists", &key, Status::Miss, 0);
+            }
+        }
+
+        response_buf.extend_from_slice(format!(":{count}\r\n").as_bytes());
+
+        Ok(())
+    })
+    .await
+}
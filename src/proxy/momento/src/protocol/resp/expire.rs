@@ -0,0 +1,143 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use crate::klog::{klog_1, Status};
+use crate::*;
+use momento::cache::UpdateTtlResponse;
+use protocol_resp::{
+    Expire, ExpireAt, ExpireMilliseconds, EXPIRE, EXPIREAT, EXPIREAT_EX, EXPIREAT_HIT,
+    EXPIREAT_MISS, EXPIRE_EX, EXPIRE_HIT, EXPIRE_MISS, PEXPIRE, PEXPIRE_EX, PEXPIRE_HIT,
+    PEXPIRE_MISS,
+};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::update_method_metrics;
+
+pub async fn expire(
+    client: &mut CacheClient,
+    cache_name: &str,
+    response_buf: &mut Vec<u8>,
+    req: &Expire,
+) -> ProxyResult {
+    update_method_metrics(&EXPIRE, &EXPIRE_EX, async move {
+        let ttl = (req.seconds() > 0).then(|| Duration::from_secs(req.seconds() as u64));
+
+        if update_ttl(client, cache_name, "expire", req.key(), ttl).await? {
+            EXPIRE_HIT.increment();
+            response_buf.extend_from_slice(b":1\r\n");
+        } else {
+            EXPIRE_MISS.increment();
+            response_buf.extend_from_slice(b":0\r\n");
+        }
+
+        Ok(())
+    })
+    .await
+}
+
+pub async fn pexpire(
+    client: &mut CacheClient,
+    cache_name: &str,
+    response_buf: &mut Vec<u8>,
+    req: &ExpireMilliseconds,
+) -> ProxyResult {
+    update_method_metrics(&PEXPIRE, &PEXPIRE_EX, async move {
+        let ttl =
+            (req.milliseconds() > 0).then(|| Duration::from_millis(req.milliseconds() as u64));
+
+        if update_ttl(client, cache_name, "pexpire", req.key(), ttl).await? {
+            PEXPIRE_HIT.increment();
+            response_buf.extend_from_slice(b":1\r\n");
+        } else {
+            PEXPIRE_MISS.increment();
+            response_buf.extend_from_slice(b":0\r\n");
+        }
+
+        Ok(())
+    })
+    .await
+}
+
+pub async fn expireat(
+    client: &mut CacheClient,
+    cache_name: &str,
+    response_buf: &mut Vec<u8>,
+    req: &ExpireAt,
+) -> ProxyResult {
+    update_method_metrics(&EXPIREAT, &EXPIREAT_EX, async move {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        let seconds = req.timestamp().saturating_sub(now);
+        let ttl = (seconds > 0).then(|| Duration::from_secs(seconds as u64));
+
+        if update_ttl(client, cache_name, "expireat", req.key(), ttl).await? {
+            EXPIREAT_HIT.increment();
+            response_buf.extend_from_slice(b":1\r\n");
+        } else {
+            EXPIREAT_MISS.increment();
+            response_buf.extend_from_slice(b":0\r\n");
+        }
+
+        Ok(())
+    })
+    .await
+}
+
+/// Updates the TTL for a key, returning whether the key was found. A TTL of
+/// `None` means the key should expire immediately, which Momento does not
+/// accept as a TTL, so the key is deleted instead.
+async fn update_ttl(
+    client: &mut CacheClient,
+    cache_name: &str,
+    command: &str,
+    key: &[u8],
+    ttl: Option<Duration>,
+) -> Result<bool, ProxyError> {
+    let found = match ttl {
+        Some(ttl) => {
+            match timeout(
+                Duration::from_millis(200),
+                client.update_ttl(cache_name, key, ttl),
+            )
+            .await
+            {
+                Ok(Ok(UpdateTtlResponse::Set)) => true,
+                Ok(Ok(UpdateTtlResponse::Miss)) => false,
+                Ok(Err(e)) => {
+                    klog_1(&command, &key, Status::ServerError, 0);
+                    return Err(ProxyError::from(e));
+                }
+                Err(e) => {
+                    klog_1(&command, &key, Status::Timeout, 0);
+                    return Err(ProxyError::from(e));
+                }
+            }
+        }
+        None => {
+            // NOTE: Momento does not tell us whether the key existed, so we
+            // report that it did, as we do for `DEL`.
+            match timeout(Duration::from_millis(200), client.delete(cache_name, key)).await {
+                Ok(Ok(_)) => true,
+                Ok(Err(e)) => {
+                    klog_1(&command, &key, Status::ServerError, 0);
+                    return Err(ProxyError::from(e));
+                }
+                Err(e) => {
+                    klog_1(&command, &key, Status::Timeout, 0);
+                    return Err(ProxyError::from(e));
+                }
+            }
+        }
+    };
+
+    if found {
+        klog_1(&command, &key, Status::Stored, 0);
+    } else {
+        klog_1(&command, &key, Status::NotFound, 0);
+    }
+
+    Ok(found)
+}
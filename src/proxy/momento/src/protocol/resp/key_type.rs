@@ -0,0 +1,61 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use crate::klog::{klog_1, Status};
+use crate::*;
+use momento::cache::{ItemGetTypeResponse, ItemType};
+use protocol_resp::{KeyType, TYPE, TYPE_EX, TYPE_HIT, TYPE_MISS};
+
+use super::update_method_metrics;
+
+pub async fn key_type(
+    client: &mut CacheClient,
+    cache_name: &str,
+    response_buf: &mut Vec<u8>,
+    req: &KeyType,
+) -> ProxyResult {
+    update_method_metrics(&TYPE, &TYPE_EX, async move {
+        let response = match timeout(
+            Duration::from_millis(200),
+            client.item_get_type(cache_name, req.key()),
+        )
+        .await
+        {
+            Ok(Ok(r)) => r,
+            Ok(Err(e)) => {
+                klog_1(&"type", &req.key(), Status::ServerError, 0);
+                return Err(ProxyError::from(e));
+            }
+            Err(e) => {
+                klog_1(&"type", &req.key(), Status::Timeout, 0);
+                return Err(ProxyError::from(e));
+            }
+        };
+
+        match response {
+            ItemGetTypeResponse::Hit { key_type } => {
+                TYPE_HIT.increment();
+
+                let name = match key_type {
+                    ItemType::Scalar => "string",
+                    ItemType::Dictionary => "hash",
+                    ItemType::List => "list",
+                    ItemType::Set => "set",
+                    ItemType::SortedSet => "zset",
+                };
+
+                response_buf.extend_from_slice(format!("+{name}\r\n").as_bytes());
+                klog_1(&"type", &req.key(), Status::Hit, response_buf.len());
+            }
+            ItemGetTypeResponse::Miss => {
+                TYPE_MISS.increment();
+                response_buf.extend_from_slice(b"+none\r\n");
+                klog_1(&"type", &req.key(), Status::Miss, response_buf.len());
+            }
+        }
+
+        Ok(())
+    })
+    .await
+}
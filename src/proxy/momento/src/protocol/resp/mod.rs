@@ -7,6 +7,8 @@ pub use protocol_resp::{Request, RequestParser};
 use std::future::Future;
 
 mod del;
+mod exists;
+mod expire;
 mod get;
 mod hdel;
 mod hexists;
@@ -18,6 +20,7 @@ mod hlen;
 mod hmget;
 mod hset;
 mod hvals;
+mod key_type;
 mod lindex;
 mod llen;
 mod lpop;
@@ -30,9 +33,11 @@ mod sdiff;
 mod set;
 mod sinter;
 mod sismember;
+mod slowlog;
 mod smembers;
 mod srem;
 mod sunion;
+mod ttl;
 
 pub use self::lindex::*;
 pub use self::llen::*;
@@ -44,10 +49,13 @@ pub use self::rpush::*;
 pub use self::sdiff::*;
 pub use self::sinter::*;
 pub use self::sismember::*;
+pub use self::slowlog::*;
 pub use self::smembers::*;
 pub use self::srem::*;
 pub use self::sunion::*;
 pub use del::*;
+pub use exists::*;
+pub use expire::*;
 pub use get::*;
 pub use hdel::*;
 pub use hexists::*;
@@ -59,8 +67,10 @@ pub use hlen::*;
 pub use hmget::*;
 pub use hset::*;
 pub use hvals::*;
+pub use key_type::*;
 pub use sadd::*;
 pub use set::*;
+pub use ttl::*;
 
 pub(crate) fn momento_error_to_resp_error(buf: &mut Vec<u8>, command: &str, error: MomentoError) {
     use crate::BACKEND_EX;
@@ -0,0 +1,19 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use crate::*;
+use protocol_resp::{Compose, SlowLog, SLOWLOG, SLOWLOG_EX};
+
+use super::update_method_metrics;
+
+/// Serves the slow log of the proxy itself. This is never forwarded to the
+/// backend.
+pub async fn slowlog(response_buf: &mut Vec<u8>, req: &SlowLog) -> ProxyResult {
+    update_method_metrics(&SLOWLOG, &SLOWLOG_EX, async move {
+        req.respond(&common::slowlog::SLOWLOG).compose(response_buf);
+
+        Ok(())
+    })
+    .await
+}
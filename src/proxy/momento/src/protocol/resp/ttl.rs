@@ -0,0 +1,96 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+use crate::klog::{klog_1, Status};
+use crate::*;
+use momento::cache::ItemGetTtlResponse;
+use protocol_resp::{
+    TimeToLive, TimeToLiveMilliseconds, PTTL, PTTL_EX, PTTL_HIT, PTTL_MISS, TTL, TTL_EX, TTL_HIT,
+    TTL_MISS,
+};
+
+use super::update_method_metrics;
+
+pub async fn ttl(
+    client: &mut CacheClient,
+    cache_name: &str,
+    response_buf: &mut Vec<u8>,
+    req: &TimeToLive,
+) -> ProxyResult {
+    update_method_metrics(&TTL, &TTL_EX, async move {
+        match item_get_ttl(client, cache_name, "ttl", req.key()).await? {
+            Some(ttl) => {
+                TTL_HIT.increment();
+                response_buf.extend_from_slice(format!(":{}\r\n", ttl.as_secs()).as_bytes());
+            }
+            None => {
+                TTL_MISS.increment();
+                response_buf.extend_from_slice(b":-2\r\n");
+            }
+        }
+
+        Ok(())
+    })
+    .await
+}
+
+pub async fn pttl(
+    client: &mut CacheClient,
+    cache_name: &str,
+    response_buf: &mut Vec<u8>,
+    req: &TimeToLiveMilliseconds,
+) -> ProxyResult {
+    update_method_metrics(&PTTL, &PTTL_EX, async move {
+        match item_get_ttl(client, cache_name, "pttl", req.key()).await? {
+            Some(ttl) => {
+                PTTL_HIT.increment();
+                response_buf.extend_from_slice(format!(":{}\r\n", ttl.as_millis()).as_bytes());
+            }
+            None => {
+                PTTL_MISS.increment();
+                response_buf.extend_from_slice(b":-2\r\n");
+            }
+        }
+
+        Ok(())
+    })
+    .await
+}
+
+/// Fetches the remaining TTL for a key. Every item in Momento has a TTL, so
+/// there is no equivalent of a key without an expiry.
+async fn item_get_ttl(
+    client: &mut CacheClient,
+    cache_name: &str,
+    command: &str,
+    key: &[u8],
+) -> Result<Option<Duration>, ProxyError> {
+    let response = match timeout(
+        Duration::from_millis(200),
+        client.item_get_ttl(cache_name, key),
+    )
+    .await
+    {
+        Ok(Ok(r)) => r,
+        Ok(Err(e)) => {
+            klog_1(&command, &key, Status::ServerError, 0);
+            return Err(ProxyError::from(e));
+        }
+        Err(e) => {
+            klog_1(&command, &key, Status::Timeout, 0);
+            return Err(ProxyError::from(e));
+        }
+    };
+
+    match response {
+        ItemGetTtlResponse::Hit { remaining_ttl } => {
+            klog_1(&command, &key, Status::Hit, 0);
+            Ok(Some(remaining_ttl))
+        }
+        ItemGetTtlResponse::Miss => {
+            klog_1(&command, &key, Status::Miss, 0);
+            Ok(None)
+        }
+    }
+}
@@ -27,6 +27,10 @@ impl Rds {
         // initialize metrics
         common::metrics::init();
 
+        // initialize the slow log
+        common::slowlog::SLOWLOG
+            .configure(config.slowlog().threshold(), config.slowlog().max_len());
+
         // initialize storage
         let storage = Storage::new(&config)?;
 
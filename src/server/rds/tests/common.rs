@@ -30,6 +30,97 @@ pub fn tests() {
         ],
     );
 
+    // check the keyspace commands against a key without a ttl
+    test(
+        "exists and type",
+        &[
+            ("set exists bar\r\n", Some(RESP_OK)),
+            ("exists exists missing exists\r\n", Some(":2\r\n")),
+            ("type exists\r\n", Some("+string\r\n")),
+            ("type missing\r\n", Some("+none\r\n")),
+        ],
+    );
+
+    // check that ttls can be set, read back, and removed
+    test(
+        "expire and ttl",
+        &[
+            ("ttl missing\r\n", Some(":-2\r\n")),
+            ("expire missing 60\r\n", Some(":0\r\n")),
+            ("set ttl bar\r\n", Some(RESP_OK)),
+            ("ttl ttl\r\n", Some(":-1\r\n")),
+            ("expire ttl 60\r\n", Some(":1\r\n")),
+            ("ttl ttl\r\n", Some(":5")),
+            ("get ttl\r\n", Some(&bulk_string("bar"))),
+            ("persist ttl\r\n", Some(":1\r\n")),
+            ("persist ttl\r\n", Some(":0\r\n")),
+            ("pttl ttl\r\n", Some(":-1\r\n")),
+            ("pexpire ttl 9223372036854775807\r\n", Some(":1\r\n")),
+            ("get ttl\r\n", Some(&bulk_string("bar"))),
+            ("pexpire ttl 0\r\n", Some(":1\r\n")),
+            ("get ttl\r\n", Some(RESP_NIL)),
+        ],
+    );
+
+    // check integer commands, including redis error semantics
+    test(
+        "incr and decr",
+        &[
+            ("incr counter\r\n", Some(":1\r\n")),
+            ("incrby counter 41\r\n", Some(":42\r\n")),
+            ("decr counter\r\n", Some(":41\r\n")),
+            ("incrby counter -50\r\n", Some(":-9\r\n")),
+            ("get counter\r\n", Some(&bulk_string("-9"))),
+            ("set counter 9223372036854775807\r\n", Some(RESP_OK)),
+            (
+                "incr counter\r\n",
+                Some("-ERR increment or decrement would overflow\r\n"),
+            ),
+            ("set counter abc\r\n", Some(RESP_OK)),
+            (
+                "incr counter\r\n",
+                Some("-ERR value is not an integer or out of range\r\n"),
+            ),
+        ],
+    );
+
+    // check the string manipulation commands
+    test(
+        "string commands",
+        &[
+            ("append greeting hello\r\n", Some(":5\r\n")),
+            ("append greeting \" world\"\r\n", Some(":11\r\n")),
+            ("strlen greeting\r\n", Some(":11\r\n")),
+            ("getrange greeting 0 4\r\n", Some(&bulk_string("hello"))),
+            ("getrange greeting -5 -1\r\n", Some(&bulk_string("world"))),
+            ("setrange greeting 6 redis\r\n", Some(":11\r\n")),
+            ("get greeting\r\n", Some(&bulk_string("hello redis"))),
+            ("setnx greeting bye\r\n", Some(":0\r\n")),
+            ("getdel greeting\r\n", Some(&bulk_string("hello redis"))),
+            ("get greeting\r\n", Some(RESP_NIL)),
+            ("setnx greeting bye\r\n", Some(":1\r\n")),
+            // an offset beyond the largest item is rejected without creating
+            // the key
+            (
+                "setrange huge 536870000 x\r\n",
+                Some("-ERR string exceeds maximum allowed size (proto-max-bulk-len)\r\n"),
+            ),
+            ("exists huge\r\n", Some(":0\r\n")),
+        ],
+    );
+
+    // check that multiple keys can be stored and retrieved
+    test(
+        "mset and mget",
+        &[
+            ("mset k1 v1 k2 v2\r\n", Some(RESP_OK)),
+            (
+                "mget k1 missing k2\r\n",
+                Some("*3\r\n$2\r\nv1\r\n$-1\r\n$2\r\nv2\r\n"),
+            ),
+        ],
+    );
+
     std::thread::sleep(Duration::from_millis(500));
 }
 
@@ -33,9 +33,14 @@ impl Segcache {
         // initialize storage
         let storage = Storage::new(&config)?;
 
-        // initialize parser
+        // initialize parser, values larger than a segment are accepted when
+        // large items are enabled
+        let max_value_size = std::cmp::max(
+            config.seg().segment_size() as usize,
+            config.seg().item_max_size(),
+        );
         let protocol = Protocol::new()
-            .max_value_size(config.seg().segment_size() as usize)
+            .max_value_size(max_value_size)
             .time_type(config.time().time_type());
 
         // initialize process
@@ -125,6 +125,11 @@ where
         self.session.interest()
     }
 
+    /// Indicates if the underlying session is still handshaking.
+    pub fn is_handshaking(&self) -> bool {
+        self.session.is_handshaking()
+    }
+
     /// Attempt to handshake the underlying session.
     pub fn do_handshake(&mut self) -> Result<()> {
         self.session.do_handshake()
@@ -24,6 +24,7 @@ use pelikan_net::*;
 use protocol_common::Compose;
 use std::collections::VecDeque;
 use std::io::{Error, ErrorKind, Read, Result, Write};
+use std::net::SocketAddr;
 use std::os::unix::prelude::AsRawFd;
 
 #[metric(
@@ -136,6 +137,11 @@ impl Session {
         self.stream.is_handshaking()
     }
 
+    /// Returns the address of the remote end of the underlying stream.
+    pub fn peer_addr(&self) -> Result<SocketAddr> {
+        self.stream.peer_addr()
+    }
+
     /// Fill the read buffer by calling read on the underlying stream until read
     /// would block. Returns the number of bytes read. `Ok(0)` indicates that
     /// the remote side has closed the stream.
@@ -3,6 +3,7 @@
 // http://www.apache.org/licenses/LICENSE-2.0
 
 use super::*;
+use common::monitor::Observer;
 use protocol_common::Protocol;
 
 /// A basic session to represent the server side of a framed session, meaning
@@ -70,6 +71,11 @@ where
         self.session
     }
 
+    /// Returns the address of the client.
+    pub fn peer_addr(&self) -> Result<SocketAddr> {
+        self.session.peer_addr()
+    }
+
     /// Attempt to receive a single message from the current session buffer.
     pub fn receive(&mut self) -> Result<Rx> {
         let src: &[u8] = self.session.borrow();
@@ -109,6 +115,32 @@ where
         Ok(size)
     }
 
+    /// Send the requests seen by the process which are queued for this
+    /// session, which is monitoring requests, until the write buffer holds at
+    /// least `max_pending` bytes. Events which are left behind remain queued,
+    /// so a session which is slow to read causes the monitor to drop events
+    /// rather than the buffer growing without bound. These are not responses
+    /// to requests from the session, so they do not count towards the session
+    /// latency. Returns the number of events sent.
+    pub fn send_monitor(&mut self, observer: &Observer, max_pending: usize) -> usize {
+        let mut sent = 0;
+
+        while self.session.write_pending() < max_pending {
+            let event = match observer.try_recv() {
+                Some(event) => event,
+                None => break,
+            };
+
+            let size = self.parser.compose_monitor(&event, &mut self.session);
+            if size > 0 {
+                self.outstanding.push_back((None, size));
+            }
+            sent += 1;
+        }
+
+        sent
+    }
+
     /// Advances the read pointer for the session write buffer by `amt` bytes.
     /// This is used to mark the data as sent to the underlying session.
     pub fn advance_write(&mut self, amt: usize) {
@@ -23,9 +23,10 @@ const MAGIC: [u8; 8] = *b"PELIKAN!";
 const VERSION: u64 = 0;
 
 /// The datapool trait defines the abstraction that each datapool implementation
-/// should conform to.
+/// should conform to. Datapools are shared between threads which read from
+/// the same cache concurrently, so they must be `Sync`.
 #[allow(clippy::len_without_is_empty)]
-pub trait Datapool: Send {
+pub trait Datapool: Send + Sync {
     /// Immutable borrow of the data within the datapool
     fn as_slice(&self) -> &[u8];
 
@@ -109,5 +109,145 @@ fn set_benchmark(c: &mut Criterion) {
     }
 }
 
-criterion_group!(benches, get_benchmark, set_benchmark,);
+// Compares eviction policies with a skewed read-through workload that is
+// larger than the cache. Each miss is followed by a set of the key.
+fn eviction_benchmark(c: &mut Criterion) {
+    let ttl = Duration::ZERO;
+    let mut group = c.benchmark_group("eviction");
+    group.measurement_time(Duration::from_secs(30));
+    group.throughput(Throughput::Elements(1));
+
+    let (keys, values) = key_values(32, 1_000_000, 64, 1);
+    let value = &values[0];
+
+    // skew the accesses towards the start of the keyspace
+    let mut rng = rng();
+    let sequence: Vec<usize> = (0..keys.len())
+        .map(|_| {
+            let r = (rng.next_u64() >> 11) as f64 / (1_u64 << 53) as f64;
+            (r * r * r * keys.len() as f64) as usize
+        })
+        .collect();
+
+    let policies = [
+        ("random", Policy::Random),
+        ("fifo", Policy::Fifo),
+        (
+            "merge",
+            Policy::Merge {
+                max: 8,
+                merge: 4,
+                compact: 2,
+            },
+        ),
+        ("sieve", Policy::Sieve { max: 8 }),
+        ("s3fifo", Policy::S3Fifo { max: 8 }),
+    ];
+
+    for (name, policy) in policies {
+        let mut cache = Segcache::builder()
+            .hash_power(20)
+            .heap_size(16 * MB)
+            .segment_size(MB as i32)
+            .eviction(policy)
+            .build()
+            .expect("failed to create cache");
+
+        let mut index = 0;
+        let mut hits = 0_u64;
+        let mut total = 0_u64;
+
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let key = &keys[sequence[index]];
+                if cache.get(key).is_some() {
+                    hits += 1;
+                } else {
+                    let _ = cache.insert(key, value, None, ttl);
+                }
+                total += 1;
+                index += 1;
+                if index >= sequence.len() {
+                    index = 0;
+                }
+            })
+        });
+
+        println!(
+            "eviction/{name}: hit rate: {:.2}%",
+            100.0 * hits as f64 / total.max(1) as f64
+        );
+    }
+}
+
+// Measures the aggregate throughput of a mixed read/write workload against a
+// shared cache as the number of threads increases.
+fn concurrent_benchmark(c: &mut Criterion) {
+    let ttl = Duration::ZERO;
+    let mut group = c.benchmark_group("concurrent");
+    group.measurement_time(Duration::from_secs(30));
+    group.throughput(Throughput::Elements(1));
+
+    let (keys, values) = key_values(32, 1_000_000, 64, 1);
+    let keys = std::sync::Arc::new(keys);
+    let value = values[0].clone();
+
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+
+    let cache = Segcache::builder()
+        .hash_power(20)
+        .heap_size(256 * MB)
+        .segment_size(MB as i32)
+        .shards(threads.next_power_of_two() * 4)
+        .build_concurrent()
+        .expect("failed to create cache");
+
+    for threads in (0..).map(|n| 1 << n).take_while(|n| *n <= threads) {
+        group.bench_function(format!("{threads}t"), |b| {
+            b.iter_custom(|iters| {
+                let start = std::time::Instant::now();
+
+                let handles: Vec<_> = (0..threads)
+                    .map(|thread| {
+                        let cache = cache.clone();
+                        let keys = keys.clone();
+                        let value = value.clone();
+                        std::thread::spawn(move || {
+                            let mut index = thread * keys.len() / threads;
+                            for i in 0..(iters as usize / threads) {
+                                let key = &keys[index];
+                                // 10% writes
+                                if i % 10 == 0
+                                    || cache.get(key, |item| item.value().len()).is_none()
+                                {
+                                    let _ = cache.insert(key, &value[..], None, ttl);
+                                }
+                                index += 1;
+                                if index >= keys.len() {
+                                    index = 0;
+                                }
+                            }
+                        })
+                    })
+                    .collect();
+
+                for handle in handles {
+                    handle.join().unwrap();
+                }
+
+                start.elapsed()
+            })
+        });
+    }
+}
+
+criterion_group!(
+    benches,
+    get_benchmark,
+    set_benchmark,
+    eviction_benchmark,
+    concurrent_benchmark,
+);
 criterion_main!(benches);
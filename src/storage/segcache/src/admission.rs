@@ -0,0 +1,181 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! Frequency-based admission control for writes, based on TinyLFU.
+//!
+//! Reads and writes are recorded in a count-min sketch which estimates how
+//! often each key has been accessed. A bloom filter acts as a doorkeeper so
+//! that keys which are only seen once never reach the sketch. Once enough
+//! accesses have been recorded, the counters are halved and the doorkeeper is
+//! cleared so that the estimates favor recent history.
+//!
+//! When the cache is full, a new key is only admitted if its estimated
+//! frequency is higher than that of the item which would be evicted next.
+//!
+//! See the [TinyLFU paper](https://arxiv.org/abs/1512.00727) for more detail.
+
+use bloom::RawBloomFilter;
+
+/// The number of rows in the count-min sketch
+const SKETCH_DEPTH: usize = 4;
+
+/// Counters saturate at this value
+const COUNTER_MAX: u8 = 15;
+
+/// The number of accesses to record, as a multiple of the sketch width, before
+/// the counters are aged
+const SAMPLE_FACTOR: usize = 10;
+
+/// The admission policy used for writes into the cache.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Admission {
+    /// All writes are admitted.
+    #[default]
+    None,
+    /// Writes of new keys are admitted only if the key is estimated to be
+    /// accessed more frequently than the next item to be evicted.
+    TinyLfu,
+}
+
+/// A TinyLFU frequency sketch with a bloom filter doorkeeper.
+pub(crate) struct TinyLfu {
+    counters: Box<[u8]>,
+    width: usize,
+    doorkeeper: RawBloomFilter,
+    additions: usize,
+    sample_size: usize,
+}
+
+impl TinyLfu {
+    /// Create a new sketch which tracks frequencies for about `capacity` keys.
+    pub fn new(capacity: usize) -> Self {
+        let width = capacity.max(64).next_power_of_two();
+
+        Self {
+            counters: vec![0; width * SKETCH_DEPTH].into_boxed_slice(),
+            width,
+            doorkeeper: RawBloomFilter::new(width * 8, 4),
+            additions: 0,
+            sample_size: width * SAMPLE_FACTOR,
+        }
+    }
+
+    /// Record an access for the key with the provided hash.
+    pub fn record(&mut self, hash: u64) {
+        let (hash1, hash2) = split(hash);
+
+        if self.doorkeeper.contains(hash1, hash2) {
+            for row in 0..SKETCH_DEPTH {
+                let index = self.index(row, hash1, hash2);
+                if self.counters[index] < COUNTER_MAX {
+                    self.counters[index] += 1;
+                }
+            }
+        } else {
+            self.doorkeeper.insert(hash1, hash2);
+        }
+
+        self.additions += 1;
+        if self.additions >= self.sample_size {
+            self.age();
+        }
+    }
+
+    /// Returns the estimated access frequency for the key with the hash.
+    pub fn estimate(&self, hash: u64) -> u8 {
+        let (hash1, hash2) = split(hash);
+
+        let mut estimate = COUNTER_MAX;
+        for row in 0..SKETCH_DEPTH {
+            estimate = estimate.min(self.counters[self.index(row, hash1, hash2)]);
+        }
+
+        if self.doorkeeper.contains(hash1, hash2) {
+            estimate + 1
+        } else {
+            estimate
+        }
+    }
+
+    /// Returns true if the candidate should replace the victim.
+    pub fn admit(&self, candidate: u64, victim: u64) -> bool {
+        self.estimate(candidate) > self.estimate(victim)
+    }
+
+    // halves all the counters and clears the doorkeeper
+    fn age(&mut self) {
+        for counter in self.counters.iter_mut() {
+            *counter >>= 1;
+        }
+        self.doorkeeper.clear();
+        self.additions /= 2;
+    }
+
+    fn index(&self, row: usize, hash1: u64, hash2: u64) -> usize {
+        let hash = hash1.wrapping_add(hash2.wrapping_mul(row as u64 + 1));
+        row * self.width + (hash as usize & (self.width - 1))
+    }
+}
+
+// derives the pair of hashes used by the sketch and doorkeeper from a single
+// key hash
+fn split(hash: u64) -> (u64, u64) {
+    (
+        hash,
+        hash.rotate_left(32).wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frequency() {
+        let mut sketch = TinyLfu::new(1024);
+
+        assert_eq!(sketch.estimate(1), 0);
+
+        // the first access is absorbed by the doorkeeper
+        sketch.record(1);
+        assert_eq!(sketch.estimate(1), 1);
+
+        for _ in 0..5 {
+            sketch.record(1);
+        }
+        sketch.record(2);
+
+        assert!(sketch.estimate(1) > sketch.estimate(2));
+        assert!(sketch.admit(1, 2));
+        assert!(!sketch.admit(2, 1));
+        assert!(!sketch.admit(3, 2));
+
+        // counters saturate
+        for _ in 0..100 {
+            sketch.record(1);
+        }
+        assert_eq!(sketch.estimate(1), COUNTER_MAX + 1);
+    }
+
+    #[test]
+    fn aging() {
+        let mut sketch = TinyLfu::new(1024);
+
+        for _ in 0..9 {
+            sketch.record(1);
+        }
+        assert_eq!(sketch.estimate(1), 9);
+
+        // aging halves the counters and clears the doorkeeper
+        sketch.age();
+        assert_eq!(sketch.estimate(1), 4);
+
+        // aging happens automatically once the sample size is reached
+        let mut sketch = TinyLfu::new(64);
+        for _ in 0..(64 * SAMPLE_FACTOR) {
+            sketch.record(1);
+        }
+        assert!(sketch.estimate(1) < COUNTER_MAX);
+    }
+}
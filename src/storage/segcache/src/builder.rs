@@ -7,10 +7,23 @@
 use crate::*;
 use std::path::Path;
 
+#[cfg(feature = "metrics")]
+use std::sync::Arc;
+
 /// A builder that is used to construct a new [`Segcache`] instance.
+#[derive(Clone)]
 pub struct Builder {
     hash_power: u8,
+    hash_power_max: u8,
     overflow_factor: f64,
+    large_max: usize,
+    compression: Compression,
+    compression_threshold: usize,
+    admission: Admission,
+    shards: usize,
+    namespaces: Vec<NamespaceConfig>,
+    #[cfg(feature = "metrics")]
+    namespace_metrics: Option<Arc<NamespaceMetrics>>,
     segments_builder: SegmentsBuilder,
 }
 
@@ -19,7 +32,16 @@ impl Default for Builder {
     fn default() -> Self {
         Self {
             hash_power: 16,
+            hash_power_max: 0,
             overflow_factor: 0.0,
+            large_max: 0,
+            compression: Compression::None,
+            compression_threshold: 1024,
+            admission: Admission::None,
+            shards: 1,
+            namespaces: Vec::new(),
+            #[cfg(feature = "metrics")]
+            namespace_metrics: None,
             segments_builder: SegmentsBuilder::default(),
         }
     }
@@ -48,6 +70,26 @@ impl Builder {
         self
     }
 
+    /// Specify the largest hash power the hashtable may grow to. When the
+    /// hashtable becomes too full, it doubles in size and migrates buckets to
+    /// the new table incrementally as items are accessed. By default, this is
+    /// the same as the hash power and the hashtable does not grow.
+    ///
+    /// ```
+    /// use segcache::Segcache;
+    ///
+    /// // create a cache with a hashtable that starts with room for ~114k
+    /// // items and may grow to hold ~1.8M items
+    /// let cache = Segcache::builder()
+    ///     .hash_power(17)
+    ///     .hash_power_max(21)
+    ///     .build();
+    /// ```
+    pub fn hash_power_max(mut self, hash_power: u8) -> Self {
+        self.hash_power_max = hash_power;
+        self
+    }
+
     /// Specify an overflow factor which is used to scale the hashtable and
     /// provide additional capacity for chaining item buckets. A factor of 1.0
     /// will result in a hash table that is 100% larger.
@@ -117,6 +159,80 @@ impl Builder {
         self
     }
 
+    /// Specify the largest value, in bytes, which may be stored. Values which
+    /// do not fit within a single segment are split into chunks which are
+    /// stored across multiple segments. The default of zero disables this and
+    /// limits items to the segment size.
+    ///
+    /// ```
+    /// use segcache::Segcache;
+    /// use std::time::Duration;
+    ///
+    /// const MB: usize = 1024 * 1024;
+    ///
+    /// // create a cache using 1MB segments which accepts values up to 16MB
+    /// let mut cache = Segcache::builder()
+    ///     .heap_size(64 * MB)
+    ///     .segment_size(MB as i32)
+    ///     .large_item_max(16 * MB)
+    ///     .build()
+    ///     .expect("failed to create cache");
+    ///
+    /// let value = vec![0; 4 * MB];
+    /// assert!(cache.insert(b"large", &value[..], None, Duration::ZERO).is_ok());
+    /// ```
+    pub fn large_item_max(mut self, bytes: usize) -> Self {
+        self.large_max = bytes;
+        self
+    }
+
+    /// Specify the codec used to compress values. Values are compressed on
+    /// insert and decompressed on get. Values which do not become smaller are
+    /// stored uncompressed.
+    ///
+    /// ```
+    /// use segcache::{Compression, Segcache};
+    /// use std::time::Duration;
+    ///
+    /// let mut cache = Segcache::builder()
+    ///     .compression(Compression::Zstd { level: 3 })
+    ///     .compression_threshold(64)
+    ///     .build()
+    ///     .expect("failed to create cache");
+    ///
+    /// let value = "coffee".repeat(100);
+    /// cache.insert(b"drink", value.as_str(), None, Duration::ZERO);
+    /// let item = cache.get(b"drink").expect("didn't get item back");
+    /// assert_eq!(item.value(), value.as_bytes()[..]);
+    /// ```
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Specify the size, in bytes, at which values become eligible for
+    /// compression. Small values rarely compress well, so the default is 1KB.
+    pub fn compression_threshold(mut self, bytes: usize) -> Self {
+        self.compression_threshold = bytes;
+        self
+    }
+
+    /// Specify the admission policy for writes. With `Admission::TinyLfu`,
+    /// once the cache is full a new key is only stored if it is estimated to
+    /// be accessed more often than the item it would displace. This protects
+    /// the cache from being flushed by keys which are only written once.
+    /// Rejected writes return [`SegcacheError::Rejected`].
+    ///
+    /// ```
+    /// use segcache::{Admission, Segcache};
+    ///
+    /// let cache = Segcache::builder().admission(Admission::TinyLfu).build();
+    /// ```
+    pub fn admission(mut self, admission: Admission) -> Self {
+        self.admission = admission;
+        self
+    }
+
     /// Specify the eviction policy to be used. See the `Policy` documentation
     /// for more details about each strategy.
     ///
@@ -145,6 +261,220 @@ impl Builder {
         self
     }
 
+    /// Specify a file to be used as a second tier of segment storage, typically
+    /// on local flash. When a segment is evicted from memory, its live items
+    /// are moved to this file instead of being discarded, and items which are
+    /// read from it are promoted back into memory. Segments are evicted from
+    /// the file in FIFO order.
+    ///
+    /// Only the policies which evict whole segments (`Random`, `RandomFifo`,
+    /// `Fifo`, `Cte`, and `Util`) demote segments to flash. The tier is
+    /// disabled unless a flash size is also provided.
+    ///
+    /// # Panics
+    ///
+    /// This will panic if the file already exists
+    pub fn flash_path<T: AsRef<Path>>(mut self, path: Option<T>) -> Self {
+        self.segments_builder = self.segments_builder.flash_path(path);
+        self
+    }
+
+    /// Specify the size of the flash tier in bytes. The size will be divided
+    /// by the segment size to determine the number of flash segments.
+    ///
+    /// ```
+    /// use segcache::{Policy, Segcache};
+    ///
+    /// const MB: usize = 1024 * 1024;
+    ///
+    /// let path = std::env::temp_dir().join(format!("segcache-doc-{}", std::process::id()));
+    ///
+    /// // create a cache with 16MB of memory backed by 256MB of flash
+    /// let cache = Segcache::builder()
+    ///     .heap_size(16 * MB)
+    ///     .eviction(Policy::Fifo)
+    ///     .flash_path(Some(&path))
+    ///     .flash_size(256 * MB)
+    ///     .build();
+    ///
+    /// let _ = std::fs::remove_file(&path);
+    /// ```
+    pub fn flash_size(mut self, bytes: usize) -> Self {
+        self.segments_builder = self.segments_builder.flash_size(bytes);
+        self
+    }
+
+    /// Specify the number of shards used by a [`ConcurrentSegcache`]. The heap
+    /// and hashtable are divided evenly between the shards. This has no effect
+    /// when building a `Segcache`.
+    ///
+    /// ```
+    /// use segcache::Segcache;
+    ///
+    /// const MB: usize = 1024 * 1024;
+    ///
+    /// // create a cache with four 16MB shards
+    /// let cache = Segcache::builder()
+    ///     .heap_size(64 * MB)
+    ///     .shards(4)
+    ///     .build_concurrent();
+    /// ```
+    pub fn shards(mut self, shards: usize) -> Self {
+        assert!(shards > 0, "at least one shard is required");
+        self.shards = shards;
+        self
+    }
+
+    /// Add a namespace which holds the keys that start with the prefix. The
+    /// namespace receives its own shards, with `heap_size` bytes of the heap
+    /// divided between them, so writes to the namespace only evict items from
+    /// the same namespace. Keys are assigned to the namespace with the longest
+    /// matching prefix, and keys which match no prefix are held in the default
+    /// namespace, which receives the remainder of the heap. This has no effect
+    /// when building a `Segcache`.
+    ///
+    /// ```
+    /// use segcache::Segcache;
+    /// use std::time::Duration;
+    ///
+    /// const MB: usize = 1024 * 1024;
+    ///
+    /// // create a cache where sessions may use at most 16MB of the 64MB heap
+    /// let cache = Segcache::builder()
+    ///     .heap_size(64 * MB)
+    ///     .namespace("sessions", b"session:", 16 * MB)
+    ///     .build_concurrent()
+    ///     .expect("failed to create cache");
+    ///
+    /// assert!(cache.insert(b"session:42", b"active", None, Duration::ZERO).is_ok());
+    /// assert_eq!(cache.namespace(b"session:42"), "sessions");
+    /// assert_eq!(cache.namespace(b"user:42"), "default");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This will panic if the prefix is empty.
+    pub fn namespace(mut self, name: &str, prefix: &[u8], heap_size: usize) -> Self {
+        assert!(!prefix.is_empty(), "namespace prefix must not be empty");
+        self.namespaces.push(NamespaceConfig {
+            name: name.to_string(),
+            prefix: prefix.into(),
+            heap_size,
+        });
+        self
+    }
+
+    /// Consumes the builder and returns a `ConcurrentSegcache` which may be
+    /// shared between threads. If a datapool or flash path is provided, each
+    /// shard is backed by its own file which has the shard index appended to
+    /// the path.
+    ///
+    /// Returns an error if the namespaces do not leave room for at least one
+    /// segment per shard in every namespace, including the default namespace.
+    pub fn build_concurrent(self) -> Result<ConcurrentSegcache, std::io::Error> {
+        let shards = self.shards;
+        let heap_size = self.segments_builder.heap_size;
+        let flash_size = self.segments_builder.flash_size;
+
+        // the default namespace receives whatever is not reserved by the
+        // other namespaces
+        let reserved: usize = self.namespaces.iter().map(|ns| ns.heap_size).sum();
+        let mut namespaces = vec![NamespaceConfig {
+            name: DEFAULT_NAMESPACE.to_string(),
+            prefix: Box::new([]),
+            heap_size: heap_size.saturating_sub(reserved),
+        }];
+        namespaces.extend(self.namespaces.iter().cloned());
+
+        // without namespaces, the cache is built exactly as configured
+        if !self.namespaces.is_empty() {
+            for (idx, namespace) in namespaces.iter().enumerate() {
+                if namespaces[..idx]
+                    .iter()
+                    .any(|other| other.name == namespace.name || other.prefix == namespace.prefix)
+                {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!("duplicate namespace: {}", namespace.name),
+                    ));
+                }
+                if namespace.heap_size / shards < self.segments_builder.segment_size as usize {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!(
+                            "namespace {} requires at least one segment per shard",
+                            namespace.name
+                        ),
+                    ));
+                }
+            }
+        }
+
+        let total_shards = shards * namespaces.len();
+
+        let mut caches = Vec::with_capacity(total_shards);
+        let mut ranges = Vec::with_capacity(namespaces.len());
+        for namespace in namespaces {
+            // each namespace gets a share of the hashtable and flash which is
+            // proportional to its share of the heap, divided evenly between
+            // its shards
+            let parts = heap_size.div_ceil(namespace.heap_size.max(1)) * shards;
+            let bits = parts.next_power_of_two().trailing_zeros() as u8;
+            let hash_power = self.hash_power.saturating_sub(bits).max(3);
+            let hash_power_max = self.hash_power_max.saturating_sub(bits).max(3);
+            let shard_heap_size = namespace.heap_size / shards;
+            let shard_flash_size = (flash_size as u128 * namespace.heap_size as u128
+                / heap_size as u128) as usize
+                / shards;
+
+            // metrics are only broken down when there are namespaces
+            #[cfg(feature = "metrics")]
+            let metrics = if self.namespaces.is_empty() {
+                None
+            } else {
+                Some(Arc::new(NamespaceMetrics::new(&namespace.name)))
+            };
+
+            let start = caches.len();
+            for _ in 0..shards {
+                let shard = caches.len();
+                let mut builder = self.clone();
+                builder.hash_power = hash_power;
+                builder.hash_power_max = hash_power_max;
+                #[cfg(feature = "metrics")]
+                {
+                    builder.namespace_metrics = metrics.clone();
+                }
+                builder.segments_builder = builder
+                    .segments_builder
+                    .heap_size(shard_heap_size)
+                    .flash_size(shard_flash_size);
+                if total_shards > 1 {
+                    if let Some(path) = self.segments_builder.datapool_path.as_ref() {
+                        let mut path = path.clone().into_os_string();
+                        path.push(format!(".{shard}"));
+                        builder.segments_builder =
+                            builder.segments_builder.datapool_path(Some(path));
+                    }
+                    if let Some(path) = self.segments_builder.flash_path.as_ref() {
+                        let mut path = path.clone().into_os_string();
+                        path.push(format!(".{shard}"));
+                        builder.segments_builder = builder.segments_builder.flash_path(Some(path));
+                    }
+                }
+                caches.push(builder.build()?);
+            }
+
+            ranges.push(Namespace::new(
+                namespace.name,
+                namespace.prefix,
+                start..caches.len(),
+            ));
+        }
+
+        Ok(ConcurrentSegcache::new(caches, ranges))
+    }
+
     /// Consumes the builder and returns a fully-allocated `Segcache` instance.
     ///
     /// ```
@@ -159,15 +489,39 @@ impl Builder {
     ///     .eviction(Policy::Random).build();
     /// ```
     pub fn build(self) -> Result<Segcache, std::io::Error> {
-        let hashtable = HashTable::new(self.hash_power, self.overflow_factor);
+        let hashtable = HashTable::new(self.hash_power, self.overflow_factor, self.hash_power_max);
         let segments = self.segments_builder.build()?;
         let ttl_buckets = TtlBuckets::default();
 
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = self.namespace_metrics.as_ref() {
+            metrics.segment_current.add(segments.cap() as i64);
+            metrics.segment_free.add(segments.free() as i64);
+        }
+
+        // leave room for the segment magic, the item header, the chunk key,
+        // and item padding so that each chunk fits within a single segment
+        let chunk_size = (segments.segment_size() as usize)
+            .saturating_sub(8 + ITEM_HDR_SIZE + CHUNK_KEY_LEN + 8)
+            .min((1 << 24) - 1)
+            & !7;
+
         Ok(Segcache {
             hashtable,
             segments,
             ttl_buckets,
-            time: Instant::now(),
+            time: clock::now(),
+            large_max: if chunk_size > 0 { self.large_max } else { 0 },
+            chunk_size,
+            next_large_id: thread_rng().gen::<u64>(),
+            compression: self.compression,
+            compression_threshold: self.compression_threshold,
+            admission: match self.admission {
+                Admission::None => None,
+                Admission::TinyLfu => Some(Box::new(TinyLfu::new(1 << self.hash_power))),
+            },
+            #[cfg(feature = "metrics")]
+            namespace: self.namespace_metrics,
         })
     }
 }
@@ -0,0 +1,74 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! The source of time for item expiration, segment ages and eviction.
+//!
+//! This is the system clock unless the `simulator` feature is enabled. Then,
+//! once `advance_clock` has been called, the process switches to a virtual
+//! clock which only moves forward when it is advanced. This allows a trace to
+//! be replayed through the cache faster than real time while expiration still
+//! follows the timing of the trace. The virtual clock is shared by every cache
+//! in the process.
+
+use crate::Instant;
+
+#[cfg(feature = "simulator")]
+use crate::Duration;
+#[cfg(feature = "simulator")]
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+#[cfg(feature = "simulator")]
+use std::sync::OnceLock;
+
+#[cfg(feature = "simulator")]
+static SIMULATED: AtomicBool = AtomicBool::new(false);
+
+// the start of the virtual clock and the seconds elapsed since then
+#[cfg(feature = "simulator")]
+static START: OnceLock<Instant> = OnceLock::new();
+#[cfg(feature = "simulator")]
+static ELAPSED: AtomicU32 = AtomicU32::new(0);
+
+#[cfg(not(feature = "simulator"))]
+#[inline]
+pub(crate) fn now() -> Instant {
+    Instant::now()
+}
+
+#[cfg(feature = "simulator")]
+#[inline]
+pub(crate) fn now() -> Instant {
+    if !SIMULATED.load(Ordering::Acquire) {
+        return Instant::now();
+    }
+
+    let start = *START.get_or_init(Instant::now);
+    start + Duration::from_secs(ELAPSED.load(Ordering::Relaxed))
+}
+
+/// Switches to the virtual clock and advances it so that `elapsed` has passed
+/// since it was first advanced. The clock never moves backwards, so advancing
+/// it to an earlier time has no effect. Caches observe the new time on their
+/// next call to `expire()`.
+///
+/// ```
+/// use segcache::Segcache;
+/// use std::time::Duration;
+///
+/// let mut cache = Segcache::builder().build().expect("failed to create cache");
+///
+/// cache.insert(b"coffee", b"strong", None, Duration::from_secs(5));
+///
+/// segcache::advance_clock(Duration::from_secs(6));
+/// cache.expire();
+///
+/// assert!(cache.get(b"coffee").is_none());
+/// ```
+#[cfg(feature = "simulator")]
+pub fn advance_clock(elapsed: std::time::Duration) {
+    START.get_or_init(Instant::now);
+
+    let elapsed = elapsed.as_secs().min(u32::MAX as u64) as u32;
+    ELAPSED.fetch_max(elapsed, Ordering::Relaxed);
+    SIMULATED.store(true, Ordering::Release);
+}
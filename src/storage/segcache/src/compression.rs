@@ -0,0 +1,113 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! Optional compression of item values.
+//!
+//! Compressed values are marked with a flag bit in the item header. The stored
+//! value begins with a single byte which identifies the codec, followed by the
+//! compressed bytes. This allows values written with one codec to be read back
+//! after the cache has been reconfigured to use another.
+
+#[cfg(feature = "metrics")]
+use crate::*;
+
+const CODEC_LZ4: u8 = 1;
+const CODEC_ZSTD: u8 = 2;
+
+/// The codec which is used to compress item values.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Compression {
+    /// Values are stored uncompressed
+    #[default]
+    None,
+    /// Values are compressed with LZ4, which favors speed over ratio
+    Lz4,
+    /// Values are compressed with Zstandard at the provided level
+    Zstd { level: i32 },
+}
+
+impl Compression {
+    /// Compress the value, returning `None` if compression is disabled or would
+    /// not reduce the size of the value.
+    pub(crate) fn compress(&self, value: &[u8]) -> Option<Vec<u8>> {
+        #[cfg(feature = "metrics")]
+        let start = std::time::Instant::now();
+
+        let (codec, compressed) = match self {
+            Self::None => {
+                return None;
+            }
+            Self::Lz4 => (CODEC_LZ4, lz4_flex::compress_prepend_size(value)),
+            Self::Zstd { level } => (CODEC_ZSTD, zstd::bulk::compress(value, *level).ok()?),
+        };
+
+        #[cfg(feature = "metrics")]
+        COMPRESS_TIME.add(start.elapsed().as_nanos() as _);
+
+        if compressed.len() + 1 >= value.len() {
+            return None;
+        }
+
+        let mut result = Vec::with_capacity(compressed.len() + 1);
+        result.push(codec);
+        result.extend_from_slice(&compressed);
+
+        #[cfg(feature = "metrics")]
+        {
+            ITEM_COMPRESS.increment();
+            ITEM_COMPRESS_BYTES_IN.add(value.len() as _);
+            ITEM_COMPRESS_BYTES_OUT.add(result.len() as _);
+        }
+
+        Some(result)
+    }
+}
+
+/// Decompress a value which was produced by [`Compression::compress`].
+/// Returns `None` if the value is corrupt or uses an unknown codec.
+pub(crate) fn decompress(value: &[u8]) -> Option<Vec<u8>> {
+    #[cfg(feature = "metrics")]
+    let start = std::time::Instant::now();
+
+    let (codec, compressed) = value.split_first()?;
+    let result = match *codec {
+        CODEC_LZ4 => lz4_flex::decompress_size_prepended(compressed).ok(),
+        CODEC_ZSTD => zstd::stream::decode_all(compressed).ok(),
+        _ => None,
+    };
+
+    #[cfg(feature = "metrics")]
+    {
+        DECOMPRESS_TIME.add(start.elapsed().as_nanos() as _);
+        if result.is_none() {
+            ITEM_DECOMPRESS_EX.increment();
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let value = br#"{"name":"coffee","strength":"strong","name":"coffee"}"#.repeat(8);
+
+        for codec in [Compression::Lz4, Compression::Zstd { level: 3 }] {
+            let compressed = codec.compress(&value).expect("failed to compress");
+            assert!(compressed.len() < value.len());
+            assert_eq!(
+                decompress(&compressed).expect("failed to decompress"),
+                value
+            );
+        }
+
+        // incompressible or disabled
+        assert!(Compression::Lz4.compress(b"a").is_none());
+        assert!(Compression::None.compress(&value).is_none());
+        assert!(decompress(&[0, 1, 2]).is_none());
+    }
+}
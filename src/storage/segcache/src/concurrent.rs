@@ -0,0 +1,556 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! A thread-safe variant of [`Segcache`] which may be shared by many threads.
+//!
+//! The keyspace is partitioned into a number of shards, each of which is an
+//! independent `Segcache` with its own hashtable, segments, and TTL buckets,
+//! protected by its own lock. Operations on different shards proceed in
+//! parallel, and writes only allocate from the segments which belong to the
+//! shard for the key, so there is no global allocator to contend on.
+//!
+//! Reads take their shard lock in shared mode, so any number of threads may
+//! read from the same shard in parallel. Items borrow directly from segment
+//! memory, so [`ConcurrentSegcache::get`] passes the item to a closure while
+//! the lock is held and returns whatever the closure produces. Callers should
+//! copy what they need out of the item within the closure, and build their
+//! response after it returns, once the lock has been released.
+//!
+//! A read in [`Segcache`] is not a pure lookup: it updates the item frequency
+//! and the admission policy, may migrate hashtable buckets during a resize,
+//! and may copy an item back from flash. Shared reads only perform the lookup.
+//! The accesses they record are queued on the shard and applied by the next
+//! thread which locks the shard exclusively. The queue is bounded, and
+//! accesses which arrive while it is full are dropped, which only makes the
+//! frequency estimates less precise. Reads of items in the flash tier, of
+//! large items, and of values which cannot be decompressed take the exclusive
+//! lock instead, as do all writes.
+//!
+//! Segments are still allocated per shard rather than per thread, and there is
+//! no epoch-based reclamation of evicted segments. No reference into segment
+//! memory outlives the shard lock, so a segment may be reused as soon as the
+//! writer which evicted it releases the lock. Writers to the same shard are
+//! serialized, and configuring several shards per worker thread keeps the
+//! chance that two writers contend on the same lock low.
+//!
+//! Commands which read a key and then write it back, such as an append, must
+//! hold the lock for the whole operation so that they do not lose concurrent
+//! updates. Use [`ConcurrentSegcache::lock`] for a single key, or
+//! [`ConcurrentSegcache::lock_many`] for commands which span several keys.
+//!
+//! When namespaces are configured, each namespace owns a range of the shards
+//! and a key is hashed to one of the shards of the namespace which matches
+//! its prefix. See [`Builder::namespace`].
+
+use crate::segcache::{Access, Lookup};
+use crate::snapshot::{verify, SnapshotReader, SnapshotWriter, SNAPSHOT_BATCH};
+use crate::*;
+
+use ahash::RandomState;
+use crossbeam_channel::{Receiver, Sender};
+use parking_lot::{RwLock, RwLockWriteGuard};
+
+use std::collections::BTreeMap;
+use std::ops::Deref;
+use std::sync::Arc;
+
+// seeds for the shard hasher, which are distinct from the hashtable seeds so
+// that the shard selection is independent of the bucket selection
+const SHARD_SEEDS: [u64; 4] = [
+    0x8c8b_0a9e_5f3d_2c71,
+    0x1f4e_6b2a_9d07_c3e5,
+    0x6a3c_d9f1_0b85_7e24,
+    0xe5b7_2f60_c14a_983d,
+];
+
+// the number of reads served under the shared lock which may be queued on a
+// shard before further accesses are dropped
+pub(crate) const ACCESS_QUEUE_DEPTH: usize = 1024;
+
+/// A sharded, thread-safe [`Segcache`]. Cloning a `ConcurrentSegcache` is
+/// cheap and returns a handle to the same underlying storage.
+#[derive(Clone)]
+pub struct ConcurrentSegcache {
+    shards: Arc<[Shard]>,
+    // the default namespace is first, followed by those with a prefix
+    namespaces: Arc<[Namespace]>,
+    hash_builder: RandomState,
+    max_item_size: usize,
+}
+
+/// A single shard, along with the accesses from shared reads which have not
+/// yet been applied to it.
+struct Shard {
+    cache: RwLock<Segcache>,
+    accesses: (Sender<Access>, Receiver<Access>),
+}
+
+impl Shard {
+    fn new(cache: Segcache) -> Self {
+        Self {
+            cache: RwLock::new(cache),
+            accesses: crossbeam_channel::bounded(ACCESS_QUEUE_DEPTH),
+        }
+    }
+
+    /// Locks the shard exclusively, applying any queued accesses.
+    fn write(&self) -> RwLockWriteGuard<'_, Segcache> {
+        let mut cache = self.cache.write();
+        self.apply(&mut cache);
+        cache
+    }
+
+    /// Locks the shard exclusively if no other thread holds the lock.
+    fn try_write(&self) -> Option<RwLockWriteGuard<'_, Segcache>> {
+        let mut cache = self.cache.try_write()?;
+        self.apply(&mut cache);
+        Some(cache)
+    }
+
+    fn apply(&self, cache: &mut Segcache) {
+        for access in self.accesses.1.try_iter() {
+            cache.access(access);
+        }
+    }
+
+    /// Queues an access from a shared read, dropping it if the queue is full.
+    fn record(&self, access: Access) {
+        let _ = self.accesses.0.try_send(access);
+    }
+}
+
+/// An [`Item`] which was updated in a [`ConcurrentSegcache`]. The shard which
+/// holds the item remains locked exclusively until the guard is dropped.
+pub struct ItemGuard<'a> {
+    // NOTE: the item borrows from segment memory owned by the shard, so it is
+    // declared first to ensure it is dropped before the lock is released
+    item: Item,
+    _shard: RwLockWriteGuard<'a, Segcache>,
+}
+
+/// A set of shards which are locked together. See
+/// [`ConcurrentSegcache::lock_many`].
+pub struct ShardsGuard<'a> {
+    cache: &'a ConcurrentSegcache,
+    // sorted by the shard index
+    shards: Vec<(usize, RwLockWriteGuard<'a, Segcache>)>,
+}
+
+impl ShardsGuard<'_> {
+    /// Returns the locked shard which holds the key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shard for the key is not one of those which were locked.
+    pub fn shard(&mut self, key: &[u8]) -> &mut Segcache {
+        let index = self.cache.shard(key);
+        let position = self
+            .shards
+            .binary_search_by_key(&index, |(index, _)| *index)
+            .expect("the shard for the key is not locked");
+        &mut self.shards[position].1
+    }
+}
+
+impl Deref for ItemGuard<'_> {
+    type Target = Item;
+
+    fn deref(&self) -> &Item {
+        &self.item
+    }
+}
+
+impl ConcurrentSegcache {
+    pub(crate) fn new(shards: Vec<Segcache>, namespaces: Vec<Namespace>) -> Self {
+        assert!(!shards.is_empty(), "at least one shard is required");
+
+        // every shard is built with the same segment size and item limit
+        let max_item_size = shards[0].max_item_size();
+
+        Self {
+            shards: shards.into_iter().map(Shard::new).collect(),
+            namespaces: namespaces.into(),
+            hash_builder: RandomState::with_seeds(
+                SHARD_SEEDS[0],
+                SHARD_SEEDS[1],
+                SHARD_SEEDS[2],
+                SHARD_SEEDS[3],
+            ),
+            max_item_size,
+        }
+    }
+
+    /// Returns the total number of shards across all namespaces.
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Returns the names of the namespaces, starting with the default
+    /// namespace.
+    pub fn namespaces(&self) -> impl Iterator<Item = &str> {
+        self.namespaces.iter().map(|namespace| namespace.name())
+    }
+
+    /// Returns an upper bound on the size of a value which may be stored. See
+    /// [`Segcache::max_item_size`].
+    pub fn max_item_size(&self) -> usize {
+        self.max_item_size
+    }
+
+    /// Returns the name of the namespace which holds the key.
+    pub fn namespace(&self, key: &[u8]) -> &str {
+        self.namespace_for(key).name()
+    }
+
+    /// Lock and return the shard which holds the key. This allows performing
+    /// several operations on a key atomically with respect to other threads.
+    ///
+    /// ```
+    /// use segcache::Segcache;
+    /// use std::time::Duration;
+    ///
+    /// let cache = Segcache::builder()
+    ///     .shards(4)
+    ///     .build_concurrent()
+    ///     .expect("failed to create cache");
+    ///
+    /// let mut shard = cache.lock(b"coffee");
+    /// if shard.get_no_freq_incr(b"coffee").is_none() {
+    ///     assert!(shard.insert(b"coffee", b"strong", None, Duration::ZERO).is_ok());
+    /// }
+    /// ```
+    pub fn lock(&self, key: &[u8]) -> RwLockWriteGuard<'_, Segcache> {
+        self.shards[self.shard(key)].write()
+    }
+
+    /// Lock the shards which hold any of the keys, so that several keys may be
+    /// updated atomically with respect to other threads. Shards are always
+    /// locked in the same order, so threads which lock overlapping sets of
+    /// shards cannot deadlock. As with [`lock`](Self::lock), the thread must
+    /// not perform any other operation on the cache until the guard is
+    /// dropped.
+    ///
+    /// ```
+    /// use segcache::Segcache;
+    /// use std::time::Duration;
+    ///
+    /// let cache = Segcache::builder()
+    ///     .shards(4)
+    ///     .build_concurrent()
+    ///     .expect("failed to create cache");
+    ///
+    /// let keys: [&[u8]; 2] = [b"coffee", b"tea"];
+    /// let mut shards = cache.lock_many(keys);
+    /// for key in keys {
+    ///     assert!(shards.shard(key).insert(key, b"hot", None, Duration::ZERO).is_ok());
+    /// }
+    /// ```
+    pub fn lock_many<'k>(&self, keys: impl IntoIterator<Item = &'k [u8]>) -> ShardsGuard<'_> {
+        let mut indices: Vec<usize> = keys.into_iter().map(|key| self.shard(key)).collect();
+        indices.sort_unstable();
+        indices.dedup();
+
+        ShardsGuard {
+            cache: self,
+            shards: indices
+                .into_iter()
+                .map(|index| (index, self.shards[index].write()))
+                .collect(),
+        }
+    }
+
+    /// Get the item with the provided key, increasing its frequency. The item
+    /// is passed to the closure while the shard is locked, and the result of
+    /// the closure is returned once the lock has been released. The closure
+    /// must not perform any other operation on the cache.
+    ///
+    /// ```
+    /// use segcache::Segcache;
+    /// use std::time::Duration;
+    ///
+    /// let cache = Segcache::builder()
+    ///     .shards(4)
+    ///     .build_concurrent()
+    ///     .expect("failed to create cache");
+    ///
+    /// assert!(cache.insert(b"coffee", b"strong", None, Duration::ZERO).is_ok());
+    ///
+    /// let cache = cache.clone();
+    /// std::thread::spawn(move || {
+    ///     let strong = cache.get(b"coffee", |item| item.value() == b"strong");
+    ///     assert_eq!(strong, Some(true));
+    /// })
+    /// .join()
+    /// .unwrap();
+    /// ```
+    pub fn get<R>(&self, key: &[u8], f: impl FnOnce(&Item) -> R) -> Option<R> {
+        let shard = &self.shards[self.shard(key)];
+
+        {
+            let cache = shard.cache.read();
+            match cache.lookup(key) {
+                Lookup::Hit(item, access) => {
+                    let result = f(&item);
+                    cache.count_get(true);
+                    shard.record(access);
+                    return Some(result);
+                }
+                Lookup::Miss(access) => {
+                    cache.count_get(false);
+                    shard.record(access);
+                    return None;
+                }
+                Lookup::Exclusive => {}
+            }
+        }
+
+        let item = shard.write().get(key).map(|item| f(&item));
+        item
+    }
+
+    /// Get the item with the provided key without increasing its frequency.
+    /// See [`get`](Self::get).
+    pub fn get_no_freq_incr<R>(&self, key: &[u8], f: impl FnOnce(&Item) -> R) -> Option<R> {
+        let shard = &self.shards[self.shard(key)];
+
+        {
+            let cache = shard.cache.read();
+            match cache.lookup(key) {
+                Lookup::Hit(item, _) => {
+                    return Some(f(&item));
+                }
+                Lookup::Miss(_) => {
+                    return None;
+                }
+                Lookup::Exclusive => {}
+            }
+        }
+
+        let item = shard.write().get_no_freq_incr(key).map(|item| f(&item));
+        item
+    }
+
+    /// Insert a new item. See [`Segcache::insert`].
+    pub fn insert<'a, T: Into<Value<'a>>>(
+        &self,
+        key: &'a [u8],
+        value: T,
+        optional: Option<&[u8]>,
+        ttl: std::time::Duration,
+    ) -> Result<(), SegcacheError> {
+        self.lock(key).insert(key, value, optional, ttl)
+    }
+
+    /// Perform a compare-and-swap. See [`Segcache::cas`].
+    pub fn cas<'a, T: Into<Value<'a>>>(
+        &self,
+        key: &'a [u8],
+        value: T,
+        optional: Option<&[u8]>,
+        ttl: std::time::Duration,
+        cas: u32,
+    ) -> Result<(), SegcacheError> {
+        self.lock(key).cas(key, value, optional, ttl, cas)
+    }
+
+    /// Remove the item with the provided key. See [`Segcache::delete`].
+    pub fn delete(&self, key: &[u8]) -> bool {
+        self.lock(key).delete(key)
+    }
+
+    /// Returns the remaining TTL for the key. See [`Segcache::ttl`].
+    pub fn ttl(&self, key: &[u8]) -> Result<Option<std::time::Duration>, SegcacheError> {
+        self.lock(key).ttl(key)
+    }
+
+    /// Update the TTL for the key. See [`Segcache::set_ttl`].
+    pub fn set_ttl(&self, key: &[u8], ttl: std::time::Duration) -> Result<(), SegcacheError> {
+        self.lock(key).set_ttl(key, ttl)
+    }
+
+    /// Increment a numeric value. See [`Segcache::wrapping_add`].
+    pub fn wrapping_add(&self, key: &[u8], rhs: u64) -> Result<ItemGuard<'_>, SegcacheError> {
+        let mut shard = self.lock(key);
+        let item = shard.wrapping_add(key, rhs)?;
+        Ok(ItemGuard {
+            item,
+            _shard: shard,
+        })
+    }
+
+    /// Decrement a numeric value. See [`Segcache::saturating_sub`].
+    pub fn saturating_sub(&self, key: &[u8], rhs: u64) -> Result<ItemGuard<'_>, SegcacheError> {
+        let mut shard = self.lock(key);
+        let item = shard.saturating_sub(key, rhs)?;
+        Ok(ItemGuard {
+            item,
+            _shard: shard,
+        })
+    }
+
+    /// Expire segments in every shard. Shards which are currently locked by
+    /// another thread are skipped, since expiration is only an optimization
+    /// and will be retried on a later call. Returns the number of segments
+    /// which were expired.
+    pub fn expire(&self) -> usize {
+        self.shards
+            .iter()
+            .filter_map(|shard| shard.try_write())
+            .map(|mut shard| shard.expire())
+            .sum()
+    }
+
+    /// Remove all items from every shard. Returns the number of segments
+    /// which were cleared.
+    pub fn clear(&self) -> usize {
+        self.shards.iter().map(|shard| shard.write().clear()).sum()
+    }
+
+    /// Returns metadata for up to `count` live items, starting from the cursor,
+    /// along with the cursor from which to resume. Shards are scanned in turn,
+    /// with the shard index held in the low bits of the cursor. As with
+    /// [`Segcache::scan`], a cursor of zero starts and ends the scan.
+    pub fn scan(&self, cursor: u64, count: usize) -> (u64, Vec<KeyInfo>) {
+        let bits = self.shards.len().next_power_of_two().trailing_zeros();
+        let mask = (1 << bits) - 1;
+
+        let mut shard = (cursor & mask) as usize;
+        let mut inner = cursor >> bits;
+        let mut items = Vec::new();
+
+        while shard < self.shards.len() && items.len() < count {
+            let (next, mut batch) = self.shards[shard].write().scan(inner, count - items.len());
+            items.append(&mut batch);
+
+            if next == 0 {
+                shard += 1;
+                inner = 0;
+            } else {
+                inner = next;
+            }
+        }
+
+        if shard < self.shards.len() {
+            ((inner << bits) | shard as u64, items)
+        } else {
+            (0, items)
+        }
+    }
+
+    /// Returns the occupancy of each TTL bucket, combined across every shard.
+    /// Each shard is locked in turn while its segments are walked. See
+    /// [`Segcache::ttl_stats`].
+    pub fn ttl_stats(&self) -> Vec<TtlStats> {
+        let mut buckets: BTreeMap<usize, TtlStats> = BTreeMap::new();
+
+        for shard in self.shards.iter() {
+            for stats in shard.write().ttl_stats() {
+                match buckets.get_mut(&stats.bucket()) {
+                    Some(entry) => entry.merge(&stats),
+                    None => {
+                        buckets.insert(stats.bucket(), stats);
+                    }
+                }
+            }
+        }
+
+        buckets.into_values().collect()
+    }
+
+    /// Returns a summary of the segments, combined across every shard. See
+    /// [`Segcache::segment_stats`].
+    pub fn segment_stats(&self) -> SegmentStats {
+        let mut stats = SegmentStats::default();
+        for shard in self.shards.iter() {
+            stats.merge(&shard.write().segment_stats());
+        }
+        stats
+    }
+
+    /// Writes the live items from every shard to a portable snapshot. Each
+    /// shard is only locked while reading a batch of keys or a single item, so
+    /// the cache may be written concurrently, with the same weak guarantees as
+    /// a scan. See [`Segcache::save_snapshot`].
+    pub fn save_snapshot<W: std::io::Write>(&self, writer: W) -> std::io::Result<usize> {
+        let mut snapshot = SnapshotWriter::new(writer)?;
+
+        let mut cursor = 0;
+        loop {
+            let (next, keys) = self.scan(cursor, SNAPSHOT_BATCH);
+            for info in keys {
+                if let Some(result) =
+                    self.get_no_freq_incr(info.key(), |item| snapshot.item(item, info.ttl()))
+                {
+                    result?;
+                }
+            }
+
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+
+        snapshot.finish()
+    }
+
+    /// Inserts the items from a snapshot into the shards which own their keys.
+    /// See [`Segcache::load_snapshot`].
+    pub fn load_snapshot<R: std::io::Read + std::io::Seek>(
+        &self,
+        mut reader: R,
+    ) -> std::io::Result<usize> {
+        verify(&mut reader)?;
+        let mut snapshot = SnapshotReader::new(reader)?;
+
+        let mut loaded = 0;
+        while let Some(item) = snapshot.next_item()? {
+            let ttl = match item.ttl() {
+                Some(ttl) => ttl,
+                None => continue,
+            };
+
+            if self
+                .insert(item.key(), item.value(), item.optional(), ttl)
+                .is_ok()
+            {
+                loaded += 1;
+            }
+        }
+
+        Ok(loaded)
+    }
+
+    /// Returns the total number of items across all shards.
+    #[cfg(any(test, feature = "debug"))]
+    pub fn items(&self) -> usize {
+        self.shards.iter().map(|shard| shard.write().items()).sum()
+    }
+
+    /// Returns the namespace with the longest prefix of the key, or the
+    /// default namespace if there is no match.
+    fn namespace_for(&self, key: &[u8]) -> &Namespace {
+        self.namespaces[1..]
+            .iter()
+            .filter(|namespace| key.starts_with(namespace.prefix()))
+            .max_by_key(|namespace| namespace.prefix().len())
+            .unwrap_or(&self.namespaces[0])
+    }
+
+    fn shard(&self, key: &[u8]) -> usize {
+        let shards = if self.namespaces.len() == 1 {
+            0..self.shards.len()
+        } else {
+            self.namespace_for(key).shards()
+        };
+
+        if shards.len() == 1 {
+            return shards.start;
+        }
+
+        let mut hasher = self.hash_builder.build_hasher();
+        hasher.write(key);
+        shards.start + (hasher.finish() % shards.len() as u64) as usize
+    }
+}
@@ -15,6 +15,8 @@ pub enum SegcacheError {
     EvictionEx,
     #[error("item oversized ({size:?} bytes)")]
     ItemOversized { size: usize },
+    #[error("optional data oversized ({size:?} bytes)")]
+    OptionalOversized { size: usize },
     #[error("no free segments")]
     NoFreeSegments,
     #[error("item exists")]
@@ -25,4 +27,8 @@ pub enum SegcacheError {
     DataCorrupted,
     #[error("item is not numeric")]
     NotNumeric,
+    #[error("key is reserved")]
+    ReservedKey,
+    #[error("write rejected by admission policy")]
+    Rejected,
 }
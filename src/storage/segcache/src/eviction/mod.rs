@@ -19,6 +19,14 @@ mod policy;
 
 pub use policy::Policy;
 
+/// The number of ghost entries to allocate for each segment when using S3-FIFO.
+const GHOST_ENTRIES_PER_SEGMENT: usize = 256;
+
+/// The access frequency an item in the S3-FIFO small queue needs to be promoted
+/// to the main queue. Items which are inserted after a ghost hit start with
+/// this frequency.
+pub(crate) const S3FIFO_PROMOTE_FREQ: u64 = 2;
+
 /// The `Eviction` struct is used to rank and return segments for eviction. It
 /// implements eviction strategies corresponding to the `Policy`.
 pub struct Eviction {
@@ -27,6 +35,10 @@ pub struct Eviction {
     ranked_segs: Box<[Option<NonZeroU32>]>,
     index: usize,
     rng: Box<Random>,
+    // fingerprints of items recently evicted from the S3-FIFO small queue
+    ghost: Box<[u32]>,
+    // items are not moved between segments until this time
+    pinned_until: Instant,
 }
 
 impl Eviction {
@@ -38,12 +50,20 @@ impl Eviction {
         ranked_segs.resize_with(nseg, || None);
         let ranked_segs = ranked_segs.into_boxed_slice();
 
+        let ghost = if let Policy::S3Fifo { .. } = policy {
+            vec![0; nseg * GHOST_ENTRIES_PER_SEGMENT].into_boxed_slice()
+        } else {
+            Vec::new().into_boxed_slice()
+        };
+
         Self {
             policy,
-            last_update_time: Instant::now(),
+            last_update_time: clock::now(),
             ranked_segs,
             index: 0,
             rng: Box::new(rng()),
+            ghost,
+            pinned_until: clock::now(),
         }
     }
 
@@ -52,6 +72,20 @@ impl Eviction {
         self.policy
     }
 
+    /// Keeps items from being moved between segments until `until`. Pinning
+    /// never shortens an earlier pin.
+    pub fn pin(&mut self, until: Instant) {
+        if until > self.pinned_until {
+            self.pinned_until = until;
+        }
+    }
+
+    /// Returns true if items may not currently be moved between segments.
+    #[inline]
+    pub fn is_pinned(&self) -> bool {
+        clock::now() < self.pinned_until
+    }
+
     /// Returns the segment id of the least valuable segment
     pub fn least_valuable_seg(&mut self) -> Option<NonZeroU32> {
         let index = self.index;
@@ -69,10 +103,40 @@ impl Eviction {
         self.rng.gen()
     }
 
+    /// Records the hash of an item which was evicted from the S3-FIFO small
+    /// queue. The ghost is direct-mapped, so older entries may be overwritten.
+    pub fn ghost_insert(&mut self, hash: u64) {
+        if !self.ghost.is_empty() {
+            let idx = (hash % self.ghost.len() as u64) as usize;
+            self.ghost[idx] = ghost_fingerprint(hash);
+        }
+    }
+
+    /// Returns true if an item with the hash was recently evicted from the
+    /// S3-FIFO small queue, removing it from the ghost.
+    pub fn ghost_remove(&mut self, hash: u64) -> bool {
+        if self.ghost.is_empty() {
+            return false;
+        }
+
+        let idx = (hash % self.ghost.len() as u64) as usize;
+        if self.ghost[idx] == ghost_fingerprint(hash) {
+            self.ghost[idx] = 0;
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn should_rerank(&mut self) -> bool {
-        let now = Instant::now();
+        let now = clock::now();
         match self.policy {
-            Policy::None | Policy::Random | Policy::RandomFifo | Policy::Merge { .. } => false,
+            Policy::None
+            | Policy::Random
+            | Policy::RandomFifo
+            | Policy::Merge { .. }
+            | Policy::Sieve { .. }
+            | Policy::S3Fifo { .. } => false,
             Policy::Fifo | Policy::Cte | Policy::Util => {
                 if self.ranked_segs[0].is_none()
                     || (now - self.last_update_time).as_secs() > 1
@@ -90,7 +154,12 @@ impl Eviction {
     pub fn rerank(&mut self, headers: &[SegmentHeader]) {
         let mut ids: Vec<NonZeroU32> = headers.iter().map(|h| h.id()).collect();
         match self.policy {
-            Policy::None | Policy::Random | Policy::RandomFifo | Policy::Merge { .. } => {
+            Policy::None
+            | Policy::Random
+            | Policy::RandomFifo
+            | Policy::Merge { .. }
+            | Policy::Sieve { .. }
+            | Policy::S3Fifo { .. } => {
                 return;
             }
             Policy::Fifo => {
@@ -163,12 +232,12 @@ impl Eviction {
     #[inline]
     /// Returns the maximum number of segments which can be merged during a
     /// single merge operation. Applies to both eviction and compaction merge
-    /// passes.
+    /// passes, and bounds the number of segments scanned by the SIEVE and
+    /// S3-FIFO policies.
     pub fn max_merge(&self) -> usize {
-        if let Policy::Merge { max, .. } = self.policy {
-            max
-        } else {
-            8
+        match self.policy {
+            Policy::Merge { max, .. } | Policy::Sieve { max } | Policy::S3Fifo { max } => max,
+            _ => 8,
         }
     }
 
@@ -220,3 +289,9 @@ impl Eviction {
         self.target_ratio() * (self.n_merge() - 1) as f64 + 0.05
     }
 }
+
+// the fingerprint stored in the ghost for an item hash, which is never zero so
+// that empty entries never match
+fn ghost_fingerprint(hash: u64) -> u32 {
+    ((hash >> 32) as u32) | 1
+}
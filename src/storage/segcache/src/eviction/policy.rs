@@ -59,4 +59,26 @@ pub enum Policy {
         /// Note: Compaction will be disabled by setting this parameter to zero.
         compact: usize,
     },
+    /// SIEVE eviction, adapted to segments. The oldest segments in a TTL
+    /// bucket are scanned and items which have not been accessed since they
+    /// were last scanned are evicted. Accessed items are retained with their
+    /// access frequency cleared and are copied together into the oldest
+    /// segment, freeing the segments they were copied from. If every scanned
+    /// item was accessed, the oldest segment is evicted outright.
+    Sieve {
+        /// The maximum number of segments to scan in a single pass.
+        max: usize,
+    },
+    /// S3-FIFO eviction, adapted to segments. Segments which have not yet been
+    /// scanned form a small probationary queue and segments which hold items
+    /// that survived a scan form the main queue. Items in the small queue must
+    /// have been accessed more than once to be promoted into the main queue,
+    /// and the hashes of items evicted from it are remembered in a ghost queue
+    /// so that they are admitted directly to the main queue when they are
+    /// inserted again. Items in the main queue are retained if they were
+    /// accessed since they were last scanned.
+    S3Fifo {
+        /// The maximum number of segments to scan in a single pass.
+        max: usize,
+    },
 }
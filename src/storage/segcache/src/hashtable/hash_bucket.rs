@@ -8,7 +8,7 @@
 //! Bucket Info:
 //! ```text
 //! ┌──────────────────────────────┬──────┬──────┬──────────────┐
-//! │             CAS              │ ---- │CHAIN │  TIMESTAMP   │
+//! │             CAS              │FLAGS │CHAIN │  TIMESTAMP   │
 //! │                              │      │ LEN  │              │
 //! │            32 bit            │8 bit │8 bit │    16 bit    │
 //! │                              │      │ LEN  │              │
@@ -35,6 +35,9 @@ pub(crate) const BUCKET_CHAIN_LEN_MASK: u64 = 0x0000_0000_00FF_0000;
 pub(crate) const TS_MASK: u64 = 0x0000_0000_0000_FFFF;
 /// A mask to get the bits containing the CAS value from the bucket info
 pub(crate) const CAS_MASK: u64 = 0xFFFF_FFFF_0000_0000;
+/// A flag in the bucket info which marks a primary bucket of the old table as
+/// migrated while the hashtable is growing
+pub(crate) const BUCKET_MIGRATED: u64 = 0x0000_0000_0100_0000;
 
 /// Number of bits to shift the bucket info masked with the chain length mask
 /// to get the actual chain length
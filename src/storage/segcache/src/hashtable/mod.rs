@@ -64,6 +64,15 @@
 //! This works out so that we have capacity to store 7 items for every bucket
 //! allocated to a chain.
 //!
+//! If a maximum hash power is configured, the hashtable may grow online. When
+//! the load factor or the chain lengths indicate the table is too small, a new
+//! table with twice as many primary buckets is allocated. Buckets are then
+//! migrated from the old table a few at a time as part of normal operations,
+//! and lookups are directed to whichever table currently holds the bucket for
+//! the key. Once every bucket has been migrated, the old table is dropped.
+//! Items which do not fit into the new table, because its overflow buckets
+//! have been used up, are evicted as they are migrated.
+//!
 
 // hashtable
 
@@ -73,6 +82,19 @@ const N_BUCKET_SLOT: usize = 8;
 /// Maximum number of buckets in a chain. Must be <= 255.
 const MAX_CHAIN_LEN: u64 = 16;
 
+/// Chaining a bucket onto a chain of this length will start an expansion of
+/// the hashtable, if growth is enabled.
+const GROW_CHAIN_LEN: u64 = MAX_CHAIN_LEN / 2;
+
+/// An expansion is started once the number of items exceeds this percentage of
+/// the item slots in the primary buckets, if growth is enabled.
+const GROW_LOAD_FACTOR_PCT: u64 = 90;
+
+/// The number of buckets migrated by each operation, in addition to the bucket
+/// which is being accessed, while an expansion is in progress.
+const MIGRATE_STEP: u64 = 4;
+
+use crate::eviction::S3FIFO_PROMOTE_FREQ;
 use crate::*;
 use ahash::RandomState;
 use core::marker::PhantomData;
@@ -93,10 +115,10 @@ struct IterState {
 }
 
 impl IterState {
-    fn new(hashtable: &HashTable, hash: u64) -> Self {
-        let bucket_id = (hash & hashtable.mask) as usize;
-        let buckets_len = hashtable.data.len();
-        let bucket = hashtable.data[bucket_id];
+    fn new(buckets: &Buckets, hash: u64) -> Self {
+        let bucket_id = (hash & buckets.mask) as usize;
+        let buckets_len = buckets.data.len();
+        let bucket = buckets.data[bucket_id];
         let chain_len = chain_len(bucket.data[0]) as usize;
 
         Self {
@@ -121,6 +143,21 @@ impl IterState {
             N_BUCKET_SLOT - 1
         }
     }
+
+    /// Moves to the next item slot, following the chain into the next bucket
+    /// once every slot in the current bucket has been visited. The function
+    /// returns the id of the bucket which is chained after the given bucket.
+    fn advance(&mut self, next_bucket: impl FnOnce(usize) -> usize) {
+        if self.item_slot < self.n_item_slot() - 1 {
+            self.item_slot += 1;
+        } else if self.chain_idx < self.chain_len {
+            self.chain_idx += 1;
+            self.item_slot = 0;
+            self.bucket_id = next_bucket(self.bucket_id);
+        } else {
+            self.finished = true;
+        }
+    }
 }
 
 struct IterMut<'a> {
@@ -132,10 +169,10 @@ struct IterMut<'a> {
 }
 
 impl<'a> IterMut<'a> {
-    fn new(hashtable: &'a mut HashTable, hash: u64) -> Self {
-        let state = IterState::new(hashtable, hash);
+    fn new(buckets: &'a mut Buckets, hash: u64) -> Self {
+        let state = IterState::new(buckets, hash);
 
-        let ptr = hashtable.data.as_mut_ptr();
+        let ptr = buckets.data.as_mut_ptr();
 
         Self {
             ptr,
@@ -153,8 +190,6 @@ impl<'a> Iterator for IterMut<'a> {
             return None;
         }
 
-        let n_item_slot = self.state.n_item_slot();
-
         // SAFETY: this assert ensures memory safety for the pointer operations
         // that follow as in-line unsafe blocks. We first check to make sure the
         // bucket_id is within range for the slice of buckets. As long as this
@@ -167,53 +202,58 @@ impl<'a> Iterator for IterMut<'a> {
         let item_info =
             unsafe { &mut (*self.ptr.add(self.state.bucket_id)).data[self.state.item_slot] };
 
-        if self.state.item_slot < n_item_slot - 1 {
-            self.state.item_slot += 1;
-        } else {
-            // finished iterating in this bucket, see if it's chained
-            if self.state.chain_idx < self.state.chain_len {
-                self.state.chain_idx += 1;
-                self.state.item_slot = 0;
-                let next_bucket_id = unsafe {
-                    (*self.ptr.add(self.state.bucket_id)).data[N_BUCKET_SLOT - 1] as usize
-                };
-                self.state.bucket_id = next_bucket_id;
-            } else {
-                self.state.finished = true;
-            }
-        }
+        let ptr = self.ptr;
+        self.state
+            .advance(|bucket_id| unsafe { (*ptr.add(bucket_id)).data[N_BUCKET_SLOT - 1] as usize });
 
         Some(item_info)
     }
 }
 
-/// Main structure for performing item lookup. Contains a contiguous allocation
-/// of [`HashBucket`]s which are used to store item info and metadata.
-#[repr(C)]
-pub(crate) struct HashTable {
-    hash_builder: Box<RandomState>,
-    power: u64,
-    mask: u64,
-    data: Box<[HashBucket]>,
-    started: Instant,
-    next_to_chain: u64,
-    _pad: [u8; 8],
+/// Iterates over the item info slots in the chain for a hash without
+/// modifying them. This is used by lookups through a shared reference.
+struct Iter<'a> {
+    buckets: &'a Buckets,
+    state: IterState,
 }
 
-impl HashTable {
-    /// Creates a new hashtable with a specified power and overflow factor. The
-    /// hashtable will have the capacity to store up to
-    /// `7 * 2^(power - 3) * (1 + overflow_factor)` items.
-    pub fn new(power: u8, overflow_factor: f64) -> HashTable {
-        if overflow_factor < 0.0 {
-            panic!("hashtable overflow factor must be >= 0.0");
+impl<'a> Iter<'a> {
+    fn new(buckets: &'a Buckets, hash: u64) -> Self {
+        Self {
+            buckets,
+            state: IterState::new(buckets, hash),
         }
+    }
+}
 
-        // overflow factor is effectively bounded by the max chain length
-        if overflow_factor > MAX_CHAIN_LEN as f64 {
-            panic!("hashtable overflow factor must be <= {}", MAX_CHAIN_LEN);
+impl Iterator for Iter<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.state.finished {
+            return None;
         }
 
+        let data = &self.buckets.data;
+        let item_info = data[self.state.bucket_id].data[self.state.item_slot];
+
+        self.state
+            .advance(|bucket_id| data[bucket_id].data[N_BUCKET_SLOT - 1] as usize);
+
+        Some(item_info)
+    }
+}
+
+/// A contiguous allocation of [`HashBucket`]s. The first `mask + 1` buckets
+/// are the primary buckets and the remainder are used for chaining.
+struct Buckets {
+    mask: u64,
+    data: Box<[HashBucket]>,
+    next_to_chain: u64,
+}
+
+impl Buckets {
+    fn new(power: u8, overflow_factor: f64) -> Self {
         let slots = 1_u64 << power;
         let buckets = slots / 8;
         let mask = buckets - 1;
@@ -228,6 +268,121 @@ impl HashTable {
             slots, buckets, total_buckets,
         );
 
+        Self {
+            mask,
+            data: data.into_boxed_slice(),
+            next_to_chain: buckets,
+        }
+    }
+
+    /// The number of primary buckets
+    fn primary(&self) -> u64 {
+        self.mask + 1
+    }
+
+    /// The hash power, which determines the number of primary buckets
+    fn power(&self) -> u8 {
+        self.primary().trailing_zeros() as u8 + 3
+    }
+
+    /// The number of overflow buckets relative to the number of primary buckets
+    fn overflow_factor(&self) -> f64 {
+        (self.data.len() as u64 - self.primary()) as f64 / self.primary() as f64
+    }
+
+    /// Returns the bucket info of the primary bucket for the hash
+    fn info(&self, hash: u64) -> u64 {
+        self.data[(hash & self.mask) as usize].data[0]
+    }
+
+    /// Returns a mutable reference to the bucket info of the primary bucket for
+    /// the hash
+    fn info_mut(&mut self, hash: u64) -> &mut u64 {
+        &mut self.data[(hash & self.mask) as usize].data[0]
+    }
+
+    /// Chains an overflow bucket onto the end of the chain for the hash and
+    /// stores the item info in it. Returns the new chain length, or `None` if
+    /// the chain is at its maximum length or there are no overflow buckets
+    /// remaining.
+    fn chain(&mut self, hash: u64, item_info: u64) -> Option<u64> {
+        let mut bucket_id = (hash & self.mask) as usize;
+        let chain_len = chain_len(self.data[bucket_id].data[0]);
+
+        if chain_len >= MAX_CHAIN_LEN || (self.next_to_chain as usize) >= self.data.len() {
+            return None;
+        }
+
+        // we need to chase through the buckets to get the id of the last
+        // bucket in the chain
+        for _ in 0..chain_len {
+            bucket_id = self.data[bucket_id].data[N_BUCKET_SLOT - 1] as usize;
+        }
+
+        let next_id = self.next_to_chain as usize;
+        self.next_to_chain += 1;
+
+        self.data[next_id].data[0] = self.data[bucket_id].data[N_BUCKET_SLOT - 1];
+        self.data[next_id].data[1] = item_info;
+        self.data[bucket_id].data[N_BUCKET_SLOT - 1] = next_id as u64;
+
+        *self.info_mut(hash) += 0x0000_0000_0001_0000;
+
+        Some(chain_len + 1)
+    }
+
+    /// Stores the item info in the first empty slot in the chain for the hash,
+    /// chaining an overflow bucket if there are none. Returns false if there
+    /// was no room for the item.
+    fn push(&mut self, hash: u64, item_info: u64) -> bool {
+        for slot in IterMut::new(self, hash) {
+            if *slot == 0 {
+                *slot = item_info;
+                return true;
+            }
+        }
+
+        self.chain(hash, item_info).is_some()
+    }
+}
+
+/// Tracks the buckets of the old table while the hashtable is growing.
+struct Migration {
+    old: Buckets,
+    next_to_migrate: u64,
+    migrated: u64,
+}
+
+/// Main structure for performing item lookup. Contains a contiguous allocation
+/// of [`HashBucket`]s which are used to store item info and metadata. While
+/// the hashtable is growing, the buckets which have not yet been migrated are
+/// held in a second allocation.
+#[repr(C)]
+pub(crate) struct HashTable {
+    hash_builder: Box<RandomState>,
+    buckets: Buckets,
+    migration: Option<Box<Migration>>,
+    items: u64,
+    started: Instant,
+    max_power: u8,
+}
+
+impl HashTable {
+    /// Creates a new hashtable with a specified power and overflow factor. The
+    /// hashtable will have the capacity to store up to
+    /// `7 * 2^(power - 3) * (1 + overflow_factor)` items. If the max power is
+    /// greater than the power, the hashtable will double in size as needed
+    /// until it reaches the max power.
+    pub fn new(power: u8, overflow_factor: f64, max_power: u8) -> HashTable {
+        if overflow_factor < 0.0 {
+            panic!("hashtable overflow factor must be >= 0.0");
+        }
+
+        // overflow factor is effectively bounded by the max chain length
+        if overflow_factor > MAX_CHAIN_LEN as f64 {
+            panic!("hashtable overflow factor must be <= {}", MAX_CHAIN_LEN);
+        }
+
         let hash_builder = RandomState::with_seeds(
             0xbb8c484891ec6c86,
             0x0522a25ae9c769f9,
@@ -237,35 +392,100 @@ impl HashTable {
 
         Self {
             hash_builder: Box::new(hash_builder),
-            power: power.into(),
-            mask,
-            data: data.into_boxed_slice(),
-            started: Instant::now(),
-            next_to_chain: buckets,
-            _pad: [0; 8],
+            buckets: Buckets::new(power, overflow_factor),
+            migration: None,
+            items: 0,
+            started: clock::now(),
+            max_power: max_power.max(power),
         }
     }
 
-    /// Lookup an item by key and return it
-    pub fn get(&mut self, key: &[u8], time: Instant, segments: &mut Segments) -> Option<Item> {
-        let hash = self.hash(key);
-        let tag = tag_from_hash(hash);
-        let bucket_id = hash & self.mask;
+    /// Returns the current hash power, which increases as the hashtable grows
+    #[cfg(test)]
+    pub(crate) fn power(&self) -> u8 {
+        self.buckets.power()
+    }
+
+    /// Returns true if the hashtable is currently migrating buckets to a larger
+    /// table
+    #[cfg(test)]
+    pub(crate) fn is_growing(&self) -> bool {
+        self.migration.is_some()
+    }
 
-        let bucket_info = self.data[bucket_id as usize].data[0];
+    /// Uses up the overflow buckets of the current table, as inserts into
+    /// buckets which were already migrated may do while the hashtable is
+    /// growing
+    #[cfg(test)]
+    pub(crate) fn exhaust_overflow(&mut self) {
+        self.buckets.next_to_chain = self.buckets.data.len() as u64;
+    }
 
-        let curr_ts = (time - self.started).as_secs() & PROC_TS_MASK;
+    /// Returns the buckets which currently hold the chain for the hash. Until a
+    /// bucket has been migrated, it remains in the old table.
+    fn buckets_mut(&mut self, hash: u64) -> &mut Buckets {
+        if let Some(migration) = self.migration.as_deref_mut() {
+            if migration.old.info(hash) & BUCKET_MIGRATED == 0 {
+                return &mut migration.old;
+            }
+        }
+        &mut self.buckets
+    }
+
+    /// Returns the buckets which currently hold the chain for the hash. See
+    /// [`buckets_mut`](Self::buckets_mut).
+    fn buckets(&self, hash: u64) -> &Buckets {
+        if let Some(migration) = self.migration.as_deref() {
+            if migration.old.info(hash) & BUCKET_MIGRATED == 0 {
+                return &migration.old;
+            }
+        }
+        &self.buckets
+    }
+
+    /// Clears the frequency smoothing bit of the items in the chain for the
+    /// hash if the bucket was last accessed in a different second.
+    fn smooth_freq(buckets: &mut Buckets, hash: u64, curr_ts: u32) {
+        let bucket_info = buckets.info(hash);
 
         if curr_ts != get_ts(bucket_info) as u32 {
-            self.data[bucket_id as usize].data[0] = (bucket_info & !TS_MASK) | (curr_ts as u64);
+            *buckets.info_mut(hash) = (bucket_info & !TS_MASK) | (curr_ts as u64);
 
-            let iter = IterMut::new(self, hash);
+            let iter = IterMut::new(buckets, hash);
             for item_info in iter {
                 *item_info &= CLEAR_FREQ_SMOOTH_MASK;
             }
         }
+    }
 
-        let iter = IterMut::new(self, hash);
+    /// Increments the frequency held in the item info. Increments are
+    /// probabilistic once the frequency exceeds 16, and only one increment
+    /// is counted per item each second.
+    fn incr_freq(item_info: &mut u64) {
+        let mut freq = get_freq(*item_info);
+        if freq < 127 {
+            let rand = thread_rng().gen::<u64>();
+            if freq <= 16 || rand % freq == 0 {
+                freq = ((freq + 1) | 0x80) << FREQ_BIT_SHIFT;
+            } else {
+                freq = (freq | 0x80) << FREQ_BIT_SHIFT;
+            }
+            *item_info = (*item_info & !FREQ_MASK) | freq;
+        }
+    }
+
+    /// Lookup an item by key and return it
+    pub fn get(&mut self, key: &[u8], time: Instant, segments: &mut Segments) -> Option<Item> {
+        let hash = self.hash(key);
+        let tag = tag_from_hash(hash);
+
+        let curr_ts = (time - self.started).as_secs() & PROC_TS_MASK;
+
+        let buckets = self.buckets_mut(hash);
+
+        Self::smooth_freq(buckets, hash, curr_ts);
+
+        let iter = IterMut::new(buckets, hash);
 
         for item_info in iter {
             if get_tag(*item_info) == tag {
@@ -275,21 +495,9 @@ impl HashTable {
                     HASH_TAG_COLLISION.increment();
                 } else {
                     // update item frequency
-                    let mut freq = get_freq(*item_info);
-                    if freq < 127 {
-                        let rand = thread_rng().gen::<u64>();
-                        if freq <= 16 || rand % freq == 0 {
-                            freq = ((freq + 1) | 0x80) << FREQ_BIT_SHIFT;
-                        } else {
-                            freq = (freq | 0x80) << FREQ_BIT_SHIFT;
-                        }
-                        *item_info = (*item_info & !FREQ_MASK) | freq;
-                    }
+                    Self::incr_freq(item_info);
 
-                    let item = Item::new(
-                        current_item,
-                        get_cas(self.data[(hash & self.mask) as usize].data[0]),
-                    );
+                    let item = Item::new(current_item, get_cas(buckets.info(hash)));
                     item.check_magic();
 
                     return Some(item);
@@ -300,13 +508,62 @@ impl HashTable {
         None
     }
 
+    /// Lookup an item by key through a shared reference. Neither the item
+    /// frequency nor the hashtable are modified, and buckets are not migrated
+    /// while the hashtable is growing. Returns the item along with its item
+    /// info, which may be passed to [`touch`](Self::touch) later to count the
+    /// access.
+    pub(crate) fn peek(&self, key: &[u8], segments: &Segments) -> Option<(Item, u64)> {
+        let hash = self.hash(key);
+        let tag = tag_from_hash(hash);
+
+        let buckets = self.buckets(hash);
+
+        for item_info in Iter::new(buckets, hash) {
+            if get_tag(item_info) == tag {
+                let current_item = segments.peek_item(item_info).unwrap();
+                if current_item.key() != key {
+                    #[cfg(feature = "metrics")]
+                    HASH_TAG_COLLISION.increment();
+                } else {
+                    let item = Item::new(current_item, get_cas(buckets.info(hash)));
+                    item.check_magic();
+
+                    return Some((item, item_info));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Counts an access to an item which was found by [`peek`](Self::peek),
+    /// just as [`get`](Self::get) would have. Nothing is changed if the item
+    /// has since been moved or removed.
+    pub(crate) fn touch(&mut self, hash: u64, item_info: u64, time: Instant) {
+        let curr_ts = (time - self.started).as_secs() & PROC_TS_MASK;
+
+        let buckets = self.buckets_mut(hash);
+
+        Self::smooth_freq(buckets, hash, curr_ts);
+
+        for slot in IterMut::new(buckets, hash) {
+            if *slot & !FREQ_MASK == item_info & !FREQ_MASK {
+                Self::incr_freq(slot);
+                return;
+            }
+        }
+    }
+
     /// Lookup an item by key and return it without incrementing the item
     /// frequency. This may be used to compose higher-level functions which do
     /// not want a successful item lookup to count as a hit for that item.
     pub fn get_no_freq_incr(&mut self, key: &[u8], segments: &mut Segments) -> Option<Item> {
         let hash = self.hash(key);
 
-        let iter = IterMut::new(self, hash);
+        let buckets = self.buckets_mut(hash);
+
+        let iter = IterMut::new(buckets, hash);
 
         let tag = tag_from_hash(hash);
 
@@ -317,10 +574,7 @@ impl HashTable {
                     #[cfg(feature = "metrics")]
                     HASH_TAG_COLLISION.increment();
                 } else {
-                    let item = Item::new(
-                        current_item,
-                        get_cas(self.data[(hash & self.mask) as usize].data[0]),
-                    );
+                    let item = Item::new(current_item, get_cas(buckets.info(hash)));
                     item.check_magic();
 
                     return Some(item);
@@ -331,12 +585,40 @@ impl HashTable {
         None
     }
 
+    /// Lookup the id of the segment which holds the item with the key. This
+    /// does not count as an access for the item.
+    pub(crate) fn get_item_seg(
+        &mut self,
+        key: &[u8],
+        segments: &mut Segments,
+    ) -> Option<NonZeroU32> {
+        let hash = self.hash(key);
+
+        let iter = IterMut::new(self.buckets_mut(hash), hash);
+
+        let tag = tag_from_hash(hash);
+
+        for item_info in iter {
+            if get_tag(*item_info) == tag {
+                let current_item = segments.get_item(*item_info).unwrap();
+                if current_item.key() != key {
+                    #[cfg(feature = "metrics")]
+                    HASH_TAG_COLLISION.increment();
+                } else {
+                    return get_seg_id(*item_info);
+                }
+            }
+        }
+
+        None
+    }
+
     /// Return the frequency for the item with the key
     pub fn get_freq(&mut self, key: &[u8], segment: &mut Segment, offset: u64) -> Option<u64> {
         let hash = self.hash(key);
         let tag = tag_from_hash(hash);
 
-        let iter = IterMut::new(self, hash);
+        let iter = IterMut::new(self.buckets_mut(hash), hash);
 
         for item_info in iter {
             if get_tag(*item_info) == tag
@@ -350,6 +632,24 @@ impl HashTable {
         None
     }
 
+    /// Clears the frequency for the item with the key
+    pub fn reset_freq(&mut self, key: &[u8], segment: &mut Segment, offset: u64) {
+        let hash = self.hash(key);
+        let tag = tag_from_hash(hash);
+
+        let iter = IterMut::new(self.buckets_mut(hash), hash);
+
+        for item_info in iter {
+            if get_tag(*item_info) == tag
+                && get_seg_id(*item_info) == Some(segment.id())
+                && get_offset(*item_info) == offset
+            {
+                *item_info = clear_freq(*item_info);
+                return;
+            }
+        }
+    }
+
     /// Relinks the item to a new location
     #[allow(clippy::result_unit_err)]
     pub fn relink_item(
@@ -363,7 +663,7 @@ impl HashTable {
         let hash = self.hash(key);
         let tag = tag_from_hash(hash);
 
-        let iter = IterMut::new(self, hash);
+        let iter = IterMut::new(self.buckets_mut(hash), hash);
 
         for item_info in iter {
             if get_tag(*item_info) == tag {
@@ -387,7 +687,7 @@ impl HashTable {
     pub(crate) fn is_item_at(&mut self, key: &[u8], seg: NonZeroU32, offset: u64) -> bool {
         let hash = self.hash(key);
         let tag = tag_from_hash(hash);
-        let iter = IterMut::new(self, hash);
+        let iter = IterMut::new(self.buckets_mut(hash), hash);
 
         for item_info in iter {
             if get_tag(*item_info) == tag {
@@ -404,7 +704,7 @@ impl HashTable {
     }
 
     /// Inserts a new item into the hashtable. This may fail if the hashtable is
-    /// full.
+    /// full and cannot grow.
     #[allow(clippy::result_unit_err)]
     pub fn insert(
         &mut self,
@@ -413,6 +713,7 @@ impl HashTable {
         offset: u64,
         ttl_buckets: &mut TtlBuckets,
         segments: &mut Segments,
+        orphans: &mut Vec<LargeItem>,
     ) -> Result<(), ()> {
         #[cfg(feature = "metrics")]
         HASH_INSERT.increment();
@@ -425,9 +726,54 @@ impl HashTable {
 
         let mut insert_item_info = build_item_info(tag, seg, offset);
 
+        // items which were recently evicted from the S3-FIFO small queue are
+        // admitted as if they had already been promoted
+        if segments.ghost_remove(hash) {
+            insert_item_info |= S3FIFO_PROMOTE_FREQ << FREQ_BIT_SHIFT;
+        }
+
+        self.migrate(hash, segments, ttl_buckets, orphans);
+
+        let mut result = self.link(hash, item.key(), insert_item_info, ttl_buckets, segments);
+
+        // a full chain, or running out of overflow buckets, starts an expansion
+        // and the insert is retried once the bucket has been migrated. Any
+        // expansion which is already in progress must be completed first.
+        if result.is_err() {
+            self.migrate_all(segments, ttl_buckets, orphans);
+            if self.grow() {
+                self.migrate(hash, segments, ttl_buckets, orphans);
+                result = self.link(hash, item.key(), insert_item_info, ttl_buckets, segments);
+            }
+        }
+
+        if result.is_err() {
+            #[cfg(feature = "metrics")]
+            HASH_INSERT_EX.increment();
+        } else if self.items * 100
+            > self.buckets.primary() * (N_BUCKET_SLOT as u64 - 1) * GROW_LOAD_FACTOR_PCT
+        {
+            self.grow();
+        }
+
+        result
+    }
+
+    /// Internal function which stores the item info in the chain for the hash,
+    /// replacing any existing item with the same key.
+    fn link(
+        &mut self,
+        hash: u64,
+        key: &[u8],
+        mut insert_item_info: u64,
+        ttl_buckets: &mut TtlBuckets,
+        segments: &mut Segments,
+    ) -> Result<(), ()> {
+        let tag = tag_from_hash(hash);
+
         let mut removed: Option<u64> = None;
 
-        let iter = IterMut::new(self, hash);
+        let iter = IterMut::new(self.buckets_mut(hash), hash);
 
         for item_info in iter {
             if get_tag(*item_info) != tag {
@@ -438,7 +784,7 @@ impl HashTable {
                 }
                 continue;
             }
-            if segments.get_item(*item_info).unwrap().key() != item.key() {
+            if segments.get_item(*item_info).unwrap().key() != key {
                 #[cfg(feature = "metrics")]
                 HASH_TAG_COLLISION.increment();
             } else {
@@ -458,39 +804,186 @@ impl HashTable {
         }
 
         if insert_item_info != 0 {
-            let mut bucket_id = (hash & self.mask) as usize;
-            let chain_len = chain_len(self.data[bucket_id].data[0]);
-
-            if chain_len < MAX_CHAIN_LEN && (self.next_to_chain as usize) < self.data.len() {
-                // we need to chase through the buckets to get the id of the last
-                // bucket in the chain
-                for _ in 0..chain_len {
-                    bucket_id = self.data[bucket_id].data[N_BUCKET_SLOT - 1] as usize;
-                }
-
-                let next_id = self.next_to_chain as usize;
-                self.next_to_chain += 1;
-
-                self.data[next_id].data[0] = self.data[bucket_id].data[N_BUCKET_SLOT - 1];
-                self.data[next_id].data[1] = insert_item_info;
+            if let Some(chain_len) = self.buckets_mut(hash).chain(hash, insert_item_info) {
                 insert_item_info = 0;
-                self.data[bucket_id].data[N_BUCKET_SLOT - 1] = next_id as u64;
 
-                self.data[(hash & self.mask) as usize].data[0] += 0x0000_0000_0001_0000;
+                if chain_len >= GROW_CHAIN_LEN {
+                    self.grow();
+                }
             }
         }
 
         if insert_item_info == 0 {
-            self.data[(hash & self.mask) as usize].data[0] += 1 << CAS_BIT_SHIFT;
+            if removed.is_none() {
+                self.items += 1;
+            }
+            *self.buckets_mut(hash).info_mut(hash) += 1 << CAS_BIT_SHIFT;
             Ok(())
         } else {
-            #[cfg(feature = "metrics")]
-            HASH_INSERT_EX.increment();
-
             Err(())
         }
     }
 
+    /// Starts an expansion of the hashtable to twice as many primary buckets.
+    /// Returns false if the hashtable is already growing or has reached the
+    /// maximum hash power.
+    fn grow(&mut self) -> bool {
+        if self.migration.is_some() || self.buckets.power() >= self.max_power {
+            return false;
+        }
+
+        let buckets = Buckets::new(self.buckets.power() + 1, self.buckets.overflow_factor());
+        let old = std::mem::replace(&mut self.buckets, buckets);
+
+        debug!(
+            "hashtable growing from power {} to {} with {} items",
+            old.power(),
+            self.buckets.power(),
+            self.items
+        );
+
+        #[cfg(feature = "metrics")]
+        {
+            HASH_GROW.increment();
+            HASH_MIGRATE_PENDING.add(old.primary() as _);
+        }
+
+        self.migration = Some(Box::new(Migration {
+            old,
+            next_to_migrate: 0,
+            migrated: 0,
+        }));
+
+        true
+    }
+
+    /// Migrates the bucket for the hash if the hashtable is growing. A few
+    /// additional buckets are migrated in order so that the expansion
+    /// completes even if some buckets are never accessed. Items which do not
+    /// fit into the new table are removed, and the manifests of any large
+    /// items among them are appended to `orphans`.
+    pub(crate) fn migrate(
+        &mut self,
+        hash: u64,
+        segments: &mut Segments,
+        ttl_buckets: &mut TtlBuckets,
+        orphans: &mut Vec<LargeItem>,
+    ) {
+        let mask = match self.migration.as_deref() {
+            Some(migration) => migration.old.mask,
+            None => return,
+        };
+
+        self.migrate_bucket(hash & mask, segments, ttl_buckets, orphans);
+
+        let mut remaining = MIGRATE_STEP;
+        while remaining > 0 {
+            let bucket_id = match self.migration.as_deref_mut() {
+                Some(migration) => {
+                    migration.next_to_migrate += 1;
+                    migration.next_to_migrate - 1
+                }
+                None => return,
+            };
+            if self.migrate_bucket(bucket_id, segments, ttl_buckets, orphans) {
+                remaining -= 1;
+            }
+        }
+    }
+
+    /// Migrates all remaining buckets, completing any expansion in progress.
+    fn migrate_all(
+        &mut self,
+        segments: &mut Segments,
+        ttl_buckets: &mut TtlBuckets,
+        orphans: &mut Vec<LargeItem>,
+    ) {
+        while let Some(migration) = self.migration.as_deref_mut() {
+            let bucket_id = migration.next_to_migrate;
+            migration.next_to_migrate += 1;
+            self.migrate_bucket(bucket_id, segments, ttl_buckets, orphans);
+        }
+    }
+
+    /// Moves the items in a primary bucket of the old table, including any
+    /// chained buckets, into the new table. Returns false if the bucket had
+    /// already been migrated.
+    fn migrate_bucket(
+        &mut self,
+        bucket_id: u64,
+        segments: &mut Segments,
+        ttl_buckets: &mut TtlBuckets,
+        orphans: &mut Vec<LargeItem>,
+    ) -> bool {
+        let migration = match self.migration.as_deref_mut() {
+            Some(migration) => migration,
+            None => return false,
+        };
+        let old = &mut migration.old;
+
+        let bucket_info = old.info(bucket_id);
+        if bucket_info & BUCKET_MIGRATED != 0 {
+            return false;
+        }
+
+        // the items in an old bucket map to one of two buckets in the new
+        // table. Neither has been used before, as keys are directed to the old
+        // table until now, so both take over the CAS value and timestamp.
+        let primary = old.primary();
+        let buckets = &mut self.buckets;
+        *buckets.info_mut(bucket_id) = bucket_info & (CAS_MASK | TS_MASK);
+        *buckets.info_mut(bucket_id + primary) = bucket_info & (CAS_MASK | TS_MASK);
+
+        let mut dropped = Vec::new();
+
+        for item_info in IterMut::new(old, bucket_id) {
+            if *item_info == 0 {
+                continue;
+            }
+
+            let mut hasher = self.hash_builder.build_hasher();
+            hasher.write(segments.get_item(*item_info).unwrap().key());
+            let hash = hasher.finish();
+
+            if !buckets.push(hash, *item_info) {
+                // the overflow buckets of the new table may have been used up
+                // by inserts into buckets which were already migrated, so the
+                // item is evicted once the bucket has been migrated
+                dropped.push(*item_info);
+            }
+        }
+
+        *old.info_mut(bucket_id) |= BUCKET_MIGRATED;
+        migration.migrated += 1;
+
+        #[cfg(feature = "metrics")]
+        {
+            HASH_MIGRATE.increment();
+            HASH_MIGRATE_PENDING.decrement();
+        }
+
+        if migration.migrated == primary {
+            debug!("hashtable finished growing to power {}", buckets.power());
+            self.migration = None;
+        }
+
+        // the items are no longer reachable through either table, so they are
+        // removed from their segments, which reclaims any segment left empty
+        for item_info in dropped {
+            if let Some(large) = segments.get_item(item_info).and_then(|item| item.large()) {
+                orphans.push(large);
+            }
+
+            self.items -= 1;
+            let _ = segments.remove_item(item_info, ttl_buckets, self);
+
+            #[cfg(feature = "metrics")]
+            ITEM_EVICT.increment();
+        }
+
+        true
+    }
+
     /// Used to implement higher-level CAS operations. This function looks up an
     /// item by key and checks if the CAS value matches the provided value.
     ///
@@ -507,9 +1000,10 @@ impl HashTable {
     ) -> Result<(), SegcacheError> {
         let hash = self.hash(key);
         let tag = tag_from_hash(hash);
-        let bucket_id = hash & self.mask;
 
-        let iter = IterMut::new(self, hash);
+        let buckets = self.buckets_mut(hash);
+
+        let iter = IterMut::new(buckets, hash);
 
         for item_info in iter {
             if get_tag(*item_info) == tag {
@@ -530,8 +1024,8 @@ impl HashTable {
                         *item_info = (*item_info & !FREQ_MASK) | freq;
                     }
 
-                    if cas == get_cas(self.data[bucket_id as usize].data[0]) {
-                        self.data[bucket_id as usize].data[0] += 1 << CAS_BIT_SHIFT;
+                    if cas == get_cas(buckets.info(hash)) {
+                        *buckets.info_mut(hash) += 1 << CAS_BIT_SHIFT;
                         return Ok(());
                     } else {
                         return Err(SegcacheError::Exists);
@@ -549,11 +1043,14 @@ impl HashTable {
         key: &[u8],
         ttl_buckets: &mut TtlBuckets,
         segments: &mut Segments,
+        orphans: &mut Vec<LargeItem>,
     ) -> bool {
         let hash = self.hash(key);
         let tag = tag_from_hash(hash);
 
-        let iter = IterMut::new(self, hash);
+        self.migrate(hash, segments, ttl_buckets, orphans);
+
+        let iter = IterMut::new(self.buckets_mut(hash), hash);
 
         let mut removed: Option<u64> = None;
 
@@ -580,6 +1077,8 @@ impl HashTable {
             #[cfg(feature = "metrics")]
             ITEM_DELETE.increment();
 
+            self.items -= 1;
+
             let _ = segments.remove_item(removed_item, ttl_buckets, self);
             true
         } else {
@@ -613,7 +1112,7 @@ impl HashTable {
         let tag = tag_from_hash(hash);
         let evict_item_info = build_item_info(tag, segment.id(), offset as u64);
 
-        let iter = IterMut::new(self, hash);
+        let iter = IterMut::new(self.buckets_mut(hash), hash);
 
         for item_info in iter {
             let current_item_info = clear_freq(*item_info);
@@ -633,6 +1132,7 @@ impl HashTable {
             if evict_item_info == current_item_info {
                 segment.remove_item(current_item_info);
                 *item_info = 0;
+                self.items -= 1;
                 return true;
             }
         }
@@ -640,8 +1140,8 @@ impl HashTable {
         false
     }
 
-    /// Internal function used to calculate a hash value for a key
-    fn hash(&self, key: &[u8]) -> u64 {
+    /// Calculates the hash value for a key
+    pub(crate) fn hash(&self, key: &[u8]) -> u64 {
         #[cfg(feature = "metrics")]
         HASH_LOOKUP.increment();
 
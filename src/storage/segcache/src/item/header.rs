@@ -18,13 +18,13 @@
 //!
 //! Flags:
 //! ```text
-//! ┌──────────────┬──────────────┬──────────────────────────────┐
-//! │    TYPED?    │   PADDING    │             OLEN             │
-//! │              │              │                              │
-//! │    1 bit     │    1 bit     │            6 bit             │
-//! │              │              │                              │
-//! │      64      │      65      │  66                      71  │
-//! └──────────────┴──────────────┴──────────────────────────────┘
+//! ┌──────────────┬──────────────┬──────────────┬────────────────────────┐
+//! │    TYPED?    │ COMPRESSED?  │    CHUNK?    │          OLEN          │
+//! │              │              │              │                        │
+//! │    1 bit     │    1 bit     │    1 bit     │         5 bit          │
+//! │              │              │              │                        │
+//! │      64      │      65      │      66      │  67                71  │
+//! └──────────────┴──────────────┴──────────────┴────────────────────────┘
 //! ```
 
 // item constants
@@ -62,22 +62,35 @@ const TYPE_SHIFT: u32 = 24;
 // olen/del/typed
 /// A mask to get the optional data length in bytes from the item header's flags
 /// field
-const OLEN_MASK: u8 = 0b00111111;
+const OLEN_MASK: u8 = 0b00011111;
+/// The maximum length of the optional data in bytes, as limited by the width
+/// of the length field
+pub(crate) const OLEN_MAX: usize = OLEN_MASK as usize;
 /// A mask to get the bit indicating the item value should be treated as a
 /// typed value from the item header's flags field
 const TYPED_MASK: u8 = 0b10000000;
+/// A mask to get the bit indicating the item value is compressed from the item
+/// header's flags field
+const COMPRESSED_MASK: u8 = 0b01000000;
+/// A mask to get the bit indicating the item is a chunk of a large item from
+/// the item header's flags field
+const CHUNK_MASK: u8 = 0b00100000;
 
+use super::large::LARGE_ITEM_MANIFEST_LEN;
 use core::convert::TryFrom;
 
 #[derive(Copy, Clone, Debug)]
 pub(super) enum ValueType {
     U64,
+    /// The manifest of a large item, see [`crate::item::LargeItem`]
+    Large,
 }
 
 impl ValueType {
     pub fn len(&self) -> u32 {
         (match self {
             Self::U64 => std::mem::size_of::<u64>(),
+            Self::Large => LARGE_ITEM_MANIFEST_LEN,
         }) as u32
     }
 }
@@ -87,6 +100,7 @@ impl TryFrom<u8> for ValueType {
     fn try_from(other: u8) -> Result<Self, <Self as TryFrom<u8>>::Error> {
         match other {
             0 => Ok(Self::U64),
+            1 => Ok(Self::Large),
             _ => Err(()),
         }
     }
@@ -97,6 +111,7 @@ impl Into<u8> for ValueType {
     fn into(self) -> u8 {
         match self {
             Self::U64 => 0,
+            Self::Large => 1,
         }
     }
 }
@@ -112,7 +127,7 @@ pub struct ItemHeader {
     #[cfg(feature = "magic")]
     magic: u32,
     len: u32,  // packs vlen:24 klen:8
-    flags: u8, // packs is_num:1, deleted:1, olen:6
+    flags: u8, // packs is_num:1, compressed:1, olen:6
 }
 
 impl ItemHeader {
@@ -165,6 +180,34 @@ impl ItemHeader {
         self.flags & TYPED_MASK != 0
     }
 
+    /// Is the item value compressed?
+    #[inline]
+    pub fn is_compressed(&self) -> bool {
+        self.flags & COMPRESSED_MASK != 0
+    }
+
+    /// Mark the item value as compressed
+    #[inline]
+    pub fn set_compressed(&mut self, compressed: bool) {
+        if compressed {
+            self.flags |= COMPRESSED_MASK;
+        } else {
+            self.flags &= !COMPRESSED_MASK;
+        }
+    }
+
+    /// Is the item a chunk of a large item?
+    #[inline]
+    pub fn is_chunk(&self) -> bool {
+        self.flags & CHUNK_MASK != 0
+    }
+
+    /// Mark the item as a chunk of a large item
+    #[inline]
+    pub fn set_chunk(&mut self) {
+        self.flags |= CHUNK_MASK;
+    }
+
     pub(super) fn value_type(&self) -> Option<ValueType> {
         if self.is_typed() {
             ValueType::try_from((self.len >> TYPE_SHIFT) as u8).ok()
@@ -232,6 +275,8 @@ impl std::fmt::Debug for ItemHeader {
             .field("klen", &self.klen())
             .field("vlen", &self.vlen())
             .field("type", &self.value_type())
+            .field("compressed", &self.is_compressed())
+            .field("chunk", &self.is_chunk())
             .field("olen", &self.olen())
             .finish()
     }
@@ -246,6 +291,8 @@ impl std::fmt::Debug for ItemHeader {
             .field("klen", &self.klen())
             .field("vlen", &self.vlen())
             .field("typed", &self.is_typed())
+            .field("compressed", &self.is_compressed())
+            .field("chunk", &self.is_chunk())
             .field("olen", &self.olen())
             .finish()
     }
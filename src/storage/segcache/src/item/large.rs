@@ -0,0 +1,120 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! Large items are values which do not fit within a single segment. They are
+//! split into chunks which are stored as ordinary items, and a small manifest
+//! is stored under the item's key.
+//!
+//! Manifest:
+//! ```text
+//! ┌──────────────────────────────┬──────────────┬──────────────┐
+//! │              ID              │     VLEN     │    CHUNKS    │
+//! │                              │              │              │
+//! │            64 bit            │    32 bit    │    32 bit    │
+//! │                              │              │              │
+//! │0                           63│64          95│96         127│
+//! └──────────────────────────────┴──────────────┴──────────────┘
+//! ```
+//!
+//! Each chunk is keyed by a reserved prefix byte, the manifest id, and the
+//! chunk index, so the chunks of a large item are never confused with those of
+//! an item which previously had the same key. Keys in the chunk namespace are
+//! rejected by the public API, so clients cannot read or overwrite chunks. All
+//! chunks and the manifest are written with the same TTL and so share a TTL
+//! bucket. A manifest whose chunks have been evicted or expired is treated as a
+//! miss and removed on access, and the chunks of an evicted manifest are
+//! removed after the eviction.
+
+/// The length of the key used for each chunk
+pub(crate) const CHUNK_KEY_LEN: usize = 13;
+
+/// The first byte of every chunk key. This is never the first byte of a UTF-8
+/// string, so it does not collide with text keys.
+const CHUNK_KEY_PREFIX: u8 = 0xff;
+
+/// The length of the encoded manifest in bytes
+pub(crate) const LARGE_ITEM_MANIFEST_LEN: usize = 16;
+
+/// The manifest which describes how a large item is split into chunks.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct LargeItem {
+    id: u64,
+    len: u32,
+    chunks: u32,
+}
+
+impl LargeItem {
+    /// Create a manifest for a value of the given length which is split into
+    /// chunks of at most `chunk_size` bytes.
+    pub(crate) fn new(id: u64, len: usize, chunk_size: usize) -> Self {
+        Self {
+            id,
+            len: len as u32,
+            chunks: len.div_ceil(chunk_size) as u32,
+        }
+    }
+
+    /// Decode a manifest from the value bytes of an item.
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != LARGE_ITEM_MANIFEST_LEN {
+            return None;
+        }
+
+        Some(Self {
+            id: u64::from_be_bytes(bytes[0..8].try_into().ok()?),
+            len: u32::from_be_bytes(bytes[8..12].try_into().ok()?),
+            chunks: u32::from_be_bytes(bytes[12..16].try_into().ok()?),
+        })
+    }
+
+    /// Encode the manifest so it can be stored as the value of an item.
+    pub(crate) fn to_bytes(self) -> [u8; LARGE_ITEM_MANIFEST_LEN] {
+        let mut bytes = [0; LARGE_ITEM_MANIFEST_LEN];
+        bytes[0..8].copy_from_slice(&self.id.to_be_bytes());
+        bytes[8..12].copy_from_slice(&self.len.to_be_bytes());
+        bytes[12..16].copy_from_slice(&self.chunks.to_be_bytes());
+        bytes
+    }
+
+    /// The total length of the value in bytes.
+    pub(crate) fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// The number of chunks the value is split into.
+    pub(crate) fn chunks(&self) -> u32 {
+        self.chunks
+    }
+
+    /// Returns the key which is used to store the chunk at the given index.
+    pub(crate) fn chunk_key(&self, index: u32) -> [u8; CHUNK_KEY_LEN] {
+        let mut key = [0; CHUNK_KEY_LEN];
+        key[0] = CHUNK_KEY_PREFIX;
+        key[1..9].copy_from_slice(&self.id.to_be_bytes());
+        key[9..13].copy_from_slice(&index.to_be_bytes());
+        key
+    }
+}
+
+/// Returns true if the key is in the namespace which is reserved for chunks.
+pub(crate) fn is_chunk_key(key: &[u8]) -> bool {
+    key.len() == CHUNK_KEY_LEN && key[0] == CHUNK_KEY_PREFIX
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest() {
+        let large = LargeItem::new(42, 10_000, 4096);
+        assert_eq!(large.len(), 10_000);
+        assert_eq!(large.chunks(), 3);
+        assert_eq!(LargeItem::from_bytes(&large.to_bytes()), Some(large));
+        assert_ne!(large.chunk_key(0), large.chunk_key(1));
+        assert!(is_chunk_key(&large.chunk_key(2)));
+        assert!(!is_chunk_key(b"coffee"));
+        assert!(LargeItem::from_bytes(b"short").is_none());
+    }
+}
@@ -5,6 +5,7 @@
 //! Items are the base unit of data stored within the cache.
 
 mod header;
+mod large;
 mod raw;
 mod reserved;
 
@@ -14,7 +15,8 @@ pub(crate) use header::ITEM_MAGIC_SIZE;
 use crate::SegcacheError;
 use crate::Value;
 
-pub(crate) use header::{ItemHeader, ITEM_HDR_SIZE};
+pub(crate) use header::{ItemHeader, ITEM_HDR_SIZE, OLEN_MAX};
+pub(crate) use large::{is_chunk_key, LargeItem, CHUNK_KEY_LEN, LARGE_ITEM_MANIFEST_LEN};
 pub(crate) use raw::RawItem;
 pub(crate) use reserved::ReservedItem;
 
@@ -22,12 +24,35 @@ pub(crate) use reserved::ReservedItem;
 pub struct Item {
     cas: u32,
     raw: RawItem,
+    // the value for items which are not stored contiguously as-is, such as
+    // large items which span multiple segments or compressed items
+    value: Option<Box<[u8]>>,
 }
 
 impl Item {
     /// Creates a new `Item` from its parts
     pub(crate) fn new(raw: RawItem, cas: u32) -> Self {
-        Item { cas, raw }
+        Item {
+            cas,
+            raw,
+            value: None,
+        }
+    }
+
+    /// Returns the manifest if this item is the head of a large item
+    pub(crate) fn large(&self) -> Option<LargeItem> {
+        self.raw.large()
+    }
+
+    /// Returns true if the stored value is compressed
+    pub(crate) fn is_compressed(&self) -> bool {
+        self.raw.is_compressed()
+    }
+
+    /// Attach the value which was assembled from chunks or decompressed
+    pub(crate) fn with_value(mut self, value: Box<[u8]>) -> Self {
+        self.value = Some(value);
+        self
     }
 
     /// If the `magic` or `debug` features are enabled, this allows for checking
@@ -48,7 +73,10 @@ impl Item {
 
     /// Borrow the item value
     pub fn value(&self) -> Value {
-        self.raw.value()
+        match &self.value {
+            Some(value) => Value::Bytes(value),
+            None => self.raw.value(),
+        }
     }
 
     /// CAS value for the item
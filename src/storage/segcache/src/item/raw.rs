@@ -78,7 +78,37 @@ impl RawItem {
             Some(ValueType::U64) => Value::U64(u64::from_be_bytes([
                 bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
             ])),
-            None => Value::Bytes(bytes),
+            // NOTE: for large items this is the encoded manifest, the value
+            // itself must be assembled from the chunks
+            Some(ValueType::Large) | None => Value::Bytes(bytes),
+        }
+    }
+
+    /// Returns true if the item value is compressed
+    pub(crate) fn is_compressed(&self) -> bool {
+        self.header().is_compressed()
+    }
+
+    /// Mark the item value as compressed
+    pub(crate) fn set_compressed(&mut self) {
+        unsafe { (*self.header_mut()).set_compressed(true) }
+    }
+
+    /// Returns true if the item is a chunk of a large item
+    pub(crate) fn is_chunk(&self) -> bool {
+        self.header().is_chunk()
+    }
+
+    /// Mark the item as a chunk of a large item
+    pub(crate) fn set_chunk(&mut self) {
+        unsafe { (*self.header_mut()).set_chunk() }
+    }
+
+    /// Returns the manifest if this item is the head of a large item
+    pub(crate) fn large(&self) -> Option<LargeItem> {
+        match (self.header().value_type(), self.value()) {
+            (Some(ValueType::Large), Value::Bytes(bytes)) => LargeItem::from_bytes(bytes),
+            _ => None,
         }
     }
 
@@ -158,6 +188,32 @@ impl RawItem {
         }
     }
 
+    /// Copy the manifest for a large item into the item
+    pub(crate) fn define_large(&mut self, key: &[u8], large: LargeItem, optional: &[u8]) {
+        unsafe {
+            (*self.header_mut()).init();
+            (*self.header_mut()).set_type(Some(ValueType::Large));
+            (*self.header_mut()).set_olen(optional.len() as u8);
+            std::ptr::copy_nonoverlapping(
+                optional.as_ptr(),
+                self.data.add(self.optional_offset()),
+                optional.len(),
+            );
+            (*self.header_mut()).set_klen(key.len() as u8);
+            std::ptr::copy_nonoverlapping(
+                key.as_ptr(),
+                self.data.add(self.key_offset()),
+                key.len(),
+            );
+            let bytes = large.to_bytes();
+            std::ptr::copy_nonoverlapping(
+                bytes.as_ptr(),
+                self.data.add(self.value_offset()),
+                bytes.len(),
+            );
+        }
+    }
+
     // Gets the offset to the optional data
     #[inline]
     fn optional_offset(&self) -> usize {
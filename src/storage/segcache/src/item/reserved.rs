@@ -5,6 +5,7 @@
 //! A reserved item is an item which has been allocated, but has not been
 //! defined or linked in the hashtable.
 
+use crate::LargeItem;
 use crate::RawItem;
 use crate::Value;
 use core::num::NonZeroU32;
@@ -29,6 +30,21 @@ impl ReservedItem {
         self.item.define(key, value, optional)
     }
 
+    /// Store the key, large item manifest, and optional data into the item
+    pub fn define_large(&mut self, key: &[u8], large: LargeItem, optional: &[u8]) {
+        self.item.define_large(key, large, optional)
+    }
+
+    /// Mark the value stored in the item as compressed
+    pub fn set_compressed(&mut self) {
+        self.item.set_compressed()
+    }
+
+    /// Mark the item as a chunk of a large item
+    pub fn set_chunk(&mut self) {
+        self.item.set_chunk()
+    }
+
     /// Get the `RawItem` that backs the `ReservedItem`
     pub fn item(&self) -> RawItem {
         self.item
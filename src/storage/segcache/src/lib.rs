@@ -18,8 +18,9 @@
 //! * eager expiration of items
 //! * low metadata overhead
 //!
-//! Non-goals:
-//! * not designed for concurrent access
+//! A [`ConcurrentSegcache`] partitions the keyspace across several independent
+//! caches so that it may be shared between threads. The keyspace may also be
+//! divided into namespaces by key prefix, each with its own share of the heap.
 //!
 
 // macro includes
@@ -35,17 +36,25 @@ use std::convert::TryInto;
 
 // NOTE: this represents the versioning of the internal data layout and must be
 // incremented when breaking changes are made to the datastructures
-const VERSION: u64 = 0;
+const VERSION: u64 = 1;
 
 // submodules
+mod admission;
 mod builder;
+mod clock;
+mod compression;
+mod concurrent;
 mod error;
 mod eviction;
 mod hashtable;
 mod item;
+mod namespace;
 mod rand;
+mod scan;
 mod segcache;
 mod segments;
+mod snapshot;
+mod stats;
 mod ttl_buckets;
 mod value;
 
@@ -58,16 +67,26 @@ mod tests;
 
 // publicly exported items from submodules
 pub use crate::segcache::Segcache;
+pub use admission::Admission;
 pub use builder::Builder;
+#[cfg(feature = "simulator")]
+pub use clock::advance_clock;
+pub use compression::Compression;
+pub use concurrent::{ConcurrentSegcache, ItemGuard, ShardsGuard};
 pub use error::SegcacheError;
 pub use eviction::Policy;
 pub use item::Item;
+pub use scan::KeyInfo;
+pub use stats::{SegmentStats, TtlStats};
 pub use value::Value;
 
 // items from submodules which are imported for convenience to the crate level
 pub(crate) use crate::rand::*;
+pub(crate) use admission::TinyLfu;
+pub(crate) use compression::decompress;
 pub(crate) use hashtable::*;
 pub(crate) use item::*;
+pub(crate) use namespace::*;
 pub(crate) use segments::*;
 pub(crate) use ttl_buckets::*;
 
@@ -58,6 +58,18 @@ pub static SEGMENT_CLEAR: Counter = Counter::new();
 )]
 pub static SEGMENT_EXPIRE: Counter = Counter::new();
 
+#[metric(
+    name = "segment_demote",
+    description = "number of segments evicted from memory into the flash tier"
+)]
+pub static SEGMENT_DEMOTE: Counter = Counter::new();
+
+#[metric(
+    name = "segment_flash_evict",
+    description = "number of segments evicted from the flash tier"
+)]
+pub static SEGMENT_FLASH_EVICT: Counter = Counter::new();
+
 #[metric(
     name = "clear_time",
     description = "amount of time, in nanoseconds, spent clearing segments"
@@ -76,6 +88,18 @@ pub static EXPIRE_TIME: Counter = Counter::new();
 )]
 pub static EVICT_TIME: Counter = Counter::new();
 
+#[metric(
+    name = "compress_time",
+    description = "amount of time, in nanoseconds, spent compressing values"
+)]
+pub static COMPRESS_TIME: Counter = Counter::new();
+
+#[metric(
+    name = "decompress_time",
+    description = "amount of time, in nanoseconds, spent decompressing values"
+)]
+pub static DECOMPRESS_TIME: Counter = Counter::new();
+
 #[metric(name = "segment_free", description = "current number of free segments")]
 pub static SEGMENT_FREE: Gauge = Gauge::new();
 
@@ -116,6 +140,24 @@ pub static HASH_REMOVE: Counter = Counter::new();
 )]
 pub static HASH_LOOKUP: Counter = Counter::new();
 
+#[metric(
+    name = "hash_grow",
+    description = "number of times the hash table has started growing"
+)]
+pub static HASH_GROW: Counter = Counter::new();
+
+#[metric(
+    name = "hash_migrate",
+    description = "number of hash buckets migrated while growing the hash table"
+)]
+pub static HASH_MIGRATE: Counter = Counter::new();
+
+#[metric(
+    name = "hash_migrate_pending",
+    description = "current number of hash buckets waiting to be migrated"
+)]
+pub static HASH_MIGRATE_PENDING: Gauge = Gauge::new();
+
 // item related
 #[metric(
     name = "item_allocate",
@@ -159,6 +201,48 @@ pub static ITEM_COMPACTED: Counter = Counter::new();
 )]
 pub static ITEM_RELINK: Counter = Counter::new();
 
+#[metric(
+    name = "item_promote",
+    description = "number of items copied from the flash tier back into memory"
+)]
+pub static ITEM_PROMOTE: Counter = Counter::new();
+
+#[metric(
+    name = "item_large_insert",
+    description = "number of items inserted which span multiple segments"
+)]
+pub static ITEM_LARGE_INSERT: Counter = Counter::new();
+
+#[metric(
+    name = "item_large_incomplete",
+    description = "number of large items found with missing chunks"
+)]
+pub static ITEM_LARGE_INCOMPLETE: Counter = Counter::new();
+
+#[metric(
+    name = "item_compress",
+    description = "number of items which were stored compressed"
+)]
+pub static ITEM_COMPRESS: Counter = Counter::new();
+
+#[metric(
+    name = "item_compress_bytes_in",
+    description = "number of value bytes before compression"
+)]
+pub static ITEM_COMPRESS_BYTES_IN: Counter = Counter::new();
+
+#[metric(
+    name = "item_compress_bytes_out",
+    description = "number of value bytes after compression"
+)]
+pub static ITEM_COMPRESS_BYTES_OUT: Counter = Counter::new();
+
+#[metric(
+    name = "item_decompress_ex",
+    description = "number of items which could not be decompressed"
+)]
+pub static ITEM_DECOMPRESS_EX: Counter = Counter::new();
+
 #[metric(name = "item_current", description = "current number of live items")]
 pub static ITEM_CURRENT: Gauge = Gauge::new();
 
@@ -176,3 +260,15 @@ pub static ITEM_DEAD: Gauge = Gauge::new();
     description = "current number of dead bytes for storing items"
 )]
 pub static ITEM_DEAD_BYTES: Gauge = Gauge::new();
+
+#[metric(
+    name = "admission_admit",
+    description = "number of writes admitted by the admission filter"
+)]
+pub static ADMISSION_ADMIT: Counter = Counter::new();
+
+#[metric(
+    name = "admission_reject",
+    description = "number of writes rejected by the admission filter"
+)]
+pub static ADMISSION_REJECT: Counter = Counter::new();
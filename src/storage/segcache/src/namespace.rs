@@ -0,0 +1,100 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! Namespaces partition a [`ConcurrentSegcache`](crate::ConcurrentSegcache) between tenants which share
+//! a single cache.
+//!
+//! Each namespace is selected by a key prefix and owns its own set of shards,
+//! whose segments are carved out of the total heap according to the quota for
+//! the namespace. Since a namespace can only allocate from its own segments,
+//! eviction is confined to the namespace, and a tenant which writes heavily
+//! only evicts its own items. Keys which do not match any prefix belong to
+//! the default namespace, which receives the remainder of the heap.
+//!
+//! When namespaces are configured, each namespace reports its own metrics
+//! which are named `namespace/<name>/<metric>`.
+
+#[cfg(feature = "metrics")]
+use metriken::{Counter, DynBoxedMetric, Gauge, MetricBuilder};
+
+use std::ops::Range;
+
+/// The name of the namespace which holds keys that do not match any prefix.
+pub(crate) const DEFAULT_NAMESPACE: &str = "default";
+
+/// The configuration for a namespace, as provided to the builder.
+#[derive(Clone, Debug)]
+pub(crate) struct NamespaceConfig {
+    pub name: String,
+    pub prefix: Box<[u8]>,
+    pub heap_size: usize,
+}
+
+/// A namespace within a `ConcurrentSegcache`, which owns a contiguous range
+/// of the shards.
+pub(crate) struct Namespace {
+    name: String,
+    prefix: Box<[u8]>,
+    shards: Range<usize>,
+}
+
+impl Namespace {
+    pub fn new(name: String, prefix: Box<[u8]>, shards: Range<usize>) -> Self {
+        Self {
+            name,
+            prefix,
+            shards,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn prefix(&self) -> &[u8] {
+        &self.prefix
+    }
+
+    pub fn shards(&self) -> Range<usize> {
+        self.shards.clone()
+    }
+}
+
+/// Per-namespace metrics, which are shared by the shards of the namespace.
+#[cfg(feature = "metrics")]
+pub(crate) struct NamespaceMetrics {
+    pub get_hit: DynBoxedMetric<Counter>,
+    pub get_miss: DynBoxedMetric<Counter>,
+    pub insert: DynBoxedMetric<Counter>,
+    pub insert_ex: DynBoxedMetric<Counter>,
+    pub segment_evict: DynBoxedMetric<Counter>,
+    pub segment_free: DynBoxedMetric<Gauge>,
+    pub segment_current: DynBoxedMetric<Gauge>,
+}
+
+#[cfg(feature = "metrics")]
+impl NamespaceMetrics {
+    pub fn new(namespace: &str) -> Self {
+        let counter = |metric: &str, description: &str| {
+            MetricBuilder::new(format!("namespace/{namespace}/{metric}"))
+                .description(description.to_string())
+                .build(Counter::new())
+        };
+        let gauge = |metric: &str, description: &str| {
+            MetricBuilder::new(format!("namespace/{namespace}/{metric}"))
+                .description(description.to_string())
+                .build(Gauge::new())
+        };
+
+        Self {
+            get_hit: counter("get_hit", "number of reads which found the key"),
+            get_miss: counter("get_miss", "number of reads which did not find the key"),
+            insert: counter("insert", "number of items inserted"),
+            insert_ex: counter("insert_ex", "number of items which could not be inserted"),
+            segment_evict: counter("segment_evict", "number of segments evicted"),
+            segment_free: gauge("segment_free", "current number of free segments"),
+            segment_current: gauge("segment_current", "total number of segments"),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! Cursor-based iteration over the live items in the cache.
+//!
+//! A scan returns a batch of items along with a cursor from which the scan may
+//! be resumed. The cursor encodes the segment id and offset of the next item to
+//! visit, using the same layout as the item info in the hashtable, so no state
+//! is held by the cache between calls. A cursor of zero starts a new scan and
+//! is returned again once the scan is complete.
+//!
+//! Since segments are reused as the cache is written, a scan provides weak
+//! guarantees: items which are written or removed during the scan may or may
+//! not be returned, and items may be returned more than once.
+//!
+//! Eviction and compaction may move items into segments which the cursor has
+//! already passed. To keep a scan from missing items which stay in the cache
+//! for its whole duration, each call pins the items of the cache for
+//! [`SCAN_LEASE`]. While items are pinned, segments are evicted whole instead
+//! of being merged or compacted, and items read from flash are not promoted.
+//! A scan whose calls are further apart than the lease may miss items.
+
+use std::time::Duration;
+
+/// How long each call to scan keeps items from being moved between segments.
+pub(crate) const SCAN_LEASE: crate::Duration = crate::Duration::from_secs(10);
+
+/// Metadata for a live item, returned when scanning the cache.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyInfo {
+    key: Box<[u8]>,
+    ttl: Option<Duration>,
+    size: usize,
+}
+
+impl KeyInfo {
+    pub(crate) fn new(key: &[u8], ttl: Option<Duration>, size: usize) -> Self {
+        Self {
+            key: key.into(),
+            ttl,
+            size,
+        }
+    }
+
+    /// The key of the item.
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// The remaining time-to-live for the item, `None` if it does not expire.
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+
+    /// The number of bytes the item occupies in its segment, including the
+    /// item header.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
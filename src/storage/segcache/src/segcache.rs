@@ -4,12 +4,38 @@
 
 //! Core datastructure
 
+use crate::scan::SCAN_LEASE;
+use crate::snapshot::{verify, SnapshotReader, SnapshotWriter, SNAPSHOT_BATCH};
 use crate::Value;
 use crate::*;
+use core::num::NonZeroU32;
 use std::cmp::min;
 
+#[cfg(feature = "metrics")]
+use std::sync::Arc;
+
 const RESERVE_RETRIES: usize = 3;
 
+/// The result of a lookup through a shared reference. See [`Segcache::lookup`].
+pub(crate) enum Lookup {
+    /// The item was found. The access should be recorded later.
+    Hit(Item, Access),
+    /// There is no item with the key. The access should be recorded later.
+    Miss(Access),
+    /// The lookup must be retried with exclusive access to the cache.
+    Exclusive,
+}
+
+/// A read which was served through a shared reference, and which has not yet
+/// been counted by the admission policy or the item frequency. See
+/// [`Segcache::access`].
+#[derive(Clone, Copy)]
+pub(crate) struct Access {
+    hash: u64,
+    // zero for a miss
+    item_info: u64,
+}
+
 /// A pre-allocated key-value store with eager expiration. It uses a
 /// segment-structured design that stores data in fixed-size segments, grouping
 /// objects with nearby expiration time into the same segment, and lifting most
@@ -19,6 +45,22 @@ pub struct Segcache {
     pub(crate) segments: Segments,
     pub(crate) ttl_buckets: TtlBuckets,
     pub(crate) time: Instant,
+    // the largest value which may be stored by splitting it across segments,
+    // zero if large items are disabled
+    pub(crate) large_max: usize,
+    // the number of value bytes held by each chunk of a large item
+    pub(crate) chunk_size: usize,
+    // the id to use for the next large item
+    pub(crate) next_large_id: u64,
+    // the codec used to compress values
+    pub(crate) compression: Compression,
+    // values smaller than this are not compressed
+    pub(crate) compression_threshold: usize,
+    // the frequency sketch used to filter writes, if admission is enabled
+    pub(crate) admission: Option<Box<TinyLfu>>,
+    // the metrics for the namespace which this cache belongs to, if any
+    #[cfg(feature = "metrics")]
+    pub(crate) namespace: Option<Arc<NamespaceMetrics>>,
 }
 
 impl Segcache {
@@ -57,6 +99,29 @@ impl Segcache {
         self.segments.items()
     }
 
+    /// Returns an upper bound on the size of a value which may be stored. This
+    /// is the large item limit when large items are enabled, and the segment
+    /// size otherwise.
+    ///
+    /// ```
+    /// use segcache::Segcache;
+    ///
+    /// const MB: usize = 1024 * 1024;
+    ///
+    /// let cache = Segcache::builder()
+    ///     .segment_size(MB as i32)
+    ///     .build()
+    ///     .expect("failed to create cache");
+    /// assert_eq!(cache.max_item_size(), MB);
+    /// ```
+    pub fn max_item_size(&self) -> usize {
+        if self.large_max > 0 {
+            self.large_max
+        } else {
+            self.segments.segment_size() as usize
+        }
+    }
+
     /// Get the item in the `Segcache` with the provided key
     ///
     /// ```
@@ -71,7 +136,171 @@ impl Segcache {
     /// assert_eq!(item.value(), b"strong");
     /// ```
     pub fn get(&mut self, key: &[u8]) -> Option<Item> {
-        self.hashtable.get(key, self.time, &mut self.segments)
+        if self.is_reserved(key) {
+            return None;
+        }
+
+        let hash = self.hashtable.hash(key);
+        if let Some(admission) = self.admission.as_mut() {
+            admission.record(hash);
+        }
+
+        self.migrate(hash);
+
+        let item = self
+            .hashtable
+            .get(key, self.time, &mut self.segments)
+            .and_then(|item| self.promote(key, item))
+            .and_then(|item| self.load(key, item, true));
+
+        self.count_get(item.is_some());
+
+        item
+    }
+
+    /// Counts a read for the namespace metrics, if any.
+    pub(crate) fn count_get(&self, hit: bool) {
+        #[cfg(feature = "metrics")]
+        if let Some(namespace) = self.namespace.as_ref() {
+            if hit {
+                namespace.get_hit.increment();
+            } else {
+                namespace.get_miss.increment();
+            }
+        }
+
+        #[cfg(not(feature = "metrics"))]
+        let _ = hit;
+    }
+
+    /// Lookup an item through a shared reference, which allows many readers to
+    /// proceed in parallel. Nothing is modified, and the namespace metrics are
+    /// left to the caller, so the returned [`Access`]
+    /// must be passed to [`access`](Self::access) once exclusive access is
+    /// available, to update the admission policy and the item frequency as
+    /// [`get`](Self::get) would have. Reads which may need to modify the cache,
+    /// such as those of items in the flash tier or of large items, return
+    /// [`Lookup::Exclusive`] and should be retried with `get`.
+    pub(crate) fn lookup(&self, key: &[u8]) -> Lookup {
+        if self.is_reserved(key) {
+            return Lookup::Exclusive;
+        }
+
+        let hash = self.hashtable.hash(key);
+
+        let (item, item_info) = match self.hashtable.peek(key, &self.segments) {
+            Some(found) => found,
+            None => {
+                return Lookup::Miss(Access { hash, item_info: 0 });
+            }
+        };
+
+        if item.large().is_some()
+            || get_seg_id(item_info).is_some_and(|id| self.segments.is_flash(id))
+        {
+            return Lookup::Exclusive;
+        }
+
+        let item = if item.is_compressed() {
+            // values which fail to decompress are removed by `get`
+            match item.value() {
+                Value::Bytes(bytes) => match decompress(bytes) {
+                    Some(value) => item.with_value(value.into_boxed_slice()),
+                    None => return Lookup::Exclusive,
+                },
+                Value::U64(_) => return Lookup::Exclusive,
+            }
+        } else {
+            item
+        };
+
+        Lookup::Hit(item, Access { hash, item_info })
+    }
+
+    /// Records a read which was served by [`lookup`](Self::lookup).
+    pub(crate) fn access(&mut self, access: Access) {
+        if let Some(admission) = self.admission.as_mut() {
+            admission.record(access.hash);
+        }
+
+        self.migrate(access.hash);
+
+        if access.item_info != 0 {
+            self.hashtable
+                .touch(access.hash, access.item_info, self.time);
+        }
+    }
+
+    /// Migrates the hashtable bucket for the hash, along with a few others, if
+    /// the hashtable is growing. This must happen before any item is borrowed
+    /// from segment memory, as items which do not fit into the new table are
+    /// removed, which may cause their segments to be compacted.
+    fn migrate(&mut self, hash: u64) {
+        let mut orphans = Vec::new();
+        self.hashtable.migrate(
+            hash,
+            &mut self.segments,
+            &mut self.ttl_buckets,
+            &mut orphans,
+        );
+        self.remove_orphans(orphans);
+    }
+
+    /// Copies an item which was read from the flash tier back into memory, so
+    /// that subsequent reads are served from memory. Large items are read from
+    /// flash in place. Returns the item at its current location, which may be
+    /// `None` if making room in memory evicted it from flash.
+    fn promote(&mut self, key: &[u8], item: Item) -> Option<Item> {
+        // promoting an item moves it behind the cursor of a scan
+        if item.large().is_some() || self.segments.items_pinned() {
+            return Some(item);
+        }
+
+        match self.hashtable.get_item_seg(key, &mut self.segments) {
+            Some(seg_id) if self.segments.is_flash(seg_id) => {}
+            _ => {
+                return Some(item);
+            }
+        }
+
+        // keep the remaining ttl, items which are about to expire are left
+        // where they are
+        let ttl = match self.ttl(key) {
+            Ok(Some(ttl)) if ttl.is_zero() => {
+                return Some(item);
+            }
+            Ok(ttl) => Duration::from_secs(min(
+                u32::MAX as u64,
+                ttl.map(|ttl| ttl.as_secs()).unwrap_or(0),
+            ) as u32),
+            Err(_) => {
+                return Some(item);
+            }
+        };
+
+        // the item must be copied out, as reserving space may cause the flash
+        // segment which holds it to be reused
+        let value = item.value().to_owned();
+        let optional = item.optional().map(|o| o.to_vec());
+        let compressed = item.is_compressed();
+
+        let optional = optional.as_deref().unwrap_or(&[]);
+        let size = item_size(key.len(), size_of(&value.as_value()), optional.len());
+
+        let result = self.reserve(size, ttl).and_then(|mut reserved| {
+            reserved.define(key, value.as_value(), optional);
+            if compressed {
+                reserved.set_compressed();
+            }
+            self.link(reserved)
+        });
+
+        #[cfg(feature = "metrics")]
+        if result.is_ok() {
+            ITEM_PROMOTE.increment();
+        }
+
+        self.hashtable.get_no_freq_incr(key, &mut self.segments)
     }
 
     /// Get the item in the `Segcache` with the provided key without
@@ -84,11 +313,20 @@ impl Segcache {
     /// assert!(cache.get_no_freq_incr(b"coffee").is_none());
     /// ```
     pub fn get_no_freq_incr(&mut self, key: &[u8]) -> Option<Item> {
-        self.hashtable.get_no_freq_incr(key, &mut self.segments)
+        if self.is_reserved(key) {
+            return None;
+        }
+
+        self.migrate(self.hashtable.hash(key));
+
+        let item = self.hashtable.get_no_freq_incr(key, &mut self.segments)?;
+        self.load(key, item, false)
     }
 
     /// Insert a new item into the cache. May return an error indicating that
-    /// the insert was not successful.
+    /// the insert was not successful. Writes which are dropped by the
+    /// admission policy return [`SegcacheError::Rejected`]. The optional data
+    /// is limited to 31 bytes.
     /// ```
     /// use segcache::{Policy, Segcache};
     /// use std::time::Duration;
@@ -111,41 +349,183 @@ impl Segcache {
         optional: Option<&[u8]>,
         ttl: std::time::Duration,
     ) -> Result<(), SegcacheError> {
+        if self.is_reserved(key) {
+            return Err(SegcacheError::ReservedKey);
+        }
+
         let value: Value = value.into();
 
         // default optional data is empty
         let optional = optional.unwrap_or(&[]);
 
-        // calculate size for item
-        let size = (((ITEM_HDR_SIZE + key.len() + size_of(&value) + optional.len()) >> 3) + 1) << 3;
+        // the length of the optional data must fit in the item header
+        if optional.len() > OLEN_MAX {
+            return Err(SegcacheError::OptionalOversized {
+                size: optional.len(),
+            });
+        }
 
         let ttl = Duration::from_secs(min(u32::MAX as u64, ttl.as_secs()) as u32);
 
-        // try to get a `ReservedItem`
+        // writes which are rejected by the admission filter are dropped
+        if !self.admit(key, ttl) {
+            return Err(SegcacheError::Rejected);
+        }
+
+        // compress values which meet the threshold
+        let compressed = match value {
+            Value::Bytes(bytes) if bytes.len() >= self.compression_threshold => {
+                self.compression.compress(bytes)
+            }
+            _ => None,
+        };
+        let value = match &compressed {
+            Some(compressed) => Value::Bytes(compressed),
+            None => value,
+        };
+
+        // calculate size for item
+        let size = item_size(key.len(), size_of(&value), optional.len());
+
+        // find any previous large item so its chunks can be released once the
+        // new item has been linked
+        let previous = if self.large_max > 0 {
+            self.hashtable
+                .get_no_freq_incr(key, &mut self.segments)
+                .and_then(|item| item.large())
+        } else {
+            None
+        };
+
+        let result = match value {
+            Value::Bytes(value)
+                if self.large_max > 0 && size > self.segments.segment_size() as usize =>
+            {
+                self.insert_large(key, value, optional, ttl, compressed.is_some())
+            }
+            value => self.reserve(size, ttl).and_then(|mut reserved| {
+                reserved.define(key, value, optional);
+                if compressed.is_some() {
+                    reserved.set_compressed();
+                }
+                self.link(reserved)
+            }),
+        };
+
+        if result.is_ok() {
+            if let Some(previous) = previous {
+                self.remove_chunks(previous, previous.chunks());
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        if let Some(namespace) = self.namespace.as_ref() {
+            if result.is_ok() {
+                namespace.insert.increment();
+            } else {
+                namespace.insert_ex.increment();
+            }
+        }
+
+        result
+    }
+
+    /// Records the write with the admission filter and returns true if it
+    /// should be stored. Updates to existing keys are always admitted, as are
+    /// all writes while there are free segments. Otherwise the key must be
+    /// accessed more frequently than the oldest item in the TTL bucket, which
+    /// is approximately the next item to be evicted.
+    fn admit(&mut self, key: &[u8], ttl: Duration) -> bool {
+        let admission = match self.admission.as_mut() {
+            Some(admission) => admission,
+            None => {
+                return true;
+            }
+        };
+
+        let hash = self.hashtable.hash(key);
+        admission.record(hash);
+
+        if self.segments.free() > 0
+            || self
+                .hashtable
+                .get_item_seg(key, &mut self.segments)
+                .is_some()
+        {
+            return true;
+        }
+
+        let victim = self
+            .ttl_buckets
+            .get_mut_bucket(ttl)
+            .head()
+            .and_then(|id| self.segments.oldest_item(id, &mut self.hashtable))
+            .map(|item| self.hashtable.hash(item.key()));
+
+        let admit = match victim {
+            Some(victim) => admission.admit(hash, victim),
+            None => true,
+        };
+
+        #[cfg(feature = "metrics")]
+        if admit {
+            ADMISSION_ADMIT.increment();
+        } else {
+            ADMISSION_REJECT.increment();
+        }
+
+        admit
+    }
+
+    /// Reserves space for an item of the given size in a segment from the TTL
+    /// bucket for the TTL, evicting segments as necessary.
+    fn reserve(&mut self, size: usize, ttl: Duration) -> Result<ReservedItem, SegcacheError> {
+        #[cfg(feature = "metrics")]
+        let free = self.segments.free();
+
+        let result = self.reserve_segment(size, ttl);
+
+        #[cfg(feature = "metrics")]
+        self.update_free(free);
+
+        result
+    }
+
+    fn reserve_segment(
+        &mut self,
+        size: usize,
+        ttl: Duration,
+    ) -> Result<ReservedItem, SegcacheError> {
         let mut retries = RESERVE_RETRIES;
-        let reserved;
         loop {
             match self
                 .ttl_buckets
                 .get_mut_bucket(ttl)
                 .reserve(size, &mut self.segments)
             {
-                Ok(mut reserved_item) => {
-                    reserved_item.define(key, value, optional);
-                    reserved = reserved_item;
-                    break;
+                Ok(reserved_item) => {
+                    return Ok(reserved_item);
                 }
                 Err(TtlBucketsError::ItemOversized { size }) => {
                     return Err(SegcacheError::ItemOversized { size });
                 }
                 Err(TtlBucketsError::NoFreeSegments) => {
-                    if self
-                        .segments
-                        .evict(&mut self.ttl_buckets, &mut self.hashtable)
-                        .is_err()
-                    {
+                    let mut orphans = Vec::new();
+                    let result = self.segments.evict(
+                        &mut self.ttl_buckets,
+                        &mut self.hashtable,
+                        &mut orphans,
+                    );
+                    self.remove_orphans(orphans);
+
+                    if result.is_err() {
                         retries -= 1;
                     } else {
+                        #[cfg(feature = "metrics")]
+                        if let Some(namespace) = self.namespace.as_ref() {
+                            namespace.segment_evict.increment();
+                        }
+
                         // we successfully evicted a segment, return to start of
                         // loop to reserve the item
                         continue;
@@ -166,22 +546,26 @@ impl Segcache {
             }
             retries -= 1;
         }
+    }
 
+    /// Links a defined item into the hashtable.
+    fn link(&mut self, reserved: ReservedItem) -> Result<(), SegcacheError> {
         // insert into the hashtable, or roll-back by removing the item
         // TODO(bmartin): we can probably roll-back the offset and re-use the
         // space in the segment, currently we consume the space even if the
         // hashtable is overfull
-        if self
-            .hashtable
-            .insert(
-                reserved.item(),
-                reserved.seg(),
-                reserved.offset() as u64,
-                &mut self.ttl_buckets,
-                &mut self.segments,
-            )
-            .is_err()
-        {
+        let mut orphans = Vec::new();
+        let result = self.hashtable.insert(
+            reserved.item(),
+            reserved.seg(),
+            reserved.offset() as u64,
+            &mut self.ttl_buckets,
+            &mut self.segments,
+            &mut orphans,
+        );
+        self.remove_orphans(orphans);
+
+        if result.is_err() {
             // this just needs to alter the segment header and update stats
             let _ = self.segments.remove_at(
                 reserved.seg(),
@@ -195,6 +579,154 @@ impl Segcache {
         }
     }
 
+    /// Stores a value which is too large for a single segment by splitting it
+    /// into chunks. The chunks are linked first and the manifest is linked
+    /// last, so a partially written item is never visible.
+    fn insert_large(
+        &mut self,
+        key: &[u8],
+        value: &[u8],
+        optional: &[u8],
+        ttl: Duration,
+        compressed: bool,
+    ) -> Result<(), SegcacheError> {
+        if value.len() > self.large_max {
+            return Err(SegcacheError::ItemOversized { size: value.len() });
+        }
+
+        let large = LargeItem::new(self.next_large_id, value.len(), self.chunk_size);
+        self.next_large_id = self.next_large_id.wrapping_add(1);
+
+        for (index, chunk) in value.chunks(self.chunk_size).enumerate() {
+            let chunk_key = large.chunk_key(index as u32);
+            let result = self
+                .reserve(item_size(CHUNK_KEY_LEN, chunk.len(), 0), ttl)
+                .and_then(|mut reserved| {
+                    reserved.define(&chunk_key, Value::Bytes(chunk), &[]);
+                    reserved.set_chunk();
+                    self.link(reserved)
+                });
+            if let Err(e) = result {
+                self.remove_chunks(large, index as u32);
+                return Err(e);
+            }
+        }
+
+        let result = self
+            .reserve(
+                item_size(key.len(), LARGE_ITEM_MANIFEST_LEN, optional.len()),
+                ttl,
+            )
+            .and_then(|mut reserved| {
+                reserved.define_large(key, large, optional);
+                if compressed {
+                    reserved.set_compressed();
+                }
+                self.link(reserved)
+            });
+
+        match result {
+            Ok(()) => {
+                #[cfg(feature = "metrics")]
+                ITEM_LARGE_INSERT.increment();
+
+                Ok(())
+            }
+            Err(e) => {
+                self.remove_chunks(large, large.chunks());
+                Err(e)
+            }
+        }
+    }
+
+    /// Prepares an item which was found in the hashtable to be returned. The
+    /// value of a large item is assembled from its chunks, and compressed
+    /// values are decompressed. If any chunk has been evicted, or the value
+    /// cannot be decompressed, the item is removed and treated as a miss.
+    fn load(&mut self, key: &[u8], item: Item, freq_incr: bool) -> Option<Item> {
+        let item = match item.large() {
+            Some(large) => {
+                let mut value = Vec::with_capacity(large.len());
+                for index in 0..large.chunks() {
+                    let chunk_key = large.chunk_key(index);
+                    let chunk = if freq_incr {
+                        self.hashtable
+                            .get(&chunk_key, self.time, &mut self.segments)
+                    } else {
+                        self.hashtable
+                            .get_no_freq_incr(&chunk_key, &mut self.segments)
+                    };
+                    match chunk.as_ref().map(|chunk| chunk.value()) {
+                        Some(Value::Bytes(bytes)) => value.extend_from_slice(bytes),
+                        _ => break,
+                    }
+                }
+
+                if value.len() != large.len() {
+                    #[cfg(feature = "metrics")]
+                    ITEM_LARGE_INCOMPLETE.increment();
+
+                    self.delete(key);
+                    return None;
+                }
+
+                item.with_value(value.into_boxed_slice())
+            }
+            None => item,
+        };
+
+        if !item.is_compressed() {
+            return Some(item);
+        }
+
+        let value = match item.value() {
+            Value::Bytes(bytes) => decompress(bytes),
+            Value::U64(_) => None,
+        };
+
+        match value {
+            Some(value) => Some(item.with_value(value.into_boxed_slice())),
+            None => {
+                self.delete(key);
+                None
+            }
+        }
+    }
+
+    /// Removes the chunks of large items whose manifest was evicted. Removing
+    /// a chunk may migrate hashtable buckets, which can orphan further large
+    /// items, so this continues until none remain.
+    pub(crate) fn remove_orphans(&mut self, mut orphans: Vec<LargeItem>) {
+        while let Some(large) = orphans.pop() {
+            self.unlink_chunks(large, large.chunks(), &mut orphans);
+        }
+    }
+
+    /// Returns true if the key is reserved for the chunks of large items.
+    fn is_reserved(&self, key: &[u8]) -> bool {
+        self.large_max > 0 && is_chunk_key(key)
+    }
+
+    /// Removes the first `count` chunks of a large item.
+    fn remove_chunks(&mut self, large: LargeItem, count: u32) {
+        let mut orphans = Vec::new();
+        self.unlink_chunks(large, count, &mut orphans);
+        self.remove_orphans(orphans);
+    }
+
+    /// Removes the first `count` chunks of a large item. The manifests of any
+    /// large items which are evicted along the way are appended to `orphans`.
+    fn unlink_chunks(&mut self, large: LargeItem, count: u32, orphans: &mut Vec<LargeItem>) {
+        for index in 0..count {
+            self.hashtable.delete(
+                &large.chunk_key(index),
+                &mut self.ttl_buckets,
+                &mut self.segments,
+                orphans,
+            );
+        }
+    }
+
     /// Performs a CAS operation, inserting the item only if the CAS value
     /// matches the current value for that item.
     ///
@@ -232,6 +764,10 @@ impl Segcache {
         ttl: std::time::Duration,
         cas: u32,
     ) -> Result<(), SegcacheError> {
+        if self.is_reserved(key) {
+            return Err(SegcacheError::NotFound);
+        }
+
         match self.hashtable.try_update_cas(key, cas, &mut self.segments) {
             Ok(()) => self.insert(key, value, optional, ttl),
             Err(e) => Err(e),
@@ -257,8 +793,349 @@ impl Segcache {
     /// ```
     // TODO(bmartin): a result would be better here
     pub fn delete(&mut self, key: &[u8]) -> bool {
-        self.hashtable
-            .delete(key, &mut self.ttl_buckets, &mut self.segments)
+        if self.is_reserved(key) {
+            return false;
+        }
+
+        let mut orphans = Vec::new();
+
+        if self.large_max > 0 {
+            if let Some(large) = self
+                .hashtable
+                .get_no_freq_incr(key, &mut self.segments)
+                .and_then(|item| item.large())
+            {
+                self.unlink_chunks(large, large.chunks(), &mut orphans);
+            }
+        }
+
+        let deleted =
+            self.hashtable
+                .delete(key, &mut self.ttl_buckets, &mut self.segments, &mut orphans);
+        self.remove_orphans(orphans);
+
+        deleted
+    }
+
+    /// Returns the remaining time-to-live for the item with the provided key.
+    /// Items do not carry their own expiry, so this is derived from the
+    /// expiration time of the segment which holds the item and has the same
+    /// granularity as the TTL bucket. Items stored without a TTL, or with a
+    /// TTL beyond the largest bucket, return `None`.
+    ///
+    /// ```
+    /// use segcache::{Policy, Segcache, SegcacheError};
+    /// use std::time::Duration;
+    ///
+    /// let mut cache = Segcache::builder().build().expect("failed to create cache");
+    ///
+    /// assert_eq!(cache.ttl(b"coffee"), Err(SegcacheError::NotFound));
+    ///
+    /// cache.insert(b"coffee", b"strong", None, Duration::ZERO);
+    /// assert_eq!(cache.ttl(b"coffee"), Ok(None));
+    ///
+    /// cache.insert(b"coffee", b"strong", None, Duration::from_secs(60));
+    /// let ttl = cache.ttl(b"coffee").unwrap().expect("no ttl");
+    /// assert!(ttl <= Duration::from_secs(60));
+    /// ```
+    pub fn ttl(&mut self, key: &[u8]) -> Result<Option<std::time::Duration>, SegcacheError> {
+        if self.is_reserved(key) {
+            return Err(SegcacheError::NotFound);
+        }
+
+        let seg_id = self
+            .hashtable
+            .get_item_seg(key, &mut self.segments)
+            .ok_or(SegcacheError::NotFound)?;
+        let segment = self
+            .segments
+            .get_mut(seg_id)
+            .map_err(|_| SegcacheError::DataCorrupted)?;
+
+        let ttl = segment.ttl();
+        if self.ttl_buckets.is_max_ttl(ttl) {
+            return Ok(None);
+        }
+
+        let expire_at = segment.create_at() + ttl;
+        let now = clock::now();
+        if expire_at <= now {
+            // the segment has expired but has not been reclaimed yet
+            return Err(SegcacheError::NotFound);
+        }
+
+        Ok(Some(std::time::Duration::from_secs(
+            (expire_at - now).as_secs() as u64,
+        )))
+    }
+
+    /// Changes the time-to-live for the item with the provided key. Since items
+    /// are grouped into segments by TTL, this re-inserts the item into a
+    /// segment from the appropriate TTL bucket. A TTL of zero removes the
+    /// expiration.
+    ///
+    /// ```
+    /// use segcache::{Policy, Segcache, SegcacheError};
+    /// use std::time::Duration;
+    ///
+    /// let mut cache = Segcache::builder().build().expect("failed to create cache");
+    ///
+    /// cache.insert(b"coffee", b"strong", None, Duration::ZERO);
+    /// assert!(cache.set_ttl(b"coffee", Duration::from_secs(60)).is_ok());
+    /// assert!(cache.ttl(b"coffee").unwrap().is_some());
+    ///
+    /// let item = cache.get(b"coffee").expect("didn't get item back");
+    /// assert_eq!(item.value(), b"strong");
+    /// ```
+    pub fn set_ttl(&mut self, key: &[u8], ttl: std::time::Duration) -> Result<(), SegcacheError> {
+        let (value, optional) = {
+            let item = self.get_no_freq_incr(key).ok_or(SegcacheError::NotFound)?;
+            (item.value().to_owned(), item.optional().map(|o| o.to_vec()))
+        };
+
+        self.insert(key, value.as_value(), optional.as_deref(), ttl)
+    }
+
+    /// Returns metadata for up to `count` live items, starting from the cursor,
+    /// along with the cursor from which to resume. A cursor of zero starts a
+    /// new scan, and is returned when the scan is complete. Items which are
+    /// written or removed during a scan may or may not be returned, and items
+    /// may be returned more than once.
+    ///
+    /// Each call keeps items from being moved between segments for the next
+    /// ten seconds, so that items which stay in the cache are not missed. A
+    /// scan which waits longer than that between calls may miss items.
+    ///
+    /// ```
+    /// use segcache::Segcache;
+    /// use std::time::Duration;
+    ///
+    /// let mut cache = Segcache::builder().build().expect("failed to create cache");
+    /// cache.insert(b"coffee", b"strong", None, Duration::ZERO);
+    /// cache.insert(b"tea", b"green", None, Duration::ZERO);
+    ///
+    /// let mut keys = Vec::new();
+    /// let mut cursor = 0;
+    /// loop {
+    ///     let (next, items) = cache.scan(cursor, 1);
+    ///     keys.extend(items.iter().map(|item| item.key().to_vec()));
+    ///     if next == 0 {
+    ///         break;
+    ///     }
+    ///     cursor = next;
+    /// }
+    /// assert_eq!(keys.len(), 2);
+    /// ```
+    pub fn scan(&mut self, cursor: u64, count: usize) -> (u64, Vec<KeyInfo>) {
+        self.segments.pin_items(SCAN_LEASE);
+
+        let (mut seg_id, mut offset) = match get_seg_id(cursor) {
+            Some(id) => (id.get(), get_offset(cursor) as usize),
+            None => (1, 0),
+        };
+
+        let now = clock::now();
+        let mut items = Vec::new();
+        while items.len() < count && seg_id <= self.segments.total() {
+            // safety: segment ids start from 1
+            let id = unsafe { NonZeroU32::new_unchecked(seg_id) };
+            let mut segment = self.segments.get_mut(id).unwrap();
+
+            match segment.next_live_item(offset, &mut self.hashtable) {
+                Some((item_offset, item)) => {
+                    let ttl = segment.ttl();
+                    let ttl = if self.ttl_buckets.is_max_ttl(ttl) {
+                        None
+                    } else {
+                        let expire_at = segment.create_at() + ttl;
+                        let remaining = expire_at
+                            .checked_duration_since(now)
+                            .map(|d| d.as_secs())
+                            .unwrap_or(0);
+                        Some(std::time::Duration::from_secs(remaining as u64))
+                    };
+                    items.push(KeyInfo::new(item.key(), ttl, item.size()));
+                    offset = item_offset + item.size();
+                }
+                None => {
+                    seg_id += 1;
+                    offset = 0;
+                }
+            }
+        }
+
+        let cursor = match NonZeroU32::new(seg_id) {
+            Some(id) if seg_id <= self.segments.total() => build_item_info(0, id, offset as u64),
+            _ => 0,
+        };
+
+        (cursor, items)
+    }
+
+    /// Returns the occupancy of each TTL bucket which holds segments or in
+    /// which segments have been merged, ordered by bucket. This walks every
+    /// segment in the cache.
+    ///
+    /// ```
+    /// use segcache::Segcache;
+    /// use std::time::Duration;
+    ///
+    /// let mut cache = Segcache::builder().build().expect("failed to create cache");
+    /// cache.insert(b"coffee", b"strong", None, Duration::from_secs(60));
+    ///
+    /// let stats = cache.ttl_stats();
+    /// assert_eq!(stats.len(), 1);
+    /// assert_eq!(stats[0].items(), 1);
+    /// assert_eq!(stats[0].dead_bytes(), 0);
+    /// ```
+    pub fn ttl_stats(&mut self) -> Vec<TtlStats> {
+        let now = clock::now();
+        let mut stats = Vec::new();
+
+        for (bucket, ttl_bucket) in self.ttl_buckets.buckets.iter().enumerate() {
+            let mut entry = TtlStats::new(
+                bucket,
+                std::time::Duration::from_secs(ttl_bucket.ttl() as u64),
+                ttl_bucket.merges() as u64,
+            );
+
+            let mut next = ttl_bucket.head();
+            while let Some(id) = next {
+                let segment = self.segments.get_mut(id).unwrap();
+                let age = now
+                    .checked_duration_since(segment.create_at())
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+                entry.add_segment(
+                    segment.live_items() as usize,
+                    segment.live_bytes() as usize,
+                    segment.write_offset() as usize,
+                    std::time::Duration::from_secs(age as u64),
+                );
+                next = segment.next_seg();
+            }
+
+            if entry.segments() > 0 || entry.merges() > 0 {
+                stats.push(entry);
+            }
+        }
+
+        stats
+    }
+
+    /// Returns a summary of the segments, including the age distribution of
+    /// those which are in use. This walks every segment in the cache.
+    ///
+    /// ```
+    /// use segcache::Segcache;
+    /// use std::time::Duration;
+    ///
+    /// let mut cache = Segcache::builder().build().expect("failed to create cache");
+    /// cache.insert(b"coffee", b"strong", None, Duration::from_secs(60));
+    ///
+    /// let stats = cache.segment_stats();
+    /// assert_eq!(stats.total() - stats.free(), 1);
+    /// assert_eq!(stats.ages().map(|(_, count)| count).sum::<usize>(), 1);
+    /// ```
+    pub fn segment_stats(&mut self) -> SegmentStats {
+        let now = clock::now();
+        let mut stats = SegmentStats::new(
+            self.segments.segment_size() as usize,
+            self.segments.total() as usize,
+            self.segments.free(),
+        );
+
+        for ttl_bucket in self.ttl_buckets.buckets.iter() {
+            stats.add_merges(ttl_bucket.merges() as u64);
+
+            let mut next = ttl_bucket.head();
+            while let Some(id) = next {
+                let segment = self.segments.get_mut(id).unwrap();
+                let age = now
+                    .checked_duration_since(segment.create_at())
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+                stats.add_segment(std::time::Duration::from_secs(age as u64));
+                next = segment.next_seg();
+            }
+        }
+
+        stats
+    }
+
+    /// Writes the live items to a portable snapshot, which may be loaded into
+    /// another cache with [`Segcache::load_snapshot`]. Returns the number of
+    /// items which were written. The writer should be buffered.
+    ///
+    /// ```
+    /// use segcache::Segcache;
+    /// use std::io::Cursor;
+    /// use std::time::Duration;
+    ///
+    /// let mut cache = Segcache::builder().build().expect("failed to create cache");
+    /// cache.insert(b"coffee", b"strong", None, Duration::from_secs(3600));
+    ///
+    /// let mut snapshot = Vec::new();
+    /// assert_eq!(cache.save_snapshot(&mut snapshot).unwrap(), 1);
+    ///
+    /// let mut restored = Segcache::builder().build().expect("failed to create cache");
+    /// assert_eq!(restored.load_snapshot(Cursor::new(snapshot)).unwrap(), 1);
+    /// assert_eq!(restored.get(b"coffee").unwrap().value(), b"strong");
+    /// ```
+    pub fn save_snapshot<W: std::io::Write>(&mut self, writer: W) -> std::io::Result<usize> {
+        let mut snapshot = SnapshotWriter::new(writer)?;
+
+        let mut cursor = 0;
+        loop {
+            let (next, keys) = self.scan(cursor, SNAPSHOT_BATCH);
+            for info in keys {
+                if let Some(item) = self.get_no_freq_incr(info.key()) {
+                    snapshot.item(&item, info.ttl())?;
+                }
+            }
+
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+
+        snapshot.finish()
+    }
+
+    /// Inserts the items from a snapshot which was written by
+    /// [`Segcache::save_snapshot`], keeping the expiry time of each item.
+    /// Items which have expired since the snapshot was written are skipped.
+    /// Returns the number of items which were inserted, which does not include
+    /// items rejected by the admission policy.
+    ///
+    /// Since the checksum can only be verified once the entire snapshot is
+    /// read, the snapshot is read twice: once to verify it and again to insert
+    /// the items. An invalid snapshot returns an error and leaves the contents
+    /// of the cache unchanged.
+    pub fn load_snapshot<R: std::io::Read + std::io::Seek>(
+        &mut self,
+        mut reader: R,
+    ) -> std::io::Result<usize> {
+        verify(&mut reader)?;
+        let mut snapshot = SnapshotReader::new(reader)?;
+
+        let mut loaded = 0;
+        while let Some(item) = snapshot.next_item()? {
+            let ttl = match item.ttl() {
+                Some(ttl) => ttl,
+                None => continue,
+            };
+
+            if self
+                .insert(item.key(), item.value(), item.optional(), ttl)
+                .is_ok()
+            {
+                loaded += 1;
+            }
+        }
+
+        Ok(loaded)
     }
 
     /// Loops through the TTL Buckets to handle eager expiration, returns the
@@ -283,15 +1160,44 @@ impl Segcache {
     /// assert!(cache.get(b"coffee").is_none());
     /// ```
     pub fn expire(&mut self) -> usize {
-        self.time = Instant::now();
-        self.ttl_buckets
-            .expire(&mut self.hashtable, &mut self.segments)
+        #[cfg(feature = "metrics")]
+        let free = self.segments.free();
+
+        self.time = clock::now();
+        let expired = self
+            .ttl_buckets
+            .expire(&mut self.hashtable, &mut self.segments);
+
+        #[cfg(feature = "metrics")]
+        self.update_free(free);
+
+        expired
     }
 
     pub fn clear(&mut self) -> usize {
-        self.time = Instant::now();
-        self.ttl_buckets
-            .clear(&mut self.hashtable, &mut self.segments)
+        #[cfg(feature = "metrics")]
+        let free = self.segments.free();
+
+        self.time = clock::now();
+        let cleared = self
+            .ttl_buckets
+            .clear(&mut self.hashtable, &mut self.segments);
+
+        #[cfg(feature = "metrics")]
+        self.update_free(free);
+
+        cleared
+    }
+
+    /// Reports the change in the number of free segments since the provided
+    /// count to the namespace metrics.
+    #[cfg(feature = "metrics")]
+    fn update_free(&self, previous: usize) {
+        if let Some(namespace) = self.namespace.as_ref() {
+            namespace
+                .segment_free
+                .add(self.segments.free() as i64 - previous as i64);
+        }
     }
 
     /// Checks the integrity of all segments
@@ -309,6 +1215,12 @@ impl Segcache {
     /// Returns an error if the key is invalid, the item is not found, or the
     /// stored value is not a numeric type.
     pub fn wrapping_add(&mut self, key: &[u8], rhs: u64) -> Result<Item, SegcacheError> {
+        if self.is_reserved(key) {
+            return Err(SegcacheError::NotFound);
+        }
+
+        self.migrate(self.hashtable.hash(key));
+
         let mut item = self
             .hashtable
             .get(key, self.time, &mut self.segments)
@@ -321,6 +1233,12 @@ impl Segcache {
     /// key. Returns an error if the key is invalid, the item is not found, or
     /// the stored value is not a numeric type.
     pub fn saturating_sub(&mut self, key: &[u8], rhs: u64) -> Result<Item, SegcacheError> {
+        if self.is_reserved(key) {
+            return Err(SegcacheError::NotFound);
+        }
+
+        self.migrate(self.hashtable.hash(key));
+
         let mut item = self
             .hashtable
             .get(key, self.time, &mut self.segments)
@@ -329,3 +1247,9 @@ impl Segcache {
         Ok(item)
     }
 }
+
+/// Calculates the number of bytes needed to store an item, including the
+/// header and padding.
+fn item_size(klen: usize, vlen: usize, olen: usize) -> usize {
+    (((ITEM_HDR_SIZE + klen + vlen + olen) >> 3) + 1) << 3
+}
@@ -11,11 +11,14 @@ use crate::segments::*;
 use std::path::{Path, PathBuf};
 
 /// The `SegmentsBuilder` allows for the configuration of the segment storage.
+#[derive(Clone)]
 pub(crate) struct SegmentsBuilder {
-    pub(super) heap_size: usize,
-    pub(super) segment_size: i32,
+    pub(crate) heap_size: usize,
+    pub(crate) segment_size: i32,
     pub(super) evict_policy: Policy,
-    pub(super) datapool_path: Option<PathBuf>,
+    pub(crate) datapool_path: Option<PathBuf>,
+    pub(crate) flash_size: usize,
+    pub(crate) flash_path: Option<PathBuf>,
 }
 
 impl Default for SegmentsBuilder {
@@ -25,6 +28,8 @@ impl Default for SegmentsBuilder {
             heap_size: 64 * 1024 * 1024,
             evict_policy: Policy::Random,
             datapool_path: None,
+            flash_size: 0,
+            flash_path: None,
         }
     }
 }
@@ -70,6 +75,21 @@ impl SegmentsBuilder {
         self
     }
 
+    /// Specify the size of the flash tier in bytes. The flash size will be
+    /// divided by the segment size to determine the number of flash segments.
+    pub fn flash_size(mut self, bytes: usize) -> Self {
+        self.flash_size = bytes;
+        self
+    }
+
+    /// Specify a file to be used for the flash tier. If provided, a file will
+    /// be created at the corresponding path and segments evicted from memory
+    /// will be moved into it.
+    pub fn flash_path<T: AsRef<Path>>(mut self, path: Option<T>) -> Self {
+        self.flash_path = path.map(|p| p.as_ref().to_owned());
+        self
+    }
+
     /// Construct the [`Segments`] from the builder
     pub fn build(self) -> Result<Segments, std::io::Error> {
         Segments::from_builder(self)
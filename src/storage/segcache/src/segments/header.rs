@@ -15,15 +15,15 @@
 //! │   PREV SEG   │   NEXT SEG   │  CREATE AT   │   MERGE AT   │
 //! │              │              │              │              │
 //! │    32 bit    │    32 bit    │    32 bit    │    32 bit    │
-//! ├──────────────┼──┬──┬────────┴──────────────┴──────────────┤
-//! │     TTL      │  │  │               PADDING                │   Accessible
-//! │              │  │◀─┼──────────────────────────────────────┼──    8 bit
-//! │    32 bit    │8b│8b│                80 bit                │
-//! ├──────────────┴──┴──┴──────────────────────────────────────┤    Evictable
+//! ├──────────────┼──┬──┬──┬─────┴──────────────┴──────────────┤
+//! │     TTL      │  │  │  │            PADDING                │   Accessible
+//! │              │  │◀─┼──┼───────────────────────────────────┼──    8 bit
+//! │    32 bit    │8b│8b│8b│             72 bit                │
+//! ├──────────────┴──┴──┴──┴───────────────────────────────────┤    Evictable
 //! │                          PADDING                          │      8 bit
 //! │                                                           │
-//! │                          128 bit                          │
-//! └───────────────────────────────────────────────────────────┘
+//! │                          128 bit                          │    Main
+//! └───────────────────────────────────────────────────────────┘      8 bit
 //! ```
 
 use super::SEG_MAGIC;
@@ -60,12 +60,14 @@ pub struct SegmentHeader {
     accessible: bool,
     /// Is the segment evictable?
     evictable: bool,
-    _pad: [u8; 25],
+    /// Is the segment part of the S3-FIFO main queue?
+    main: bool,
+    _pad: [u8; 24],
 }
 
 impl SegmentHeader {
     pub fn new(id: NonZeroU32) -> Self {
-        let now = Instant::now();
+        let now = clock::now();
         Self {
             id,
             write_offset: 0,
@@ -78,7 +80,8 @@ impl SegmentHeader {
             merge_at: now,
             accessible: false,
             evictable: false,
-            _pad: [0; 25],
+            main: false,
+            _pad: [0; 24],
         }
     }
 
@@ -89,7 +92,7 @@ impl SegmentHeader {
         assert!(!self.accessible());
         assert!(!self.evictable());
 
-        let now = Instant::now();
+        let now = clock::now();
 
         self.reset();
 
@@ -98,6 +101,7 @@ impl SegmentHeader {
         self.live_items = 0;
         self.create_at = now;
         self.merge_at = now;
+        self.main = false;
         self.accessible = true;
     }
 
@@ -252,7 +256,14 @@ impl SegmentHeader {
     #[inline]
     /// Update the created time
     pub fn mark_created(&mut self) {
-        self.create_at = Instant::now();
+        self.create_at = clock::now();
+    }
+
+    #[inline]
+    /// Set the created time, used when the segment takes over the items of
+    /// another segment
+    pub fn set_create_at(&mut self, instant: Instant) {
+        self.create_at = instant;
     }
 
     #[inline]
@@ -264,7 +275,19 @@ impl SegmentHeader {
     #[inline]
     /// Update the created time
     pub fn mark_merged(&mut self) {
-        self.merge_at = Instant::now();
+        self.merge_at = clock::now();
+    }
+
+    #[inline]
+    /// Is the segment part of the S3-FIFO main queue?
+    pub fn is_main(&self) -> bool {
+        self.main
+    }
+
+    #[inline]
+    /// Mark the segment as part of the S3-FIFO main queue.
+    pub fn set_main(&mut self, main: bool) {
+        self.main = main;
     }
 
     #[inline]
@@ -272,6 +295,6 @@ impl SegmentHeader {
     pub fn can_evict(&self) -> bool {
         self.evictable()
             && self.next_seg().is_some()
-            && (self.create_at() + self.ttl()) >= (Instant::now() + SEG_MATURE_TIME)
+            && (self.create_at() + self.ttl()) >= (clock::now() + SEG_MATURE_TIME)
     }
 }
@@ -8,6 +8,9 @@ use core::num::NonZeroU32;
 
 pub const SEG_MAGIC: u64 = 0xBADC0FFEEBADCAFE;
 
+// the maximum number of items checked when looking for the oldest live item
+const OLDEST_ITEM_SCAN: usize = 16;
+
 /// A `Segment` is a contiguous allocation of bytes and an associated header
 /// which contains metadata. This structure allows us to operate on mutable
 /// borrows of the header and data sections to perform basic operations.
@@ -213,6 +216,18 @@ impl<'a> Segment<'a> {
         self.header.mark_merged()
     }
 
+    /// Returns true if the segment is part of the S3-FIFO main queue
+    #[inline]
+    pub fn is_main(&self) -> bool {
+        self.header.is_main()
+    }
+
+    /// Mark the segment as part of the S3-FIFO main queue
+    #[inline]
+    pub fn set_main(&mut self, main: bool) {
+        self.header.set_main(main)
+    }
+
     /// Return the previous segment's id. This will be a segment before it in a
     /// TtlBucket or on the free queue. A `None` indicates that this segment is
     /// the head of a bucket or the free queue.
@@ -328,6 +343,44 @@ impl<'a> Segment<'a> {
         }))
     }
 
+    /// Returns the first live item at or after the offset, along with its
+    /// offset. This is used to scan the items in storage, so the offset may be
+    /// stale. Scanning stops at any item which does not fit within the written
+    /// portion of the segment.
+    pub(crate) fn next_live_item(
+        &mut self,
+        offset: usize,
+        hashtable: &mut HashTable,
+    ) -> Option<(usize, RawItem)> {
+        if !self.accessible() {
+            return None;
+        }
+
+        let max_offset = self.max_item_offset();
+        let mut offset = if cfg!(feature = "magic") {
+            std::cmp::max(offset, std::mem::size_of_val(&SEG_MAGIC))
+        } else {
+            offset
+        };
+
+        while offset <= max_offset {
+            let item = self.get_item_at(offset).unwrap();
+            let size = item.size();
+            if item.klen() == 0 || offset + size > self.write_offset() as usize {
+                return None;
+            }
+
+            // chunks of large items are only reachable through the manifest
+            if !item.is_chunk() && hashtable.is_item_at(item.key(), self.id(), offset as u64) {
+                return Some((offset, item));
+            }
+
+            offset += size;
+        }
+
+        None
+    }
+
     /// This is used as part of segment merging, it moves all occupied space to
     /// the beginning of the segment, leaving the end of the segment free
     #[allow(clippy::unnecessary_wraps)]
@@ -511,14 +564,30 @@ impl<'a> Segment<'a> {
         Ok(())
     }
 
+    /// Moves the live items of this segment into an empty segment of the flash
+    /// tier. The target takes the creation time and TTL of this segment so
+    /// that the items expire as they would have in memory.
+    pub(crate) fn demote(
+        &mut self,
+        target: &mut Segment,
+        hashtable: &mut HashTable,
+    ) -> Result<(), SegmentsError> {
+        target.header.set_create_at(self.create_at());
+        target.set_ttl(self.ttl());
+        target.set_accessible(true);
+        self.copy_into(target, hashtable)
+    }
+
     /// This is used as part of segment merging, it removes items from the
     /// segment based on a cutoff frequency and target ratio. Since the cutoff
-    /// frequency is adjusted, it is returned as the result.
+    /// frequency is adjusted, it is returned as the result. The manifests of
+    /// evicted large items are appended to `orphans`.
     pub(crate) fn prune(
         &mut self,
         hashtable: &mut HashTable,
         cutoff_freq: f64,
         target_ratio: f64,
+        orphans: &mut Vec<LargeItem>,
     ) -> f64 {
         let max_offset = self.max_item_offset();
         let mut offset = if cfg!(feature = "magic") {
@@ -585,13 +654,7 @@ impl<'a> Segment<'a> {
                     weighted_frequency,
                     cutoff
                 );
-                if !hashtable.evict(item.key(), offset.try_into().unwrap(), self) {
-                    // this *shouldn't* happen, but to keep header integrity, we
-                    // warn and remove the item even if it wasn't in the
-                    // hashtable
-                    warn!("unlinked item was present in segment");
-                    self.remove_item_at(offset);
-                }
+                self.evict_item(hashtable, &item, offset, orphans);
                 n_dropped += item_size;
                 offset += item_size;
                 continue;
@@ -612,10 +675,125 @@ impl<'a> Segment<'a> {
         cutoff
     }
 
+    /// This is used by the SIEVE and S3-FIFO eviction policies. Items which
+    /// have been accessed fewer than `threshold` times since they were last
+    /// scanned are evicted, and the access frequency of the retained items is
+    /// cleared. If provided, the hashes of evicted items are appended to
+    /// `evicted`. The manifests of evicted large items are appended to
+    /// `orphans`.
+    pub(crate) fn sieve(
+        &mut self,
+        hashtable: &mut HashTable,
+        threshold: u64,
+        mut evicted: Option<&mut Vec<u64>>,
+        orphans: &mut Vec<LargeItem>,
+    ) {
+        let max_offset = self.max_item_offset();
+        let mut offset = if cfg!(feature = "magic") {
+            std::mem::size_of_val(&SEG_MAGIC)
+        } else {
+            0
+        };
+
+        while offset <= max_offset {
+            let item = self.get_item_at(offset).unwrap();
+            if item.klen() == 0 && self.live_items() == 0 {
+                break;
+            }
+
+            item.check_magic();
+
+            let item_size = item.size();
+
+            let deleted = !hashtable.is_item_at(item.key(), self.id(), offset as u64);
+            if deleted {
+                offset += item_size;
+                continue;
+            }
+
+            let item_frequency = hashtable
+                .get_freq(item.key(), self, offset as u64)
+                .unwrap_or(0);
+
+            if item_frequency < threshold {
+                if let Some(evicted) = evicted.as_mut() {
+                    evicted.push(hashtable.hash(item.key()));
+                }
+                self.evict_item(hashtable, &item, offset, orphans);
+            } else {
+                hashtable.reset_freq(item.key(), self, offset as u64);
+            }
+
+            offset += item_size;
+        }
+    }
+
+    /// Evicts a single item from the segment. If the item is the manifest of a
+    /// large item, the manifest is appended to `orphans` so that its chunks,
+    /// which may be held by other segments, can be removed.
+    fn evict_item(
+        &mut self,
+        hashtable: &mut HashTable,
+        item: &RawItem,
+        offset: usize,
+        orphans: &mut Vec<LargeItem>,
+    ) {
+        let large = item.large();
+        if hashtable.evict(item.key(), offset.try_into().unwrap(), self) {
+            if let Some(large) = large {
+                orphans.push(large);
+            }
+        } else {
+            // this *shouldn't* happen, but to keep header integrity, we
+            // warn and remove the item even if it wasn't in the
+            // hashtable
+            warn!("unlinked item was present in segment");
+            self.remove_item_at(offset);
+        }
+    }
+
+    /// Returns the first item in the segment which is still linked into the
+    /// hashtable. Only a bounded number of items are checked.
+    pub(crate) fn oldest_item(&mut self, hashtable: &mut HashTable) -> Option<RawItem> {
+        let max_offset = self.max_item_offset();
+        let mut offset = if cfg!(feature = "magic") {
+            std::mem::size_of_val(&SEG_MAGIC)
+        } else {
+            0
+        };
+
+        for _ in 0..OLDEST_ITEM_SCAN {
+            if offset > max_offset {
+                break;
+            }
+
+            let item = self.get_item_at(offset)?;
+            if item.klen() == 0 {
+                break;
+            }
+
+            item.check_magic();
+
+            if hashtable.is_item_at(item.key(), self.id(), offset as u64) {
+                return Some(item);
+            }
+
+            offset += item.size();
+        }
+
+        None
+    }
+
     /// Remove all items from the segment, unlinking them from the hashtable.
     /// If expire is true, this is treated as an expiration option. Otherwise it
-    /// is treated as an eviction.
-    pub(crate) fn clear(&mut self, hashtable: &mut HashTable, expire: bool) {
+    /// is treated as an eviction. If provided, the manifests of evicted large
+    /// items are appended to `orphans`.
+    pub(crate) fn clear(
+        &mut self,
+        hashtable: &mut HashTable,
+        expire: bool,
+        mut orphans: Option<&mut Vec<LargeItem>>,
+    ) {
         self.set_accessible(false);
         self.set_evictable(false);
 
@@ -653,6 +831,9 @@ impl<'a> Segment<'a> {
                 trace!("evicting from hashtable");
                 let removed = if expire {
                     hashtable.expire(item.key(), offset.try_into().unwrap(), self)
+                } else if let Some(orphans) = orphans.as_deref_mut() {
+                    self.evict_item(hashtable, &item, offset, orphans);
+                    true
                 } else {
                     hashtable.evict(item.key(), offset.try_into().unwrap(), self)
                 };
@@ -14,8 +14,9 @@ use datatier::*;
 pub(crate) struct Segments {
     /// Pointer to slice of headers
     headers: Box<[SegmentHeader]>,
-    /// Pointer to raw data
-    data: Box<dyn Datapool>,
+    /// Pointer to raw data, with one datapool for each tier. Segments in the
+    /// flash tier, if enabled, follow those in memory
+    data: Box<[Box<dyn Datapool>]>,
     /// Segment size in bytes
     segment_size: i32,
     /// Number of free segments
@@ -28,6 +29,8 @@ pub(crate) struct Segments {
     flush_at: Instant,
     /// Eviction configuration and state
     evict: Box<Eviction>,
+    /// Index of the next flash segment to write to
+    flash_next: u32,
 }
 
 impl Segments {
@@ -47,13 +50,30 @@ impl Segments {
             "heap size requires too many segments, reduce heap size or increase segment size"
         );
 
+        // segments in the flash tier are numbered after those in memory
+        let flash_segments = if builder.flash_path.is_some() {
+            builder.flash_size / (builder.segment_size as usize)
+        } else {
+            0
+        };
+
+        debug!(
+            "flash size: {} flash segments: {}",
+            builder.flash_size, flash_segments
+        );
+
+        assert!(
+            segments + flash_segments < (1 << 24),
+            "flash size requires too many segments, reduce flash size or increase segment size"
+        );
+
         let evict_policy = builder.evict_policy;
 
         debug!("eviction policy: {:?}", evict_policy);
 
         let mut headers = Vec::with_capacity(0);
-        headers.reserve_exact(segments);
-        for id in 0..segments {
+        headers.reserve_exact(segments + flash_segments);
+        for id in 0..(segments + flash_segments) {
             // safety: we start iterating from 1 and seg id is constrained to < 2^24
             let header = SegmentHeader::new(unsafe { NonZeroU32::new_unchecked(id as u32 + 1) });
             headers.push(header);
@@ -87,6 +107,34 @@ impl Segments {
             }
         }
 
+        let mut data = vec![data];
+        match builder.flash_path {
+            Some(file) if flash_segments > 0 => {
+                let mut flash: Box<dyn Datapool> = Box::new(MmapFile::create(
+                    file,
+                    flash_segments * segment_size as usize,
+                    crate::VERSION,
+                )?);
+
+                // flash segments are not linked into any chain and remain
+                // inaccessible until a segment is demoted into them
+                for idx in 0..flash_segments {
+                    let begin = segment_size as usize * idx;
+                    let end = begin + segment_size as usize;
+
+                    let mut segment = Segment::from_raw_parts(
+                        &mut headers[segments + idx],
+                        &mut flash.as_mut_slice()[begin..end],
+                    );
+                    segment.init();
+                    segment.set_accessible(false);
+                }
+
+                data.push(flash);
+            }
+            _ => {}
+        }
+
         #[cfg(feature = "metrics")]
         {
             SEGMENT_CURRENT.set(segments as _);
@@ -99,9 +147,10 @@ impl Segments {
             cap: segments as u32,
             free: segments as u32,
             free_q: NonZeroU32::new(1),
-            data,
-            flush_at: Instant::now(),
+            data: data.into_boxed_slice(),
+            flush_at: clock::now(),
             evict: Box::new(Eviction::new(segments, evict_policy)),
+            flash_next: 0,
         })
     }
 
@@ -112,11 +161,31 @@ impl Segments {
     }
 
     /// Returns the number of free segments
-    #[cfg(test)]
     pub fn free(&self) -> usize {
         self.free as usize
     }
 
+    /// Returns the total number of segments across both tiers
+    pub fn total(&self) -> u32 {
+        self.headers.len() as u32
+    }
+
+    /// Returns the number of segments in memory
+    #[cfg(feature = "metrics")]
+    pub fn cap(&self) -> u32 {
+        self.cap
+    }
+
+    /// Returns the number of segments in the flash tier
+    fn flash_cap(&self) -> u32 {
+        self.headers.len() as u32 - self.cap
+    }
+
+    /// Returns true if the segment id refers to a segment in the flash tier
+    pub fn is_flash(&self, id: NonZeroU32) -> bool {
+        id.get() > self.cap
+    }
+
     /// Returns the time the segments were last flushed
     pub fn flush_at(&self) -> Instant {
         self.flush_at
@@ -142,53 +211,210 @@ impl Segments {
         seg_id: Option<NonZeroU32>,
         offset: usize,
     ) -> Option<RawItem> {
-        let seg_id = seg_id.map(|v| v.get())?;
+        let seg_id = seg_id?;
         trace!("getting item from: seg: {} offset: {}", seg_id, offset);
-        assert!(seg_id <= self.cap);
+        assert!(seg_id.get() <= self.cap + self.flash_cap());
 
-        let seg_begin = self.segment_size() as usize * (seg_id as usize - 1);
-        let seg_end = seg_begin + self.segment_size() as usize;
-        let mut segment = Segment::from_raw_parts(
-            &mut self.headers[seg_id as usize - 1],
-            &mut self.data.as_mut_slice()[seg_begin..seg_end],
-        );
+        let mut segment = self.get_mut(seg_id).ok()?;
 
         segment.get_item_at(offset)
     }
 
-    /// Tries to clear a segment by id
+    /// Retrieve a `RawItem` from the segment and offset held in the item info
+    /// through a shared reference. The item must only be read.
+    pub(crate) fn peek_item(&self, item_info: u64) -> Option<RawItem> {
+        let seg_id = get_seg_id(item_info)?;
+        let offset = get_offset(item_info) as usize;
+        assert!(seg_id.get() <= self.cap + self.flash_cap());
+
+        let id = seg_id.get() as usize - 1;
+        let (data, idx) = if id < self.cap as usize {
+            (&self.data[0], id)
+        } else {
+            (&self.data[1], id - self.cap as usize)
+        };
+
+        let segment_size = self.segment_size as usize;
+        assert!(offset <= segment_size - ITEM_HDR_SIZE);
+
+        let seg_data = &data.as_slice()[segment_size * idx..segment_size * (idx + 1)];
+
+        // the pointer is only used to read the item, as `RawItem` has no
+        // separate type for immutable access
+        Some(RawItem::from_ptr(seg_data[offset..].as_ptr() as *mut u8))
+    }
+
+    /// Tries to clear a segment by id. If the flash tier is enabled, segments
+    /// which are evicted have their live items demoted to flash instead. The
+    /// manifests of evicted large items are appended to `orphans`.
     fn clear_segment(
         &mut self,
         id: NonZeroU32,
         hashtable: &mut HashTable,
         expire: bool,
+        orphans: &mut Vec<LargeItem>,
     ) -> Result<(), ()> {
-        let mut segment = self.get_mut(id).unwrap();
-        if segment.next_seg().is_none() && !expire {
-            Err(())
-        } else {
+        {
+            let mut segment = self.get_mut(id).unwrap();
+            if segment.next_seg().is_none() && !expire {
+                return Err(());
+            }
             // TODO(bmartin): this should probably result in an error and not be
             // an assert
             assert!(segment.evictable(), "segment was not evictable");
             segment.set_evictable(false);
             segment.set_accessible(false);
-            segment.clear(hashtable, expire);
-            Ok(())
+        }
+
+        if !expire && self.flash_cap() > 0 {
+            self.demote(id, hashtable, orphans);
+        }
+
+        // any items which could not be demoted are evicted here
+        self.get_mut(id)
+            .unwrap()
+            .clear(hashtable, expire, Some(orphans));
+        Ok(())
+    }
+
+    /// Moves the live items of a segment in memory into the next segment of
+    /// the flash tier, relinking them in the hashtable.
+    fn demote(&mut self, id: NonZeroU32, hashtable: &mut HashTable, orphans: &mut Vec<LargeItem>) {
+        let flash_id = self.next_flash(hashtable, orphans);
+
+        let seg_size = self.segment_size as usize;
+        let idx = id.get() as usize - 1;
+        let flash_idx = flash_id.get() as usize - 1 - self.cap as usize;
+
+        // the flash headers follow the headers for the segments in memory, so
+        // we can split them to borrow one of each
+        let (headers, flash_headers) = self.headers.split_at_mut(self.cap as usize);
+        let (data, flash) = self.data.split_at_mut(1);
+        let mut segment = Segment::from_raw_parts(
+            &mut headers[idx],
+            &mut data[0].as_mut_slice()[(idx * seg_size)..((idx + 1) * seg_size)],
+        );
+        let mut target = Segment::from_raw_parts(
+            &mut flash_headers[flash_idx],
+            &mut flash[0].as_mut_slice()[(flash_idx * seg_size)..((flash_idx + 1) * seg_size)],
+        );
+
+        if segment.demote(&mut target, hashtable).is_err() {
+            warn!("failed to demote segment: {}", id);
+        }
+
+        #[cfg(feature = "metrics")]
+        SEGMENT_DEMOTE.increment();
+    }
+
+    /// Returns the id of the next flash segment to demote into. Flash segments
+    /// are reused in FIFO order, so any items still held by the segment are
+    /// evicted.
+    fn next_flash(
+        &mut self,
+        hashtable: &mut HashTable,
+        orphans: &mut Vec<LargeItem>,
+    ) -> NonZeroU32 {
+        // safety: we are always adding 1 to the index
+        let id = unsafe { NonZeroU32::new_unchecked(self.cap + self.flash_next + 1) };
+        self.flash_next = (self.flash_next + 1) % self.flash_cap();
+
+        let mut segment = self.get_mut(id).unwrap();
+        if segment.accessible() {
+            #[cfg(feature = "metrics")]
+            SEGMENT_FLASH_EVICT.increment();
+
+            segment.clear(hashtable, false, Some(orphans));
+        }
+
+        id
+    }
+
+    /// Clears segments in the flash tier which have expired, or which were
+    /// written before the last flush. Returns the number of segments cleared.
+    pub(crate) fn expire_flash(&mut self, hashtable: &mut HashTable) -> usize {
+        let now = clock::now();
+        let flush_at = self.flush_at;
+
+        let mut expired = 0;
+        for id in (self.cap + 1)..=(self.cap + self.flash_cap()) {
+            // this is safe because we start iterating from 1
+            let mut segment = self
+                .get_mut(unsafe { NonZeroU32::new_unchecked(id) })
+                .unwrap();
+            if segment.accessible()
+                && (segment.create_at() + segment.ttl() <= now || segment.create_at() < flush_at)
+            {
+                segment.clear(hashtable, true, None);
+
+                #[cfg(feature = "metrics")]
+                SEGMENT_EXPIRE.increment();
+
+                expired += 1;
+            }
+        }
+        expired
+    }
+
+    /// Clears all segments in the flash tier. Returns the number of segments
+    /// cleared.
+    pub(crate) fn clear_flash(&mut self, hashtable: &mut HashTable) -> usize {
+        let mut cleared = 0;
+        for id in (self.cap + 1)..=(self.cap + self.flash_cap()) {
+            // this is safe because we start iterating from 1
+            let mut segment = self
+                .get_mut(unsafe { NonZeroU32::new_unchecked(id) })
+                .unwrap();
+            if segment.accessible() {
+                segment.clear(hashtable, true, None);
+                cleared += 1;
+            }
+        }
+        cleared
+    }
+
+    /// Keeps items from being moved between segments for `duration`, so that a
+    /// scan does not miss items which are moved behind its cursor. While items
+    /// are pinned, the policies which move items fall back to evicting whole
+    /// segments from the head of a `TtlBucket`, as with random FIFO eviction,
+    /// and segments are not compacted.
+    pub(crate) fn pin_items(&mut self, duration: Duration) {
+        self.evict.pin(clock::now() + duration);
+    }
+
+    /// Returns true if items may not currently be moved between segments.
+    pub(crate) fn items_pinned(&self) -> bool {
+        self.evict.is_pinned()
+    }
+
+    /// Returns the eviction policy currently in effect, which accounts for
+    /// items being pinned by a scan.
+    fn evict_policy(&self) -> Policy {
+        match self.evict.policy() {
+            Policy::Merge { .. } | Policy::Sieve { .. } | Policy::S3Fifo { .. }
+                if self.evict.is_pinned() =>
+            {
+                Policy::RandomFifo
+            }
+            policy => policy,
         }
     }
 
     /// Perform eviction based on the configured eviction policy. A success from
     /// this function indicates that a segment was put onto the free queue and
-    /// that `pop_free()` should return some segment id.
+    /// that `pop_free()` should return some segment id. The manifests of any
+    /// large items which were evicted are appended to `orphans`, as their
+    /// chunks are not removed by the eviction.
     pub fn evict(
         &mut self,
         ttl_buckets: &mut TtlBuckets,
         hashtable: &mut HashTable,
+        orphans: &mut Vec<LargeItem>,
     ) -> Result<(), SegmentsError> {
         #[cfg(feature = "metrics")]
         let now = Instant::now();
 
-        match self.evict.policy() {
+        match self.evict_policy() {
             Policy::Merge { .. } => {
                 #[cfg(feature = "metrics")]
                 SEGMENT_EVICT.increment();
@@ -207,10 +433,11 @@ impl Segments {
                     let ttl_bucket = &mut ttl_buckets.buckets[bucket_id];
                     if let Some(first_seg) = ttl_bucket.head() {
                         let start = ttl_bucket.next_to_merge().unwrap_or(first_seg);
-                        match self.merge_evict(start, hashtable) {
+                        match self.merge_evict(start, ttl_bucket, hashtable, orphans) {
                             Ok(next_to_merge) => {
                                 debug!("merged ttl_bucket: {} seg: {}", bucket_id, start);
                                 ttl_bucket.set_next_to_merge(next_to_merge);
+                                ttl_bucket.incr_merges();
 
                                 #[cfg(feature = "metrics")]
                                 EVICT_TIME.add(now.elapsed().as_nanos() as _);
@@ -236,6 +463,42 @@ impl Segments {
 
                 Err(SegmentsError::NoEvictableSegments)
             }
+            Policy::Sieve { .. } | Policy::S3Fifo { .. } => {
+                #[cfg(feature = "metrics")]
+                SEGMENT_EVICT.increment();
+
+                // pick a ttl bucket weighted by the number of segments, as
+                // with random fifo eviction
+                let mut seg_idx = self.evict.random();
+
+                seg_idx %= self.cap;
+                let ttl = self.headers[seg_idx as usize].ttl();
+                let offset = ttl_buckets.get_bucket_index(ttl);
+                let buckets = ttl_buckets.buckets.len();
+
+                for i in 0..buckets {
+                    let bucket_id = (offset + i) % buckets;
+                    if let Some(head) = ttl_buckets.buckets[bucket_id].head() {
+                        if self
+                            .sieve_evict(head, ttl_buckets, hashtable, orphans)
+                            .is_ok()
+                        {
+                            #[cfg(feature = "metrics")]
+                            EVICT_TIME.add(now.elapsed().as_nanos() as _);
+
+                            return Ok(());
+                        }
+                    }
+                }
+
+                #[cfg(feature = "metrics")]
+                {
+                    SEGMENT_EVICT_EX.increment();
+                    EVICT_TIME.add(now.elapsed().as_nanos() as _);
+                }
+
+                Err(SegmentsError::NoEvictableSegments)
+            }
             Policy::None => {
                 #[cfg(feature = "metrics")]
                 EVICT_TIME.add(now.elapsed().as_nanos() as _);
@@ -248,7 +511,7 @@ impl Segments {
 
                 if let Some(id) = self.least_valuable_seg(ttl_buckets) {
                     let result = self
-                        .clear_segment(id, hashtable, false)
+                        .clear_segment(id, hashtable, false, orphans)
                         .map_err(|_| SegmentsError::EvictFailure);
 
                     if result.is_err() {
@@ -259,10 +522,11 @@ impl Segments {
                     }
 
                     let id_idx = id.get() as usize - 1;
+                    let ttl_bucket = ttl_buckets.get_mut_bucket(self.headers[id_idx].ttl());
                     if self.headers[id_idx].prev_seg().is_none() {
-                        let ttl_bucket = ttl_buckets.get_mut_bucket(self.headers[id_idx].ttl());
                         ttl_bucket.set_head(self.headers[id_idx].next_seg());
                     }
+                    ttl_bucket.remove_segment(&self.get_mut(id)?);
                     self.push_free(id);
 
                     #[cfg(feature = "metrics")]
@@ -288,10 +552,17 @@ impl Segments {
         if id < self.headers.len() {
             let header = self.headers.get_mut(id).unwrap();
 
-            let seg_start = self.segment_size as usize * id;
-            let seg_end = self.segment_size as usize * (id + 1);
+            // segments beyond the capacity in memory are in the flash tier
+            let (data, idx) = if id < self.cap as usize {
+                (&mut self.data[0], id)
+            } else {
+                (&mut self.data[1], id - self.cap as usize)
+            };
+
+            let seg_start = self.segment_size as usize * idx;
+            let seg_end = self.segment_size as usize * (idx + 1);
 
-            let seg_data = &mut self.data.as_mut_slice()[seg_start..seg_end];
+            let seg_data = &mut data.as_mut_slice()[seg_start..seg_end];
 
             let segment = Segment::from_raw_parts(header, seg_data);
             segment.check_magic();
@@ -302,7 +573,7 @@ impl Segments {
     }
 
     /// Gets a mutable `Segment` view for two segments after making sure the
-    /// borrows are disjoint.
+    /// borrows are disjoint. Both segments must be in memory.
     pub(crate) fn get_mut_pair(
         &mut self,
         a: NonZeroU32,
@@ -313,7 +584,7 @@ impl Segments {
         } else {
             let a = a.get() as usize - 1;
             let b = b.get() as usize - 1;
-            if a >= self.headers.len() || b >= self.headers.len() {
+            if a >= self.cap as usize || b >= self.cap as usize {
                 return Err(SegmentsError::BadSegmentId);
             }
             // we have already guaranteed that 'a' and 'b' are not the same, so
@@ -325,7 +596,7 @@ impl Segments {
                 let header_a = &mut self.headers[a] as *mut _;
                 let header_b = &mut self.headers[b] as *mut _;
 
-                let data = self.data.as_mut_slice();
+                let data = self.data[0].as_mut_slice();
 
                 // split the borrowed data
                 let split = (std::cmp::min(a, b) + 1) * seg_size;
@@ -409,11 +680,29 @@ impl Segments {
         assert!(!self.headers[id_idx].evictable());
         self.headers[id_idx].set_accessible(false);
 
+        // reused segments start out in the s3-fifo small queue
+        self.headers[id_idx].set_main(false);
         self.headers[id_idx].reset();
 
         self.free += 1;
     }
 
+    /// Returns true if an item with the hash was recently evicted from the
+    /// S3-FIFO small queue. The entry is removed from the ghost queue.
+    pub(crate) fn ghost_remove(&mut self, hash: u64) -> bool {
+        self.evict.ghost_remove(hash)
+    }
+
+    /// Returns the oldest live item in the segment, which is used as the
+    /// eviction victim by the admission filter.
+    pub(crate) fn oldest_item(
+        &mut self,
+        id: NonZeroU32,
+        hashtable: &mut HashTable,
+    ) -> Option<RawItem> {
+        self.get_mut(id).ok()?.oldest_item(hashtable)
+    }
+
     /// Try to take a segment from the free queue. Returns the segment id which
     /// must then be linked into a segment chain.
     pub(crate) fn pop_free(&mut self) -> Option<NonZeroU32> {
@@ -471,7 +760,7 @@ impl Segments {
         &mut self,
         ttl_buckets: &mut TtlBuckets,
     ) -> Option<NonZeroU32> {
-        match self.evict.policy() {
+        match self.evict_policy() {
             Policy::None => None,
             Policy::Random => {
                 let mut start: u32 = self.evict.random();
@@ -512,7 +801,7 @@ impl Segments {
             }
             _ => {
                 if self.evict.should_rerank() {
-                    self.evict.rerank(&self.headers);
+                    self.evict.rerank(&self.headers[..(self.cap as usize)]);
                 }
                 while let Some(id) = self.evict.least_valuable_seg() {
                     if let Ok(seg) = self.get_mut(id) {
@@ -559,15 +848,16 @@ impl Segments {
             if segment.live_items() == 0 && segment.can_evict() {
                 // even though the item has zero live items, we clear it as a
                 // way of updating the dead item metrics.
-                segment.clear(hashtable, false);
+                segment.clear(hashtable, false, None);
 
                 segment.set_evictable(false);
                 // if it's the head of a ttl bucket, we need to manually relink
                 // the bucket head while we have access to the ttl buckets
+                let ttl_bucket = ttl_buckets.get_mut_bucket(segment.ttl());
                 if segment.prev_seg().is_none() {
-                    let ttl_bucket = ttl_buckets.get_mut_bucket(segment.ttl());
                     ttl_bucket.set_head(segment.next_seg());
                 }
+                ttl_bucket.remove_segment(&segment);
                 self.push_free(seg_id);
                 return Ok(());
             }
@@ -575,8 +865,9 @@ impl Segments {
 
         // for merge eviction, we check if the segment is now below the target
         // ratio which serves as a low watermark for occupancy. if it is, we do
-        // a no-evict merge (compaction only, no-pruning)
-        if let Policy::Merge { .. } = self.evict.policy() {
+        // a no-evict merge (compaction only, no-pruning). compaction is skipped
+        // while items are pinned by a scan
+        if let Policy::Merge { .. } = self.evict_policy() {
             let target_ratio = self.evict.compact_ratio();
 
             let id_idx = seg_id.get() as usize - 1;
@@ -611,11 +902,14 @@ impl Segments {
 
                 // if the next segment is empty enough, proceed to merge compaction
                 if next_ratio <= target_ratio {
-                    let _ = self.merge_compact(seg_id, hashtable);
+                    let ttl_bucket = ttl_buckets.get_mut_bucket(self.headers[id_idx].ttl());
+                    let merged = self.merge_compact(seg_id, ttl_bucket, hashtable).is_ok();
                     // we need to make sure the ttl bucket doesn't have a pointer to
                     // any of the segments we removed through merging.
-                    let ttl_bucket = ttl_buckets.get_mut_bucket(self.headers[id_idx].ttl());
                     ttl_bucket.set_next_to_merge(None);
+                    if merged {
+                        ttl_bucket.incr_merges();
+                    }
                 }
             }
         }
@@ -627,7 +921,7 @@ impl Segments {
     #[cfg(any(test, feature = "debug"))]
     pub(crate) fn items(&mut self) -> usize {
         let mut total = 0;
-        for id in 1..=self.cap {
+        for id in 1..=(self.cap + self.flash_cap()) {
             // this is safe because we start iterating from 1
             let segment = self
                 .get_mut(unsafe { NonZeroU32::new_unchecked(id) })
@@ -650,7 +944,7 @@ impl Segments {
     #[cfg(feature = "debug")]
     pub(crate) fn check_integrity(&mut self, hashtable: &mut HashTable) -> bool {
         let mut integrity = true;
-        for id in 0..self.cap {
+        for id in 0..(self.cap + self.flash_cap()) {
             if !self
                 .get_mut(NonZeroU32::new(id + 1).unwrap())
                 .unwrap()
@@ -729,7 +1023,9 @@ impl Segments {
     fn merge_evict(
         &mut self,
         start: NonZeroU32,
+        ttl_bucket: &mut TtlBucket,
         hashtable: &mut HashTable,
+        orphans: &mut Vec<LargeItem>,
     ) -> Result<Option<NonZeroU32>, SegmentsError> {
         #[cfg(feature = "metrics")]
         SEGMENT_MERGE.increment();
@@ -767,7 +1063,7 @@ impl Segments {
             let dst_old_size = dst.live_bytes();
 
             trace!("prune merge with cutoff: {}", cutoff);
-            cutoff = dst.prune(hashtable, cutoff, target_ratio);
+            cutoff = dst.prune(hashtable, cutoff, target_ratio, orphans);
             trace!("cutoff is now: {}", cutoff);
 
             dst.compact(hashtable)?;
@@ -809,7 +1105,7 @@ impl Segments {
             }
 
             trace!("pruning source segment");
-            cutoff = src.prune(hashtable, cutoff, target_ratio);
+            cutoff = src.prune(hashtable, cutoff, target_ratio, orphans);
 
             trace!(
                 "src {}: {} bytes -> {} bytes",
@@ -830,7 +1126,8 @@ impl Segments {
             );
 
             next_id = src.next_seg();
-            src.clear(hashtable, false);
+            src.clear(hashtable, false, Some(orphans));
+            ttl_bucket.remove_segment(&src);
             self.push_free(src_id);
             merged += 1;
         }
@@ -841,6 +1138,7 @@ impl Segments {
     fn merge_compact(
         &mut self,
         start: NonZeroU32,
+        ttl_bucket: &mut TtlBucket,
         hashtable: &mut HashTable,
     ) -> Result<Option<NonZeroU32>, SegmentsError> {
         #[cfg(feature = "metrics")]
@@ -938,11 +1236,145 @@ impl Segments {
             );
 
             next_id = src.next_seg();
-            src.clear(hashtable, false);
+            src.clear(hashtable, false, None);
+            ttl_bucket.remove_segment(&src);
             self.push_free(src_id);
             merged += 1;
         }
 
         Ok(next_id)
     }
+
+    /// Eviction for the SIEVE and S3-FIFO policies. Starting from the oldest
+    /// segment to scan in a TTL bucket, each segment in the chain is scanned
+    /// to evict items which have not been accessed and the surviving items are
+    /// copied into the first segment, freeing the segments they were copied
+    /// from. If no segment could be freed, the first segment is evicted.
+    fn sieve_evict(
+        &mut self,
+        head: NonZeroU32,
+        ttl_buckets: &mut TtlBuckets,
+        hashtable: &mut HashTable,
+        orphans: &mut Vec<LargeItem>,
+    ) -> Result<(), SegmentsError> {
+        let s3fifo = matches!(self.evict.policy(), Policy::S3Fifo { .. });
+        let ttl_bucket = ttl_buckets.get_mut_bucket(self.headers[head.get() as usize - 1].ttl());
+
+        let dst_id = if s3fifo {
+            self.s3fifo_start(ttl_bucket, head)
+        } else {
+            head
+        };
+
+        let chain_len = self.merge_evict_chain_len(dst_id);
+        if chain_len == 0 {
+            return Err(SegmentsError::NoEvictableSegments);
+        }
+
+        let seg_size = self.segment_size();
+
+        // hashes of the items evicted from the s3-fifo small queue
+        let mut ghosts = Vec::new();
+
+        // scanned segments join the main queue, which grows into the small
+        // queue that follows it in the chain
+        let mut promoted = 0;
+
+        let mut next_id = {
+            let mut dst = self.get_mut(dst_id)?;
+            let small = s3fifo && !dst.is_main();
+            dst.sieve(
+                hashtable,
+                if small { S3FIFO_PROMOTE_FREQ } else { 1 },
+                small.then_some(&mut ghosts),
+                orphans,
+            );
+            dst.compact(hashtable)?;
+            if !dst.is_main() {
+                promoted += 1;
+            }
+            dst.set_main(true);
+            dst.mark_merged();
+            dst.next_seg()
+        };
+        let mut unscanned = next_id;
+
+        let mut scanned = 1;
+        let mut freed = 0;
+
+        while let Some(src_id) = next_id {
+            if scanned >= chain_len {
+                break;
+            }
+
+            let (mut dst, mut src) = self.get_mut_pair(dst_id, src_id)?;
+
+            let small = s3fifo && !src.is_main();
+            src.sieve(
+                hashtable,
+                if small { S3FIFO_PROMOTE_FREQ } else { 1 },
+                small.then_some(&mut ghosts),
+                orphans,
+            );
+            if !src.is_main() {
+                promoted += 1;
+            }
+            src.set_main(true);
+            unscanned = src.next_seg();
+            scanned += 1;
+
+            if dst.live_bytes() + src.live_bytes() > seg_size {
+                trace!("stop sieve: target segment is full");
+                break;
+            }
+
+            let _ = src.copy_into(&mut dst, hashtable);
+
+            next_id = src.next_seg();
+            src.clear(hashtable, false, Some(orphans));
+            ttl_bucket.remove_segment(&src);
+            self.push_free(src_id);
+            freed += 1;
+        }
+
+        if promoted > 0 {
+            ttl_bucket.set_main(promoted, unscanned);
+        }
+
+        if freed == 0 {
+            // every scanned item had been accessed, their access frequencies
+            // are now cleared so we evict the first segment outright
+            self.clear_segment(dst_id, hashtable, false, orphans)
+                .map_err(|_| SegmentsError::EvictFailure)?;
+
+            let id_idx = dst_id.get() as usize - 1;
+            if self.headers[id_idx].prev_seg().is_none() {
+                ttl_bucket.set_head(self.headers[id_idx].next_seg());
+            }
+            ttl_bucket.remove_segment(&self.get_mut(dst_id)?);
+            self.push_free(dst_id);
+        }
+
+        for hash in ghosts {
+            self.evict.ghost_insert(hash);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the segment to begin an S3-FIFO scan from. Segments in the main
+    /// queue are always at the front of the TTL bucket chain, and the TTL
+    /// bucket tracks where the small queue begins. The small queue is scanned
+    /// first unless it has shrunk to less than a tenth of the chain.
+    fn s3fifo_start(&self, ttl_bucket: &TtlBucket, head: NonZeroU32) -> NonZeroU32 {
+        let total = ttl_bucket.nseg();
+        let small = total - ttl_bucket.nmain();
+
+        match ttl_bucket.small_head() {
+            Some(id) if self.headers[id.get() as usize - 1].can_evict() && small * 10 >= total => {
+                id
+            }
+            _ => head,
+        }
+    }
 }
@@ -0,0 +1,333 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! A portable snapshot format for the contents of the cache.
+//!
+//! Unlike a datapool, which is an image of the segment memory, a snapshot only
+//! contains the logical contents of the cache, so it may be loaded into a cache
+//! with a different configuration or data layout version, or on another host.
+//!
+//! A snapshot is a header, followed by one record for each item, and a
+//! trailer. All integers are little-endian.
+//!
+//! Header:
+//! ```text
+//! ┌──────────────────────────────┬──────────────┐
+//! │            MAGIC             │   VERSION    │
+//! │                              │              │
+//! │            64 bit            │    32 bit    │
+//! │          "SEGSNAP\0"         │              │
+//! └──────────────────────────────┴──────────────┘
+//! ```
+//!
+//! Record:
+//! ```text
+//! ┌──────┬──────┬─────┬──────┬──────────┬────────┬───────┬───────────┐
+//! │ KIND │ KLEN │ KEY │ OLEN │ OPTIONAL │  VLEN  │ VALUE │ EXPIRE AT │
+//! │      │      │     │      │          │        │       │           │
+//! │8 bit │8 bit │     │8 bit │          │ 32 bit │       │  64 bit   │
+//! └──────┴──────┴─────┴──────┴──────────┴────────┴───────┴───────────┘
+//! ```
+//!
+//! The kind indicates whether the value is a byte string or a numeric value,
+//! which is stored as 8 bytes. The optional data holds the flags for protocols
+//! such as memcache. Expiry is stored as an absolute time in seconds since the
+//! unix epoch, with zero indicating that the item does not expire, so that the
+//! remaining TTL is preserved regardless of when the snapshot is loaded.
+//!
+//! Trailer:
+//! ```text
+//! ┌──────┬──────────────────────────────┬──────────────────────────────┐
+//! │ END  │            COUNT             │           CHECKSUM           │
+//! │      │                              │                              │
+//! │8 bit │            64 bit            │           256 bit            │
+//! │  0   │                              │                              │
+//! └──────┴──────────────────────────────┴──────────────────────────────┘
+//! ```
+//!
+//! The checksum is the BLAKE3 hash of all preceding bytes.
+
+use crate::value::OwnedValue;
+use crate::{Item, Value};
+
+use std::io::{Error, ErrorKind, Read, Result, Seek, SeekFrom, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const MAGIC: [u8; 8] = *b"SEGSNAP\0";
+
+// NOTE: this must be incremented when breaking changes are made to the
+// snapshot format
+const VERSION: u32 = 0;
+
+// record kinds, zero marks the end of the records
+const KIND_END: u8 = 0;
+const KIND_BYTES: u8 = 1;
+const KIND_U64: u8 = 2;
+
+/// The number of items to read from the cache for each scan when writing a
+/// snapshot.
+pub(crate) const SNAPSHOT_BATCH: usize = 1024;
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Reads an entire snapshot to verify its checksum, and then returns the reader
+/// to where the snapshot starts. This allows a snapshot to be checked before
+/// any of its items are loaded.
+pub(crate) fn verify<R: Read + Seek>(reader: &mut R) -> Result<()> {
+    let start = reader.stream_position()?;
+
+    let mut snapshot = SnapshotReader::new(&mut *reader)?;
+    while snapshot.next_item()?.is_some() {}
+
+    reader.seek(SeekFrom::Start(start))?;
+    Ok(())
+}
+
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+/// Writes items to a snapshot, hashing the bytes as they are written.
+pub(crate) struct SnapshotWriter<W: Write> {
+    writer: W,
+    hasher: blake3::Hasher,
+    count: u64,
+    now: u64,
+}
+
+impl<W: Write> SnapshotWriter<W> {
+    pub fn new(writer: W) -> Result<Self> {
+        let mut snapshot = Self {
+            writer,
+            hasher: blake3::Hasher::new(),
+            count: 0,
+            now: unix_now(),
+        };
+
+        snapshot.write(&MAGIC)?;
+        snapshot.write(&VERSION.to_le_bytes())?;
+
+        Ok(snapshot)
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        self.hasher.update(bytes);
+        self.writer.write_all(bytes)
+    }
+
+    /// Write an item with the remaining TTL, `None` if it does not expire.
+    pub fn item(&mut self, item: &Item, ttl: Option<Duration>) -> Result<()> {
+        let expire_at = match ttl {
+            Some(ttl) => self.now + ttl.as_secs(),
+            None => 0,
+        };
+
+        let optional = item.optional().unwrap_or(&[]);
+
+        match item.value() {
+            Value::Bytes(value) => {
+                self.write(&[KIND_BYTES, item.key().len() as u8])?;
+                self.write(item.key())?;
+                self.write(&[optional.len() as u8])?;
+                self.write(optional)?;
+                self.write(&(value.len() as u32).to_le_bytes())?;
+                self.write(value)?;
+            }
+            Value::U64(value) => {
+                self.write(&[KIND_U64, item.key().len() as u8])?;
+                self.write(item.key())?;
+                self.write(&[optional.len() as u8])?;
+                self.write(optional)?;
+                self.write(&8_u32.to_le_bytes())?;
+                self.write(&value.to_le_bytes())?;
+            }
+        }
+
+        self.write(&expire_at.to_le_bytes())?;
+        self.count += 1;
+
+        Ok(())
+    }
+
+    /// Write the trailer and flush the writer, returning the number of items
+    /// in the snapshot.
+    pub fn finish(mut self) -> Result<usize> {
+        self.write(&[KIND_END])?;
+        self.write(&self.count.to_le_bytes())?;
+
+        let checksum = self.hasher.finalize();
+        self.writer.write_all(checksum.as_bytes())?;
+        self.writer.flush()?;
+
+        Ok(self.count as usize)
+    }
+}
+
+/// An item which was read from a snapshot.
+pub(crate) struct SnapshotItem {
+    key: Vec<u8>,
+    value: OwnedValue,
+    optional: Vec<u8>,
+    ttl: Option<Duration>,
+}
+
+impl SnapshotItem {
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    pub fn value(&self) -> Value<'_> {
+        self.value.as_value()
+    }
+
+    pub fn optional(&self) -> Option<&[u8]> {
+        if self.optional.is_empty() {
+            None
+        } else {
+            Some(&self.optional)
+        }
+    }
+
+    /// The remaining TTL for the item, which is zero if the item does not
+    /// expire. Returns `None` if the item expired before it was read.
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+}
+
+/// Reads items from a snapshot, verifying the checksum once all the items
+/// have been read.
+pub(crate) struct SnapshotReader<R: Read> {
+    reader: R,
+    hasher: blake3::Hasher,
+    count: u64,
+    done: bool,
+    now: u64,
+}
+
+impl<R: Read> SnapshotReader<R> {
+    pub fn new(reader: R) -> Result<Self> {
+        let mut snapshot = Self {
+            reader,
+            hasher: blake3::Hasher::new(),
+            count: 0,
+            done: false,
+            now: unix_now(),
+        };
+
+        let mut magic = [0; 8];
+        snapshot.read(&mut magic)?;
+        if magic != MAGIC {
+            return Err(invalid("not a snapshot"));
+        }
+
+        let version = u32::from_le_bytes(snapshot.read_array()?);
+        if version != VERSION {
+            return Err(invalid("unsupported snapshot version"));
+        }
+
+        Ok(snapshot)
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<()> {
+        self.read_unhashed(buf)?;
+        self.hasher.update(buf);
+        Ok(())
+    }
+
+    fn read_unhashed(&mut self, buf: &mut [u8]) -> Result<()> {
+        self.reader.read_exact(buf).map_err(|e| {
+            if e.kind() == ErrorKind::UnexpectedEof {
+                invalid("snapshot is truncated")
+            } else {
+                e
+            }
+        })
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut buf = [0; N];
+        self.read(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn read_vec(&mut self, len: usize) -> Result<Vec<u8>> {
+        let mut buf = vec![0; len];
+        self.read(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// Read the next item, returning `None` once the trailer has been read and
+    /// the checksum has been verified.
+    pub fn next_item(&mut self) -> Result<Option<SnapshotItem>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let [kind] = self.read_array()?;
+        if kind == KIND_END {
+            return self.finish().map(|_| None);
+        }
+
+        let [klen] = self.read_array()?;
+        let key = self.read_vec(klen as usize)?;
+        let [olen] = self.read_array()?;
+        let optional = self.read_vec(olen as usize)?;
+        let vlen = u32::from_le_bytes(self.read_array()?);
+
+        let value = match kind {
+            KIND_BYTES => OwnedValue::Bytes(self.read_vec(vlen as usize)?.into_boxed_slice()),
+            KIND_U64 if vlen == 8 => OwnedValue::U64(u64::from_le_bytes(self.read_array()?)),
+            _ => {
+                return Err(invalid("malformed snapshot record"));
+            }
+        };
+
+        let expire_at = u64::from_le_bytes(self.read_array()?);
+
+        if key.is_empty() {
+            return Err(invalid("malformed snapshot record"));
+        }
+
+        let ttl = match expire_at {
+            0 => Some(Duration::ZERO),
+            _ if expire_at > self.now => Some(Duration::from_secs(expire_at - self.now)),
+            _ => None,
+        };
+
+        self.count += 1;
+
+        Ok(Some(SnapshotItem {
+            key,
+            value,
+            optional,
+            ttl,
+        }))
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        let count = u64::from_le_bytes(self.read_array()?);
+        let expected = self.hasher.finalize();
+
+        let mut checksum = [0; 32];
+        self.read_unhashed(&mut checksum)?;
+
+        if checksum != *expected.as_bytes() {
+            return Err(invalid("snapshot checksum mismatch"));
+        }
+
+        if count != self.count {
+            return Err(invalid("snapshot item count mismatch"));
+        }
+
+        self.done = true;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! Introspection of how the cache is using its segments.
+//!
+//! Unlike the metrics, which are counters across the lifetime of the process,
+//! these are gathered on demand by walking the segment chain of each TTL
+//! bucket, so they describe the contents of the cache at the time they are
+//! requested. Bytes which have been written to a segment but which no longer
+//! belong to a live item, for example because the item was deleted or
+//! overwritten, are reported as dead bytes. They are reclaimed when the segment
+//! expires, is evicted, or is merged.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// The occupancy of the segments in a single TTL bucket.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TtlStats {
+    bucket: usize,
+    ttl: Duration,
+    segments: usize,
+    items: usize,
+    live_bytes: usize,
+    dead_bytes: usize,
+    age: Duration,
+    merges: u64,
+}
+
+impl TtlStats {
+    pub(crate) fn new(bucket: usize, ttl: Duration, merges: u64) -> Self {
+        Self {
+            bucket,
+            ttl,
+            segments: 0,
+            items: 0,
+            live_bytes: 0,
+            dead_bytes: 0,
+            age: Duration::ZERO,
+            merges,
+        }
+    }
+
+    pub(crate) fn add_segment(&mut self, items: usize, live: usize, written: usize, age: Duration) {
+        self.segments += 1;
+        self.items += items;
+        self.live_bytes += live;
+        self.dead_bytes += written.saturating_sub(live);
+        self.age = self.age.max(age);
+    }
+
+    /// Combines the stats for the same bucket from another shard.
+    pub(crate) fn merge(&mut self, other: &Self) {
+        self.segments += other.segments;
+        self.items += other.items;
+        self.live_bytes += other.live_bytes;
+        self.dead_bytes += other.dead_bytes;
+        self.age = self.age.max(other.age);
+        self.merges += other.merges;
+    }
+
+    /// The index of the TTL bucket.
+    pub fn bucket(&self) -> usize {
+        self.bucket
+    }
+
+    /// The smallest TTL of the items held by the bucket. The last bucket also
+    /// holds the items which do not expire.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// The number of segments in the bucket.
+    pub fn segments(&self) -> usize {
+        self.segments
+    }
+
+    /// The number of live items in the bucket.
+    pub fn items(&self) -> usize {
+        self.items
+    }
+
+    /// The number of bytes held by live items, including the item headers.
+    pub fn live_bytes(&self) -> usize {
+        self.live_bytes
+    }
+
+    /// The number of bytes which were written to the segments but are no
+    /// longer held by a live item.
+    pub fn dead_bytes(&self) -> usize {
+        self.dead_bytes
+    }
+
+    /// The age of the oldest segment in the bucket.
+    pub fn age(&self) -> Duration {
+        self.age
+    }
+
+    /// The number of times segments in the bucket have been merged.
+    pub fn merges(&self) -> u64 {
+        self.merges
+    }
+}
+
+/// A summary of the segments across the whole cache.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SegmentStats {
+    segment_size: usize,
+    total: usize,
+    free: usize,
+    merges: u64,
+    // the number of segments in use, keyed by the upper bound of their age in
+    // seconds
+    ages: BTreeMap<u64, usize>,
+}
+
+impl SegmentStats {
+    pub(crate) fn new(segment_size: usize, total: usize, free: usize) -> Self {
+        Self {
+            segment_size,
+            total,
+            free,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn add_segment(&mut self, age: Duration) {
+        let bound = age.as_secs().max(1).next_power_of_two();
+        *self.ages.entry(bound).or_default() += 1;
+    }
+
+    pub(crate) fn add_merges(&mut self, merges: u64) {
+        self.merges += merges;
+    }
+
+    /// Combines the stats from another shard.
+    pub(crate) fn merge(&mut self, other: &Self) {
+        self.segment_size = self.segment_size.max(other.segment_size);
+        self.total += other.total;
+        self.free += other.free;
+        self.merges += other.merges;
+        for (bound, count) in &other.ages {
+            *self.ages.entry(*bound).or_default() += count;
+        }
+    }
+
+    /// The size of each segment in bytes.
+    pub fn segment_size(&self) -> usize {
+        self.segment_size
+    }
+
+    /// The total number of segments, including those in the flash tier.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// The number of segments on the free queue.
+    pub fn free(&self) -> usize {
+        self.free
+    }
+
+    /// The number of times segments have been merged.
+    pub fn merges(&self) -> u64 {
+        self.merges
+    }
+
+    /// The age distribution of the segments which are in use. Each entry is
+    /// the upper bound of an age range, which is a power of two number of
+    /// seconds, and the number of segments whose age falls in that range.
+    pub fn ages(&self) -> impl Iterator<Item = (Duration, usize)> + '_ {
+        self.ages
+            .iter()
+            .map(|(bound, count)| (Duration::from_secs(*bound), *count))
+    }
+}
@@ -6,6 +6,7 @@ use super::*;
 use crate::hashtable::HashBucket;
 use crate::item::ITEM_HDR_SIZE;
 use core::num::NonZeroU32;
+use std::io::Cursor;
 
 use std::time::Duration;
 
@@ -107,6 +108,43 @@ fn cas() {
     assert_eq!(cache.cas(b"coffee", b"iced", None, ttl, item.cas()), Ok(()));
 }
 
+#[test]
+fn optional_data() {
+    let ttl = Duration::ZERO;
+    let segment_size = 4096;
+    let segments = 64;
+    let heap_size = segments * segment_size as usize;
+
+    let mut cache = Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .build()
+        .expect("failed to create cache");
+
+    // the longest optional data which fits in the item header
+    let optional = [7; 31];
+    assert!(cache
+        .insert(b"coffee", b"hot", Some(&optional), ttl)
+        .is_ok());
+    let item = cache.get(b"coffee").expect("didn't get item back");
+    assert_eq!(item.optional(), Some(&optional[..]));
+
+    // longer optional data is rejected rather than truncated
+    let optional = [7; 32];
+    assert_eq!(
+        cache.insert(b"tea", b"hot", Some(&optional), ttl),
+        Err(SegcacheError::OptionalOversized { size: 32 })
+    );
+    assert!(cache.get(b"tea").is_none());
+
+    let item = cache.get(b"coffee").unwrap();
+    assert_eq!(
+        cache.cas(b"coffee", b"iced", Some(&optional), ttl, item.cas()),
+        Err(SegcacheError::OptionalOversized { size: 32 })
+    );
+    assert_eq!(cache.get(b"coffee").unwrap().value(), b"hot");
+}
+
 #[test]
 fn overwrite() {
     let ttl = Duration::ZERO;
@@ -247,6 +285,128 @@ fn collisions() {
     assert_eq!(cache.items(), 7);
 }
 
+#[test]
+fn hashtable_grow() {
+    let ttl = Duration::ZERO;
+    let segment_size = 4096;
+    let segments = 64;
+    let heap_size = segments * segment_size as usize;
+
+    let mut cache = Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .hash_power(3)
+        .hash_power_max(12)
+        .overflow_factor(1.0)
+        .build()
+        .expect("failed to create cache");
+    assert_eq!(cache.hashtable.power(), 3);
+
+    // with growth enabled, inserts beyond the capacity of the initial
+    // hashtable succeed
+    for i in 0..500 {
+        let v = format!("{i}");
+        assert!(cache.insert(v.as_bytes(), v.as_bytes(), None, ttl).is_ok());
+    }
+    assert_eq!(cache.items(), 500);
+    assert!(cache.hashtable.power() > 3);
+    assert!(cache.hashtable.power() <= 12);
+
+    // items remain reachable regardless of which table holds them
+    for i in 0..500 {
+        let v = format!("{i}");
+        let item = cache.get(v.as_bytes()).expect("didn't get item back");
+        assert_eq!(item.value(), v.as_bytes()[..]);
+    }
+
+    // accesses migrate buckets until the old table is dropped
+    for i in 0..500 {
+        let v = format!("{i}");
+        assert!(cache.get(v.as_bytes()).is_some());
+    }
+    assert!(!cache.hashtable.is_growing());
+
+    for i in 0..250 {
+        let v = format!("{i}");
+        assert!(cache.delete(v.as_bytes()));
+    }
+    assert_eq!(cache.items(), 250);
+    assert!(cache.get(b"0").is_none());
+    assert!(cache.get(b"499").is_some());
+}
+
+#[test]
+fn hashtable_grow_overflow() {
+    let segment_size = 1024;
+    let segments = 64;
+    let heap_size = segments * segment_size as usize;
+
+    let mut cache = Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .hash_power(4)
+        .hash_power_max(5)
+        .overflow_factor(1.0)
+        .large_item_max(4 * 1024)
+        .build()
+        .expect("failed to create cache");
+
+    // keys which share a primary bucket in both the initial table and the
+    // table it grows into
+    let mut keys = (0..)
+        .map(|i| format!("{i}"))
+        .filter(|key| cache.hashtable.hash(key.as_bytes()) & 0b11 == 0)
+        .take(16)
+        .collect::<Vec<_>>()
+        .into_iter();
+
+    let kept: Vec<String> = keys.by_ref().take(7).collect();
+    for key in &kept {
+        assert!(cache
+            .insert(key.as_bytes(), key.as_bytes(), None, Duration::ZERO)
+            .is_ok());
+    }
+
+    // the remaining items are spread across segments in another TTL bucket
+    let ttl = Duration::from_secs(3600);
+    let large = keys.next().unwrap();
+    assert!(cache
+        .insert(large.as_bytes(), &vec![0; 2048], None, ttl)
+        .is_ok());
+
+    let mut dropped = vec![large];
+    while !cache.hashtable.is_growing() {
+        let key = keys.next().unwrap();
+        assert!(cache
+            .insert(key.as_bytes(), &vec![0; 300], None, ttl)
+            .is_ok());
+        dropped.push(key);
+    }
+
+    // once the overflow buckets of the new table are used up, the items
+    // beyond the first seven in the chain cannot be migrated
+    cache.hashtable.exhaust_overflow();
+    for key in &kept {
+        assert!(cache.get(key.as_bytes()).is_some());
+    }
+    assert!(!cache.hashtable.is_growing());
+
+    for key in &dropped {
+        assert!(cache.get(key.as_bytes()).is_none());
+    }
+
+    // the chunks of the large item are removed with it
+    assert_eq!(cache.items(), kept.len());
+
+    // segments which were emptied are reclaimed, other than the one in each
+    // TTL bucket which is still open for writes
+    for stats in cache.ttl_stats() {
+        if stats.items() == 0 {
+            assert!(stats.segments() <= 1);
+        }
+    }
+}
+
 #[test]
 fn full_cache_long() {
     let ttl = Duration::ZERO;
@@ -483,6 +643,131 @@ fn saturating_sub() {
     assert_eq!(item.value(), 0, "item is: {item:?}");
 }
 
+#[test]
+fn large_item() {
+    let ttl = Duration::ZERO;
+    let segment_size = 4096;
+    let segments = 64;
+    let heap_size = segments * segment_size as usize;
+
+    let mut cache = Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .large_item_max(32 * 1024)
+        .build()
+        .expect("failed to create cache");
+
+    let value: Vec<u8> = (0..20_000).map(|i| i as u8).collect();
+    assert!(cache.insert(b"coffee", &value, None, ttl).is_ok());
+    assert_eq!(cache.items(), 6);
+
+    let item = cache.get(b"coffee").expect("didn't get item back");
+    assert_eq!(item.value(), value[..]);
+
+    // replacing the value releases the chunks
+    assert!(cache.insert(b"coffee", b"strong", None, ttl).is_ok());
+    assert_eq!(cache.items(), 1);
+    assert_eq!(cache.get(b"coffee").unwrap().value(), b"strong");
+
+    // deleting the value releases the chunks
+    assert!(cache.insert(b"coffee", &value, None, ttl).is_ok());
+    assert!(cache.delete(b"coffee"));
+    assert_eq!(cache.items(), 0);
+
+    // values beyond the configured limit are rejected
+    let value = vec![0; 64 * 1024];
+    assert!(cache.insert(b"coffee", &value, None, ttl).is_err());
+    assert!(cache.get(b"coffee").is_none());
+}
+
+#[test]
+fn large_item_chunks() {
+    let ttl = Duration::ZERO;
+    let segment_size = 4096;
+    let segments = 64;
+    let heap_size = segments * segment_size as usize;
+
+    let mut cache = Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .large_item_max(32 * 1024)
+        .build()
+        .expect("failed to create cache");
+
+    let value: Vec<u8> = (0..20_000).map(|i| i as u8).collect();
+    assert!(cache.insert(b"coffee", &value, None, ttl).is_ok());
+
+    // chunks cannot be reached through their keys
+    let large = cache
+        .hashtable
+        .get_no_freq_incr(b"coffee", &mut cache.segments)
+        .and_then(|item| item.large())
+        .expect("no manifest");
+    let chunk_key = large.chunk_key(0);
+    assert!(cache.get(&chunk_key).is_none());
+    assert!(cache.get_no_freq_incr(&chunk_key).is_none());
+    assert_eq!(
+        cache.insert(&chunk_key, b"strong", None, ttl),
+        Err(SegcacheError::ReservedKey)
+    );
+    assert!(!cache.delete(&chunk_key));
+    assert_eq!(cache.get(b"coffee").unwrap().value(), value[..]);
+
+    // evicting the manifest releases the chunks
+    let seg_id = cache
+        .hashtable
+        .get_item_seg(b"coffee", &mut cache.segments)
+        .expect("no manifest");
+    let mut orphans = Vec::new();
+    cache
+        .segments
+        .get_mut(seg_id)
+        .unwrap()
+        .clear(&mut cache.hashtable, false, Some(&mut orphans));
+    assert_eq!(orphans, vec![large]);
+    cache.remove_orphans(orphans);
+    assert_eq!(cache.items(), 0);
+    assert!(cache.get(b"coffee").is_none());
+}
+
+#[test]
+fn compression() {
+    let ttl = Duration::ZERO;
+    let segment_size = 4096;
+    let segments = 64;
+    let heap_size = segments * segment_size as usize;
+
+    let mut cache = Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .large_item_max(64 * 1024)
+        .compression(Compression::Lz4)
+        .compression_threshold(64)
+        .build()
+        .expect("failed to create cache");
+
+    // small values are stored as-is
+    assert!(cache.insert(b"small", b"strong", None, ttl).is_ok());
+    let item = cache.get(b"small").expect("didn't get item back");
+    assert!(!item.is_compressed());
+    assert_eq!(item.value(), b"strong");
+
+    // compressible values are stored compressed
+    let value = br#"{"drink":"coffee","strength":"strong"}"#.repeat(64);
+    assert!(cache.insert(b"coffee", &value, None, ttl).is_ok());
+    let item = cache.get(b"coffee").expect("didn't get item back");
+    assert!(item.is_compressed());
+    assert_eq!(item.value(), value[..]);
+
+    // incompressible values which are too large for a segment are split into
+    // chunks
+    let value: Vec<u8> = (0..40_000u32)
+        .map(|i| (i.wrapping_mul(2654435761) >> 13) as u8)
+        .collect();
+    assert!(cache.insert(b"large", &value, None, ttl).is_ok());
+    assert_eq!(cache.get(b"large").unwrap().value(), value[..]);
+}
+
 #[test]
 // This test caught a case where we interpreted old data as part of an item
 // header. Specifically, the first insert sets bytes that will be in-range for
@@ -731,3 +1016,826 @@ fn fuzz_2() {
     let _ = cache.insert(&[1], &[3, 0, 1], None, Duration::from_secs(0));
     let _ = cache.insert(&[1], &[3, 4, 2], None, Duration::from_secs(114));
 }
+
+#[test]
+fn sieve_eviction() {
+    let ttl = Duration::ZERO;
+    let segment_size = 4096;
+    let segments = 64;
+    let heap_size = segments * segment_size as usize;
+
+    for policy in [Policy::Sieve { max: 8 }, Policy::S3Fifo { max: 8 }] {
+        let mut cache = Segcache::builder()
+            .segment_size(segment_size)
+            .heap_size(heap_size)
+            .eviction(policy)
+            .build()
+            .expect("failed to create cache");
+
+        assert!(cache.insert(b"hot", b"coffee", None, ttl).is_ok());
+
+        // write many times more data than the cache can hold
+        let value = [0; 64];
+        for i in 0..20_000_u32 {
+            let key = format!("{i:08}");
+            assert!(
+                cache.insert(key.as_bytes(), &value, None, ttl).is_ok(),
+                "insert failed with {policy:?}"
+            );
+            let _ = cache.get(b"hot");
+        }
+
+        assert!(cache.items() < 20_000);
+
+        // the frequently accessed key survives sieve eviction
+        if let Policy::Sieve { .. } = policy {
+            assert!(cache.get(b"hot").is_some());
+        }
+    }
+}
+
+#[test]
+fn ttl_bucket_queues() {
+    let segment_size = 4096;
+    let segments = 64;
+    let heap_size = segments * segment_size as usize;
+
+    for policy in [
+        Policy::S3Fifo { max: 8 },
+        Policy::Sieve { max: 8 },
+        Policy::Merge {
+            max: 8,
+            merge: 4,
+            compact: 2,
+        },
+        Policy::Fifo,
+    ] {
+        let mut cache = Segcache::builder()
+            .segment_size(segment_size)
+            .heap_size(heap_size)
+            .eviction(policy)
+            .build()
+            .expect("failed to create cache");
+
+        // a mix of ttls, reads, and deletes which free segments from the middle
+        // of the chains
+        let value = [0; 64];
+        for i in 0..20_000_u32 {
+            let key = format!("{i:08}");
+            let ttl = Duration::from_secs(if i % 2 == 0 { 0 } else { 3600 });
+            assert!(cache.insert(key.as_bytes(), &value, None, ttl).is_ok());
+            if i % 7 == 0 {
+                let _ = cache.get(key.as_bytes());
+            }
+            if i % 3 == 0 && i > 100 {
+                cache.delete(format!("{:08}", i - 100).as_bytes());
+            }
+        }
+
+        // the queue sizes and boundary tracked by each ttl bucket match the chain
+        for bucket in cache.ttl_buckets.buckets.iter() {
+            let mut nseg = 0;
+            let mut nmain = 0;
+            let mut small_head = None;
+
+            let mut id = bucket.head();
+            while let Some(current) = id {
+                let segment = cache.segments.get_mut(current).unwrap();
+                nseg += 1;
+                if segment.is_main() {
+                    assert!(small_head.is_none(), "main segment follows the small queue");
+                    nmain += 1;
+                } else if small_head.is_none() {
+                    small_head = Some(current);
+                }
+                id = segment.next_seg();
+            }
+
+            assert_eq!(bucket.nseg(), nseg);
+            assert_eq!(bucket.nmain(), nmain);
+            assert_eq!(bucket.small_head(), small_head);
+        }
+    }
+}
+
+#[test]
+fn flash_tier() {
+    let ttl = Duration::ZERO;
+    let segment_size = 4096;
+    let segments = 8;
+    let heap_size = segments * segment_size as usize;
+    let flash_size = 32 * segment_size as usize;
+
+    let path = std::env::temp_dir().join(format!("segcache-flash-tier-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut cache = Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .eviction(Policy::Fifo)
+        .flash_path(Some(&path))
+        .flash_size(flash_size)
+        .build()
+        .expect("failed to create cache");
+
+    // write more than fits in memory, the oldest segments are demoted
+    let value = [1; 64];
+    for i in 0..500_u32 {
+        let key = format!("{i:08}");
+        assert!(cache.insert(key.as_bytes(), &value, None, ttl).is_ok());
+    }
+    assert_eq!(cache.items(), 500);
+
+    let demoted = (0..500_u32)
+        .map(|i| format!("{i:08}"))
+        .find(|key| {
+            let seg = cache
+                .hashtable
+                .get_item_seg(key.as_bytes(), &mut cache.segments);
+            cache.segments.is_flash(seg.unwrap())
+        })
+        .expect("no items were demoted");
+
+    // a hit on flash promotes the item back into memory
+    let item = cache.get(demoted.as_bytes()).expect("didn't get item back");
+    assert_eq!(item.value(), value[..]);
+    let seg = cache
+        .hashtable
+        .get_item_seg(demoted.as_bytes(), &mut cache.segments);
+    assert!(!cache.segments.is_flash(seg.unwrap()));
+    assert_eq!(cache.items(), 500);
+
+    for i in 0..500_u32 {
+        let key = format!("{i:08}");
+        let item = cache.get(key.as_bytes()).expect("didn't get item back");
+        assert_eq!(item.value(), value[..]);
+    }
+
+    // once flash is full, its oldest segments are evicted
+    for i in 500..5000_u32 {
+        let key = format!("{i:08}");
+        assert!(cache.insert(key.as_bytes(), &value, None, ttl).is_ok());
+    }
+    assert!(cache.items() < 5000);
+    assert!(cache.get(b"00004999").is_some());
+
+    cache.clear();
+    assert_eq!(cache.items(), 0);
+    assert!(cache.get(b"00004999").is_none());
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn admission() {
+    let ttl = Duration::ZERO;
+    let segment_size = 4096;
+    let segments = 64;
+    let heap_size = segments * segment_size as usize;
+
+    let mut cache = Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .admission(Admission::TinyLfu)
+        .build()
+        .expect("failed to create cache");
+
+    // all writes are admitted while there are free segments
+    let value = [0; 64];
+    let mut i = 0;
+    while cache.segments.free() > 0 {
+        let key = format!("{i:08}");
+        assert!(cache.insert(key.as_bytes(), &value, None, ttl).is_ok());
+        assert!(cache.get(key.as_bytes()).is_some());
+        i += 1;
+    }
+
+    // a key which is only written once is rejected
+    assert_eq!(
+        cache.insert(b"coffee", b"strong", None, ttl),
+        Err(SegcacheError::Rejected)
+    );
+    assert!(cache.get(b"coffee").is_none());
+
+    // updates to existing keys are always admitted
+    let key = format!("{:08}", i - 1);
+    assert!(cache.insert(key.as_bytes(), b"strong", None, ttl).is_ok());
+    assert_eq!(cache.get(key.as_bytes()).unwrap().value(), b"strong");
+
+    // a frequently requested key is admitted
+    for _ in 0..4 {
+        assert!(cache.get(b"tea").is_none());
+    }
+    assert!(cache.insert(b"tea", b"green", None, ttl).is_ok());
+    assert_eq!(cache.get(b"tea").unwrap().value(), b"green");
+
+    // items from a snapshot which are rejected are not counted as loaded
+    let mut source = Segcache::builder().build().expect("failed to create cache");
+    assert!(source.insert(b"latte", b"milky", None, ttl).is_ok());
+    let mut snapshot = Vec::new();
+    assert_eq!(source.save_snapshot(&mut snapshot).unwrap(), 1);
+    assert_eq!(cache.load_snapshot(Cursor::new(&snapshot)).unwrap(), 0);
+    assert!(cache.get(b"latte").is_none());
+}
+
+#[test]
+fn scan() {
+    let segment_size = 4096;
+    let segments = 64;
+    let heap_size = segments * segment_size as usize;
+
+    let mut cache = Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .build()
+        .expect("failed to create cache");
+
+    for i in 0..500_u32 {
+        let key = format!("{i}");
+        let ttl = Duration::from_secs(if i % 2 == 0 { 0 } else { 3600 });
+        assert!(cache
+            .insert(key.as_bytes(), key.as_bytes(), None, ttl)
+            .is_ok());
+    }
+
+    // overwritten and deleted items are skipped
+    assert!(cache.insert(b"0", b"zero", None, Duration::ZERO).is_ok());
+    assert!(cache.delete(b"1"));
+
+    let mut keys = std::collections::HashSet::new();
+    let mut cursor = 0;
+    loop {
+        let (next, items) = cache.scan(cursor, 7);
+        assert!(items.len() <= 7);
+        for item in items {
+            assert!(item.size() > item.key().len());
+            let i: u32 = std::str::from_utf8(item.key()).unwrap().parse().unwrap();
+            if i % 2 == 0 {
+                assert_eq!(item.ttl(), None);
+            } else {
+                assert!(item.ttl().unwrap() <= std::time::Duration::from_secs(3600));
+            }
+            assert!(keys.insert(item.key().to_vec()), "key returned twice");
+        }
+        if next == 0 {
+            break;
+        }
+        cursor = next;
+    }
+    assert_eq!(keys.len(), 499);
+    assert!(!keys.contains(&b"1".to_vec()));
+
+    // the keys of a sharded cache are scanned across all shards, and the
+    // chunks of large items are not returned
+    let cache = Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .large_item_max(16 * 1024)
+        .shards(3)
+        .build_concurrent()
+        .expect("failed to create cache");
+
+    for i in 0..499_u32 {
+        let key = format!("{i}");
+        assert!(cache
+            .insert(key.as_bytes(), key.as_bytes(), None, Duration::ZERO)
+            .is_ok());
+    }
+    let value = vec![0x5A; 10_000];
+    assert!(cache.insert(b"large", &value, None, Duration::ZERO).is_ok());
+
+    let mut keys = std::collections::HashSet::new();
+    let mut cursor = 0;
+    loop {
+        let (next, items) = cache.scan(cursor, 64);
+        keys.extend(items.into_iter().map(|item| item.key().to_vec()));
+        if next == 0 {
+            break;
+        }
+        cursor = next;
+    }
+    assert_eq!(keys.len(), 500);
+    assert!(keys.contains(&b"large".to_vec()));
+}
+
+#[test]
+fn scan_during_merge() {
+    let segment_size = 4096;
+    let segments = 64;
+    let heap_size = segments * segment_size as usize;
+
+    let mut cache = Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .eviction(Policy::Merge {
+            max: 8,
+            merge: 4,
+            compact: 2,
+        })
+        .build()
+        .expect("failed to create cache");
+
+    // fill the cache until segments are being merged
+    let value = [0; 64];
+    let mut next = 0_u32;
+    while cache.segment_stats().merges() == 0 {
+        let key = format!("{next:08}");
+        assert!(cache
+            .insert(key.as_bytes(), &value, None, Duration::ZERO)
+            .is_ok());
+        next += 1;
+    }
+    let existing = next;
+    let merges = cache.segment_stats().merges();
+
+    // keep writing between the batches of a scan, which evicts segments but
+    // does not move items behind the cursor
+    let mut keys = std::collections::HashSet::new();
+    let mut cursor = 0;
+    loop {
+        let (cursor_next, items) = cache.scan(cursor, 16);
+        keys.extend(items.iter().map(|item| item.key().to_vec()));
+        if cursor_next == 0 {
+            break;
+        }
+        cursor = cursor_next;
+
+        for _ in 0..8 {
+            let key = format!("{next:08}");
+            assert!(cache
+                .insert(key.as_bytes(), &value, None, Duration::ZERO)
+                .is_ok());
+            next += 1;
+        }
+    }
+    assert_eq!(cache.segment_stats().merges(), merges);
+
+    // every item which was in the cache for the whole scan was returned
+    let mut kept = 0;
+    for i in 0..existing {
+        let key = format!("{i:08}");
+        if cache.get_no_freq_incr(key.as_bytes()).is_some() {
+            assert!(keys.contains(key.as_bytes()), "missed key: {key}");
+            kept += 1;
+        }
+    }
+    assert!(kept > 0);
+}
+
+#[test]
+fn concurrent() {
+    let ttl = Duration::ZERO;
+    let segment_size = 4096;
+    let segments = 64;
+    let heap_size = segments * segment_size as usize;
+
+    let cache = Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .shards(4)
+        .build_concurrent()
+        .expect("failed to create cache");
+
+    assert_eq!(cache.shards(), 4);
+
+    let threads: Vec<_> = (0..4)
+        .map(|thread| {
+            let cache = cache.clone();
+            std::thread::spawn(move || {
+                for i in 0..100 {
+                    let key = format!("{thread}:{i}");
+                    assert!(cache
+                        .insert(key.as_bytes(), key.as_bytes(), None, ttl)
+                        .is_ok());
+                    let hit = cache.get(key.as_bytes(), |item| item.value() == key.as_bytes()[..]);
+                    assert_eq!(hit, Some(true));
+                }
+            })
+        })
+        .collect();
+
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    assert_eq!(cache.items(), 400);
+
+    // numeric operations are atomic with respect to other threads
+    assert!(cache.insert(b"counter", 0_u64, None, ttl).is_ok());
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let cache = cache.clone();
+            std::thread::spawn(move || {
+                for _ in 0..100 {
+                    assert!(cache.wrapping_add(b"counter", 1).is_ok());
+                }
+            })
+        })
+        .collect();
+
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    assert_eq!(
+        cache.get(b"counter", |item| item.value() == 400),
+        Some(true)
+    );
+
+    // holding the shard locks makes a read-modify-write atomic, whether it
+    // touches one key or several
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let cache = cache.clone();
+            std::thread::spawn(move || {
+                for _ in 0..100 {
+                    let mut shard = cache.lock(b"log");
+                    let len = shard
+                        .get(b"log")
+                        .map(|item| item.value().len())
+                        .unwrap_or(0);
+                    let value = vec![b'.'; len + 1];
+                    assert!(shard.insert(b"log", value.as_slice(), None, ttl).is_ok());
+                    drop(shard);
+
+                    let keys: [&[u8]; 3] = [b"a", b"b", b"c"];
+                    let mut shards = cache.lock_many(keys);
+                    for key in keys {
+                        let shard = shards.shard(key);
+                        let count = match shard.get(key) {
+                            Some(item) => match item.value() {
+                                Value::U64(count) => count,
+                                Value::Bytes(_) => panic!("counter is not numeric"),
+                            },
+                            None => 0,
+                        };
+                        assert!(shard.insert(key, count + 1, None, ttl).is_ok());
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    assert_eq!(cache.get(b"log", |item| item.value().len()), Some(400));
+    for key in [b"a", b"b", b"c"] {
+        assert_eq!(cache.get(key, |item| item.value() == 400), Some(true));
+    }
+
+    assert!(cache.delete(b"counter"));
+    cache.clear();
+    assert_eq!(cache.items(), 0);
+}
+
+#[test]
+fn concurrent_reads() {
+    let cache = Segcache::builder()
+        .segment_size(4096)
+        .heap_size(64 * 4096)
+        .shards(1)
+        .build_concurrent()
+        .expect("failed to create cache");
+
+    assert!(cache
+        .insert(b"coffee", b"strong", None, Duration::ZERO)
+        .is_ok());
+
+    // readers share the shard lock, so both closures run at the same time and
+    // this would deadlock if reads were exclusive
+    let barrier = std::sync::Arc::new(std::sync::Barrier::new(2));
+    let threads: Vec<_> = (0..2)
+        .map(|_| {
+            let cache = cache.clone();
+            let barrier = barrier.clone();
+            std::thread::spawn(move || {
+                cache.get(b"coffee", |item| {
+                    barrier.wait();
+                    item.value() == b"strong"
+                })
+            })
+        })
+        .collect();
+
+    for thread in threads {
+        assert_eq!(thread.join().unwrap(), Some(true));
+    }
+
+    // the queued accesses are applied once the shard is locked exclusively
+    for _ in 0..(2 * crate::concurrent::ACCESS_QUEUE_DEPTH) {
+        assert!(cache.get(b"coffee", |_| ()).is_some());
+        assert!(cache.get(b"tea", |_| ()).is_none());
+    }
+    let mut shard = cache.lock(b"coffee");
+    assert!(shard.get(b"coffee").is_some());
+    assert!(shard.insert(b"tea", b"green", None, Duration::ZERO).is_ok());
+    drop(shard);
+
+    assert_eq!(
+        cache.get(b"tea", |item| item.value() == b"green"),
+        Some(true)
+    );
+}
+
+#[test]
+fn namespaces() {
+    let ttl = Duration::ZERO;
+    let segment_size = 4096;
+    let segments = 64;
+    let heap_size = segments * segment_size as usize;
+
+    let cache = Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .shards(2)
+        .namespace("noisy", b"noisy:", 16 * segment_size as usize)
+        .namespace("vip", b"noisy:vip:", 8 * segment_size as usize)
+        .build_concurrent()
+        .expect("failed to create cache");
+
+    assert_eq!(cache.shards(), 6);
+    assert_eq!(
+        cache.namespaces().collect::<Vec<_>>(),
+        vec!["default", "noisy", "vip"]
+    );
+
+    // keys belong to the namespace with the longest matching prefix
+    assert_eq!(cache.namespace(b"quiet:1"), "default");
+    assert_eq!(cache.namespace(b"noisy:1"), "noisy");
+    assert_eq!(cache.namespace(b"noisy:vip:1"), "vip");
+    assert_eq!(cache.namespace(b"noisy"), "default");
+
+    for i in 0..100 {
+        let key = format!("quiet:{i}");
+        assert!(cache
+            .insert(key.as_bytes(), key.as_bytes(), None, ttl)
+            .is_ok());
+    }
+
+    // writing far more than the quota to one namespace only evicts its own
+    // items
+    let value = [0; 512];
+    for i in 0..2000 {
+        let key = format!("noisy:{i}");
+        assert!(cache.insert(key.as_bytes(), &value[..], None, ttl).is_ok());
+    }
+
+    for i in 0..100 {
+        let key = format!("quiet:{i}");
+        let hit = cache.get(key.as_bytes(), |item| item.value() == key.as_bytes()[..]);
+        assert_eq!(hit, Some(true));
+    }
+
+    let noisy = (0..2000)
+        .filter(|i| cache.get(format!("noisy:{i}").as_bytes(), |_| ()).is_some())
+        .count();
+    assert!(noisy > 0);
+    assert!(noisy < 16 * segment_size as usize / 512);
+
+    #[cfg(feature = "metrics")]
+    {
+        let evictions = |namespace: &str| {
+            let name = format!("namespace/{namespace}/segment_evict");
+            metriken::metrics()
+                .iter()
+                .find(|metric| metric.name() == name)
+                .and_then(|metric| metric.as_any())
+                .and_then(|any| any.downcast_ref::<metriken::Counter>())
+                .map(|counter| counter.value())
+                .expect("missing metric")
+        };
+        assert!(evictions("noisy") > 0);
+        assert_eq!(evictions("default"), 0);
+        assert_eq!(evictions("vip"), 0);
+    }
+
+    // every namespace must have at least one segment per shard
+    assert!(Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .shards(2)
+        .namespace("noisy", b"noisy:", segment_size as usize)
+        .build_concurrent()
+        .is_err());
+    assert!(Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .namespace("noisy", b"noisy:", heap_size)
+        .build_concurrent()
+        .is_err());
+    assert!(Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .namespace("noisy", b"noisy:", 4 * segment_size as usize)
+        .namespace("noisy", b"loud:", 4 * segment_size as usize)
+        .build_concurrent()
+        .is_err());
+}
+
+#[test]
+fn snapshot() {
+    let segment_size = 4096;
+    let segments = 64;
+    let heap_size = segments * segment_size as usize;
+
+    let mut cache = Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .large_item_max(16 * 1024)
+        .compression(Compression::Lz4)
+        .compression_threshold(256)
+        .build()
+        .expect("failed to create cache");
+
+    for i in 0..100_u32 {
+        let key = format!("{i}");
+        assert!(cache
+            .insert(key.as_bytes(), key.as_bytes(), None, Duration::ZERO)
+            .is_ok());
+    }
+    assert!(cache
+        .insert(b"flags", b"value", Some(&[0, 0, 0, 42]), Duration::ZERO)
+        .is_ok());
+    assert!(cache
+        .insert(b"expiring", b"value", None, Duration::from_secs(3600))
+        .is_ok());
+    assert!(cache
+        .insert(b"counter", 42_u64, None, Duration::ZERO)
+        .is_ok());
+    let compressible = vec![0x5A; 1024];
+    assert!(cache
+        .insert(b"compressed", &compressible, None, Duration::ZERO)
+        .is_ok());
+    let large: Vec<u8> = (0..10_000_u32).map(|i| i as u8).collect();
+    assert!(cache.insert(b"large", &large, None, Duration::ZERO).is_ok());
+
+    let mut snapshot = Vec::new();
+    assert_eq!(cache.save_snapshot(&mut snapshot).unwrap(), 105);
+
+    // a snapshot may be loaded into a cache with a different configuration
+    let mut restored = Segcache::builder()
+        .segment_size(2 * segment_size)
+        .heap_size(heap_size)
+        .large_item_max(16 * 1024)
+        .build()
+        .expect("failed to create cache");
+    assert_eq!(restored.load_snapshot(Cursor::new(&snapshot)).unwrap(), 105);
+
+    for i in 0..100_u32 {
+        let key = format!("{i}");
+        let item = restored.get(key.as_bytes()).expect("didn't get item back");
+        assert_eq!(item.value(), key.as_bytes()[..]);
+    }
+    let item = restored.get(b"flags").unwrap();
+    assert_eq!(item.optional(), Some(&[0, 0, 0, 42][..]));
+    assert_eq!(restored.get(b"counter").unwrap().value(), 42);
+    assert_eq!(
+        restored.get(b"compressed").unwrap().value(),
+        compressible[..]
+    );
+    assert_eq!(restored.get(b"large").unwrap().value(), large[..]);
+    assert_eq!(restored.ttl(b"flags"), Ok(None));
+    // the expiry is kept, subject to the granularity of the ttl buckets
+    let ttl = restored.ttl(b"expiring").unwrap().unwrap();
+    assert!(ttl > Duration::from_secs(3300) && ttl <= Duration::from_secs(3600));
+
+    // a corrupted snapshot is rejected without loading any items or
+    // disturbing the existing contents
+    let mut corrupted = snapshot.clone();
+    corrupted[20] ^= 0xFF;
+    let mut restored = Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .large_item_max(16 * 1024)
+        .build()
+        .expect("failed to create cache");
+    assert!(restored.insert(b"existing", b"value", None, ttl).is_ok());
+    assert!(restored.load_snapshot(Cursor::new(&corrupted)).is_err());
+    assert_eq!(restored.items(), 1);
+    assert_eq!(restored.get(b"existing").unwrap().value(), b"value");
+
+    // as is a truncated snapshot
+    let truncated = &snapshot[..snapshot.len() - 1];
+    assert!(restored.load_snapshot(Cursor::new(truncated)).is_err());
+    assert!(restored
+        .load_snapshot(Cursor::new(&b"not a snapshot"[..]))
+        .is_err());
+    assert_eq!(restored.items(), 1);
+
+    // a sharded cache is saved and loaded across all shards
+    let sharded = Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .large_item_max(16 * 1024)
+        .shards(3)
+        .build_concurrent()
+        .expect("failed to create cache");
+    assert_eq!(sharded.load_snapshot(Cursor::new(&snapshot)).unwrap(), 105);
+    assert_eq!(
+        sharded.get(b"large", |item| item.value() == large[..]),
+        Some(true)
+    );
+
+    let mut snapshot = Vec::new();
+    assert_eq!(sharded.save_snapshot(&mut snapshot).unwrap(), 105);
+}
+
+#[test]
+fn stats() {
+    let segment_size = 4096;
+    let segments = 64;
+    let heap_size = segments * segment_size as usize;
+
+    let mut cache = Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .eviction(Policy::Merge {
+            max: 8,
+            merge: 4,
+            compact: 2,
+        })
+        .build()
+        .expect("failed to create cache");
+
+    let ttl = Duration::from_secs(60);
+    for i in 0..10_u32 {
+        let key = format!("{i}");
+        assert!(cache.insert(key.as_bytes(), b"value", None, ttl).is_ok());
+    }
+
+    // overwritten and deleted items leave dead bytes behind
+    assert!(cache.insert(b"0", b"other", None, ttl).is_ok());
+    assert!(cache.delete(b"1"));
+
+    let stats = cache.ttl_stats();
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].bucket(), 7);
+    assert_eq!(stats[0].ttl(), Duration::from_secs(57));
+    assert_eq!(stats[0].segments(), 1);
+    assert_eq!(stats[0].items(), 9);
+    assert!(stats[0].live_bytes() > 0);
+    assert!(stats[0].dead_bytes() > 0);
+    assert_eq!(stats[0].merges(), 0);
+
+    let stats = cache.segment_stats();
+    assert_eq!(stats.segment_size(), 4096);
+    assert_eq!(stats.total(), 64);
+    assert_eq!(stats.free(), 63);
+    assert_eq!(
+        stats.ages().collect::<Vec<_>>(),
+        vec![(Duration::from_secs(1), 1)]
+    );
+
+    // write many times more data than the cache can hold, so that segments
+    // without a ttl are merged
+    let value = [0; 64];
+    for i in 0..20_000_u32 {
+        let key = format!("{i:08}");
+        assert!(cache
+            .insert(key.as_bytes(), &value, None, Duration::ZERO)
+            .is_ok());
+    }
+
+    let stats = cache.ttl_stats();
+    let last = stats.last().unwrap();
+    assert_eq!(last.bucket(), 1023);
+    assert!(last.merges() > 0);
+
+    let merges = stats.iter().map(|s| s.merges()).sum::<u64>();
+    assert_eq!(cache.segment_stats().merges(), merges);
+
+    // sieve eviction removes segments without merging them
+    let mut cache = Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .eviction(Policy::Sieve { max: 8 })
+        .build()
+        .expect("failed to create cache");
+    for i in 0..20_000_u32 {
+        let key = format!("{i:08}");
+        assert!(cache
+            .insert(key.as_bytes(), &value, None, Duration::ZERO)
+            .is_ok());
+    }
+    assert_eq!(cache.segment_stats().merges(), 0);
+
+    // stats are combined across the shards of a concurrent cache
+    let cache = Segcache::builder()
+        .segment_size(segment_size)
+        .heap_size(heap_size)
+        .shards(4)
+        .build_concurrent()
+        .expect("failed to create cache");
+
+    for i in 0..100_u32 {
+        let key = format!("{i}");
+        assert!(cache.insert(key.as_bytes(), b"value", None, ttl).is_ok());
+    }
+
+    let stats = cache.ttl_stats();
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].items(), 100);
+    assert_eq!(cache.segment_stats().total(), 64);
+}
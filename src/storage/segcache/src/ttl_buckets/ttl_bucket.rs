@@ -11,11 +11,11 @@
 //! │   HEAD SEG   │   TAIL SEG   │     TTL     │     NSEG     │
 //! │              │              │             │              │
 //! │    32 bit    │    32 bit    │    32 bit   │    32 bit    │
-//! ├──────────────┼──────────────┴─────────────┴──────────────┤
-//! │  NEXT MERGE  │                  PADDING                  │
-//! │              │                                           │
-//! │    32 bit    │                  96 bit                   │
-//! ├──────────────┴───────────────────────────────────────────┤
+//! ├──────────────┼──────────────┼─────────────┼──────────────┤
+//! │  NEXT MERGE  │    MERGES    │    NMAIN    │  SMALL HEAD  │
+//! │              │              │             │              │
+//! │    32 bit    │    32 bit    │    32 bit   │    32 bit    │
+//! ├──────────────┴──────────────┴─────────────┴──────────────┤
 //! │                         PADDING                          │
 //! │                                                          │
 //! │                         128 bit                          │
@@ -39,7 +39,14 @@ pub struct TtlBucket {
     ttl: i32,
     nseg: i32,
     next_to_merge: Option<NonZeroU32>,
-    _pad: [u8; 44],
+    merges: u32,
+    // the number of segments in the S3-FIFO main queue, which are always at
+    // the front of the chain
+    nmain: i32,
+    // the first segment of the S3-FIFO small queue, which follows the main
+    // queue in the chain
+    small_head: Option<NonZeroU32>,
+    _pad: [u8; 32],
 }
 
 impl TtlBucket {
@@ -51,7 +58,10 @@ impl TtlBucket {
             ttl,
             nseg: 0,
             next_to_merge: None,
-            _pad: [0; 44],
+            merges: 0,
+            nmain: 0,
+            small_head: None,
+            _pad: [0; 32],
         }
     }
 
@@ -76,6 +86,58 @@ impl TtlBucket {
         self.next_to_merge = next;
     }
 
+    /// Returns the smallest TTL, in seconds, of the items held by the
+    /// `TtlBucket`.
+    pub(crate) fn ttl(&self) -> i32 {
+        self.ttl
+    }
+
+    /// Returns the number of times segments within the `TtlBucket` have been
+    /// merged. The count wraps on overflow.
+    pub(crate) fn merges(&self) -> u32 {
+        self.merges
+    }
+
+    /// Record that segments within the `TtlBucket` have been merged.
+    pub(crate) fn incr_merges(&mut self) {
+        self.merges = self.merges.wrapping_add(1);
+    }
+
+    /// Returns the number of segments in the `TtlBucket`.
+    pub(crate) fn nseg(&self) -> i32 {
+        self.nseg
+    }
+
+    /// Returns the number of segments in the S3-FIFO main queue.
+    pub(crate) fn nmain(&self) -> i32 {
+        self.nmain
+    }
+
+    /// Returns the first segment of the S3-FIFO small queue.
+    pub(crate) fn small_head(&self) -> Option<NonZeroU32> {
+        self.small_head
+    }
+
+    /// Record that the S3-FIFO main queue has grown by `promoted` segments,
+    /// and that the small queue now starts at `small_head`.
+    pub(crate) fn set_main(&mut self, promoted: i32, small_head: Option<NonZeroU32>) {
+        self.nmain += promoted;
+        self.small_head = small_head;
+    }
+
+    /// Record that a segment is being removed from the chain. This must be
+    /// called before the segment is returned to the free queue, the caller is
+    /// responsible for relinking the chain.
+    pub(crate) fn remove_segment(&mut self, segment: &Segment) {
+        self.nseg -= 1;
+        if segment.is_main() {
+            self.nmain -= 1;
+        }
+        if self.small_head == Some(segment.id()) {
+            self.small_head = segment.next_seg();
+        }
+    }
+
     /// Expire segments from this TtlBucket, returns the number of segments
     /// expired.
     pub(super) fn expire(&mut self, hashtable: &mut HashTable, segments: &mut Segments) -> usize {
@@ -84,7 +146,7 @@ impl TtlBucket {
         }
 
         let mut expired = 0;
-        let ts = Instant::now();
+        let ts = clock::now();
 
         loop {
             let seg_id = self.head;
@@ -98,7 +160,8 @@ impl TtlBucket {
                         self.head = None;
                         self.tail = None;
                     }
-                    segment.clear(hashtable, true);
+                    segment.clear(hashtable, true, None);
+                    self.remove_segment(&segment);
                     segments.push_free(seg_id);
 
                     #[cfg(feature = "metrics")]
@@ -133,7 +196,8 @@ impl TtlBucket {
                     self.head = None;
                     self.tail = None;
                 }
-                segment.clear(hashtable, true);
+                segment.clear(hashtable, true, None);
+                self.remove_segment(&segment);
                 segments.push_free(seg_id);
 
                 #[cfg(feature = "metrics")]
@@ -168,6 +232,9 @@ impl TtlBucket {
             }
             self.tail = Some(id);
             self.nseg += 1;
+            if self.small_head.is_none() {
+                self.small_head = Some(id);
+            }
             debug_assert!(!segment.evictable(), "segment should not be evictable");
             segment.set_evictable(true);
             segment.set_accessible(true);
@@ -70,7 +70,7 @@ impl TtlBuckets {
         }
 
         let buckets = buckets.into_boxed_slice();
-        let last_expired = Instant::now();
+        let last_expired = clock::now();
 
         Self {
             buckets,
@@ -99,6 +99,12 @@ impl TtlBuckets {
         }
     }
 
+    /// Returns true if the TTL maps to the last bucket. Items stored without a
+    /// TTL share this bucket with items whose TTL exceeds the largest range.
+    pub(crate) fn is_max_ttl(&self, ttl: Duration) -> bool {
+        self.get_bucket_index(ttl) == MAX_TTL_BUCKET_IDX
+    }
+
     // TODO(bmartin): confirm handling for negative TTLs here...
     /// Get a mutable reference to the `TtlBucket` for the given TTL.
     pub(crate) fn get_mut_bucket(&mut self, ttl: Duration) -> &mut TtlBucket {
@@ -110,7 +116,7 @@ impl TtlBuckets {
     }
 
     pub(crate) fn expire(&mut self, hashtable: &mut HashTable, segments: &mut Segments) -> usize {
-        let now = Instant::now();
+        let now = clock::now();
 
         if now == self.last_expired {
             return 0;
@@ -123,6 +129,7 @@ impl TtlBuckets {
         for bucket in self.buckets.iter_mut() {
             expired += bucket.expire(hashtable, segments);
         }
+        expired += segments.expire_flash(hashtable);
         let duration = start.elapsed();
         debug!("expired: {} segments in {:?}", expired, duration);
 
@@ -138,7 +145,8 @@ impl TtlBuckets {
         for bucket in self.buckets.iter_mut() {
             cleared += bucket.clear(hashtable, segments);
         }
-        segments.set_flush_at(Instant::now());
+        cleared += segments.clear_flash(hashtable);
+        segments.set_flush_at(clock::now());
         let duration = start.elapsed();
         debug!("expired: {} segments in {:?}", cleared, duration);
 
@@ -0,0 +1,96 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! Splitting of a command line into arguments. Arguments are separated by
+//! whitespace and may be quoted. Double quoted arguments may contain escapes:
+//! `\n`, `\r`, `\t`, `\"`, `\\` and `\xHH`. Single quoted arguments are taken
+//! as they are, except for `\'`.
+
+/// Split the line into arguments, or return an error if a quote is not closed
+/// or is not followed by whitespace.
+pub fn split(line: &str) -> Result<Vec<Vec<u8>>, &'static str> {
+    let mut args = Vec::new();
+    let mut chars = line.trim().chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        let quote = match chars.peek() {
+            None => return Ok(args),
+            Some('"') | Some('\'') => chars.next(),
+            Some(_) => None,
+        };
+
+        let mut arg = Vec::new();
+        loop {
+            let c = match (chars.next(), quote) {
+                (None, None) => break,
+                (None, Some(_)) => return Err("unbalanced quotes"),
+                (Some(c), None) if c.is_whitespace() => break,
+                (Some(c), Some(q)) if c == q => {
+                    if chars.peek().is_some_and(|c| !c.is_whitespace()) {
+                        return Err("closing quote must be followed by a space");
+                    }
+                    break;
+                }
+                (Some('\\'), Some('"')) => match chars.next() {
+                    Some('n') => '\n',
+                    Some('r') => '\r',
+                    Some('t') => '\t',
+                    Some('x') => {
+                        let hex: String = chars.by_ref().take(2).collect();
+                        match u8::from_str_radix(&hex, 16) {
+                            Ok(byte) if hex.len() == 2 => {
+                                arg.push(byte);
+                                continue;
+                            }
+                            _ => return Err("invalid hex escape"),
+                        }
+                    }
+                    Some(c) => c,
+                    None => return Err("unbalanced quotes"),
+                },
+                (Some('\\'), Some('\'')) if chars.peek() == Some(&'\'') => {
+                    chars.next();
+                    '\''
+                }
+                (Some(c), _) => c,
+            };
+
+            let mut buf = [0; 4];
+            arg.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+        }
+
+        args.push(arg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_args() {
+        assert_eq!(
+            split("  get   key ").unwrap(),
+            vec![b"get".to_vec(), b"key".to_vec()]
+        );
+        assert_eq!(
+            split(r#"set key "a value\r\n" '\x00 it\'s'"#).unwrap(),
+            vec![
+                b"set".to_vec(),
+                b"key".to_vec(),
+                b"a value\r\n".to_vec(),
+                b"\\x00 it's".to_vec()
+            ]
+        );
+        assert_eq!(split(r#"set key "\xff\x00""#).unwrap()[2], vec![0xff, 0x00]);
+        assert_eq!(split(r#"set key """#).unwrap()[2], b"");
+        assert!(split("").unwrap().is_empty());
+
+        assert!(split(r#"set key "value"#).is_err());
+        assert!(split(r#"set key "value"x"#).is_err());
+        assert!(split(r#"set key "\x4""#).is_err());
+    }
+}
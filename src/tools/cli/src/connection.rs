@@ -0,0 +1,128 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! A connection to the server which is used synchronously. The streams from
+//! `pelikan_net` are non-blocking, so each operation which would block waits
+//! for the stream to become ready instead.
+
+use pelikan_net::{Connector, Events, Interest, Poll, Stream, Token};
+use protocol_common::ParseOk;
+use std::io::{Error, ErrorKind, Read, Write};
+use std::time::Duration;
+
+const TOKEN: Token = Token(0);
+
+pub struct Connection {
+    poll: Poll,
+    events: Events,
+    stream: Stream,
+    timeout: Duration,
+    buffer: Vec<u8>,
+}
+
+impl Connection {
+    /// Connect to the target and complete the TLS handshake, if there is one.
+    pub fn connect(connector: &Connector, target: &str, timeout: Duration) -> Result<Self, Error> {
+        let poll = Poll::new()?;
+        let mut stream = connector.connect(target)?;
+        poll.registry()
+            .register(&mut stream, TOKEN, Interest::READABLE | Interest::WRITABLE)?;
+
+        let mut connection = Self {
+            poll,
+            events: Events::with_capacity(4),
+            stream,
+            timeout,
+            buffer: Vec::new(),
+        };
+
+        loop {
+            if connection.stream.is_handshaking() {
+                match connection.stream.do_handshake() {
+                    Ok(()) => break,
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => connection.wait(true)?,
+                    Err(e) => return Err(e),
+                }
+            } else if connection.stream.is_established() {
+                break;
+            } else {
+                connection.wait(true)?;
+                // a failed connect is only reported by the readiness event
+                if connection.events.iter().any(|e| e.is_error()) {
+                    return Err(ErrorKind::ConnectionRefused.into());
+                }
+            }
+        }
+
+        let _ = connection.stream.set_nodelay(true);
+
+        Ok(connection)
+    }
+
+    pub fn send(&mut self, mut data: &[u8]) -> Result<(), Error> {
+        while !data.is_empty() {
+            match self.stream.write(data) {
+                Ok(0) => return Err(ErrorKind::WriteZero.into()),
+                Ok(len) => data = &data[len..],
+                Err(e) if e.kind() == ErrorKind::WouldBlock => self.wait(true)?,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        loop {
+            match self.stream.flush() {
+                Ok(()) => return Ok(()),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => self.wait(true)?,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Receive the next message with the parser, which should return an error
+    /// with the kind `WouldBlock` if the message is incomplete. If `timeout`
+    /// is false, this waits for the message indefinitely.
+    pub fn receive<T>(
+        &mut self,
+        timeout: bool,
+        parse: impl Fn(&[u8]) -> Result<ParseOk<T>, Error>,
+    ) -> Result<T, Error> {
+        loop {
+            if !self.buffer.is_empty() {
+                match parse(&self.buffer) {
+                    Ok(parsed) => {
+                        self.buffer.drain(0..parsed.consumed());
+                        return Ok(parsed.into_inner());
+                    }
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => {}
+                    Err(e) => return Err(e),
+                }
+            }
+
+            let mut chunk = [0; 16384];
+            match self.stream.read(&mut chunk) {
+                Ok(0) => return Err(Error::new(ErrorKind::UnexpectedEof, "connection closed")),
+                Ok(len) => self.buffer.extend_from_slice(&chunk[..len]),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => self.wait(timeout)?,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    fn wait(&mut self, timeout: bool) -> Result<(), Error> {
+        let timeout = if timeout { Some(self.timeout) } else { None };
+
+        loop {
+            match self.poll.poll(&mut self.events, timeout) {
+                Ok(()) if self.events.is_empty() => {
+                    return Err(Error::new(ErrorKind::TimedOut, "timed out"));
+                }
+                Ok(()) => return Ok(()),
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! Command history for the interactive mode, which is kept in a file so that
+//! it persists between sessions. Previous commands may be listed with
+//! `history` and run again with `!!` or `!N`.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+/// The number of commands which are loaded from the history file.
+const MAX_ENTRIES: usize = 1000;
+
+#[derive(Default)]
+pub struct History {
+    entries: Vec<String>,
+    file: Option<File>,
+}
+
+impl History {
+    /// Load the history from the file and append new commands to it. History
+    /// is kept in memory only if the file cannot be opened.
+    pub fn open(path: Option<PathBuf>) -> Self {
+        let mut history = Self::default();
+
+        let path = match path {
+            Some(path) => path,
+            None => return history,
+        };
+
+        if let Ok(file) = File::open(&path) {
+            history.entries = BufReader::new(file)
+                .lines()
+                .map_while(Result::ok)
+                .filter(|line| !line.trim().is_empty())
+                .collect();
+            let excess = history.entries.len().saturating_sub(MAX_ENTRIES);
+            history.entries.drain(0..excess);
+        }
+
+        history.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .ok();
+
+        history
+    }
+
+    pub fn push(&mut self, line: &str) {
+        if self.entries.last().map(|l| l.as_str()) == Some(line) {
+            return;
+        }
+
+        self.entries.push(line.to_string());
+        if let Some(file) = self.file.as_mut() {
+            let _ = writeln!(file, "{line}");
+        }
+    }
+
+    /// Expand a reference to a previous command: `!!` for the last command or
+    /// `!N` for the Nth command as numbered by `history`. Other lines are
+    /// returned unchanged.
+    pub fn expand<'a>(&'a self, line: &'a str) -> Result<&'a str, String> {
+        let reference = match line.strip_prefix('!') {
+            Some(reference) => reference,
+            None => return Ok(line),
+        };
+
+        let entry = if reference == "!" {
+            self.entries.last()
+        } else {
+            reference
+                .parse::<usize>()
+                .ok()
+                .and_then(|n| n.checked_sub(1))
+                .and_then(|n| self.entries.get(n))
+        };
+
+        entry
+            .map(|e| e.as_str())
+            .ok_or_else(|| format!("{line}: event not found"))
+    }
+
+    pub fn print(&self, w: &mut dyn Write) -> std::io::Result<()> {
+        for (i, entry) in self.entries.iter().enumerate() {
+            writeln!(w, "{:>5}  {entry}", i + 1)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand() {
+        let mut history = History::default();
+        history.push("get a");
+        history.push("get b");
+        history.push("get b");
+
+        assert_eq!(history.expand("get c"), Ok("get c"));
+        assert_eq!(history.expand("!!"), Ok("get b"));
+        assert_eq!(history.expand("!1"), Ok("get a"));
+        assert!(history.expand("!3").is_err());
+        assert!(history.expand("!0").is_err());
+
+        let mut output = Vec::new();
+        history.print(&mut output).unwrap();
+        assert_eq!(output, b"    1  get a\n    2  get b\n");
+    }
+}
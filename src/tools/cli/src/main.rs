@@ -0,0 +1,374 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! A command-line client for Pelikan servers, which speaks the memcache text
+//! and binary protocols, RESP, ping, and the admin protocol.
+//!
+//! Commands may be given as arguments, read from a file or from stdin in batch
+//! mode, or entered interactively with a history which is kept between
+//! sessions. Requests are built and responses are parsed with the same
+//! protocol crates which are used by the servers.
+
+use args::split;
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use connection::Connection;
+use history::History;
+use pelikan_net::{Connector, TcpConnector};
+use protocol::{Protocol, Request};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Error, ErrorKind, IsTerminal, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+mod args;
+mod connection;
+mod history;
+mod protocol;
+
+/// The name of the history file in the home directory.
+const HISTORY_FILE: &str = ".pelikan_cli_history";
+
+const HELP: &str = "\
+Commands are sent to the server as they are typed, with arguments separated
+by spaces. Arguments may be quoted, and double quoted arguments may contain
+escapes such as \\r, \\n and \\xHH. For the memcache storage commands, give
+the value in place of its length, eg: set key 0 0 \"a value\"
+
+In addition to the protocol commands:
+  help        show this help
+  history     list previous commands
+  !!          run the last command again
+  !N          run command N from the history again
+  quit, exit  leave the client";
+
+struct Client {
+    protocol: Protocol,
+    connector: Connector,
+    target: String,
+    timeout: Duration,
+    connection: Option<Connection>,
+}
+
+fn main() {
+    let matches = Command::new(env!("CARGO_BIN_NAME"))
+        .version(env!("CARGO_PKG_VERSION"))
+        .long_about(
+            "A command-line client for Pelikan servers. Runs the command given \
+            as arguments, the commands read from a file or from stdin, or \
+            starts an interactive session.",
+        )
+        .arg(
+            Arg::new("target")
+                .short('t')
+                .long("target")
+                .help("Address of the server [default: port 12321, or 9999 for admin]"),
+        )
+        .arg(
+            Arg::new("protocol")
+                .short('p')
+                .long("protocol")
+                .help("Protocol spoken by the server")
+                .value_parser(["memcache", "memcache-binary", "resp", "ping", "admin"])
+                .default_value("memcache"),
+        )
+        .arg(
+            Arg::new("timeout")
+                .long("timeout")
+                .help("Connect and response timeout in milliseconds")
+                .value_parser(value_parser!(u64))
+                .default_value("1000"),
+        )
+        .arg(
+            Arg::new("file")
+                .short('f')
+                .long("file")
+                .help("Run the commands in the file, one per line"),
+        )
+        .arg(
+            Arg::new("history")
+                .long("history")
+                .help("History file [default: ~/.pelikan_cli_history]"),
+        )
+        .arg(
+            Arg::new("tls")
+                .long("tls")
+                .help("Connect using TLS")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("ca-file")
+                .long("ca-file")
+                .help("Trusted CA certificates used to verify the server"),
+        )
+        .arg(
+            Arg::new("certificate")
+                .long("certificate")
+                .help("Client certificate, for servers which require one"),
+        )
+        .arg(
+            Arg::new("private-key")
+                .long("private-key")
+                .help("Private key for the client certificate"),
+        )
+        .arg(
+            Arg::new("server-name")
+                .long("server-name")
+                .help("Server name to send and verify the certificate against"),
+        )
+        .arg(
+            Arg::new("insecure")
+                .long("insecure")
+                .help("Do not verify the server certificate")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("COMMAND")
+                .help("Command to run, instead of reading commands")
+                .action(ArgAction::Append)
+                .trailing_var_arg(true)
+                .allow_hyphen_values(true),
+        )
+        .get_matches();
+
+    let protocol = match matches.get_one::<String>("protocol").unwrap().as_str() {
+        "memcache-binary" => Protocol::MemcacheBinary,
+        "resp" => Protocol::Resp,
+        "ping" => Protocol::Ping,
+        "admin" => Protocol::Admin,
+        _ => Protocol::Memcache,
+    };
+
+    let target = match matches.get_one::<String>("target") {
+        Some(target) => target.clone(),
+        None if protocol == Protocol::Admin => "127.0.0.1:9999".to_string(),
+        None => "127.0.0.1:12321".to_string(),
+    };
+
+    let connector = match connector(&matches) {
+        Ok(connector) => connector,
+        Err(e) => {
+            eprintln!("failed to initialize tls: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    let mut client = Client {
+        protocol,
+        connector,
+        target,
+        timeout: Duration::from_millis(*matches.get_one::<u64>("timeout").unwrap()),
+        connection: None,
+    };
+
+    // a single command given as arguments
+    if let Some(command) = matches.get_many::<String>("COMMAND") {
+        let args: Vec<Vec<u8>> = command.map(|arg| arg.as_bytes().to_vec()).collect();
+        if let Err(e) = client.run(&args) {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(path) = matches.get_one::<String>("file") {
+        match File::open(path) {
+            Ok(file) => batch(&mut client, BufReader::new(file)),
+            Err(e) => {
+                eprintln!("failed to open {path}: {e}");
+                std::process::exit(1);
+            }
+        }
+    } else if std::io::stdin().is_terminal() {
+        let path = matches
+            .get_one::<String>("history")
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME").map(|home| PathBuf::from(home).join(HISTORY_FILE))
+            });
+        interactive(&mut client, History::open(path));
+    } else {
+        batch(&mut client, std::io::stdin().lock());
+    }
+}
+
+#[cfg(feature = "boringssl")]
+fn connector(matches: &ArgMatches) -> Result<Connector, Error> {
+    use pelikan_net::{ServerVerification, TlsTcpConnector};
+
+    if !matches.get_flag("tls") {
+        return Ok(Connector::from(TcpConnector::new()));
+    }
+
+    let certificate = matches.get_one::<String>("certificate");
+    let private_key = matches.get_one::<String>("private-key");
+    if certificate.is_some() != private_key.is_some() {
+        return Err(Error::other("a client certificate requires a private key"));
+    }
+
+    let mut builder = TlsTcpConnector::builder();
+    if let Some(f) = matches.get_one::<String>("ca-file") {
+        builder = builder.ca_file(f);
+    }
+    if let Some(f) = certificate {
+        builder = builder.certificate_file(f);
+    }
+    if let Some(f) = private_key {
+        builder = builder.private_key_file(f);
+    }
+
+    let verification = if matches.get_flag("insecure") {
+        ServerVerification::None
+    } else if let Some(name) = matches.get_one::<String>("server-name") {
+        builder = builder.server_name(name);
+        ServerVerification::Full
+    } else {
+        ServerVerification::Peer
+    };
+
+    Ok(Connector::from(builder.verification(verification).build()?))
+}
+
+#[cfg(not(feature = "boringssl"))]
+fn connector(matches: &ArgMatches) -> Result<Connector, Error> {
+    if matches.get_flag("tls") {
+        return Err(Error::other("this build does not support tls"));
+    }
+
+    Ok(Connector::from(TcpConnector::new()))
+}
+
+/// Run each line as a command, stopping at the first error.
+fn batch(client: &mut Client, input: impl BufRead) {
+    for line in input.lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("failed to read commands: {e}");
+                std::process::exit(1);
+            }
+        };
+
+        let result = split(&line)
+            .map_err(|e| Error::new(ErrorKind::InvalidInput, e))
+            .and_then(|args| client.run(&args));
+
+        if let Err(e) = result {
+            eprintln!("{line}: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn interactive(client: &mut Client, mut history: History) {
+    let prompt = format!("{} {}> ", client.protocol.name(), client.target);
+    let stdin = std::io::stdin();
+
+    loop {
+        print!("{prompt}");
+        let _ = std::io::stdout().flush();
+
+        let mut line = String::new();
+        match stdin.read_line(&mut line) {
+            Ok(0) => {
+                println!();
+                return;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("failed to read command: {e}");
+                return;
+            }
+        }
+
+        let line = match history.expand(line.trim()) {
+            Ok(line) => line.to_string(),
+            Err(e) => {
+                eprintln!("{e}");
+                continue;
+            }
+        };
+        if line.is_empty() {
+            continue;
+        }
+        history.push(&line);
+
+        match line.as_str() {
+            "quit" | "exit" => return,
+            "help" => {
+                println!("{HELP}");
+                continue;
+            }
+            "history" => {
+                let _ = history.print(&mut std::io::stdout());
+                continue;
+            }
+            _ => {}
+        }
+
+        let result = split(&line)
+            .map_err(|e| Error::new(ErrorKind::InvalidInput, e))
+            .and_then(|args| client.run(&args));
+
+        if let Err(e) = result {
+            eprintln!("(error) {e}");
+        }
+    }
+}
+
+impl Client {
+    /// Send the command to the server and print the response. The connection
+    /// is made when it is first needed, and again after an error.
+    fn run(&mut self, args: &[Vec<u8>]) -> Result<(), Error> {
+        if args.is_empty() {
+            return Ok(());
+        }
+
+        let request = self.protocol.request(args)?;
+        let mut buf = Vec::new();
+        self.protocol.compose(&request, &mut buf)?;
+
+        let result = self.exchange(&request, &buf);
+        if let Err(e) = &result {
+            if e.kind() != ErrorKind::InvalidInput {
+                self.connection = None;
+            }
+        }
+        result
+    }
+
+    fn exchange(&mut self, request: &Request, buf: &[u8]) -> Result<(), Error> {
+        let connection = match self.connection.as_mut() {
+            Some(connection) => connection,
+            None => self.connection.insert(
+                Connection::connect(&self.connector, &self.target, self.timeout).map_err(|e| {
+                    Error::new(
+                        e.kind(),
+                        format!("failed to connect to {}: {e}", self.target),
+                    )
+                })?,
+            ),
+        };
+
+        connection.send(buf)?;
+
+        if request.noreply() {
+            return Ok(());
+        }
+
+        let protocol = self.protocol;
+        let response = connection.receive(true, |buf| protocol.parse_response(request, buf))?;
+        println!("{response}");
+
+        // streamed events are printed until the server closes the connection
+        // or the client is interrupted
+        if request.streams() {
+            loop {
+                let event = connection.receive(false, |buf| protocol.parse_event(request, buf))?;
+                println!("{event}");
+            }
+        }
+
+        Ok(())
+    }
+}
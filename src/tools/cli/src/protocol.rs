@@ -0,0 +1,345 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! Conversion of command arguments into requests for each protocol, and of
+//! responses into text for display. Requests are built by parsing the command
+//! as the server would, and are then composed and parsed with the protocol
+//! crates, so the client accepts the same commands as the servers.
+
+use protocol_admin::{AdminProtocol, AdminRequest};
+use protocol_common::{BufMut, Compose, Parse, ParseOk, Protocol as _};
+use protocol_memcache::{BinaryProtocol, TextProtocol};
+use protocol_ping::PingProtocol;
+use protocol_resp::{Response as Message, ResponseParser};
+use std::io::{Error, ErrorKind};
+
+/// The memcache storage commands, which take a value. For these the value is
+/// given in place of its length, eg: `set key 0 0 value`.
+const STORAGE: &[&[u8]] = &[b"set", b"add", b"replace", b"append", b"prepend", b"cas"];
+
+/// The position of the value for the memcache storage commands.
+const VALUE: usize = 4;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Protocol {
+    Memcache,
+    MemcacheBinary,
+    Resp,
+    Ping,
+    Admin,
+}
+
+pub enum Request {
+    Memcache(protocol_memcache::Request),
+    Resp { message: Message, monitor: bool },
+    Ping(protocol_ping::Request),
+    Admin(AdminRequest),
+}
+
+impl Protocol {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Memcache => "memcache",
+            Self::MemcacheBinary => "memcache-binary",
+            Self::Resp => "resp",
+            Self::Ping => "ping",
+            Self::Admin => "admin",
+        }
+    }
+
+    /// Build the request for the command arguments.
+    pub fn request(&self, args: &[Vec<u8>]) -> Result<Request, Error> {
+        self.parse_request(args).map_err(|e| match e.kind() {
+            // the whole request was given, so it is missing arguments
+            ErrorKind::WouldBlock => Error::new(ErrorKind::InvalidInput, "incomplete request"),
+            _ => Error::new(ErrorKind::InvalidInput, "invalid request"),
+        })
+    }
+
+    fn parse_request(&self, args: &[Vec<u8>]) -> Result<Request, Error> {
+        match self {
+            Self::Memcache | Self::MemcacheBinary => {
+                let request = TextProtocol::new().parse_request(&memcache_line(args))?;
+                Ok(Request::Memcache(request.into_inner()))
+            }
+            Self::Resp => Ok(Request::Resp {
+                message: Message::array(args.iter().map(|a| Message::bulk_string(a)).collect()),
+                monitor: args
+                    .first()
+                    .is_some_and(|a| a.eq_ignore_ascii_case(b"monitor")),
+            }),
+            Self::Ping => {
+                let request = PingProtocol::default().parse_request(&line(args))?;
+                Ok(Request::Ping(request.into_inner()))
+            }
+            Self::Admin => {
+                let request = AdminProtocol::default().parse_request(&line(args))?;
+                Ok(Request::Admin(request.into_inner()))
+            }
+        }
+    }
+
+    pub fn compose(&self, request: &Request, buf: &mut dyn BufMut) -> Result<usize, Error> {
+        match (self, request) {
+            (Self::MemcacheBinary, Request::Memcache(r)) => {
+                BinaryProtocol::default().compose_request(r, buf)
+            }
+            (_, Request::Memcache(r)) => TextProtocol::new().compose_request(r, buf),
+            (_, Request::Resp { message, .. }) => Ok(message.compose(buf)),
+            (_, Request::Ping(r)) => PingProtocol::default().compose_request(r, buf),
+            (_, Request::Admin(r)) => AdminProtocol::default().compose_request(r, buf),
+        }
+    }
+
+    /// Parse the response to the request and format it for display.
+    pub fn parse_response(&self, request: &Request, buf: &[u8]) -> Result<ParseOk<String>, Error> {
+        let mut text = Vec::new();
+
+        let consumed = match (self, request) {
+            (Self::MemcacheBinary, Request::Memcache(r)) => {
+                let response = BinaryProtocol::default().parse_response(r, buf)?;
+                let consumed = response.consumed();
+                TextProtocol::new().compose_response(r, &response.into_inner(), &mut text)?;
+                consumed
+            }
+            (_, Request::Memcache(r)) => {
+                let protocol = TextProtocol::new();
+                let response = protocol.parse_response(r, buf)?;
+                let consumed = response.consumed();
+                protocol.compose_response(r, &response.into_inner(), &mut text)?;
+                consumed
+            }
+            (_, Request::Resp { .. }) => return self.parse_event(request, buf),
+            (_, Request::Ping(r)) => {
+                let protocol = PingProtocol::default();
+                let response = protocol.parse_response(r, buf)?;
+                let consumed = response.consumed();
+                protocol.compose_response(r, &response.into_inner(), &mut text)?;
+                consumed
+            }
+            (_, Request::Admin(r)) => {
+                let protocol = AdminProtocol::default();
+                let response = protocol.parse_response(r, buf)?;
+                let consumed = response.consumed();
+                protocol.compose_response(r, &response.into_inner(), &mut text)?;
+                consumed
+            }
+        };
+
+        Ok(ParseOk::new(display(&text), consumed))
+    }
+
+    /// Parse one of the events which follow the response to a request which
+    /// streams, such as `monitor` or `watch`.
+    pub fn parse_event(&self, request: &Request, buf: &[u8]) -> Result<ParseOk<String>, Error> {
+        match request {
+            Request::Resp { .. } => {
+                let message = ResponseParser::default().parse(buf)?;
+                let consumed = message.consumed();
+                Ok(ParseOk::new(format_resp(&message.into_inner()), consumed))
+            }
+            _ => {
+                let line_end = buf
+                    .windows(2)
+                    .position(|w| w == b"\r\n")
+                    .ok_or_else(|| Error::from(ErrorKind::WouldBlock))?;
+                Ok(ParseOk::new(display(&buf[..line_end]), line_end + 2))
+            }
+        }
+    }
+}
+
+impl Request {
+    /// Returns true if the server does not respond to the request.
+    pub fn noreply(&self) -> bool {
+        match self {
+            Self::Memcache(r) => r.noreply(),
+            Self::Admin(r) => *r == AdminRequest::Quit,
+            _ => false,
+        }
+    }
+
+    /// Returns true if the server streams events after the response.
+    pub fn streams(&self) -> bool {
+        match self {
+            Self::Resp { monitor, .. } => *monitor,
+            Self::Admin(r) => matches!(r, AdminRequest::Watch(_)),
+            _ => false,
+        }
+    }
+}
+
+/// The arguments as a single line for the text protocols.
+fn line(args: &[Vec<u8>]) -> Vec<u8> {
+    let mut line = args.join(&b' ');
+    line.extend_from_slice(b"\r\n");
+    line
+}
+
+/// The arguments as a memcache request. The value of a storage command is
+/// replaced with its length and sent on the following line.
+fn memcache_line(args: &[Vec<u8>]) -> Vec<u8> {
+    let storage = args
+        .first()
+        .is_some_and(|command| STORAGE.iter().any(|s| command.eq_ignore_ascii_case(s)));
+
+    match args.get(VALUE) {
+        Some(value) if storage => {
+            let mut header = args.to_vec();
+            header[VALUE] = value.len().to_string().into_bytes();
+            let mut line = line(&header);
+            line.extend_from_slice(value);
+            line.extend_from_slice(b"\r\n");
+            line
+        }
+        _ => line(args),
+    }
+}
+
+/// Protocol text with the line endings made suitable for a terminal.
+fn display(text: &[u8]) -> String {
+    let text = String::from_utf8_lossy(text).replace("\r\n", "\n");
+    text.trim_end_matches('\n').to_string()
+}
+
+/// Format a RESP message in the style of `redis-cli`.
+fn format_resp(message: &Message) -> String {
+    match message {
+        Message::SimpleString(s) => s.as_ref().to_string(),
+        Message::Error(e) => format!("(error) {}", e.as_ref()),
+        Message::Integer(i) => format!("(integer) {}", i.value()),
+        Message::BulkString(s) => match s.bytes() {
+            Some(bytes) => quote(bytes),
+            None => "(nil)".to_string(),
+        },
+        Message::Array(array) => match array.len() {
+            None => "(nil)".to_string(),
+            Some(0) => "(empty array)".to_string(),
+            Some(len) => {
+                let width = len.to_string().len();
+                let mut lines = Vec::new();
+                for (i, item) in array.into_iter().enumerate() {
+                    let prefix = format!("{:>width$}) ", i + 1);
+                    for (j, line) in format_resp(item).lines().enumerate() {
+                        if j == 0 {
+                            lines.push(format!("{prefix}{line}"));
+                        } else {
+                            lines.push(format!("{:1$}{line}", "", prefix.len()));
+                        }
+                    }
+                }
+                lines.join("\n")
+            }
+        },
+    }
+}
+
+/// Quote a value, escaping anything which is not printable.
+fn quote(bytes: &[u8]) -> String {
+    let mut quoted = String::from("\"");
+    for byte in bytes {
+        match byte {
+            b'\n' => quoted.push_str("\\n"),
+            b'\r' => quoted.push_str("\\r"),
+            b'\t' => quoted.push_str("\\t"),
+            b'"' | b'\\' => {
+                quoted.push('\\');
+                quoted.push(*byte as char);
+            }
+            b' '..=b'~' => quoted.push(*byte as char),
+            _ => quoted.push_str(&format!("\\x{byte:02x}")),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::split;
+
+    fn compose(protocol: Protocol, command: &str) -> Vec<u8> {
+        let request = protocol.request(&split(command).unwrap()).unwrap();
+        let mut buf = Vec::new();
+        protocol.compose(&request, &mut buf).unwrap();
+        buf
+    }
+
+    fn parse(protocol: Protocol, command: &str, response: &[u8]) -> String {
+        let request = protocol.request(&split(command).unwrap()).unwrap();
+        let parsed = protocol.parse_response(&request, response).unwrap();
+        assert_eq!(parsed.consumed(), response.len());
+        parsed.into_inner()
+    }
+
+    #[test]
+    fn memcache() {
+        assert_eq!(
+            compose(Protocol::Memcache, "set key 0 0 \"a value\""),
+            b"set key 0 0 7\r\na value\r\n"
+        );
+        assert_eq!(compose(Protocol::Memcache, "get a b"), b"get a b\r\n");
+        assert!(Protocol::Memcache
+            .request(&split("set key 0").unwrap())
+            .is_err());
+
+        assert_eq!(
+            parse(
+                Protocol::Memcache,
+                "get key",
+                b"VALUE key 0 5\r\nvalue\r\nEND\r\n"
+            ),
+            "VALUE key 0 5\nvalue\nEND"
+        );
+
+        // binary responses are shown as their text equivalents
+        let binary = compose(Protocol::MemcacheBinary, "get key");
+        assert_eq!(binary[0], 0x80);
+    }
+
+    #[test]
+    fn resp() {
+        assert_eq!(
+            compose(Protocol::Resp, "get key"),
+            b"*2\r\n$3\r\nget\r\n$3\r\nkey\r\n"
+        );
+
+        assert_eq!(parse(Protocol::Resp, "get key", b"$-1\r\n"), "(nil)");
+        assert_eq!(parse(Protocol::Resp, "del key", b":1\r\n"), "(integer) 1");
+        assert_eq!(
+            parse(Protocol::Resp, "get key", b"-ERR oops\r\n"),
+            "(error) ERR oops"
+        );
+        assert_eq!(
+            parse(
+                Protocol::Resp,
+                "hgetall key",
+                b"*2\r\n$1\r\na\r\n*2\r\n$2\r\nb\n\r\n:3\r\n"
+            ),
+            "1) \"a\"\n2) 1) \"b\\n\"\n   2) (integer) 3"
+        );
+    }
+
+    #[test]
+    fn admin() {
+        assert_eq!(compose(Protocol::Admin, "stats ttl"), b"stats ttl\r\n");
+        assert!(Protocol::Admin.request(&split("stats x").unwrap()).is_err());
+
+        assert_eq!(
+            parse(Protocol::Admin, "stats", b"STAT a 1\r\nEND\r\n"),
+            "STAT a 1\nEND"
+        );
+        assert!(Protocol::Admin
+            .request(&split("watch").unwrap())
+            .unwrap()
+            .streams());
+    }
+
+    #[test]
+    fn ping() {
+        assert_eq!(compose(Protocol::Ping, "ping"), b"ping\r\n");
+        assert_eq!(parse(Protocol::Ping, "ping", b"PONG\r\n"), "PONG");
+    }
+}
@@ -0,0 +1,255 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! Replays command logs (klog) against a cache server, at the original speed
+//! or scaled, and reports the hit rate and latency. This allows changes to the
+//! eviction policy or the sizing of a cache to be evaluated against production
+//! traffic.
+//!
+//! Requests are spread across the connections by key, so requests for the
+//! same key are replayed in order. Each connection has one request in flight
+//! at a time, so if the target is slower than the original server the replay
+//! falls behind the original timing, which is reported.
+
+use clap::{value_parser, Arg, ArgAction, Command};
+use logger::KlogEntry;
+use request::{Outcome, Protocol, Request};
+use stats::Stats;
+use std::collections::hash_map::DefaultHasher;
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{sync_channel, Receiver};
+use std::time::{Duration, Instant};
+
+mod request;
+mod stats;
+
+/// The number of requests which may be queued for each connection.
+const QUEUE_DEPTH: usize = 1024;
+
+struct Job {
+    request: Request,
+    command: String,
+    original: Option<bool>,
+}
+
+fn main() {
+    let matches = Command::new(env!("CARGO_BIN_NAME"))
+        .version(env!("CARGO_PKG_VERSION"))
+        .long_about(
+            "Replays command logs against a cache server and reports the hit \
+            rate and latency. Logs may be in either the text or the json klog \
+            format. Values are not recorded in the command log, so they are \
+            replaced with filler of the original length.",
+        )
+        .arg(
+            Arg::new("target")
+                .short('t')
+                .long("target")
+                .help("Address of the server to replay against")
+                .default_value("127.0.0.1:12321"),
+        )
+        .arg(
+            Arg::new("protocol")
+                .short('p')
+                .long("protocol")
+                .help("Protocol spoken by the server")
+                .value_parser(["memcache", "resp"])
+                .default_value("memcache"),
+        )
+        .arg(
+            Arg::new("speed")
+                .short('s')
+                .long("speed")
+                .help("Replay speed relative to the original timing, or 0 to replay as fast as possible")
+                .value_parser(value_parser!(f64))
+                .default_value("1"),
+        )
+        .arg(
+            Arg::new("connections")
+                .short('c')
+                .long("connections")
+                .help("Number of connections to the server")
+                .value_parser(value_parser!(usize))
+                .default_value("1"),
+        )
+        .arg(
+            Arg::new("timeout")
+                .long("timeout")
+                .help("Request timeout in milliseconds")
+                .value_parser(value_parser!(u64))
+                .default_value("1000"),
+        )
+        .arg(
+            Arg::new("KLOG")
+                .help("Command log files, replayed in order")
+                .action(ArgAction::Append)
+                .required(true),
+        )
+        .get_matches();
+
+    let protocol = match matches.get_one::<String>("protocol").unwrap().as_str() {
+        "resp" => Protocol::Resp,
+        _ => Protocol::Memcache,
+    };
+    let speed = *matches.get_one::<f64>("speed").unwrap();
+    let connections = (*matches.get_one::<usize>("connections").unwrap()).max(1);
+    let timeout = Duration::from_millis(*matches.get_one::<u64>("timeout").unwrap());
+
+    let target = matches.get_one::<String>("target").unwrap();
+    let target = match target.to_socket_addrs().ok().and_then(|mut a| a.next()) {
+        Some(target) => target,
+        None => {
+            eprintln!("failed to resolve target: {target}");
+            std::process::exit(1);
+        }
+    };
+
+    let mut senders = Vec::with_capacity(connections);
+    let mut workers = Vec::with_capacity(connections);
+    for _ in 0..connections {
+        let (sender, receiver) = sync_channel(QUEUE_DEPTH);
+        senders.push(sender);
+        workers.push(std::thread::spawn(move || {
+            worker(target, timeout, receiver)
+        }));
+    }
+
+    let start = Instant::now();
+    let mut skipped = 0;
+    let mut lag = Duration::ZERO;
+    // the time of the first record and when it was replayed
+    let mut origin: Option<(i64, Instant)> = None;
+
+    for path in matches.get_many::<String>("KLOG").unwrap() {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) => {
+                eprintln!("failed to open {path}: {e}");
+                std::process::exit(1);
+            }
+        };
+
+        for line in BufReader::new(file).split(b'\n') {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    eprintln!("failed to read {path}: {e}");
+                    break;
+                }
+            };
+
+            let record = KlogEntry::parse(&String::from_utf8_lossy(&line));
+            let (record, request) =
+                match record.and_then(|r| Request::new(protocol, &r).map(|request| (r, request))) {
+                    Some(parsed) => parsed,
+                    None => {
+                        skipped += 1;
+                        continue;
+                    }
+                };
+
+            if speed > 0.0 {
+                let (time, instant) = *origin.get_or_insert((record.time(), Instant::now()));
+                let offset = (record.time() - time).max(0) as f64 / 1000.0 / speed;
+                let due = instant + Duration::from_secs_f64(offset);
+                let now = Instant::now();
+                if due > now {
+                    std::thread::sleep(due - now);
+                } else {
+                    lag = lag.max(now - due);
+                }
+            }
+
+            let mut hasher = DefaultHasher::new();
+            record.key().hash(&mut hasher);
+            let connection = (hasher.finish() % connections as u64) as usize;
+
+            let job = Job {
+                request,
+                command: record.command().to_ascii_lowercase(),
+                original: record.hit(),
+            };
+
+            if senders[connection].send(job).is_err() {
+                eprintln!("connection worker exited");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    drop(senders);
+
+    let mut stats = Stats::default();
+    for worker in workers {
+        stats.merge(worker.join().unwrap());
+    }
+
+    let _ = stats.report(&mut std::io::stdout(), start.elapsed(), skipped, lag);
+}
+
+/// Sends each request over a single connection, reconnecting after errors.
+fn worker(target: SocketAddr, timeout: Duration, receiver: Receiver<Job>) -> Stats {
+    let mut stats = Stats::default();
+    let mut stream: Option<TcpStream> = None;
+    let mut buf = Vec::new();
+
+    for job in receiver {
+        if stream.is_none() {
+            stream = connect(target, timeout).ok();
+        }
+
+        let result = match stream.as_mut() {
+            Some(stream) => {
+                buf.clear();
+                job.request.compose(&mut buf);
+
+                let start = Instant::now();
+                exchange(stream, &job.request, &buf).map(|outcome| (outcome, start.elapsed()))
+            }
+            None => Err(ErrorKind::NotConnected.into()),
+        };
+
+        match result {
+            Ok((outcome, latency)) => stats.record(&job.command, latency, outcome, job.original),
+            Err(_) => {
+                stats.record(&job.command, Duration::ZERO, Outcome::Error, job.original);
+                stream = None;
+            }
+        }
+    }
+
+    stats
+}
+
+fn connect(target: SocketAddr, timeout: Duration) -> std::io::Result<TcpStream> {
+    let stream = TcpStream::connect_timeout(&target, timeout)?;
+    stream.set_nodelay(true)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    Ok(stream)
+}
+
+fn exchange(stream: &mut TcpStream, request: &Request, buf: &[u8]) -> std::io::Result<Outcome> {
+    stream.write_all(buf)?;
+
+    let mut response = Vec::new();
+    let mut chunk = [0; 16384];
+
+    loop {
+        let len = stream.read(&mut chunk)?;
+        if len == 0 {
+            return Err(ErrorKind::UnexpectedEof.into());
+        }
+        response.extend_from_slice(&chunk[..len]);
+
+        match request.parse_response(&response) {
+            Ok(outcome) => return Ok(outcome.into_inner()),
+            Err(e) if e.kind() == ErrorKind::WouldBlock => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
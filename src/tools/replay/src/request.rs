@@ -0,0 +1,337 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! Reconstruction of requests from the command log. The command log records
+//! the length of each value rather than the value itself, so values are
+//! replaced with filler of the same length.
+
+use logger::KlogEntry;
+use protocol_common::{BufMut, Compose, Parse, ParseOk, Protocol as _};
+use protocol_memcache::{TextProtocol, TimeType, Ttl};
+use protocol_resp::{Response as Message, ResponseParser};
+use std::io::Error;
+
+/// The byte used to fill in values.
+const FILL: u8 = b'x';
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Protocol {
+    Memcache,
+    Resp,
+}
+
+/// The result of a replayed request.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Hit,
+    Miss,
+    Ok,
+    Error,
+}
+
+pub enum Request {
+    Memcache(protocol_memcache::Request),
+    Resp { command: String, message: Message },
+}
+
+impl Request {
+    /// Reconstruct the request for a command log record. Returns `None` if
+    /// the command cannot be replayed with the protocol.
+    pub fn new(protocol: Protocol, record: &KlogEntry) -> Option<Self> {
+        match protocol {
+            Protocol::Memcache => memcache(record).map(Self::Memcache),
+            Protocol::Resp => resp(record).map(|args| Self::Resp {
+                command: record.command().to_ascii_lowercase(),
+                message: Message::array(args.iter().map(|a| Message::bulk_string(a)).collect()),
+            }),
+        }
+    }
+
+    pub fn compose(&self, buf: &mut dyn BufMut) {
+        match self {
+            Self::Memcache(request) => {
+                let _ = TextProtocol::new().compose_request(request, buf);
+            }
+            Self::Resp { message, .. } => {
+                message.compose(buf);
+            }
+        }
+    }
+
+    /// Parse the response to the request. Returns an error with the kind
+    /// `WouldBlock` if the response is incomplete.
+    pub fn parse_response(&self, buf: &[u8]) -> Result<ParseOk<Outcome>, Error> {
+        match self {
+            Self::Memcache(request) => {
+                let response = TextProtocol::new().parse_response(request, buf)?;
+                let consumed = response.consumed();
+                Ok(ParseOk::new(
+                    memcache_outcome(&response.into_inner()),
+                    consumed,
+                ))
+            }
+            Self::Resp { command, .. } => {
+                let response = ResponseParser::default().parse(buf)?;
+                let consumed = response.consumed();
+                Ok(ParseOk::new(
+                    resp_outcome(command, &response.into_inner()),
+                    consumed,
+                ))
+            }
+        }
+    }
+}
+
+fn value(len: &str) -> Option<Box<[u8]>> {
+    let len: usize = len.parse().ok()?;
+    Some(vec![FILL; len].into_boxed_slice())
+}
+
+fn memcache(record: &KlogEntry) -> Option<protocol_memcache::Request> {
+    use protocol_memcache::Request;
+
+    let args = record.args();
+    let key: Box<[u8]> = args.first()?.as_bytes().into();
+
+    // storage commands are logged as: key flags ttl length [cas]
+    let storage = || -> Option<(u32, Ttl, Box<[u8]>)> {
+        let flags = args.get(1)?.parse().ok()?;
+        let ttl = Ttl::new(args.get(2)?.parse().ok()?, TimeType::Delta);
+        Some((flags, ttl, value(args.get(3)?)?))
+    };
+
+    let request = match record.command() {
+        "get" => Request::get(vec![key].into_boxed_slice()),
+        "gets" => Request::gets(vec![key].into_boxed_slice()),
+        "delete" => Request::delete(key, false),
+        "incr" => Request::incr(key, 1, false),
+        "decr" => Request::decr(key, 1, false),
+        "set" => {
+            let (flags, ttl, value) = storage()?;
+            Request::set(key, value, flags, ttl, false)
+        }
+        "add" => {
+            let (flags, ttl, value) = storage()?;
+            Request::add(key, value, flags, ttl, false)
+        }
+        "replace" => {
+            let (flags, ttl, value) = storage()?;
+            Request::replace(key, value, flags, ttl, false)
+        }
+        "append" => {
+            let (flags, ttl, value) = storage()?;
+            Request::append(key, value, flags, ttl, false)
+        }
+        "prepend" => {
+            let (flags, ttl, value) = storage()?;
+            Request::prepend(key, value, flags, ttl, false)
+        }
+        "cas" => {
+            let (flags, ttl, value) = storage()?;
+            let cas = args.get(4)?.parse().ok()?;
+            Request::cas(key, value, flags, ttl, cas, false)
+        }
+        _ => return None,
+    };
+
+    Some(request)
+}
+
+// Most RESP commands are logged with their own arguments, so the request is
+// rebuilt from those. The exceptions are the commands which carry values,
+// which are logged with the value length, or with the number of elements.
+fn resp(record: &KlogEntry) -> Option<Vec<Box<[u8]>>> {
+    let command = record.command().to_ascii_lowercase();
+    let args: Vec<Box<[u8]>> = record
+        .args()
+        .iter()
+        .map(|arg| arg.as_bytes().into())
+        .collect();
+    let arg = |i: usize| record.args().get(i).map(|a| a.as_str());
+
+    let mut request: Vec<Box<[u8]>> = vec![command.as_bytes().into()];
+
+    match command.as_str() {
+        // key flags ttl length
+        "set" => {
+            request.push(args.first()?.clone());
+            request.push(value(arg(3)?)?);
+            if arg(2)?.parse::<i64>().ok()? > 0 {
+                request.push(b"ex"[..].into());
+                request.push(args[2].clone());
+            }
+        }
+        // key length, each key of an `mset` is logged separately
+        "mset" | "setnx" | "append" => {
+            request.push(args.first()?.clone());
+            request.push(value(arg(1)?)?);
+        }
+        // key offset length
+        "setrange" => {
+            request.extend_from_slice(args.get(0..2)?);
+            request.push(value(arg(2)?)?);
+        }
+        // key field [ttl] length, each field is logged separately
+        "hset" | "badd" => {
+            request.extend_from_slice(args.get(0..2)?);
+            request.push(value(arg(args.len().checked_sub(1)?)?)?);
+        }
+        // key count
+        "lpush" | "rpush" => {
+            request.push(args.first()?.clone());
+            let count: usize = arg(1)?.parse().ok()?;
+            request.extend((0..count).map(|i| format!("{i}").into_bytes().into()));
+        }
+        // key count
+        "zadd" => {
+            request.push(args.first()?.clone());
+            let count: usize = arg(1)?.parse().ok()?;
+            for i in 0..count {
+                request.push(b"0"[..].into());
+                request.push(format!("{i}").into_bytes().into());
+            }
+        }
+        // cursor pattern count
+        "scan" => {
+            request.push(args.first()?.clone());
+            request.push(b"match"[..].into());
+            request.push(args.get(1)?.clone());
+            request.push(b"count"[..].into());
+            request.push(args.get(2)?.clone());
+        }
+        // key min max, where infinite scores were logged as `inf`
+        "zcount" => {
+            request.push(args.first()?.clone());
+            for score in [arg(1)?, arg(2)?] {
+                let score = match score {
+                    "inf" => "+inf",
+                    score => score,
+                };
+                request.push(score.as_bytes().into());
+            }
+        }
+        _ => request.extend(args),
+    }
+
+    Some(request)
+}
+
+fn memcache_outcome(response: &protocol_memcache::Response) -> Outcome {
+    use protocol_memcache::Response;
+
+    match response {
+        Response::Values(values) => {
+            if values.values().iter().any(|v| v.len().is_some()) {
+                Outcome::Hit
+            } else {
+                Outcome::Miss
+            }
+        }
+        Response::Error(_)
+        | Response::ClientError(_)
+        | Response::ServerError(_)
+        | Response::Hangup => Outcome::Error,
+        _ => Outcome::Ok,
+    }
+}
+
+// mirrors how the servers choose the klog response code for a read
+fn resp_outcome(command: &str, response: &Message) -> Outcome {
+    match response {
+        Message::Error(_) => Outcome::Error,
+        _ if *response == Message::null() => Outcome::Miss,
+        Message::Array(a) if a.len().unwrap_or(0) == 0 => Outcome::Miss,
+        Message::Integer(ttl) if command == "ttl" || command == "pttl" => {
+            if ttl.value() == -2 {
+                Outcome::Miss
+            } else {
+                Outcome::Hit
+            }
+        }
+        Message::Integer(_) if command == "zrank" || command == "zrevrank" => Outcome::Hit,
+        Message::Integer(count) if count.value() <= 0 => Outcome::Miss,
+        Message::SimpleString(s) if s.as_ref() == "none" => Outcome::Miss,
+        _ => Outcome::Hit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compose(protocol: Protocol, line: &str) -> Option<Vec<u8>> {
+        let record = KlogEntry::parse(line).expect("failed to parse");
+        let request = Request::new(protocol, &record)?;
+        let mut buf = Vec::new();
+        request.compose(&mut buf);
+        Some(buf)
+    }
+
+    #[test]
+    fn memcache() {
+        let time = "2024-01-01T00:00:00.000+00:00";
+
+        assert_eq!(
+            compose(Protocol::Memcache, &format!("{time} \"get key\" 4 5")).unwrap(),
+            b"get key\r\n"
+        );
+        assert_eq!(
+            compose(
+                Protocol::Memcache,
+                &format!("{time} \"set key 1 60 3\" 5 8")
+            )
+            .unwrap(),
+            b"set key 1 60 3\r\nxxx\r\n"
+        );
+        assert!(compose(
+            Protocol::Memcache,
+            &format!("{time} \"hget key field\" 4 5")
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn resp() {
+        let time = "2024-01-01T00:00:00.000+00:00";
+
+        assert_eq!(
+            compose(Protocol::Resp, &format!("{time} \"set key 0 60 3\" 5 2")).unwrap(),
+            b"*5\r\n$3\r\nset\r\n$3\r\nkey\r\n$3\r\nxxx\r\n$2\r\nex\r\n$2\r\n60\r\n"
+        );
+        assert_eq!(
+            compose(Protocol::Resp, &format!("{time} \"hget key field\" 4 5")).unwrap(),
+            b"*3\r\n$4\r\nhget\r\n$3\r\nkey\r\n$5\r\nfield\r\n"
+        );
+        assert_eq!(
+            compose(
+                Protocol::Resp,
+                &format!("{time} \"hset key field 0 2\" 5 0")
+            )
+            .unwrap(),
+            b"*4\r\n$4\r\nhset\r\n$3\r\nkey\r\n$5\r\nfield\r\n$2\r\nxx\r\n"
+        );
+        assert_eq!(
+            compose(Protocol::Resp, &format!("{time} \"rpush key 2\" 5 0")).unwrap(),
+            b"*4\r\n$5\r\nrpush\r\n$3\r\nkey\r\n$1\r\n0\r\n$1\r\n1\r\n"
+        );
+    }
+
+    #[test]
+    fn outcome() {
+        let parse = |command: &str, response: &[u8]| {
+            let request = Request::Resp {
+                command: command.to_string(),
+                message: Message::null(),
+            };
+            request.parse_response(response).unwrap().into_inner()
+        };
+
+        assert_eq!(parse("get", b"$1\r\na\r\n"), Outcome::Hit);
+        assert_eq!(parse("get", b"$-1\r\n"), Outcome::Miss);
+        assert_eq!(parse("hlen", b":0\r\n"), Outcome::Miss);
+        assert_eq!(parse("ttl", b":-1\r\n"), Outcome::Hit);
+        assert_eq!(parse("zrank", b":0\r\n"), Outcome::Hit);
+        assert_eq!(parse("get", b"-ERR\r\n"), Outcome::Error);
+    }
+}
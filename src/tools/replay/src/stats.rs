@@ -0,0 +1,212 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! Accounting for the replayed requests and the final report.
+
+use crate::request::Outcome;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::time::Duration;
+
+/// The latency percentiles which are reported.
+const PERCENTILES: &[(&str, f64)] = &[
+    ("p50", 50.0),
+    ("p90", 90.0),
+    ("p99", 99.0),
+    ("p999", 99.9),
+    ("max", 100.0),
+];
+
+#[derive(Default)]
+struct CommandStats {
+    // in nanoseconds
+    latencies: Vec<u64>,
+    errors: u64,
+    hits: u64,
+    misses: u64,
+    original_hits: u64,
+    original_misses: u64,
+}
+
+impl CommandStats {
+    fn merge(&mut self, other: CommandStats) {
+        self.latencies.extend(other.latencies);
+        self.errors += other.errors;
+        self.hits += other.hits;
+        self.misses += other.misses;
+        self.original_hits += other.original_hits;
+        self.original_misses += other.original_misses;
+    }
+}
+
+#[derive(Default)]
+pub struct Stats {
+    commands: BTreeMap<String, CommandStats>,
+}
+
+impl Stats {
+    /// Record a replayed request. The `original` is whether the request was a
+    /// hit when it was logged, or `None` if it did not read a value.
+    pub fn record(
+        &mut self,
+        command: &str,
+        latency: Duration,
+        outcome: Outcome,
+        original: Option<bool>,
+    ) {
+        let stats = match self.commands.get_mut(command) {
+            Some(stats) => stats,
+            None => self.commands.entry(command.to_string()).or_default(),
+        };
+
+        if outcome == Outcome::Error {
+            stats.errors += 1;
+            return;
+        }
+
+        stats.latencies.push(latency.as_nanos() as u64);
+
+        if let Some(original) = original {
+            if original {
+                stats.original_hits += 1;
+            } else {
+                stats.original_misses += 1;
+            }
+
+            match outcome {
+                Outcome::Hit => stats.hits += 1,
+                Outcome::Miss => stats.misses += 1,
+                _ => {}
+            }
+        }
+    }
+
+    pub fn merge(&mut self, other: Stats) {
+        for (command, stats) in other.commands {
+            self.commands.entry(command).or_default().merge(stats);
+        }
+    }
+
+    /// Write a summary of the replay, followed by a breakdown by command.
+    pub fn report(
+        &mut self,
+        w: &mut dyn Write,
+        elapsed: Duration,
+        skipped: u64,
+        lag: Duration,
+    ) -> std::io::Result<()> {
+        let mut total = CommandStats::default();
+        for stats in self.commands.values_mut() {
+            stats.latencies.sort_unstable();
+            total.latencies.extend_from_slice(&stats.latencies);
+            total.errors += stats.errors;
+            total.hits += stats.hits;
+            total.misses += stats.misses;
+            total.original_hits += stats.original_hits;
+            total.original_misses += stats.original_misses;
+        }
+        total.latencies.sort_unstable();
+
+        let requests = total.latencies.len() as u64 + total.errors;
+        writeln!(
+            w,
+            "replayed {requests} requests in {:.1}s ({:.1} rps), {skipped} log lines skipped",
+            elapsed.as_secs_f64(),
+            requests as f64 / elapsed.as_secs_f64().max(f64::EPSILON),
+        )?;
+        writeln!(
+            w,
+            "fell behind the original timing by up to {:.1}ms",
+            lag.as_secs_f64() * 1000.0
+        )?;
+        writeln!(w, "errors: {}", total.errors)?;
+        writeln!(
+            w,
+            "hit rate: {} (originally {})",
+            ratio(total.hits, total.misses),
+            ratio(total.original_hits, total.original_misses)
+        )?;
+
+        write!(w, "latency (us):")?;
+        for (label, percentile) in PERCENTILES {
+            write!(w, " {label}: {}", micros(&total.latencies, *percentile))?;
+        }
+        writeln!(w)?;
+
+        writeln!(w)?;
+        writeln!(
+            w,
+            "{:<15} {:>10} {:>8} {:>10} {:>10} {:>10} {:>10}",
+            "COMMAND", "REQUESTS", "ERRORS", "HIT RATE", "ORIGINAL", "P50 (us)", "P99 (us)"
+        )?;
+        for (command, stats) in &self.commands {
+            writeln!(
+                w,
+                "{:<15} {:>10} {:>8} {:>10} {:>10} {:>10} {:>10}",
+                command,
+                stats.latencies.len() as u64 + stats.errors,
+                stats.errors,
+                ratio(stats.hits, stats.misses),
+                ratio(stats.original_hits, stats.original_misses),
+                micros(&stats.latencies, 50.0),
+                micros(&stats.latencies, 99.0),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn ratio(hits: u64, misses: u64) -> String {
+    if hits + misses == 0 {
+        return "-".to_string();
+    }
+    format!("{:.2}%", 100.0 * hits as f64 / (hits + misses) as f64)
+}
+
+// the latencies must be sorted
+fn micros(latencies: &[u64], percentile: f64) -> String {
+    if latencies.is_empty() {
+        return "-".to_string();
+    }
+    let index = ((percentile / 100.0) * latencies.len() as f64).ceil() as usize;
+    let latency = latencies[index.saturating_sub(1).min(latencies.len() - 1)];
+    format!("{:.1}", latency as f64 / 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report() {
+        let mut stats = Stats::default();
+        for i in 1..=100 {
+            let outcome = if i % 4 == 0 {
+                Outcome::Miss
+            } else {
+                Outcome::Hit
+            };
+            stats.record("get", Duration::from_micros(i), outcome, Some(true));
+        }
+        stats.record("set", Duration::from_micros(5), Outcome::Ok, None);
+        stats.record("set", Duration::from_micros(5), Outcome::Error, None);
+
+        let mut other = Stats::default();
+        other.record("get", Duration::from_micros(1), Outcome::Miss, Some(false));
+        stats.merge(other);
+
+        let mut output = Vec::new();
+        stats
+            .report(&mut output, Duration::from_secs(1), 3, Duration::ZERO)
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.starts_with("replayed 103 requests in 1.0s (103.0 rps), 3 log lines"));
+        assert!(output.contains("errors: 1\n"));
+        assert!(output.contains("hit rate: 74.26% (originally 99.01%)"));
+        assert!(output.contains("p50: 49.0"));
+        assert!(output.contains("max: 100.0"));
+    }
+}
@@ -0,0 +1,315 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! Simulates the hit rate of segcache for a trace, across heap sizes and
+//! eviction policies, without running a server. This allows the sizing of a
+//! cache and its eviction policy to be chosen from production traffic before
+//! changing the configuration.
+//!
+//! Every configuration is built in the same process and the trace is applied
+//! to each of them in lockstep. Time follows the timestamps of the trace using
+//! the segcache virtual clock, so items expire as they would have originally,
+//! however fast the trace is replayed.
+
+use clap::{Arg, ArgAction, Command};
+use segcache::Policy;
+use simulation::Simulation;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::time::Duration;
+use trace::{Event, Format};
+
+mod simulation;
+mod trace;
+
+// merge, sieve and s3fifo eviction parameters, which match the server defaults
+const MERGE_MAX: usize = 8;
+const MERGE_TARGET: usize = 4;
+const COMPACT_TARGET: usize = 2;
+
+const EVICTION: &[&str] = &[
+    "none",
+    "random",
+    "randomfifo",
+    "fifo",
+    "cte",
+    "util",
+    "merge",
+    "sieve",
+    "s3fifo",
+];
+
+fn main() {
+    let matches = Command::new(env!("CARGO_BIN_NAME"))
+        .version(env!("CARGO_PKG_VERSION"))
+        .long_about(
+            "Simulates the segcache hit rate for a trace and reports the miss \
+            ratio for each combination of heap size and eviction policy. \
+            Traces may be command logs in either klog format, or csv in the \
+            format: timestamp,key,key size,value size,client id,operation,ttl. \
+            Each configuration allocates its full heap, so the total memory \
+            used is the sum of the heap sizes for every eviction policy.",
+        )
+        .arg(
+            Arg::new("format")
+                .short('f')
+                .long("format")
+                .help("Format of the trace")
+                .value_parser(["klog", "csv"])
+                .default_value("klog"),
+        )
+        .arg(
+            Arg::new("heap-size")
+                .short('m')
+                .long("heap-size")
+                .help("Comma separated heap sizes to simulate, eg: 64M,128M,1G")
+                .value_delimiter(',')
+                .value_parser(parse_size)
+                .default_value("64M"),
+        )
+        .arg(
+            Arg::new("segment-size")
+                .long("segment-size")
+                .help("Segment size")
+                .value_parser(parse_size)
+                .default_value("1M"),
+        )
+        .arg(
+            Arg::new("eviction")
+                .short('e')
+                .long("eviction")
+                .help("Comma separated eviction policies to simulate")
+                .value_delimiter(',')
+                .value_parser(EVICTION.to_vec())
+                .default_value("merge"),
+        )
+        .arg(
+            Arg::new("csv")
+                .long("csv")
+                .help("Write the results as csv")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("TRACE")
+                .help("Trace files, applied in order")
+                .action(ArgAction::Append)
+                .required(true),
+        )
+        .get_matches();
+
+    let format = match matches.get_one::<String>("format").unwrap().as_str() {
+        "csv" => Format::Csv,
+        _ => Format::Klog,
+    };
+
+    let segment_size = *matches.get_one::<usize>("segment-size").unwrap();
+    let segment_size = match i32::try_from(segment_size) {
+        Ok(size) => size,
+        Err(_) => {
+            eprintln!("segment size is too large: {segment_size}");
+            std::process::exit(1);
+        }
+    };
+
+    let mut heap_sizes: Vec<usize> = matches
+        .get_many::<usize>("heap-size")
+        .unwrap()
+        .copied()
+        .collect();
+    heap_sizes.sort_unstable();
+    heap_sizes.dedup();
+
+    let mut simulations = Vec::new();
+    for eviction in matches.get_many::<String>("eviction").unwrap() {
+        for heap_size in &heap_sizes {
+            match Simulation::new(eviction, policy(eviction), *heap_size, segment_size) {
+                Ok(simulation) => simulations.push(simulation),
+                Err(e) => {
+                    eprintln!("failed to create cache: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+
+    let mut events = 0;
+    let mut skipped = 0;
+    // the time of the first event and the seconds simulated since then
+    let mut start = None;
+    let mut elapsed = 0;
+
+    for path in matches.get_many::<String>("TRACE").unwrap() {
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(e) => {
+                eprintln!("failed to open {path}: {e}");
+                std::process::exit(1);
+            }
+        };
+
+        for line in BufReader::new(file).split(b'\n') {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    eprintln!("failed to read {path}: {e}");
+                    break;
+                }
+            };
+
+            let event = match Event::parse(format, &String::from_utf8_lossy(&line)) {
+                Some(event) => event,
+                None => {
+                    skipped += 1;
+                    continue;
+                }
+            };
+
+            let start = *start.get_or_insert(event.time);
+            let seconds = ((event.time - start).max(0) / 1000) as u64;
+            if seconds > elapsed {
+                elapsed = seconds;
+                segcache::advance_clock(Duration::from_secs(elapsed));
+                for simulation in simulations.iter_mut() {
+                    simulation.expire();
+                }
+            }
+
+            for simulation in simulations.iter_mut() {
+                simulation.apply(&event);
+            }
+            events += 1;
+        }
+    }
+
+    let mut stdout = std::io::stdout();
+    let result = if matches.get_flag("csv") {
+        write_csv(&mut stdout, &simulations)
+    } else {
+        write_table(&mut stdout, &simulations, events, skipped, elapsed)
+    };
+    if let Err(e) = result {
+        eprintln!("failed to write results: {e}");
+        std::process::exit(1);
+    }
+}
+
+fn policy(eviction: &str) -> Policy {
+    match eviction {
+        "none" => Policy::None,
+        "random" => Policy::Random,
+        "randomfifo" => Policy::RandomFifo,
+        "fifo" => Policy::Fifo,
+        "cte" => Policy::Cte,
+        "util" => Policy::Util,
+        "sieve" => Policy::Sieve { max: MERGE_MAX },
+        "s3fifo" => Policy::S3Fifo { max: MERGE_MAX },
+        _ => Policy::Merge {
+            max: MERGE_MAX,
+            merge: MERGE_TARGET,
+            compact: COMPACT_TARGET,
+        },
+    }
+}
+
+/// Parse a size in bytes with an optional binary suffix, eg: `64M`.
+fn parse_size(size: &str) -> Result<usize, String> {
+    let size = size.trim();
+    let (number, shift) = match size.as_bytes().last().map(|s| s.to_ascii_uppercase()) {
+        Some(b'K') => (&size[..size.len() - 1], 10),
+        Some(b'M') => (&size[..size.len() - 1], 20),
+        Some(b'G') => (&size[..size.len() - 1], 30),
+        _ => (size, 0),
+    };
+
+    number
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_mul(1 << shift))
+        .filter(|n| *n > 0)
+        .ok_or_else(|| format!("invalid size: {size}"))
+}
+
+fn format_size(bytes: usize) -> String {
+    for (suffix, shift) in [("G", 30), ("M", 20), ("K", 10)] {
+        if bytes >= 1 << shift && bytes.is_multiple_of(1 << shift) {
+            return format!("{}{suffix}", bytes >> shift);
+        }
+    }
+    format!("{bytes}")
+}
+
+fn miss_ratio(simulation: &Simulation) -> String {
+    simulation
+        .miss_ratio()
+        .map(|ratio| format!("{ratio:.4}"))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn write_table(
+    w: &mut dyn Write,
+    simulations: &[Simulation],
+    events: u64,
+    skipped: u64,
+    elapsed: u64,
+) -> std::io::Result<()> {
+    writeln!(
+        w,
+        "simulated {events} requests over {elapsed}s of trace, {skipped} lines skipped"
+    )?;
+    writeln!(w)?;
+    writeln!(
+        w,
+        "{:<12} {:>10} {:>12} {:>12} {:>10} {:>10}",
+        "EVICTION", "HEAP SIZE", "GETS", "HITS", "MISS RATIO", "FAILED"
+    )?;
+    for simulation in simulations {
+        writeln!(
+            w,
+            "{:<12} {:>10} {:>12} {:>12} {:>10} {:>10}",
+            simulation.eviction(),
+            format_size(simulation.heap_size()),
+            simulation.gets(),
+            simulation.hits(),
+            miss_ratio(simulation),
+            simulation.failed(),
+        )?;
+    }
+    Ok(())
+}
+
+fn write_csv(w: &mut dyn Write, simulations: &[Simulation]) -> std::io::Result<()> {
+    writeln!(w, "eviction,heap_size,gets,hits,miss_ratio,failed")?;
+    for simulation in simulations {
+        writeln!(
+            w,
+            "{},{},{},{},{},{}",
+            simulation.eviction(),
+            simulation.heap_size(),
+            simulation.gets(),
+            simulation.hits(),
+            miss_ratio(simulation),
+            simulation.failed(),
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size() {
+        assert_eq!(parse_size("4096"), Ok(4096));
+        assert_eq!(parse_size("64k"), Ok(64 << 10));
+        assert_eq!(parse_size("64M"), Ok(64 << 20));
+        assert_eq!(parse_size("2G"), Ok(2 << 30));
+        assert!(parse_size("0").is_err());
+        assert!(parse_size("M").is_err());
+
+        assert_eq!(format_size(64 << 20), "64M");
+        assert_eq!(format_size(1536 << 10), "1536K");
+        assert_eq!(format_size(1000), "1000");
+    }
+}
@@ -0,0 +1,179 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! A single cache configuration which the trace is replayed through.
+
+use crate::trace::{Condition, Event, Operation};
+use segcache::{Policy, Segcache};
+use std::time::Duration;
+
+/// The sizing of the hashtable. It starts small and grows with the number of
+/// items so that the hashtable does not limit the hit rate.
+const HASH_POWER: u8 = 16;
+const HASH_POWER_MAX: u8 = 26;
+
+pub struct Simulation {
+    eviction: String,
+    heap_size: usize,
+    cache: Segcache,
+    // values are filled in from this, as traces only record their length
+    filler: Vec<u8>,
+    gets: u64,
+    hits: u64,
+    // writes which could not be stored, eg: the item was too large
+    failed: u64,
+}
+
+impl Simulation {
+    pub fn new(
+        eviction: &str,
+        policy: Policy,
+        heap_size: usize,
+        segment_size: i32,
+    ) -> Result<Self, std::io::Error> {
+        let cache = Segcache::builder()
+            .hash_power(HASH_POWER)
+            .hash_power_max(HASH_POWER_MAX)
+            .heap_size(heap_size)
+            .segment_size(segment_size)
+            .eviction(policy)
+            .build()?;
+
+        Ok(Self {
+            eviction: eviction.to_string(),
+            heap_size,
+            cache,
+            filler: Vec::new(),
+            gets: 0,
+            hits: 0,
+            failed: 0,
+        })
+    }
+
+    pub fn eviction(&self) -> &str {
+        &self.eviction
+    }
+
+    pub fn heap_size(&self) -> usize {
+        self.heap_size
+    }
+
+    pub fn gets(&self) -> u64 {
+        self.gets
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    pub fn failed(&self) -> u64 {
+        self.failed
+    }
+
+    /// The fraction of gets which missed, or `None` if there were no gets.
+    pub fn miss_ratio(&self) -> Option<f64> {
+        if self.gets == 0 {
+            None
+        } else {
+            Some((self.gets - self.hits) as f64 / self.gets as f64)
+        }
+    }
+
+    /// Expire items according to the virtual clock.
+    pub fn expire(&mut self) {
+        self.cache.expire();
+    }
+
+    /// Apply the event to the cache.
+    pub fn apply(&mut self, event: &Event) {
+        let key = &event.key;
+
+        match event.operation {
+            Operation::Get => {
+                self.gets += 1;
+                if self.cache.get(key).is_some() {
+                    self.hits += 1;
+                }
+            }
+            Operation::Set {
+                len,
+                ttl,
+                condition,
+            } => {
+                let store = match condition {
+                    Condition::Always => true,
+                    Condition::Absent => self.cache.get_no_freq_incr(key).is_none(),
+                    Condition::Present => self.cache.get_no_freq_incr(key).is_some(),
+                };
+                if store {
+                    self.insert(key, len, Duration::from_secs(ttl));
+                }
+            }
+            Operation::Append { len } => {
+                let current = match self.cache.get_no_freq_incr(key) {
+                    Some(item) => item.value().len(),
+                    None => return,
+                };
+                let ttl = self.cache.ttl(key).ok().flatten().unwrap_or_default();
+                self.insert(key, current + len, ttl);
+            }
+            Operation::Delete => {
+                self.cache.delete(key);
+            }
+        }
+    }
+
+    fn insert(&mut self, key: &[u8], len: usize, ttl: Duration) {
+        if self.filler.len() < len {
+            self.filler.resize(len, 0);
+        }
+        if self
+            .cache
+            .insert(key, &self.filler[..len], None, ttl)
+            .is_err()
+        {
+            self.failed += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MB: usize = 1024 * 1024;
+
+    fn event(key: &[u8], operation: Operation) -> Event {
+        Event {
+            time: 0,
+            key: key.to_vec(),
+            operation,
+        }
+    }
+
+    #[test]
+    fn apply() {
+        let mut simulation = Simulation::new("fifo", Policy::Fifo, 4 * MB, MB as i32).unwrap();
+
+        let set = |len, condition| Operation::Set {
+            len,
+            ttl: 0,
+            condition,
+        };
+
+        simulation.apply(&event(b"a", Operation::Get));
+        simulation.apply(&event(b"a", set(10, Condition::Present)));
+        simulation.apply(&event(b"a", Operation::Get));
+        simulation.apply(&event(b"a", set(10, Condition::Absent)));
+        simulation.apply(&event(b"a", Operation::Append { len: 5 }));
+        simulation.apply(&event(b"a", Operation::Get));
+        simulation.apply(&event(b"a", Operation::Delete));
+        simulation.apply(&event(b"a", Operation::Get));
+
+        assert_eq!(simulation.gets(), 4);
+        assert_eq!(simulation.hits(), 1);
+        assert_eq!(simulation.miss_ratio(), Some(0.75));
+        assert_eq!(simulation.failed(), 0);
+    }
+}
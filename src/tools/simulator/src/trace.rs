@@ -0,0 +1,223 @@
+// Copyright 2026 Pelikan Foundation LLC.
+// Licensed under the Apache License, Version 2.0
+// http://www.apache.org/licenses/LICENSE-2.0
+
+//! Parsing of trace lines into the operations which are simulated. Traces may
+//! be command logs in either the text or the json klog format, or csv in the
+//! format of the Twitter cache traces:
+//! `timestamp,key,key size,value size,client id,operation,ttl`
+
+use logger::KlogEntry;
+
+/// The byte used to pad anonymized keys in csv traces to their original size.
+const KEY_PAD: u8 = b'_';
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    Klog,
+    Csv,
+}
+
+/// When a write is applied, depending on whether the key is already present.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Condition {
+    Always,
+    Absent,
+    Present,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Operation {
+    Get,
+    /// Store a value of `len` bytes which expires after `ttl` seconds, or
+    /// never if the ttl is zero.
+    Set {
+        len: usize,
+        ttl: u64,
+        condition: Condition,
+    },
+    /// Grow an existing value by `len` bytes.
+    Append {
+        len: usize,
+    },
+    Delete,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Event {
+    /// Milliseconds since the UNIX epoch for command logs, or since the start
+    /// of the trace for csv traces.
+    pub time: i64,
+    pub key: Vec<u8>,
+    pub operation: Operation,
+}
+
+impl Event {
+    /// Parse a line of a trace. Returns `None` if the line could not be parsed
+    /// or is for a command which does not read or write a value.
+    pub fn parse(format: Format, line: &str) -> Option<Self> {
+        match format {
+            Format::Klog => parse_klog(line.trim_end()),
+            Format::Csv => parse_csv(line.trim_end()),
+        }
+    }
+}
+
+fn parse_klog(line: &str) -> Option<Event> {
+    let entry = KlogEntry::parse(line)?;
+    let args = entry.args();
+
+    let arg = |i: usize| -> Option<u64> { args.get(i)?.parse().ok() };
+
+    let operation = match entry.command().to_ascii_lowercase().as_str() {
+        "get" | "gets" => Operation::Get,
+        "delete" | "del" => Operation::Delete,
+        // memcache storage commands and the RESP `set` are logged as:
+        // key flags ttl length
+        command @ ("set" | "add" | "replace" | "cas") => Operation::Set {
+            len: arg(3)? as usize,
+            ttl: arg(2)?,
+            condition: match command {
+                "add" => Condition::Absent,
+                "replace" | "cas" => Condition::Present,
+                _ => Condition::Always,
+            },
+        },
+        // key length
+        command @ ("setnx" | "mset") => Operation::Set {
+            len: arg(1)? as usize,
+            ttl: 0,
+            condition: if command == "setnx" {
+                Condition::Absent
+            } else {
+                Condition::Always
+            },
+        },
+        // memcache: key flags ttl length, RESP: key length
+        "append" | "prepend" => Operation::Append {
+            len: if args.len() >= 4 { arg(3)? } else { arg(1)? } as usize,
+        },
+        _ => return None,
+    };
+
+    Some(Event {
+        time: entry.time(),
+        key: entry.key()?.as_bytes().to_vec(),
+        operation,
+    })
+}
+
+fn parse_csv(line: &str) -> Option<Event> {
+    let fields: Vec<&str> = line.split(',').collect();
+    if fields.len() != 7 {
+        return None;
+    }
+
+    let time: i64 = fields[0].parse().ok()?;
+    let key_size: usize = fields[2].parse().ok()?;
+    let len: usize = fields[3].parse().ok()?;
+    let ttl: u64 = fields[6].parse().ok()?;
+
+    let operation = match fields[5] {
+        "get" | "gets" => Operation::Get,
+        "delete" => Operation::Delete,
+        "set" => Operation::Set {
+            len,
+            ttl,
+            condition: Condition::Always,
+        },
+        "add" => Operation::Set {
+            len,
+            ttl,
+            condition: Condition::Absent,
+        },
+        "replace" | "cas" => Operation::Set {
+            len,
+            ttl,
+            condition: Condition::Present,
+        },
+        "append" | "prepend" => Operation::Append { len },
+        _ => return None,
+    };
+
+    let mut key = fields[1].as_bytes().to_vec();
+    if key.len() < key_size {
+        key.resize(key_size, KEY_PAD);
+    }
+
+    Some(Event {
+        time: time * 1000,
+        key,
+        operation,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn klog() {
+        let event = Event::parse(
+            Format::Klog,
+            "2024-01-01T00:00:01.250+00:00 \"set key 0 60 3\" 5 8",
+        )
+        .unwrap();
+        assert_eq!(event.time, 1704067201250);
+        assert_eq!(event.key, b"key");
+        assert_eq!(
+            event.operation,
+            Operation::Set {
+                len: 3,
+                ttl: 60,
+                condition: Condition::Always
+            }
+        );
+
+        let event = Event::parse(
+            Format::Klog,
+            r#"{"time":"2024-01-01T00:00:01.250+00:00","command":"append","args":["key","5"],"status":5,"len":0}"#,
+        )
+        .unwrap();
+        assert_eq!(event.operation, Operation::Append { len: 5 });
+
+        assert_eq!(
+            Event::parse(
+                Format::Klog,
+                "2024-01-01T00:00:00.000+00:00 \"get key\" 0 0"
+            )
+            .unwrap()
+            .operation,
+            Operation::Get
+        );
+        assert!(Event::parse(
+            Format::Klog,
+            "2024-01-01T00:00:00.000+00:00 \"hget key field\" 4 5"
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn csv() {
+        let event = Event::parse(Format::Csv, "12,abc,6,100,1,add,3600").unwrap();
+        assert_eq!(event.time, 12000);
+        assert_eq!(event.key, b"abc___");
+        assert_eq!(
+            event.operation,
+            Operation::Set {
+                len: 100,
+                ttl: 3600,
+                condition: Condition::Absent
+            }
+        );
+
+        assert_eq!(
+            Event::parse(Format::Csv, "0,abc,3,0,1,get,0")
+                .unwrap()
+                .operation,
+            Operation::Get
+        );
+        assert!(Event::parse(Format::Csv, "0,abc,3,0,1,incr,0").is_none());
+        assert!(Event::parse(Format::Csv, "0,abc,3,0").is_none());
+    }
+}